@@ -0,0 +1,14 @@
+/// compiles the grpc protocol when the `grpc` feature is enabled, and sets the `Py_3_*`
+/// cfgs of the python the bindings build for
+fn main() {
+    println!("cargo:rerun-if-changed=proto/deny_filter.proto");
+    #[cfg(feature = "python")]
+    pyo3_build_config::use_pyo3_cfgs();
+    #[cfg(feature = "grpc")]
+    {
+        let protoc = protoc_bin_vendored::protoc_bin_path().expect("vendored protoc");
+        // SAFETY: build scripts are single threaded
+        unsafe { std::env::set_var("PROTOC", protoc) };
+        tonic_prost_build::compile_protos("proto/deny_filter.proto").expect("compile grpc proto");
+    }
+}
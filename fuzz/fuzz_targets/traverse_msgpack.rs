@@ -0,0 +1,27 @@
+#![no_main]
+
+use deny_filter::limits::Limits;
+use deny_filter::options::Options;
+use deny_filter::traverse;
+use libfuzzer_sys::fuzz_target;
+
+/// the limits of a gateway scanning untrusted documents
+const LIMITED: Limits = Limits {
+    max_msgpack_depth: Some(64),
+    max_msgpack_elements: Some(10_000),
+    ..Limits::NONE
+};
+
+fuzz_target!(|data: &[u8]| {
+    // the limits of a matcher built without limit options, the depth bound included
+    let defaults = Limits::new(&Options::default());
+    let strings = traverse::msgpack_strings(data, &defaults);
+    if let Ok(limited) = traverse::msgpack_strings(data, &LIMITED) {
+        assert_eq!(strings.as_ref(), Ok(&limited));
+    }
+    // every visited string is inside the range of its encoding
+    let _ = traverse::msgpack_spans(data, &LIMITED, &mut |_, s, range| {
+        assert!(data[range].ends_with(s.as_bytes()));
+        false
+    });
+});
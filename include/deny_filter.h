@@ -0,0 +1,35 @@
+/* C API of the deny_filter crate, build with:
+ *   cargo build --release --no-default-features --features cdylib
+ * and link against target/release/libdeny_filter.{so,dylib,dll}.
+ */
+#ifndef DENY_FILTER_H
+#define DENY_FILTER_H
+
+#include <stddef.h>
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+typedef struct DenyFilter DenyFilter;
+
+/* Builds a deny list from `len` NUL terminated UTF-8 words.
+ * `backend` is NULL (aho-corasick), "aho-corasick", "regex", "daachorse", "packed" or "auto".
+ * Returns NULL on invalid input. Release with deny_free. */
+DenyFilter *deny_new(const char *const *words, size_t len, const char *backend);
+
+/* Checks `len` bytes of UTF-8 text: 1 match, 0 clean, -1 invalid input. */
+int deny_is_match(const DenyFilter *filter, const uint8_t *text, size_t len);
+
+/* Scans every string value of a msgpack document: 1 match, 0 clean, -1 malformed or invalid input. */
+int deny_scan_msgpack(const DenyFilter *filter, const uint8_t *buf, size_t len);
+
+/* Releases a deny list, NULL is ignored. */
+void deny_free(DenyFilter *filter);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* DENY_FILTER_H */
@@ -8,77 +8,834 @@ Simple example plugin for searching and replacing text.
 This module loads configurations for plugins.
 """
 
+# Standard
+from collections.abc import Iterable
+from datetime import datetime, timezone
+import math
+from typing import Any, Literal
+import unicodedata
+
 # First-Party
 from mcpgateway.plugins.framework import (
     Plugin,
     PluginConfig,
     PluginContext,
-    PluginViolation,
     PromptPrehookPayload,
     PromptPrehookResult,
 )
 from mcpgateway.services.logging_service import LoggingService
 
 # Third-Party
-from pydantic import BaseModel
+from deny_filter import DenyListHybrid
+from pydantic import BaseModel, Field, model_validator
+
+from plugins.deny_filter.deny_violation import deny_violation
 
 # Initialize logging service first
 logging_service = LoggingService()
 logger = logging_service.get_logger(__name__)
 
 
+class ProximityRule(BaseModel):
+    """Co-occurrence rule: both terms at most `within` words apart, in any order.
+
+    Attributes:
+        first: First word or phrase.
+        second: Second word or phrase.
+        within: Maximum number of words between the terms.
+        weight: Weight of the rule in scores, a finite number >= 0.
+    """
+
+    first: str
+    second: str
+    within: int
+    weight: float = Field(default=1.0, ge=0, allow_inf_nan=False)
+
+
+class DenyEntry(BaseModel):
+    """Deny word with its own match kind or weight.
+
+    Attributes:
+        word: Word or phrase to deny.
+        match: "substring", "whole_word" or "anchored" (the whole string value),
+            overrides `whole_word` of the list, None for the list default (Rust plugins).
+        weight: Weight of its matches in scores, a finite number >= 0 (Rust plugins).
+    """
+
+    word: str
+    match: Literal["substring", "whole_word", "anchored"] | None = None
+    weight: float = Field(default=1.0, ge=0, allow_inf_nan=False)
+
+
+class RegexEntry(BaseModel):
+    """Regex matched alongside the deny words, e.g. {"pattern": "\\d{16}", "type": "regex"}.
+
+    Attributes:
+        pattern: Regex in the syntax of the Rust regex crate, case insensitive unless
+            `case_fold` is "none"; its matches report the pattern as the word.
+        type: "regex".
+    """
+
+    pattern: str
+    type: Literal["regex"]
+
+
+class ViolationMessage(BaseModel):
+    """Violation texts returned to users of one locale.
+
+    Attributes:
+        reason: Short reason of the violation.
+        description: Description of the violation.
+    """
+
+    reason: str
+    description: str
+
+
+class ConfigDiff(BaseModel):
+    """Changes from one deny list configuration to another.
+
+    Attributes:
+        added: Deny words only in the new configuration.
+        removed: Deny words only in the old configuration.
+        changed: Deny word in both to its (old, new) match kind, None for the list default.
+        reweighted: Deny word in both to its (old, new) weight.
+        options: Option name to its (old, new) value for changed list options.
+    """
+
+    added: list[str] = []
+    removed: list[str] = []
+    changed: dict[str, tuple[str | None, str | None]] = {}
+    reweighted: dict[str, tuple[float, float]] = {}
+    options: dict[str, tuple[Any, Any]] = {}
+
+    def is_empty(self) -> bool:
+        """Whether the configurations are equivalent.
+
+        Returns:
+            True without any change.
+        """
+        return not (
+            self.added or self.removed or self.changed or self.reweighted or self.options
+        )
+
+
+class PluginHealth(BaseModel):
+    """Status of a deny list plugin for readiness probes.
+
+    Attributes:
+        ready: Whether the plugin scans with a list of at least one deny word.
+        patterns: Deny words and proximity rules of the active list.
+        version: Version of the active list.
+        loaded_at: When the active list was built, UTC.
+        last_error: Why the last reload failed, None when it succeeded.
+    """
+
+    ready: bool
+    patterns: int
+    version: str | None = None
+    loaded_at: datetime
+    last_error: str | None = None
+
+
 class DenyListConfig(BaseModel):
     """Configuration for deny list plugin.
 
     Attributes:
-        words: List of words to deny.
+        words: List of words to deny, plain or with a match kind and weight; plain words
+            weigh 1.0. Regex entries are matched as well (DenyListPluginRust only).
+        whole_word: Deny words only match whole words (Rust plugins).
+        stem: Stemming language, e.g. "english" (Rust plugins, implies whole words).
+        proximity: Co-occurrence rules matched in addition to the words (Rust plugins).
+        exceptions: Deny word to patterns suppressing its hits when overlapping,
+            e.g. {"shoot": ["photo shoot"]} (Rust plugins).
+        allow: Phrases and regex entries suppressing the overlapping hits of every deny
+            word, e.g. ["class action"] (Rust plugins).
+        match_kind: "leftmost_first" or "leftmost_longest" hit for nested words,
+            e.g. "free money" over "free" (Rust plugins).
+        case_fold: "ascii", "simple", "full" or "turkish" case folding, "none" is case-sensitive.
+        normalization: "nfc", "nfd" or "none" Unicode normalization.
+        collapse_repeats: Runs of 3 or more of the same character count as one or two (Rust plugins).
+        punctuation_gaps: Punctuation between letters is dropped, "v.o.i.l.a" matches "voila"
+            (Rust plugins).
+        squeeze: Whitespace between 3 or more single letters is dropped, "v o i l a" matches
+            "voila" (Rust plugins).
+        reversed: Deny words also match spelled backwards, "aliov" matches "voila"
+            (Rust plugins).
+        rot13: The ROT13 decoding of prompts is scanned as well (Rust plugins).
+        substitutions: Single character replacements, e.g. {"0": "o", "4": "a"}, whose
+            decoding of prompts is scanned as well (Rust plugins).
+        hex: Hex tokens of prompts are scanned decoded as well (Rust plugins).
+        base32: Base32 tokens of prompts are scanned decoded as well (Rust plugins).
+        base64: Base64 tokens of prompts are scanned decoded as well (Rust plugins).
+        jwt: JWT-shaped tokens of prompts are scanned as the keys and string values of their
+            header and claims, without verifying signatures (Rust plugins).
+        decode_depth: Decoding layers nested at most, 2 when not set (Rust plugins).
+        ignore_keys: Map keys whose values are not scanned (Rust plugins).
+        ignore_key_patterns: Regexes of map keys whose values are not scanned (Rust plugins).
+        max_string_len: Bytes of a string over which on_overflow applies (Rust plugins).
+        max_payload_len: Scanned string bytes of a payload over which on_overflow applies (Rust plugins).
+        max_msgpack_len: Bytes of a msgpack document over which on_overflow applies (Rust plugins).
+        max_msgpack_depth: Msgpack container nesting over which on_overflow applies (Rust plugins).
+        max_msgpack_elements: Msgpack values over which on_overflow applies (Rust plugins).
+        max_depth: Container nesting of any payload over which on_overflow applies (Rust plugins).
+        max_strings: Strings of a payload over which on_overflow applies (Rust plugins).
+        on_overflow: "scan_prefix", "allow", "block" or "raise" past a limit (Rust plugins).
+        cache_size: Recently scanned strings whose verdicts are cached, unset for no cache (Rust plugins).
+        weights: Deny word to the weight of its matches in scores, 1.0 when not set, the
+            same as the weight of an entry.
+        score_aggregation: "sum" or "max" of the weights of the distinct words matched.
+        block_threshold: Block prompts whose score reaches it instead of any prompt with a
+            deny word, the score is recorded in violation details.
+        flag_threshold: Let prompts whose score reaches it through with the score under
+            "deny_flag" in the result metadata; prompts below it pass silently.
+        version: Version of the list, recorded in violation details.
+        source: Where the list came from, recorded in violation details.
+        timestamp: When the list was published, recorded in violation details.
+        mode: "enforce" blocks prompts with deny words, "audit" lets them through with the
+            violation in the result metadata, for rolling out new lists.
+        collect_all: Scan every prompt argument and list each (field, word) hit under
+            "violations" in the violation details instead of stopping at the first one.
+        messages: Locale, e.g. "de" or "pt-BR", to the violation reason and description
+            returned for the locale given to the hooks; English when none fits.
+        tenants: Tenant id to its own configuration, used for the prompts of the tenant
+            instead of this one; tenant configurations have no tenants.
+        priority: Order of the plugin among the gateway plugins, lower runs first; the
+            plugin priority when not set.
+        hooks: Hooks the gateway routes to the plugin, "prompt_pre_fetch" only; the plugin
+            hooks when not set.
+        tags: Tags of the plugin for the gateway; the plugin tags when not set.
     """
 
-    words: list[str]
+    words: list[str | DenyEntry | RegexEntry]
+    whole_word: bool = False
+    stem: str | None = None
+    proximity: list[ProximityRule] = []
+    exceptions: dict[str, list[str]] = {}
+    allow: list[str | RegexEntry] = []
+    match_kind: Literal["leftmost_first", "leftmost_longest"] = "leftmost_first"
+    case_fold: Literal["none", "ascii", "simple", "full", "turkish"] = "full"
+    normalization: Literal["nfc", "nfd", "none"] = "nfc"
+    collapse_repeats: bool = False
+    punctuation_gaps: bool = False
+    squeeze: bool = False
+    reversed: bool = False
+    rot13: bool = False
+    substitutions: dict[str, str] = {}
+    hex: bool = False
+    base32: bool = False
+    base64: bool = False
+    jwt: bool = False
+    decode_depth: int | None = None
+    ignore_keys: list[str] = []
+    ignore_key_patterns: list[str] = []
+    max_string_len: int | None = None
+    max_payload_len: int | None = None
+    max_msgpack_len: int | None = None
+    max_msgpack_depth: int | None = None
+    max_msgpack_elements: int | None = None
+    max_depth: int | None = None
+    max_strings: int | None = None
+    on_overflow: Literal["scan_prefix", "allow", "block", "raise"] = "scan_prefix"
+    cache_size: int | None = None
+    weights: dict[str, float] = {}
+    score_aggregation: Literal["sum", "max"] = "sum"
+    block_threshold: float | None = None
+    flag_threshold: float | None = None
+    version: str | None = None
+    source: str | None = None
+    timestamp: str | None = None
+    mode: Literal["enforce", "audit"] = "enforce"
+    collect_all: bool = False
+    messages: dict[str, ViolationMessage] = {}
+    tenants: dict[str, "DenyListConfig"] = {}
+    priority: int | None = None
+    hooks: list[Literal["prompt_pre_fetch"]] | None = None
+    tags: list[str] | None = None
 
+    @model_validator(mode="after")
+    def check_weights(self) -> "DenyListConfig":
+        """Check that weights are finite, >= 0 and of deny words, given once.
+
+        Returns:
+            The validated configuration.
+
+        Raises:
+            ValueError: On an invalid weight.
+        """
+        words = set(self.deny_words())
+        for word, weight in self.weights.items():
+            if word not in words:
+                raise ValueError(f"weight for unknown deny word {word!r}")
+            if not math.isfinite(weight) or weight < 0:
+                raise ValueError(f"weight {weight} of {word!r} is not a finite number >= 0")
+        for e in self.words:
+            if isinstance(e, DenyEntry) and e.word in self.weights and e.weight != 1.0:
+                raise ValueError(f"weight of {e.word!r} given in its entry and in weights")
+        return self
+
+    @model_validator(mode="after")
+    def check_regexes(self) -> "DenyListConfig":
+        """Check that every regex entry of the words and the allow list compiles.
+
+        Returns:
+            The validated configuration.
+
+        Raises:
+            ValueError: On an invalid regex, naming its entry.
+        """
+        for field in ("words", "allow"):
+            for i, e in enumerate(getattr(self, field)):
+                if not isinstance(e, RegexEntry):
+                    continue
+                try:
+                    DenyListHybrid([], patterns=[e.pattern])
+                except ValueError as error:
+                    raise ValueError(
+                        f"{field}[{i}]: invalid regex {e.pattern!r}: {error}"
+                    ) from None
+        return self
+
+    @model_validator(mode="after")
+    def check_thresholds(self) -> "DenyListConfig":
+        """Check that prompts are flagged before they are blocked.
+
+        Returns:
+            The validated configuration.
+
+        Raises:
+            ValueError: When the flag threshold is over the block threshold.
+        """
+        if (
+            self.block_threshold is not None
+            and self.flag_threshold is not None
+            and self.flag_threshold > self.block_threshold
+        ):
+            raise ValueError(
+                f"flag_threshold {self.flag_threshold} over block_threshold {self.block_threshold}"
+            )
+        return self
+
+    @model_validator(mode="after")
+    def check_tenants(self) -> "DenyListConfig":
+        """Check that tenant configurations are not nested.
+
+        Returns:
+            The validated configuration.
+
+        Raises:
+            ValueError: When a tenant configuration has tenants.
+        """
+        for tenant, config in self.tenants.items():
+            if config.tenants:
+                raise ValueError(f"configuration of tenant {tenant!r} has tenants")
+        return self
+
+    def scored(self) -> bool:
+        """Whether prompts are judged by their score instead of any deny word.
+
+        Returns:
+            True with a block or flag threshold.
+        """
+        return self.block_threshold is not None or self.flag_threshold is not None
+
+    def score(self, words: Iterable[str]) -> float:
+        """Score of the deny words found, like `score` of the Rust deny lists.
+
+        Args:
+            words: Deny words as configured, repeats count once.
+
+        Returns:
+            Sum or maximum of their weights, 0.0 without any.
+        """
+        weights = self.word_weights()
+        found = [weights.get(w, 1.0) for w in dict.fromkeys(words)]
+        if self.score_aggregation == "max":
+            return max(found, default=0.0)
+        return float(sum(found))
+
+    def options(self) -> dict[str, Any]:
+        """Matching options passed as keyword arguments to the Rust deny lists.
+
+        Returns:
+            Options differing from the defaults.
+        """
+        options = self.model_dump(
+            exclude={
+                "words",
+                "allow",
+                "weights",
+                "mode",
+                "collect_all",
+                "block_threshold",
+                "flag_threshold",
+                "messages",
+                "tenants",
+                "priority",
+                "hooks",
+                "tags",
+            },
+            exclude_defaults=True,
+        )
+        word_match = {
+            e.word: e.match for e in self.words if isinstance(e, DenyEntry) and e.match
+        }
+        if word_match:
+            options["word_match"] = word_match
+        allow = [e for e in self.allow if isinstance(e, str)]
+        if allow:
+            options["allow"] = allow
+        allow_patterns = [e.pattern for e in self.allow if isinstance(e, RegexEntry)]
+        if allow_patterns:
+            options["allow_patterns"] = allow_patterns
+        weights = {w: x for w, x in self.word_weights().items() if x != 1.0}
+        if weights:
+            options["weights"] = weights
+        return options
+
+    def word_weights(self) -> dict[str, float]:
+        """Weight of every deny word, from its entry or `weights`.
+
+        Returns:
+            Word to weight in list order, 1.0 when not set.
+        """
+        weights: dict[str, float] = {}
+        for e in self.words:
+            if isinstance(e, str):
+                weights[e] = self.weights.get(e, 1.0)
+            elif isinstance(e, DenyEntry):
+                weights[e.word] = self.weights.get(e.word, e.weight)
+        return weights
+
+    def weight(self, word: str) -> float:
+        """Weight of a deny word as the Rust deny lists report it, lowercase unless case-sensitive.
+
+        Args:
+            word: Deny word of a match, or the `first NEAR/n second` label of a proximity rule.
+
+        Returns:
+            Its weight, 1.0 for an unknown word.
+        """
+        for w, weight in self.word_weights().items():
+            if (w if self.case_fold == "none" else w.lower()) == word:
+                return weight
+        for rule in self.proximity:
+            if f"{rule.first.lower()} NEAR/{rule.within} {rule.second.lower()}" == word:
+                return rule.weight
+        return 1.0
+
+    def message(self, locale: str | None) -> dict[str, str]:
+        """Violation texts for a locale, e.g. "de-AT" falls back to "de".
+
+        Args:
+            locale: Locale of the user, "-" or "_" separated, any case; None for the default.
+
+        Returns:
+            The reason and description configured for the locale or its language, empty
+            for the English defaults.
+        """
+        if not locale:
+            return {}
+        messages = {k.replace("_", "-").lower(): m for k, m in self.messages.items()}
+        locale = locale.replace("_", "-").lower()
+        message = messages.get(locale) or messages.get(locale.split("-")[0])
+        return message.model_dump() if message else {}
+
+    def metadata(self) -> dict[str, str]:
+        """List metadata for violation details.
+
+        Returns:
+            The version, source and timestamp that are set.
+        """
+        return self.model_dump(include={"version", "source", "timestamp"}, exclude_none=True)
+
+    def entries(self) -> dict[str, str | None]:
+        """Deny words with their match kinds, and regexes.
+
+        Returns:
+            Word to match kind in list order, None for the list default; pattern to
+            "regex" for regex entries.
+        """
+        entries: dict[str, str | None] = {}
+        for e in self.words:
+            if isinstance(e, str):
+                entries[e] = None
+            elif isinstance(e, DenyEntry):
+                entries[e.word] = e.match
+            else:
+                entries[e.pattern] = e.type
+        return entries
+
+    def diff(self, other: "DenyListConfig") -> ConfigDiff:
+        """Changes from this configuration to another, for reviewing list rollouts.
+
+        Args:
+            other: The new configuration.
+
+        Returns:
+            Added, removed and changed deny words and changed options.
+        """
+        old, new = self.entries(), other.entries()
+        old_weights, new_weights = self.word_weights(), other.word_weights()
+        mine = self.model_dump(exclude={"words", "weights"})
+        theirs = other.model_dump(exclude={"words", "weights"})
+        return ConfigDiff(
+            added=[w for w in new if w not in old],
+            removed=[w for w in old if w not in new],
+            changed={w: (old[w], new[w]) for w in old if w in new and old[w] != new[w]},
+            reweighted={
+                w: (old_weights[w], new_weights[w])
+                for w in old
+                if w in new and old_weights[w] != new_weights[w]
+            },
+            options={k: (mine[k], theirs[k]) for k in mine if mine[k] != theirs[k]},
+        )
+
+    def fold(self, text: str) -> str:
+        """Text normalized and case folded like the Rust deny lists do.
+
+        Args:
+            text: Deny word or scanned text.
+
+        Returns:
+            The text in the form deny words are matched in.
+        """
+        if self.normalization != "none":
+            text = unicodedata.normalize(self.normalization.upper(), text)
+        if self.case_fold == "ascii":
+            return "".join(c.lower() if c.isascii() else c for c in text)
+        if self.case_fold == "simple":
+            return "".join(c.lower() if len(c.lower()) == 1 else c for c in text).replace("ς", "σ")
+        if self.case_fold == "turkish":
+            text = text.replace("I", "ı").replace("İ", "i")
+        if self.case_fold == "none":
+            return text
+        return text.casefold()
+
+    def fold_word(self, word: str) -> str:
+        """Deny word in matching form, lowercased before folding like the Rust deny lists store it.
+
+        Args:
+            word: Deny word or phrase.
+
+        Returns:
+            The folded deny word.
+        """
+        return self.fold(word if self.case_fold == "none" else word.lower())
+
+    def folded_words(self) -> list[str]:
+        """Deny words in matching form, see `fold_word`.
+
+        Returns:
+            Folded deny words in list order.
+        """
+        return [self.fold_word(w) for w in self.deny_words()]
+
+    def deny_words(self) -> list[str]:
+        """Deny words without their match kinds, regex entries left out.
+
+        Returns:
+            Words and phrases to deny.
+        """
+        return [
+            e if isinstance(e, str) else e.word
+            for e in self.words
+            if not isinstance(e, RegexEntry)
+        ]
+
+    def patterns(self) -> list[str]:
+        """Patterns of the regex entries.
+
+        Returns:
+            Regexes in list order.
+        """
+        return [e.pattern for e in self.words if isinstance(e, RegexEntry)]
 
-class DenyListPlugin(Plugin):
-    """Example deny list plugin."""
 
-    def __init__(self, config: PluginConfig):
+def context_details(context: PluginContext | None) -> dict[str, Any]:
+    """Identifiers of a hook call echoed into violation details.
+
+    Args:
+        context: Context of the hook call, None without one.
+
+    Returns:
+        The request id, tenant id and user of the global context that are set.
+    """
+    if context is None:
+        return {}
+    gctx = context.global_context
+    ids = {"request_id": gctx.request_id, "tenant_id": gctx.tenant_id, "user": gctx.user}
+    return {k: v for k, v in ids.items() if v is not None}
+
+
+def tenant_of(context: PluginContext | None) -> str | None:
+    """Tenant of a hook call.
+
+    Args:
+        context: Context of the hook call, None without one.
+
+    Returns:
+        The tenant id of the global context, None when not set.
+    """
+    return context.global_context.tenant_id if context is not None else None
+
+
+def score_result(
+    config: DenyListConfig,
+    payload: PromptPrehookPayload,
+    score: float,
+    locale: str | None = None,
+    context: PluginContext | None = None,
+) -> PromptPrehookResult:
+    """Graduated result of a scored prompt: blocked, flagged or passed.
+
+    Args:
+        config: Configuration with the thresholds.
+        payload: The scored prompt payload.
+        score: Score of the prompt.
+        locale: Locale of the user, for the violation texts.
+        context: Context of the hook call, its identifiers are added to the details.
+
+    Returns:
+        A violation at or over the block threshold, the score under "deny_flag" in the
+        metadata at or over the flag threshold, the payload as is below both.
+    """
+    details = {**config.metadata(), **context_details(context), "score": score}
+    if config.block_threshold is not None and score >= config.block_threshold:
+        logger.warning("Prompt score %s over the block threshold (%s)", score, config.mode)
+        return deny_violation(payload, details, config.mode, **config.message(locale))
+    if config.flag_threshold is not None and score >= config.flag_threshold:
+        logger.info("Prompt score %s over the flag threshold", score)
+        return PromptPrehookResult(modified_payload=payload, metadata={"deny_flag": details})
+    return PromptPrehookResult(modified_payload=payload)
+
+
+class DenyListPluginBase(Plugin):
+    """Deny list plugin whose configuration can be reloaded while it serves prompts.
+
+    The configuration and the list built from it are swapped in together as one attribute,
+    so hooks read both at once and scans in flight finish on the list they started with.
+    """
+
+    regexes = False
+    """Whether the deny lists built match the regex entries of configurations."""
+
+    def __init__(self, config: PluginConfig) -> None:
         """Initialize the deny list plugin.
 
         Args:
             config: Plugin configuration.
         """
         super().__init__(config)
-        self._dconfig = DenyListConfig.model_validate(self._config.config)
-        self._deny_list = []
-        for word in self._dconfig.words:
-            self._deny_list.append(word)
+        self._active = self._load(self._config.config)
+        self._tenant_lists = self._load_tenants(self._active[0])
+        self._loaded_at = datetime.now(timezone.utc)
+        self._last_error: str | None = None
+
+    def _build(self, dconfig: DenyListConfig) -> Any:
+        """Build the deny list of a configuration.
+
+        Args:
+            dconfig: Validated configuration.
+
+        Returns:
+            The deny list the hooks scan with.
+        """
+        raise NotImplementedError
+
+    def _load(self, config: dict[str, Any] | DenyListConfig) -> tuple[DenyListConfig, Any]:
+        """Validate a configuration and build its deny list.
+
+        Args:
+            config: Plugin `config` mapping or a validated configuration.
+
+        Returns:
+            The configuration and its deny list.
+        """
+        dconfig = DenyListConfig.model_validate(config)
+        if not self.regexes and any(c.patterns() for c in (dconfig, *dconfig.tenants.values())):
+            raise ValueError(f"{type(self).__name__} does not match regex entries")
+        return dconfig, self._build(dconfig)
+
+    def _load_tenants(self, dconfig: DenyListConfig) -> dict[str, tuple[DenyListConfig, Any]]:
+        """Build the deny lists of the tenants of a configuration.
+
+        Args:
+            dconfig: Validated configuration.
+
+        Returns:
+            Tenant id to its configuration and deny list.
+        """
+        return {tenant: (c, self._build(c)) for tenant, c in dconfig.tenants.items()}
+
+    def _policy(self, tenant: str | None) -> tuple[DenyListConfig, Any]:
+        """Configuration and deny list scanning the prompts of a tenant.
+
+        Args:
+            tenant: Tenant id of the hook call, None when not known.
+
+        Returns:
+            The configuration and deny list of the tenant, the active ones for other tenants.
+        """
+        active = self._active
+        config = active[0].tenants.get(tenant) if tenant is not None else None
+        if config is None:
+            return active
+        cached = self._tenant_lists.get(tenant)
+        if cached is None or cached[0] is not config:
+            # the tenant lists of a reload racing this call, built once more
+            cached = (config, self._build(config))
+        return cached
+
+    @property
+    def _dconfig(self) -> DenyListConfig:
+        """Active configuration."""
+        return self._active[0]
+
+    @property
+    def _deny_list(self) -> Any:
+        """Active deny list."""
+        return self._active[1]
+
+    @property
+    def priority(self) -> int:
+        """Order of the plugin among the gateway plugins, from the deny list config first.
+
+        Returns:
+            The configured priority, lower runs first.
+        """
+        priority = self._dconfig.priority
+        return super().priority if priority is None else priority
+
+    @property
+    def hooks(self) -> list[str]:
+        """Hooks the gateway routes to the plugin, from the deny list config first.
+
+        Returns:
+            The configured hook names.
+        """
+        hooks = self._dconfig.hooks
+        return super().hooks if hooks is None else list(hooks)
+
+    @property
+    def tags(self) -> list[str]:
+        """Tags of the plugin, from the deny list config first.
+
+        Returns:
+            The configured tags.
+        """
+        tags = self._dconfig.tags
+        return super().tags if tags is None else list(tags)
+
+    def reload(self, config: dict[str, Any] | DenyListConfig) -> ConfigDiff:
+        """Build a new configuration and swap it in, e.g. from an admin API.
+
+        The new deny list is built before the swap; on errors the active one stays.
+
+        Args:
+            config: Plugin `config` mapping or a validated configuration.
+
+        Returns:
+            Changes from the active configuration to the new one.
+        """
+        try:
+            active = self._load(config)
+            tenant_lists = self._load_tenants(active[0])
+        except Exception as e:
+            self._last_error = str(e)
+            logger.error("Deny list reload failed: %s", e)
+            raise
+        diff = self._dconfig.diff(active[0])
+        self._tenant_lists = tenant_lists
+        self._active = active
+        self._loaded_at = datetime.now(timezone.utc)
+        self._last_error = None
+        logger.info("Deny list reloaded (version %s)", active[0].version)
+        return diff
+
+    def health(self) -> PluginHealth:
+        """Status of the active list for readiness probes.
+
+        Returns:
+            The pattern count, version and load time of the active list and the last
+            reload error.
+        """
+        dconfig = self._dconfig
+        patterns = len(dconfig.words) + len(dconfig.proximity)
+        return PluginHealth(
+            ready=patterns > 0,
+            patterns=patterns,
+            version=dconfig.version,
+            loaded_at=self._loaded_at,
+            last_error=self._last_error,
+        )
+
+
+class DenyListPlugin(DenyListPluginBase):
+    """Example deny list plugin."""
+
+    def _build(self, dconfig: DenyListConfig) -> list[str]:
+        """Build the deny list of a configuration.
+
+        Args:
+            dconfig: Validated configuration.
+
+        Returns:
+            The folded deny words.
+        """
+        return dconfig.folded_words()
 
     async def prompt_pre_fetch(
-        self, payload: PromptPrehookPayload, _context: PluginContext
+        self,
+        payload: PromptPrehookPayload,
+        context: PluginContext | None = None,
+        locale: str | None = None,
+        tenant: str | None = None,
     ) -> PromptPrehookResult:
         """The plugin hook run before a prompt is retrieved and rendered.
 
         Args:
             payload: The prompt payload to be analyzed.
-            context: contextual information about the hook call.
+            context: contextual information about the hook call, its tenant selects the
+                policy and its identifiers are added to violation details.
+            locale: Locale of the user, e.g. "de-AT", for the violation texts.
+            tenant: Tenant id selecting the policy instead of the one of the context.
 
         Returns:
             The result of the plugin's analysis, including whether the prompt can proceed.
         """
+        dconfig, deny_list = self._policy(tenant or tenant_of(context))
+        details = {**dconfig.metadata(), **context_details(context)}
+        violations: list[dict[str, str]] = []
+        scored: list[str] = []
         if payload.args:
             for key in payload.args:
-                if any(word in payload.args[key] for word in self._deny_list):
-                    violation = PluginViolation(
-                        reason="Prompt not allowed",
-                        description="A deny word was found in the prompt",
-                        code="deny",
-                        details={},
-                    )
-                    logger.warning("Deny word detected in prompt argument '%s'", key)
-                    return PromptPrehookResult(
-                        modified_payload=payload,
-                        violation=violation,
-                        continue_processing=False,
+                text = dconfig.fold(payload.args[key])
+                hits = [
+                    (text.find(folded), word)
+                    for word, folded in zip(dconfig.deny_words(), deny_list)
+                    if folded in text
+                ]
+                found = [word for _, word in sorted(hits)]
+                if not found:
+                    continue
+                if dconfig.scored():
+                    scored.extend(found)
+                    continue
+                logger.warning(
+                    "Deny word detected in prompt argument '%s' (%s)", key, dconfig.mode
+                )
+                if not dconfig.collect_all:
+                    return deny_violation(
+                        payload, details, dconfig.mode, **dconfig.message(locale)
                     )
+                violations.extend({"field": key, "word": w} for w in found)
+        if dconfig.scored():
+            return score_result(dconfig, payload, dconfig.score(scored), locale, context)
+        if violations:
+            return deny_violation(
+                payload, details, dconfig.mode, violations, **dconfig.message(locale)
+            )
         return PromptPrehookResult(modified_payload=payload)
 
     async def shutdown(self) -> None:
@@ -8,20 +8,26 @@ Aho-Corasick implementation for searching and replacing text.
 This module uses the aho-corasick package for efficient multi-pattern string matching.
 """
 
+# Standard
+from typing import Any
+
 # Third-Party
 import ahocorasick  # pyahocorasick package
 
 # First-Party
 from mcpgateway.plugins.framework import (
-    Plugin,
-    PluginConfig,
     PluginContext,
     PromptPrehookPayload,
     PromptPrehookResult,
 )
 from mcpgateway.services.logging_service import LoggingService
 
-from plugins.deny_filter.deny import DenyListConfig
+from plugins.deny_filter.deny import (
+    DenyListConfig,
+    DenyListPluginBase,
+    context_details,
+    tenant_of,
+)
 from plugins.deny_filter.deny_violation import deny_violation
 
 # Initialize logging service first
@@ -30,71 +36,110 @@ logger = logging_service.get_logger(__name__)
 
 
 
-class DenyListAcPlugin(Plugin):
+class DenyListAcPlugin(DenyListPluginBase):
     """Aho-Corasick based deny list plugin."""
 
-    def __init__(self, config: PluginConfig):
-        """Initialize the deny list plugin with Aho-Corasick automaton.
-
-        Args:
-            config: Plugin configuration.
-        """
-        super().__init__(config)
-        dconfig = DenyListConfig.model_validate(self._config.config)
-        self._automaton = self._build_automaton(dconfig.words)
-
-    def _build_automaton(self, words: list[str]) -> ahocorasick.Automaton | None:
+    def _build(self, dconfig: DenyListConfig) -> ahocorasick.Automaton | None:
         """Build the Aho-Corasick automaton for efficient pattern matching.
 
         Args:
-            words: List of words to search for.
+            dconfig: Validated configuration.
 
         Returns:
             Configured Aho-Corasick automaton, or None if no words provided.
         """
+        words = dconfig.deny_words()
         if not words:
             return None
         automaton = ahocorasick.Automaton(ahocorasick.STORE_ANY, ahocorasick.KEY_STRING)
         for word in words:
-            # Add lowercase version for case-insensitive matching
-            automaton.add_word(word.lower(), word)
+            # folded like the scanned text, see DenyListConfig.fold
+            automaton.add_word(dconfig.fold_word(word), word)
         automaton.make_automaton()
         return automaton
 
-    def _contains_deny_word(self, text: str) -> bool:
+    @property
+    def _automaton(self) -> ahocorasick.Automaton | None:
+        """Active automaton."""
+        return self._deny_list
+
+    def _contains_deny_word(
+        self, text: str, active: tuple[DenyListConfig, Any] | None = None
+    ) -> bool:
         """Check if text contains any deny words using Aho-Corasick.
 
         Args:
             text: Text to search.
+            active: Configuration and automaton to search with, the active ones when None.
 
         Returns:
             True if a deny word is found, False otherwise.
         """
-        if not text or self._automaton is None:
+        dconfig, automaton = active or self._active
+        if not text or automaton is None:
             return False
-        # Convert to lowercase for case-insensitive matching
-        text_lower = text.lower()
-        for _ in self._automaton.iter(text_lower):
+        for _ in automaton.iter(dconfig.fold(text)):
             return True
         return False
 
+    def _deny_words_in(
+        self, text: str, active: tuple[DenyListConfig, Any] | None = None
+    ) -> list[str]:
+        """Deny words found in text, each once, in order of appearance.
+
+        Args:
+            text: Text to search.
+            active: Configuration and automaton to search with, the active ones when None.
+
+        Returns:
+            The deny words as configured.
+        """
+        dconfig, automaton = active or self._active
+        if not text or automaton is None:
+            return []
+        return list(dict.fromkeys(w for _, w in automaton.iter(dconfig.fold(text))))
+
     async def prompt_pre_fetch(
-        self, payload: PromptPrehookPayload, _context: PluginContext
+        self,
+        payload: PromptPrehookPayload,
+        context: PluginContext | None = None,
+        locale: str | None = None,
+        tenant: str | None = None,
     ) -> PromptPrehookResult:
         """The plugin hook run before a prompt is retrieved and rendered.
 
         Args:
             payload: The prompt payload to be analyzed.
-            context: contextual information about the hook call.
+            context: contextual information about the hook call, its tenant selects the
+                policy and its identifiers are added to violation details.
+            locale: Locale of the user, e.g. "de-AT", for the violation texts.
+            tenant: Tenant id selecting the policy instead of the one of the context.
 
         Returns:
             The result of the plugin's analysis, including whether the prompt can proceed.
         """
+        active = self._policy(tenant or tenant_of(context))
+        dconfig = active[0]
+        details = {**dconfig.metadata(), **context_details(context)}
+        violations: list[dict[str, str]] = []
         if payload.args:
             for key, value in payload.args.items():
-                if isinstance(value, str) and self._contains_deny_word(value):
-                    logger.warning("Deny word detected in prompt argument '%s'", key)
-                    return deny_violation(payload)
+                if not isinstance(value, str) or not self._contains_deny_word(value, active):
+                    continue
+                logger.warning(
+                    "Deny word detected in prompt argument '%s' (%s)", key, dconfig.mode
+                )
+                if not dconfig.collect_all:
+                    return deny_violation(
+                        payload, details, dconfig.mode, **dconfig.message(locale)
+                    )
+                violations.extend(
+                    {"field": key, "word": w} for w in self._deny_words_in(value, active)
+                )
+        if violations:
+            return deny_violation(
+                payload, details, dconfig.mode, violations, **dconfig.message(locale)
+            )
         return PromptPrehookResult(modified_payload=payload)
 
     async def shutdown(self) -> None:
@@ -10,17 +10,21 @@ Simple example plugin for searching and replacing text.
 # First-Party
 from typing import Any
 
-from deny_filter import DenyList
+from deny_filter import DenyList, DenyListHybrid
 from mcpgateway.plugins.framework import (
-    Plugin,
-    PluginConfig,
     PluginContext,
     PromptPrehookPayload,
     PromptPrehookResult,
 )
 from mcpgateway.services.logging_service import LoggingService
 
-from plugins.deny_filter.deny import DenyListConfig
+from plugins.deny_filter.deny import (
+    DenyListConfig,
+    DenyListPluginBase,
+    context_details,
+    score_result,
+    tenant_of,
+)
 from plugins.deny_filter.deny_violation import deny_violation
 
 # Initialize logging service first
@@ -28,32 +32,73 @@ logging_service = LoggingService()
 logger = logging_service.get_logger(__name__)
 
 
-class DenyListPluginRust(Plugin):
+class DenyListPluginRust(DenyListPluginBase):
     """Example deny list plugin."""
 
-    def __init__(self, config: PluginConfig):
-        """Initialize the deny list plugin.
+    regexes = True
+
+    def _build(self, dconfig: DenyListConfig) -> Any:
+        """Build the deny list of a configuration.
 
         Args:
-            config: Plugin configuration.
+            dconfig: Validated configuration.
+
+        Returns:
+            The Rust deny list, a hybrid literal and regex list with regex entries.
         """
-        super().__init__(config)
-        dconfig = DenyListConfig.model_validate(self._config.config)
-        self._deny_list: Any = DenyList(dconfig.words)
+        if dconfig.patterns():
+            return DenyListHybrid(
+                dconfig.deny_words(), patterns=dconfig.patterns(), **dconfig.options()
+            )
+        return DenyList(dconfig.deny_words(), **dconfig.options())
 
     async def prompt_pre_fetch(
-        self, payload: PromptPrehookPayload, _context: PluginContext
+        self,
+        payload: PromptPrehookPayload,
+        context: PluginContext | None = None,
+        locale: str | None = None,
+        tenant: str | None = None,
     ) -> PromptPrehookResult:
         """The plugin hook run before a prompt is retrieved and rendered.
 
         Args:
             payload: The prompt payload to be analyzed.
-            context: contextual information about the hook call.
+            context: contextual information about the hook call, its tenant selects the
+                policy and its identifiers are added to violation details.
+            locale: Locale of the user, e.g. "de-AT", for the violation texts.
+            tenant: Tenant id selecting the policy instead of the one of the context.
 
         Returns:
             The result of the plugin's analysis, including whether the prompt can proceed.
         """
-        if payload.args and self._deny_list.scan_any(payload.args):
-            logger.warning("Deny word detected in prompt")
-            return deny_violation(payload)
+        if not payload.args:
+            return PromptPrehookResult(modified_payload=payload)
+        dconfig, deny_list = self._policy(tenant or tenant_of(context))
+        details = {**dconfig.metadata(), **context_details(context)}
+        if dconfig.scored():
+            return score_result(
+                dconfig, payload, deny_list.score(payload.args), locale, context
+            )
+        if dconfig.collect_all:
+            # the walk of scan_any, so nested values, limits and proximity rules block too;
+            # a limit blocking has no word
+            found = deny_list.violations(payload.args)
+            violations = [
+                {"field": field, "word": word, "weight": dconfig.weight(word) if word else 1.0}
+                for field, word in dict.fromkeys((v.field, v.word) for v in found)
+            ]
+            if violations:
+                logger.warning("Deny words detected in prompt (%s)", dconfig.mode)
+                return deny_violation(
+                    payload,
+                    details,
+                    dconfig.mode,
+                    violations,
+                    **dconfig.message(locale),
+                )
+        elif deny_list.scan_any(payload.args):
+            logger.warning("Deny word detected in prompt (%s)", dconfig.mode)
+            return deny_violation(
+                payload, details, dconfig.mode, **dconfig.message(locale)
+            )
         return PromptPrehookResult(modified_payload=payload)
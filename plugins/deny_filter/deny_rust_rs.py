@@ -8,13 +8,10 @@ Simple example plugin for searching and replacing text.
 This module loads configurations for plugins.
 """
 
-from typing import Any
-
 # First-Party
 from deny_filter import DenyListRs
 
 # Third-Party
-from mcpgateway.plugins.framework import PluginConfig
 from mcpgateway.services.logging_service import LoggingService
 
 from plugins.deny_filter.deny import DenyListConfig
@@ -28,12 +25,15 @@ logger = logging_service.get_logger(__name__)
 class DenyListPluginRustRs(DenyListPluginRust):
     """Example deny list plugin."""
 
-    def __init__(self, config: PluginConfig):
-        """Initialize the deny list plugin.
+    regexes = False
+
+    def _build(self, dconfig: DenyListConfig) -> DenyListRs:
+        """Build the deny list of a configuration.
 
         Args:
-            config: Plugin configuration.
+            dconfig: Validated configuration.
+
+        Returns:
+            The Rust deny list.
         """
-        super().__init__(config)
-        self._dconfig = DenyListConfig.model_validate(self._config.config)
-        self._deny_list: Any = DenyListRs(self._dconfig.words)
+        return DenyListRs(dconfig.deny_words(), **dconfig.options())
@@ -7,6 +7,8 @@ Authors: Fred Araujo, Dmitry Zakharov
 Helper function for creating deny violation responses.
 """
 
+from typing import Any
+
 from mcpgateway.plugins.framework import (
     PluginViolation,
     PromptPrehookPayload,
@@ -14,22 +16,45 @@ from mcpgateway.plugins.framework import (
 )
 
 
-def deny_violation(payload: PromptPrehookPayload) -> PromptPrehookResult:
+def deny_violation(
+    payload: PromptPrehookPayload,
+    details: dict[str, Any] | None = None,
+    mode: str = "enforce",
+    violations: list[dict[str, Any]] | None = None,
+    reason: str = "Prompt not allowed",
+    description: str = "A deny word was found in the prompt",
+) -> PromptPrehookResult:
     """Create a prompt prehook result indicating a deny word violation.
 
     Args:
         payload: The prompt payload that triggered the violation.
+        details: Violation details, e.g. the deny list version.
+        mode: "enforce" halts processing, "audit" only reports the violation.
+        violations: Every (field, word) hit of the prompt, added to the details as "violations",
+            with the weight of the word in the Rust plugins.
+        reason: Reason of the violation, e.g. in the language of the user.
+        description: Description of the violation, e.g. in the language of the user.
 
     Returns:
-        A PromptPrehookResult with violation details and processing halted.
+        A PromptPrehookResult with violation details and processing halted, or in audit
+        mode with the violation in its metadata and processing continued.
     """
+    details = dict(details or {})
+    if violations:
+        details["violations"] = violations
+    violation = PluginViolation(
+        reason=reason,
+        description=description,
+        code="deny",
+        details=details,
+    )
+    if mode == "audit":
+        return PromptPrehookResult(
+            modified_payload=payload,
+            metadata={"deny_violation": violation.model_dump()},
+        )
     return PromptPrehookResult(
         modified_payload=payload,
-        violation=PluginViolation(
-            reason="Prompt not allowed",
-            description="A deny word was found in the prompt",
-            code="deny",
-            details={},
-        ),
+        violation=violation,
         continue_processing=False,
     )
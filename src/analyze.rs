@@ -0,0 +1,102 @@
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
+#[cfg(feature = "python")]
+use pyo3::types::PyDict;
+#[cfg(feature = "python")]
+use pyo3_stub_gen::derive::{gen_stub_pyclass, gen_stub_pyfunction, gen_stub_pymethods};
+
+use crate::build_error::BuildError;
+use crate::compiled::{kinds, stored_words};
+use crate::normalize::Normalizer;
+use crate::options::{MatchKind, Options};
+use crate::report::contained;
+
+/// static analysis of a deny word list, without building an automaton
+#[cfg_attr(feature = "python", gen_stub_pyclass)]
+#[cfg_attr(
+    feature = "python",
+    pyclass(module = "deny_filter.matchers", frozen, get_all, skip_from_py_object)
+)]
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Analysis {
+    /// entries that can never match: blank, or without words in whole word mode
+    pub never_match: Vec<String>,
+    /// entry and an entry contained in it; the longer entry adds nothing to `is_match`
+    pub substrings: Vec<(String, String)>,
+    /// entry and an earlier entry always reported instead: equal, or its prefix with `leftmost_first`
+    pub unreachable: Vec<(String, String)>,
+}
+
+/// substring, unreachable and never matching deny words of `words` normalized by `options`
+/// # Errors
+/// * match kind for a word not in the list
+pub fn analyze(words: &[String], options: &Options) -> Result<Analysis, BuildError> {
+    let lower = stored_words(words, options.case_fold);
+    let mut never_match: Vec<usize> = (0..words.len())
+        .filter(|i| words[*i].trim().is_empty())
+        .collect();
+    let (mut substrings, mut unreachable) = (Vec::new(), Vec::new());
+    for (kind, ids) in kinds(&lower, options)? {
+        let normalizer = Normalizer::new(kind, options);
+        let (mut patterns, mut matchable) = (Vec::new(), Vec::new());
+        for id in ids {
+            match normalizer.pattern(&lower[id]) {
+                Ok(pattern) => {
+                    patterns.push(pattern);
+                    matchable.push(id);
+                }
+                Err(_) => never_match.push(id),
+            }
+        }
+        for (i, j, prefix) in contained(&patterns) {
+            let (entry, other) = (matchable[i], matchable[j]);
+            let equal = patterns[i] == patterns[j];
+            if !equal {
+                substrings.push((entry, other));
+            }
+            if other < entry && (equal || prefix && options.match_kind == MatchKind::LeftmostFirst)
+            {
+                unreachable.push((entry, other));
+            }
+        }
+    }
+    never_match.sort_unstable();
+    substrings.sort_unstable();
+    unreachable.sort_unstable();
+    unreachable.dedup_by_key(|(entry, _)| *entry);
+    let pairs = |pairs: Vec<(usize, usize)>| {
+        pairs
+            .into_iter()
+            .map(|(i, j)| (words[i].clone(), words[j].clone()))
+            .collect()
+    };
+    Ok(Analysis {
+        never_match: never_match.into_iter().map(|i| words[i].clone()).collect(),
+        substrings: pairs(substrings),
+        unreachable: pairs(unreachable),
+    })
+}
+
+#[cfg(feature = "python")]
+#[gen_stub_pymethods]
+#[pymethods]
+impl Analysis {
+    fn __repr__(&self) -> String {
+        format!(
+            "Analysis(never_match={:?}, substrings={:?}, unreachable={:?})",
+            self.never_match, self.substrings, self.unreachable
+        )
+    }
+}
+
+/// analysis of a deny word list, keyword options: see `Options`
+/// # Errors
+/// * unknown option or invalid value, match kind for a word not in the list
+#[cfg(feature = "python")]
+#[gen_stub_pyfunction]
+#[pyfunction]
+#[pyo3(name = "analyze", signature = (words, **options))]
+#[allow(clippy::needless_pass_by_value)] // python passes an owned list
+pub fn py_analyze(words: Vec<String>, options: Option<&Bound<'_, PyDict>>) -> PyResult<Analysis> {
+    Ok(analyze(&words, &Options::from_kwargs(options)?)?)
+}
@@ -0,0 +1,204 @@
+use arrow_array::cast::AsArray;
+use arrow_array::{Array, BooleanArray, RecordBatch};
+use arrow_schema::DataType;
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
+#[cfg(feature = "python")]
+use pyo3::types::{PyCapsule, PyTuple};
+#[cfg(feature = "python")]
+use pyo3_stub_gen::derive::{gen_stub_pyclass, gen_stub_pymethods};
+
+use crate::build_error::{BuildError, invalid};
+use crate::matcher::Matcher;
+
+fn is_string(data_type: &DataType) -> bool {
+    matches!(
+        data_type,
+        DataType::Utf8 | DataType::LargeUtf8 | DataType::Utf8View
+    )
+}
+
+/// scans every value of a string array, nulls do not match
+/// # Errors
+/// * the array is not a utf8, large utf8 or utf8 view array
+pub fn scan_array<M: Matcher + ?Sized>(
+    matcher: &M,
+    array: &dyn Array,
+) -> Result<BooleanArray, BuildError> {
+    let scan = |value: Option<&str>| Some(value.is_some_and(|s| matcher.scan_str(s)));
+    Ok(match array.data_type() {
+        DataType::Utf8 => array.as_string::<i32>().iter().map(scan).collect(),
+        DataType::LargeUtf8 => array.as_string::<i64>().iter().map(scan).collect(),
+        DataType::Utf8View => array.as_string_view().iter().map(scan).collect(),
+        other => return Err(invalid(format!("{other} is not a string type"))),
+    })
+}
+
+/// rows of `batch` with a deny word in any of `columns`, all string columns when None
+/// # Errors
+/// * a named column is missing or not a string column
+pub fn scan_batch<M: Matcher + ?Sized>(
+    matcher: &M,
+    batch: &RecordBatch,
+    columns: Option<&[String]>,
+) -> Result<BooleanArray, BuildError> {
+    let schema = batch.schema();
+    let selected: Vec<usize> = match columns {
+        Some(names) => names
+            .iter()
+            .map(|name| schema.index_of(name).map_err(invalid))
+            .collect::<Result<_, _>>()?,
+        None => (0..batch.num_columns())
+            .filter(|&i| is_string(schema.field(i).data_type()))
+            .collect(),
+    };
+    let mut mask = vec![false; batch.num_rows()];
+    for i in selected {
+        let found = scan_array(matcher, batch.column(i))
+            .map_err(|e| invalid(format!("column {:?}: {}", schema.field(i).name(), e.0)))?;
+        for (row, hit) in mask.iter_mut().zip(found.values()) {
+            *row |= hit;
+        }
+    }
+    Ok(BooleanArray::from(mask))
+}
+
+/// rows of a struct array (a record batch exported as one array) or values of a string array
+#[cfg(feature = "python")]
+fn scan_data<M: Matcher + ?Sized>(
+    matcher: &M,
+    array: &dyn Array,
+    columns: Option<&[String]>,
+) -> Result<BooleanArray, BuildError> {
+    match array.as_struct_opt() {
+        Some(rows) => scan_batch(matcher, &RecordBatch::from(rows.clone()), columns),
+        None => scan_array(matcher, array),
+    }
+}
+
+/// boolean mask returned to python, exported through the arrow pycapsule interface
+/// (`pyarrow.array(mask)`, `polars.Series(mask)`, ...)
+#[cfg(feature = "python")]
+#[gen_stub_pyclass]
+#[pyclass(module = "deny_filter.matchers", frozen)]
+pub struct BooleanMask {
+    mask: BooleanArray,
+}
+
+#[cfg(feature = "python")]
+#[gen_stub_pymethods]
+#[pymethods]
+impl BooleanMask {
+    /// (schema, array) capsules of the arrow c data interface
+    /// # Errors
+    /// * the schema cannot be exported
+    #[pyo3(signature = (requested_schema = None))]
+    #[allow(clippy::needless_pass_by_value)] // part of the pycapsule protocol, ignored
+    fn __arrow_c_array__<'py>(
+        &self,
+        py: Python<'py>,
+        requested_schema: Option<Bound<'py, PyAny>>,
+    ) -> PyResult<Bound<'py, PyTuple>> {
+        let _ = requested_schema;
+        let (array, schema) = arrow_array::ffi::to_ffi(&self.mask.to_data()).map_err(invalid)?;
+        let schema = PyCapsule::new(py, schema, Some(c"arrow_schema".into()))?;
+        let array = PyCapsule::new(py, array, Some(c"arrow_array".into()))?;
+        PyTuple::new(py, [schema.into_any(), array.into_any()])
+    }
+
+    fn __len__(&self) -> usize {
+        self.mask.len()
+    }
+
+    fn to_list(&self) -> Vec<bool> {
+        self.mask.values().iter().collect()
+    }
+}
+
+/// takes the arrow data of a python object exporting `__arrow_c_array__` (record batches,
+/// arrays) or `__arrow_c_stream__` (tables, readers)
+#[cfg(feature = "python")]
+fn import_arrow(value: &Bound<'_, PyAny>) -> PyResult<Vec<arrow_array::ArrayRef>> {
+    use arrow_array::StructArray;
+    use arrow_array::ffi::{FFI_ArrowArray, FFI_ArrowSchema, from_ffi};
+    use arrow_array::ffi_stream::{ArrowArrayStreamReader, FFI_ArrowArrayStream};
+
+    if value.hasattr("__arrow_c_array__")? {
+        let capsules = value.call_method0("__arrow_c_array__")?;
+        let schema = capsules.get_item(0)?.cast_into::<PyCapsule>()?;
+        let array = capsules.get_item(1)?.cast_into::<PyCapsule>()?;
+        let schema = schema.pointer_checked(Some(c"arrow_schema"))?;
+        let array = array.pointer_checked(Some(c"arrow_array"))?;
+        // SAFETY: the capsule names guarantee the c data interface structs; the array is
+        // moved out and its capsule keeps a released struct, the schema is only borrowed
+        let data = unsafe {
+            let array = std::ptr::replace(
+                array.as_ptr().cast::<FFI_ArrowArray>(),
+                FFI_ArrowArray::empty(),
+            );
+            from_ffi(array, &*schema.as_ptr().cast::<FFI_ArrowSchema>())
+        }
+        .map_err(invalid)?;
+        return Ok(vec![arrow_array::make_array(data)]);
+    }
+    if value.hasattr("__arrow_c_stream__")? {
+        let capsule = value
+            .call_method0("__arrow_c_stream__")?
+            .cast_into::<PyCapsule>()?;
+        let stream = capsule.pointer_checked(Some(c"arrow_array_stream"))?;
+        // SAFETY: named capsule of the c stream interface, the reader takes the stream over
+        let reader = unsafe {
+            ArrowArrayStreamReader::from_raw(stream.as_ptr().cast::<FFI_ArrowArrayStream>())
+        }
+        .map_err(invalid)?;
+        return reader
+            .map(|batch| {
+                let batch = batch.map_err(invalid)?;
+                Ok(std::sync::Arc::new(StructArray::from(batch)) as arrow_array::ArrayRef)
+            })
+            .collect();
+    }
+    Err(pyo3::exceptions::PyTypeError::new_err(
+        "expected arrow data: a record batch, table or string array",
+    ))
+}
+
+/// `scan_arrow` of the python matchers, scans run without the GIL;
+/// returns a `BooleanMask`, typed as any so matchers build without the feature
+/// # Errors
+/// * not arrow data, a missing or non string column
+#[cfg(feature = "python")]
+pub fn py_scan_arrow<'py, M: Matcher + Sync + ?Sized>(
+    matcher: &M,
+    value: &Bound<'py, PyAny>,
+    columns: Option<&[String]>,
+) -> PyResult<Bound<'py, PyAny>> {
+    let chunks = import_arrow(value)?;
+    let mask = value.py().detach(|| {
+        let mut mask = Vec::new();
+        for chunk in &chunks {
+            mask.extend(scan_data(matcher, chunk.as_ref(), columns)?.values());
+        }
+        Ok::<_, BuildError>(BooleanArray::from(mask))
+    })?;
+    Ok(Bound::new(value.py(), BooleanMask { mask })?.into_any())
+}
+
+/// `scan_series` of the python matchers: a polars boolean series named like the string
+/// `series`, the series is exported as a one column frame through the arrow stream interface
+/// # Errors
+/// * not a polars string series, polars not importable
+#[cfg(feature = "python")]
+pub fn py_scan_series<'py, M: Matcher + Sync + ?Sized>(
+    matcher: &M,
+    series: &Bound<'py, PyAny>,
+) -> PyResult<Bound<'py, PyAny>> {
+    let mask = py_scan_arrow(matcher, &series.call_method0("to_frame")?, None)?;
+    let name = series.getattr("name")?;
+    series
+        .py()
+        .import("polars")?
+        .getattr("Series")?
+        .call1((&name, mask))?
+        .call_method1("alias", (name,))
+}
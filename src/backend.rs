@@ -0,0 +1,102 @@
+use std::fmt;
+use std::str::FromStr;
+
+use crate::build_error::BuildError;
+use crate::deny_list::DenyList;
+use crate::deny_list_daac::DenyListDaac;
+use crate::deny_list_packed::{DenyListPacked, TINY_LIST};
+use crate::deny_list_rs::DenyListRs;
+use crate::matcher::Matcher;
+use crate::options::Options;
+
+/// matcher implementations selectable by name
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Backend {
+    /// aho-corasick crate
+    #[default]
+    AhoCorasick,
+    /// regex crate `RegexSet`
+    Regex,
+    /// daachorse crate
+    Daachorse,
+    /// aho-corasick packed (teddy) searcher, for a few short deny words
+    Packed,
+    /// packed for lists of at most `TINY_LIST` words, aho-corasick otherwise
+    Auto,
+}
+
+impl Backend {
+    pub const ALL: [Backend; 5] = [
+        Backend::AhoCorasick,
+        Backend::Regex,
+        Backend::Daachorse,
+        Backend::Packed,
+        Backend::Auto,
+    ];
+
+    #[must_use]
+    pub fn name(self) -> &'static str {
+        match self {
+            Backend::AhoCorasick => "aho-corasick",
+            Backend::Regex => "regex",
+            Backend::Daachorse => "daachorse",
+            Backend::Packed => "packed",
+            Backend::Auto => "auto",
+        }
+    }
+
+    /// backend that `Auto` builds for `words`
+    #[must_use]
+    pub fn select(words: &[String]) -> Backend {
+        if words.len() <= TINY_LIST {
+            Backend::Packed
+        } else {
+            Backend::AhoCorasick
+        }
+    }
+
+    /// builds a matcher of this backend
+    /// # Errors
+    /// * pattern errors of the backend
+    pub fn build(self, words: Vec<String>) -> Result<Box<dyn Matcher + Send + Sync>, BuildError> {
+        self.build_with(words, &Options::default())
+    }
+
+    /// builds a matcher of this backend with matching options
+    /// # Errors
+    /// * pattern errors of the backend
+    pub fn build_with(
+        self,
+        words: Vec<String>,
+        options: &Options,
+    ) -> Result<Box<dyn Matcher + Send + Sync>, BuildError> {
+        Ok(match self {
+            Backend::AhoCorasick => Box::new(DenyList::with_options(words, options)?),
+            Backend::Regex => Box::new(DenyListRs::with_options(words, options)?),
+            Backend::Daachorse => Box::new(DenyListDaac::with_options(words, options)?),
+            Backend::Packed => Box::new(DenyListPacked::with_options(words, options)?),
+            Backend::Auto => return Backend::select(&words).build_with(words, options),
+        })
+    }
+}
+
+impl fmt::Display for Backend {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for Backend {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Backend::ALL
+            .into_iter()
+            .find(|b| b.name() == s)
+            .ok_or_else(|| {
+                format!(
+                    "unknown backend {s:?}, expected aho-corasick, regex, daachorse, packed or auto"
+                )
+            })
+    }
+}
@@ -0,0 +1,74 @@
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
+#[cfg(feature = "python")]
+use pyo3::types::PyBytes;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Mutex, PoisonError};
+use std::thread;
+
+#[cfg(feature = "python")]
+use crate::files::default_workers;
+use crate::matcher::Matcher;
+use crate::traverse;
+
+/// `Matcher::scan_msgpack` of each document of a batch, e.g. the messages of a consumer
+/// poll, on `workers` threads; results are in input order, a malformed document does not
+/// stop the others
+pub fn scan_msgpack_many<M: Matcher + Sync + ?Sized>(
+    matcher: &M,
+    buffers: &[&[u8]],
+    workers: usize,
+) -> Vec<Result<bool, traverse::Error>> {
+    let workers = workers.clamp(1, buffers.len().max(1));
+    if workers == 1 {
+        return buffers
+            .iter()
+            .map(|buf| matcher.scan_msgpack(buf))
+            .collect();
+    }
+    let next = AtomicUsize::new(0);
+    let results = Mutex::new(Vec::with_capacity(buffers.len()));
+    thread::scope(|scope| {
+        for _ in 0..workers {
+            scope.spawn(|| {
+                loop {
+                    let i = next.fetch_add(1, Ordering::Relaxed);
+                    let Some(buf) = buffers.get(i) else {
+                        break;
+                    };
+                    let result = matcher.scan_msgpack(buf);
+                    results
+                        .lock()
+                        .unwrap_or_else(PoisonError::into_inner)
+                        .push((i, result));
+                }
+            });
+        }
+    });
+    let mut results = results.into_inner().unwrap_or_else(PoisonError::into_inner);
+    results.sort_by_key(|(i, _)| *i);
+    results.into_iter().map(|(_, result)| result).collect()
+}
+
+/// `scan_msgpack_many` of the python matchers, without the GIL; the bytes are scanned in
+/// place, not copied
+/// # Errors
+/// * the first truncated or malformed document, with its index
+#[cfg(feature = "python")]
+pub fn py_scan_msgpack_many<M: Matcher + Sync>(
+    py: Python<'_>,
+    matcher: &M,
+    buffers: &[Bound<'_, PyBytes>],
+    workers: Option<usize>,
+) -> PyResult<Vec<bool>> {
+    let buffers: Vec<&[u8]> = buffers.iter().map(PyBytesMethods::as_bytes).collect();
+    let workers = workers.unwrap_or_else(default_workers);
+    py.detach(|| scan_msgpack_many(matcher, &buffers, workers))
+        .into_iter()
+        .enumerate()
+        .map(|(i, result)| {
+            result
+                .map_err(|e| pyo3::exceptions::PyValueError::new_err(format!("document {i}: {e}")))
+        })
+        .collect()
+}
@@ -0,0 +1,145 @@
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
+#[cfg(feature = "python")]
+use pyo3::types::PyDict;
+#[cfg(feature = "python")]
+use pyo3_stub_gen::derive::{gen_stub_pyclass, gen_stub_pyfunction, gen_stub_pymethods};
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use crate::build_error::BuildError;
+use crate::deny_list_auto::CANDIDATES;
+use crate::options::Options;
+
+/// build time, memory and `is_match` latency of one backend over a sample corpus
+#[cfg_attr(feature = "python", gen_stub_pyclass)]
+#[cfg_attr(
+    feature = "python",
+    pyclass(module = "deny_filter.matchers", frozen, get_all, skip_from_py_object)
+)]
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct BackendBench {
+    /// backend name, see `Backend`
+    pub backend: String,
+    /// seconds to build the matcher
+    pub build_seconds: f64,
+    /// heap bytes of the automata as reported by the backend crate
+    pub heap_bytes: usize,
+    /// timed `is_match` calls, samples times iterations
+    pub scans: usize,
+    /// samples that match
+    pub matches: usize,
+    /// scanned megabytes (10^6 bytes) per second
+    pub throughput_mb_s: f64,
+    /// mean latency of one `is_match` call in microseconds
+    pub mean_us: f64,
+    /// median latency in microseconds
+    pub p50_us: f64,
+    /// 99th percentile latency in microseconds
+    pub p99_us: f64,
+}
+
+/// latency at `quantile` of sorted latencies, 0 without latencies
+fn percentile(sorted: &[Duration], quantile: f64) -> f64 {
+    #[allow(
+        clippy::cast_possible_truncation,
+        clippy::cast_sign_loss,
+        clippy::cast_precision_loss
+    )]
+    let i = ((sorted.len().saturating_sub(1)) as f64 * quantile).round() as usize;
+    sorted.get(i).map_or(0.0, |d| d.as_secs_f64() * 1e6)
+}
+
+/// builds each backend of `words` and times `is_match` on each of `samples`
+/// `iterations` times, in backend order; nothing is recorded in the metrics
+/// # Errors
+/// * pattern errors of a backend, invalid options
+pub fn benchmark(
+    words: &[String],
+    samples: &[String],
+    iterations: usize,
+    options: &Options,
+) -> Result<Vec<BackendBench>, BuildError> {
+    let bytes: usize = samples.iter().map(String::len).sum::<usize>() * iterations;
+    CANDIDATES
+        .into_iter()
+        .map(|backend| {
+            let start = Instant::now();
+            let matcher = backend.build_with(words.to_vec(), options)?;
+            let build_seconds = start.elapsed().as_secs_f64();
+            let hits = samples.iter().filter(|s| matcher.is_match(s)).count();
+            let mut latencies = Vec::with_capacity(samples.len() * iterations);
+            for _ in 0..iterations {
+                for sample in samples {
+                    let start = Instant::now();
+                    black_box(matcher.is_match(black_box(sample)));
+                    latencies.push(start.elapsed());
+                }
+            }
+            let total: Duration = latencies.iter().sum();
+            latencies.sort_unstable();
+            #[allow(clippy::cast_precision_loss)]
+            let (throughput_mb_s, mean_us) = if latencies.is_empty() || total.is_zero() {
+                (0.0, 0.0)
+            } else {
+                (
+                    bytes as f64 / total.as_secs_f64() / 1e6,
+                    total.as_secs_f64() * 1e6 / latencies.len() as f64,
+                )
+            };
+            Ok(BackendBench {
+                backend: backend.name().to_string(),
+                build_seconds,
+                heap_bytes: matcher.stats().heap_bytes,
+                scans: latencies.len(),
+                matches: hits,
+                throughput_mb_s,
+                mean_us,
+                p50_us: percentile(&latencies, 0.5),
+                p99_us: percentile(&latencies, 0.99),
+            })
+        })
+        .collect()
+}
+
+#[cfg(feature = "python")]
+#[gen_stub_pymethods]
+#[pymethods]
+impl BackendBench {
+    fn __repr__(&self) -> String {
+        format!(
+            "BackendBench(backend={:?}, build_seconds={:.6}, heap_bytes={}, scans={}, \
+             matches={}, throughput_mb_s={:.1}, mean_us={:.3}, p50_us={:.3}, p99_us={:.3})",
+            self.backend,
+            self.build_seconds,
+            self.heap_bytes,
+            self.scans,
+            self.matches,
+            self.throughput_mb_s,
+            self.mean_us,
+            self.p50_us,
+            self.p99_us
+        )
+    }
+}
+
+/// build time, memory and latency of each backend over `samples`, to choose a backend
+/// from data, keyword options: see `Options`
+/// # Errors
+/// * pattern errors of a backend
+/// * unknown option or invalid value
+#[cfg(feature = "python")]
+#[gen_stub_pyfunction]
+#[pyfunction]
+#[pyo3(name = "benchmark", signature = (words, samples, iterations = 100, **options))]
+#[allow(clippy::needless_pass_by_value)] // python passes owned lists
+pub fn py_benchmark(
+    py: Python<'_>,
+    words: Vec<String>,
+    samples: Vec<String>,
+    iterations: usize,
+    options: Option<&Bound<'_, PyDict>>,
+) -> PyResult<Vec<BackendBench>> {
+    let options = Options::from_kwargs(options)?;
+    Ok(py.detach(|| benchmark(&words, &samples, iterations, &options))?)
+}
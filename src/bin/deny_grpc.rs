@@ -0,0 +1,33 @@
+use clap::Parser;
+use deny_filter::backend::Backend;
+use deny_filter::grpc::DenyFilterService;
+use deny_filter::word_list;
+use std::net::SocketAddr;
+use std::path::PathBuf;
+
+/// gRPC deny list scanning service for sidecar deployments
+#[derive(Parser)]
+#[command(name = "deny-grpc", version)]
+struct Args {
+    /// word list, one word per line or json (`[...]` or `{"words": [...]}`)
+    #[arg(short, long)]
+    words: PathBuf,
+    /// matching backend: aho-corasick, regex, daachorse, packed or auto
+    #[arg(short, long, default_value_t = Backend::AhoCorasick)]
+    backend: Backend,
+    /// address to listen on
+    #[arg(short, long, default_value = "127.0.0.1:50051")]
+    listen: SocketAddr,
+}
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let args = Args::parse();
+    let words = word_list::load(&args.words)?;
+    let matcher = args.backend.build(words)?;
+    tonic::transport::Server::builder()
+        .add_service(DenyFilterService::new(matcher).into_server())
+        .serve(args.listen)
+        .await?;
+    Ok(())
+}
@@ -0,0 +1,143 @@
+use clap::{Parser, ValueEnum};
+use deny_filter::backend::Backend;
+use deny_filter::files;
+use deny_filter::hits::{self, Hit};
+use deny_filter::signed::{self, SignedLoader};
+use deny_filter::word_list;
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+/// exit code when no deny word was found
+const CLEAN: u8 = 0;
+/// exit code when at least one deny word was found
+const FOUND: u8 = 1;
+/// exit code for unreadable word lists or inputs
+const ERROR: u8 = 2;
+
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum Format {
+    /// pick by file extension, stdin is text
+    Auto,
+    Text,
+    Json,
+    /// one json document per line
+    Ndjson,
+    Msgpack,
+}
+
+/// Scans files, directories or stdin for deny words.
+/// Exit code is 0 when clean, 1 when a deny word was found and 2 on errors.
+#[derive(Parser)]
+#[command(name = "deny-scan", version)]
+struct Args {
+    /// word list, one word per line or json (`[...]` or `{"words": [...]}`)
+    #[arg(short, long)]
+    words: PathBuf,
+    /// hex ed25519 public key, the word list must be signed in `<words>.sig`
+    #[arg(long)]
+    public_key: Option<String>,
+    /// matching backend: aho-corasick, regex, daachorse, packed or auto
+    #[arg(short, long, default_value_t = Backend::AhoCorasick)]
+    backend: Backend,
+    /// input format
+    #[arg(short, long, value_enum, default_value_t = Format::Auto)]
+    format: Format,
+    /// print nothing, only set the exit code
+    #[arg(short, long)]
+    quiet: bool,
+    /// files or directories to scan, stdin when empty or `-`
+    paths: Vec<PathBuf>,
+}
+
+fn detect(path: &Path, format: Format) -> hits::Format {
+    match format {
+        Format::Text => hits::Format::Text,
+        Format::Json => hits::Format::Json,
+        Format::Ndjson => hits::Format::Ndjson,
+        Format::Msgpack => hits::Format::Msgpack,
+        Format::Auto => hits::Format::from_path(&path.to_string_lossy()),
+    }
+}
+
+fn report(args: &Args, source: &str, result: Result<Vec<Hit>, String>) -> u8 {
+    match result {
+        Ok(hits) if hits.is_empty() => CLEAN,
+        Ok(hits) => {
+            if !args.quiet {
+                for hit in hits {
+                    println!("{source}:{}: {}", hit.location, hit.pattern);
+                }
+            }
+            FOUND
+        }
+        Err(e) => {
+            eprintln!("deny-scan: {source}: {e}");
+            ERROR
+        }
+    }
+}
+
+/// the word list, verified when a public key is given
+fn load_words(args: &Args) -> io::Result<Vec<String>> {
+    let Some(key) = &args.public_key else {
+        return word_list::load(&args.words);
+    };
+    let key = signed::decode_hex(key)
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "public key is not hex"))?;
+    SignedLoader::new(&key)?.load(&args.words)
+}
+
+fn run(args: &Args) -> u8 {
+    let matcher = match load_words(args)
+        .map_err(|e| e.to_string())
+        .and_then(|words| args.backend.build(words).map_err(|e| e.to_string()))
+    {
+        Ok(matcher) => matcher,
+        Err(e) => {
+            eprintln!("deny-scan: {}: {e}", args.words.display());
+            return ERROR;
+        }
+    };
+
+    let stdin_only = args.paths.is_empty() || args.paths.iter().all(|p| p.as_os_str() == "-");
+    if stdin_only {
+        let mut data = Vec::new();
+        let result = io::stdin()
+            .read_to_end(&mut data)
+            .map_err(|e| e.to_string())
+            .and_then(|_| {
+                hits::collect(matcher.as_ref(), &data, detect(Path::new("-"), args.format))
+            });
+        return report(args, "<stdin>", result);
+    }
+
+    let mut status = CLEAN;
+    for path in &args.paths {
+        let files = match files::walk(path) {
+            Ok(files) => files,
+            Err(e) => {
+                status = status.max(report(
+                    args,
+                    &path.display().to_string(),
+                    Err(e.to_string()),
+                ));
+                continue;
+            }
+        };
+        for file in files {
+            let result = std::fs::read(&file)
+                .map_err(|e| e.to_string())
+                .and_then(|data| {
+                    hits::collect(matcher.as_ref(), &data, detect(&file, args.format))
+                });
+            status = status.max(report(args, &file.display().to_string(), result));
+        }
+    }
+    status
+}
+
+fn main() -> ExitCode {
+    let args = Args::parse();
+    ExitCode::from(run(&args))
+}
@@ -1,10 +1,72 @@
 use deny_filter::pymodule::stub_info;
 use pyo3_stub_gen::Result;
-/// creates .pyi file for pyo3 package
+use pyo3_stub_gen::StubInfo;
+use pyo3_stub_gen::generate::Module;
+use regex::Regex;
+use std::fs;
+
+const PACKAGE: &str = "deny_filter";
+
+/// creates the .pyi files of the pyo3 package and its submodules in `stubs/deny_filter`,
+/// the search path of the type checkers, and the single `deny_filter.pyi` the maturin wheel
+/// ships
 /// # Errors
 /// * invalid package
+/// * a stub cannot be written
 fn main() -> Result<()> {
-    let stub = stub_info()?;
+    let mut stub = stub_info()?;
+    let root = stub.python_root.clone();
+    fs::write(root.join(format!("{PACKAGE}.pyi")), flat(&stub))?;
+    // one file per module, which the single `deny_filter.pyi` of a pure Rust layout cannot
+    // hold
+    stub.is_mixed_layout = true;
+    stub.python_root = root.join("stubs");
     stub.generate()?;
     Ok(())
 }
+
+/// the package with the classes and functions of its submodules, as the package re-exports
+/// them, in one stub; references to `matchers.` and `plugin.` classes are made local
+fn flat(stub: &StubInfo) -> String {
+    let mut flat = stub.modules[PACKAGE].clone();
+    for (name, module) in &stub.modules {
+        if name != PACKAGE {
+            merge(&mut flat, module);
+        }
+    }
+    flat.submodules.clear();
+    flat.module_re_exports.clear();
+    let submodules: Vec<&str> = stub
+        .modules
+        .keys()
+        .filter_map(|name| name.strip_prefix(&format!("{PACKAGE}.")))
+        .collect();
+    let qualified =
+        Regex::new(&format!(r"\b(?:{})\.([A-Z])", submodules.join("|"))).expect("valid regex");
+    let text = flat.format_with_config(stub.config.use_type_statement);
+    let mut out = String::with_capacity(text.len());
+    for line in text.lines() {
+        let import = line
+            .strip_prefix(&format!("from {PACKAGE} import "))
+            .or_else(|| line.strip_prefix("from . import "));
+        if import.is_some_and(|module| submodules.contains(&module)) {
+            continue;
+        }
+        out.push_str(&qualified.replace_all(line, "$1"));
+        out.push('\n');
+    }
+    out
+}
+
+fn merge(flat: &mut Module, module: &Module) {
+    flat.class.extend(module.class.clone());
+    flat.enum_.extend(module.enum_.clone());
+    for (name, functions) in &module.function {
+        flat.function
+            .entry(name)
+            .or_default()
+            .extend(functions.clone());
+    }
+    flat.variables.extend(module.variables.clone());
+    flat.type_aliases.extend(module.type_aliases.clone());
+}
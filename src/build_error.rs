@@ -1,8 +1,33 @@
-use pyo3::PyErr;
-use pyo3::exceptions::PyValueError;
+use std::fmt;
+
+/// deny words could not be compiled into a matcher
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BuildError(pub String);
+
+impl fmt::Display for BuildError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Invalid patterns: {}", self.0)
+    }
+}
+
+impl std::error::Error for BuildError {}
+
+/// wraps any backend error
+pub fn invalid<E: fmt::Display>(e: E) -> BuildError {
+    BuildError(e.to_string())
+}
+
+#[cfg(feature = "python")]
+impl From<BuildError> for pyo3::PyErr {
+    fn from(e: BuildError) -> Self {
+        pyo3::exceptions::PyValueError::new_err(e.to_string())
+    }
+}
+
 /// common funcion to test errors
 /// # Errors
 /// * when ac-corasic or regex set fails to build
-pub fn build_error<E: std::fmt::Display>(e: E) -> PyErr {
-    PyValueError::new_err(format!("Invalid patterns: {e}"))
+#[cfg(feature = "python")]
+pub fn build_error<E: fmt::Display>(e: E) -> pyo3::PyErr {
+    invalid(e).into()
 }
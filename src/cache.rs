@@ -0,0 +1,214 @@
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
+#[cfg(feature = "python")]
+use pyo3_stub_gen::derive::{gen_stub_pyclass, gen_stub_pymethods};
+use std::collections::HashMap;
+use std::hash::{BuildHasher, RandomState};
+use std::sync::atomic::{AtomicU64, Ordering::Relaxed};
+use std::sync::{Arc, Mutex, PoisonError};
+
+use crate::options::Options;
+
+/// verdict of a scanned string and the deny word that matched first, if known
+pub type Verdict = (bool, Option<Arc<str>>);
+
+/// no entry, the end of the recency list
+const NIL: usize = usize::MAX;
+
+/// key of a string: a 128 bit hash from two hashes under independent random keys and its
+/// length, so strings sharing a key can neither be crafted nor met by chance, and the
+/// string itself is not kept
+pub(crate) type Key = (u64, u64, usize);
+
+/// the random hash keys `Key`s are made with
+#[derive(Clone, Debug, Default)]
+pub(crate) struct Keyer(RandomState, RandomState);
+
+impl Keyer {
+    pub(crate) fn key(&self, s: &str) -> Key {
+        (self.0.hash_one(s), self.1.hash_one(s), s.len())
+    }
+}
+
+struct Entry {
+    key: Key,
+    verdict: Verdict,
+    /// more recently used neighbour
+    prev: usize,
+    /// less recently used neighbour
+    next: usize,
+}
+
+/// least recently used map of at most `capacity` entries: a slab of entries linked in
+/// recency order, indexed by key
+struct Lru {
+    index: HashMap<Key, usize>,
+    entries: Vec<Entry>,
+    /// most recently used entry
+    head: usize,
+    /// least recently used entry, evicted first
+    tail: usize,
+    capacity: usize,
+}
+
+impl Lru {
+    fn new(capacity: usize) -> Self {
+        Self {
+            index: HashMap::new(),
+            entries: Vec::new(),
+            head: NIL,
+            tail: NIL,
+            capacity,
+        }
+    }
+
+    fn unlink(&mut self, i: usize) {
+        let (prev, next) = (self.entries[i].prev, self.entries[i].next);
+        match prev {
+            NIL => self.head = next,
+            prev => self.entries[prev].next = next,
+        }
+        match next {
+            NIL => self.tail = prev,
+            next => self.entries[next].prev = prev,
+        }
+    }
+
+    fn push_front(&mut self, i: usize) {
+        self.entries[i].prev = NIL;
+        self.entries[i].next = self.head;
+        match self.head {
+            NIL => self.tail = i,
+            head => self.entries[head].prev = i,
+        }
+        self.head = i;
+    }
+
+    fn get(&mut self, key: Key) -> Option<Verdict> {
+        let i = *self.index.get(&key)?;
+        self.unlink(i);
+        self.push_front(i);
+        Some(self.entries[i].verdict.clone())
+    }
+
+    fn insert(&mut self, key: Key, verdict: Verdict) {
+        if let Some(&i) = self.index.get(&key) {
+            self.entries[i].verdict = verdict;
+            self.unlink(i);
+            self.push_front(i);
+            return;
+        }
+        let i = if self.entries.len() < self.capacity {
+            self.entries.push(Entry {
+                key,
+                verdict,
+                prev: NIL,
+                next: NIL,
+            });
+            self.entries.len() - 1
+        } else {
+            let i = self.tail;
+            self.unlink(i);
+            self.index.remove(&self.entries[i].key);
+            self.entries[i].key = key;
+            self.entries[i].verdict = verdict;
+            i
+        };
+        self.index.insert(key, i);
+        self.push_front(i);
+    }
+}
+
+/// hits, misses and size of a result cache
+#[cfg_attr(feature = "python", gen_stub_pyclass)]
+#[cfg_attr(
+    feature = "python",
+    pyclass(module = "deny_filter.matchers", frozen, get_all, skip_from_py_object)
+)]
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct CacheInfo {
+    /// scans answered from the cache
+    pub hits: u64,
+    /// scans that ran and were cached
+    pub misses: u64,
+    /// cached verdicts
+    pub size: usize,
+    /// cached verdicts at most, see `cache_size`
+    pub capacity: usize,
+}
+
+#[cfg(feature = "python")]
+#[gen_stub_pymethods]
+#[pymethods]
+impl CacheInfo {
+    fn __repr__(&self) -> String {
+        format!(
+            "CacheInfo(hits={}, misses={}, size={}, capacity={})",
+            self.hits, self.misses, self.size, self.capacity
+        )
+    }
+}
+
+/// verdicts of recently scanned strings, so repeated fields (system prompts, disclaimers)
+/// are scanned once; entries are keyed by the `Key` of their string, so the cache holds no
+/// scanned text and no other string can take the verdict of a cached one
+pub struct ResultCache {
+    keyer: Keyer,
+    lru: Mutex<Lru>,
+    hits: AtomicU64,
+    misses: AtomicU64,
+}
+
+impl ResultCache {
+    #[must_use]
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            keyer: Keyer::default(),
+            lru: Mutex::new(Lru::new(capacity)),
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+        }
+    }
+
+    /// cache of `cache_size` verdicts, None without it or when 0
+    #[must_use]
+    pub fn from_options(options: &Options) -> Option<Arc<Self>> {
+        options
+            .cache_size
+            .filter(|size| *size > 0)
+            .map(|size| Arc::new(Self::new(size)))
+    }
+
+    /// cached verdict of `s`, or the verdict of `scan` cached; the scan runs without
+    /// holding the cache lock
+    pub fn verdict(&self, s: &str, scan: impl FnOnce() -> Verdict) -> Verdict {
+        let key = self.keyer.key(s);
+        let cached = self
+            .lru
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .get(key);
+        if let Some(verdict) = cached {
+            self.hits.fetch_add(1, Relaxed);
+            return verdict;
+        }
+        self.misses.fetch_add(1, Relaxed);
+        let verdict = scan();
+        self.lru
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .insert(key, verdict.clone());
+        verdict
+    }
+
+    #[must_use]
+    pub fn info(&self) -> CacheInfo {
+        let lru = self.lru.lock().unwrap_or_else(PoisonError::into_inner);
+        CacheInfo {
+            hits: self.hits.load(Relaxed),
+            misses: self.misses.load(Relaxed),
+            size: lru.index.len(),
+            capacity: lru.capacity,
+        }
+    }
+}
@@ -0,0 +1,178 @@
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
+#[cfg(feature = "python")]
+use pyo3_stub_gen::derive::{gen_stub_pyclass, gen_stub_pymethods};
+use serde_json::Value;
+
+use crate::matcher::Matcher;
+use crate::metrics::Timer;
+use crate::traverse;
+
+/// the chat message with a deny word
+#[cfg_attr(feature = "python", gen_stub_pyclass)]
+#[cfg_attr(
+    feature = "python",
+    pyclass(module = "deny_filter.plugin", frozen, get_all, skip_from_py_object)
+)]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ChatHit {
+    /// `role` of the message, empty when missing
+    pub role: String,
+    /// position of the message in the list
+    pub index: usize,
+    /// position of the text block in a list `content`, None for string content
+    pub part: Option<usize>,
+    /// deny word that matched first
+    pub word: String,
+}
+
+#[cfg(feature = "python")]
+#[gen_stub_pymethods]
+#[pymethods]
+impl ChatHit {
+    fn __repr__(&self) -> String {
+        format!(
+            "ChatHit(role={:?}, index={}, part={}, word={:?})",
+            self.role,
+            self.index,
+            self.part.map_or("None".to_string(), |p| p.to_string()),
+            self.word
+        )
+    }
+}
+
+/// deny word of `text`, None when clean
+fn word<M: Matcher + ?Sized>(matcher: &M, text: &str) -> Option<String> {
+    matcher
+        .check(text)
+        .then(|| matcher.first_match(text).unwrap_or_default().to_string())
+}
+
+/// first message of a `[{"role": ..., "content": ...}]` list whose content `hit` finds a
+/// deny word in, with the position of the content block
+fn scan_messages<M: Matcher + ?Sized>(
+    matcher: &M,
+    messages: &Value,
+    hit: impl Fn(&M, &Value) -> Option<(Option<usize>, String)>,
+) -> Option<ChatHit> {
+    let timer = Timer::start();
+    let found = messages
+        .as_array()
+        .into_iter()
+        .flatten()
+        .enumerate()
+        .find_map(|(index, message)| {
+            let (part, word) = hit(matcher, message.get("content")?)?;
+            Some(ChatHit {
+                role: message
+                    .get("role")
+                    .and_then(Value::as_str)
+                    .unwrap_or_default()
+                    .to_string(),
+                index,
+                part,
+                word,
+            })
+        });
+    matcher
+        .metrics()
+        .record_scan(timer.elapsed(), found.is_some());
+    found
+}
+
+/// deny word of a `content` string, or of the first list block `block` finds one in
+fn content_hit<M: Matcher + ?Sized>(
+    matcher: &M,
+    content: &Value,
+    block: fn(&M, &Value) -> Option<String>,
+) -> Option<(Option<usize>, String)> {
+    match content {
+        Value::String(s) => Some((None, word(matcher, s)?)),
+        Value::Array(blocks) => blocks
+            .iter()
+            .enumerate()
+            .find_map(|(i, b)| Some((Some(i), block(matcher, b)?))),
+        _ => None,
+    }
+}
+
+/// deny word of the `text` of a `{"type": "text"}` block; image, audio and file blocks
+/// have no text
+fn text_block<M: Matcher + ?Sized>(matcher: &M, block: &Value) -> Option<String> {
+    match block.get("type")?.as_str()? {
+        "text" => word(matcher, block.get("text")?.as_str()?),
+        _ => None,
+    }
+}
+
+/// deny word of an anthropic content block: `text`, the string values of a `tool_use`
+/// input, the content of a `tool_result` with its nested blocks and plain text documents;
+/// images and thinking are not scanned
+fn anthropic_block<M: Matcher + ?Sized>(matcher: &M, block: &Value) -> Option<String> {
+    match block.get("type")?.as_str()? {
+        "text" => word(matcher, block.get("text")?.as_str()?),
+        "tool_use" => {
+            let mut found = None;
+            traverse::json(block.get("input")?, &mut |_, s| {
+                found = word(matcher, s);
+                found.is_some()
+            });
+            found
+        }
+        "tool_result" => {
+            content_hit(matcher, block.get("content")?, anthropic_block).map(|(_, word)| word)
+        }
+        "document" if block.pointer("/source/type")? == "text" => {
+            word(matcher, block.pointer("/source/data")?.as_str()?)
+        }
+        _ => None,
+    }
+}
+
+/// first message of a chat completion `[{"role": ..., "content": ...}]` list with a deny word
+/// in its textual content; other keys, e.g. `name` or `tool_calls`, are not scanned
+pub fn scan_chat_messages<M: Matcher + ?Sized>(matcher: &M, messages: &Value) -> Option<ChatHit> {
+    scan_messages(matcher, messages, |matcher, content| {
+        content_hit(matcher, content, text_block)
+    })
+}
+
+/// first message of an anthropic messages list with a deny word in a text, `tool_use`,
+/// `tool_result` or text document block; `part` is the top level block of the message
+pub fn scan_anthropic_messages<M: Matcher + ?Sized>(
+    matcher: &M,
+    messages: &Value,
+) -> Option<ChatHit> {
+    scan_messages(matcher, messages, |matcher, content| {
+        content_hit(matcher, content, anthropic_block)
+    })
+}
+
+/// python messages as json
+#[cfg(feature = "python")]
+fn to_json(messages: &Bound<'_, PyAny>) -> PyResult<Value> {
+    pythonize::depythonize(messages)
+        .map_err(|e| pyo3::exceptions::PyTypeError::new_err(e.to_string()))
+}
+
+/// `scan_chat_messages` of the python matchers
+/// # Errors
+/// * messages that are not json-like python values
+#[cfg(feature = "python")]
+pub fn py_scan_chat_messages<M: Matcher + ?Sized>(
+    matcher: &M,
+    messages: &Bound<'_, PyAny>,
+) -> PyResult<Option<ChatHit>> {
+    Ok(scan_chat_messages(matcher, &to_json(messages)?))
+}
+
+/// `scan_anthropic_messages` of the python matchers
+/// # Errors
+/// * messages that are not json-like python values
+#[cfg(feature = "python")]
+pub fn py_scan_anthropic_messages<M: Matcher + ?Sized>(
+    matcher: &M,
+    messages: &Bound<'_, PyAny>,
+) -> PyResult<Option<ChatHit>> {
+    Ok(scan_anthropic_messages(matcher, &to_json(messages)?))
+}
@@ -0,0 +1,481 @@
+use aho_corasick::AhoCorasick;
+use std::cmp::Reverse;
+use std::collections::{BTreeMap, HashSet};
+use std::iter;
+use std::sync::{Arc, OnceLock};
+
+use crate::build_error::{BuildError, invalid};
+use crate::cache::ResultCache;
+use crate::decode::{Decoded, Decoder};
+use crate::engine::{Engine, Span};
+use crate::exceptions::Exceptions;
+use crate::explain::Explanation;
+use crate::keys::KeyFilter;
+use crate::limits::Limits;
+use crate::matches::{Match, set_char_offsets};
+use crate::normalize::{Normalizer, OffsetMap, bare, reversed};
+use crate::options::{CaseFold, ListMetadata, MatchKind, Options, WordMatch};
+use crate::proximity::Proximity;
+use crate::report::BuildReport;
+use crate::score::Weights;
+use crate::stats::Stats;
+
+/// deny words of one match kind, searched with their own automaton
+#[derive(Clone)]
+struct Part<E> {
+    normalizer: Normalizer,
+    engine: E,
+    /// deny word index of each pattern
+    words: Vec<usize>,
+    exceptions: Exceptions,
+    patterns: Vec<String>,
+    /// number of patterns before the reversed spellings
+    forward: usize,
+    /// standard (all matches) automaton, built on the first overlapping search
+    overlapping: OnceLock<Result<AhoCorasick, BuildError>>,
+}
+
+impl<E: Engine> Part<E> {
+    /// hits of the normalized text not suppressed by an exception
+    fn hits<'a>(&'a self, text: &'a str) -> impl Iterator<Item = Span> + 'a {
+        self.engine
+            .find_iter(text)
+            .filter(|h| !self.exceptions.suppressed(text, h.pattern, h.start, h.end))
+    }
+
+    fn overlapping(&self) -> Result<&AhoCorasick, BuildError> {
+        self.overlapping
+            .get_or_init(|| AhoCorasick::new(&self.patterns).map_err(invalid))
+            .as_ref()
+            .map_err(Clone::clone)
+    }
+}
+
+/// deny list compiled for one backend: an automaton per match kind,
+/// contextual exceptions and proximity rules, presented as one matcher
+#[derive(Clone)]
+pub struct Compiled<E> {
+    /// deny words as given
+    entries: Vec<String>,
+    words: Vec<String>,
+    /// indexes of deny words repeating an earlier one after normalization, not compiled
+    duplicates: Vec<usize>,
+    parts: Vec<Part<E>>,
+    proximity: Proximity,
+    /// decodings of the input scanned as well
+    decoder: Decoder,
+    match_kind: MatchKind,
+    limits: Limits,
+    keys: KeyFilter,
+    weights: Weights,
+    metadata: ListMetadata,
+    report: OnceLock<BuildReport>,
+    /// verdicts of recently scanned strings, shared by clones
+    cache: Option<Arc<ResultCache>>,
+}
+
+/// deny words as stored and reported: lowercase, as given when matching is case sensitive
+pub(crate) fn stored_words(words: &[String], case_fold: CaseFold) -> Vec<String> {
+    words.iter().map(|w| stored_word(w, case_fold)).collect()
+}
+
+pub(crate) fn stored_word(word: &str, case_fold: CaseFold) -> String {
+    if case_fold == CaseFold::None {
+        word.to_string()
+    } else {
+        word.to_lowercase()
+    }
+}
+
+/// option keys in the form of `stored_words`, all must be deny words
+fn word_keys<'a, V>(
+    map: &'a BTreeMap<String, V>,
+    words: &[String],
+    case_fold: CaseFold,
+    what: &str,
+) -> Result<BTreeMap<String, &'a V>, BuildError> {
+    map.iter()
+        .map(|(word, value)| {
+            let word = stored_word(word, case_fold);
+            if words.contains(&word) {
+                Ok((word, value))
+            } else {
+                Err(invalid(format!("{what} for unknown deny word {word:?}")))
+            }
+        })
+        .collect()
+}
+
+/// indexes of the non-blank deny words of each match kind present, in list order
+/// # Errors
+/// * match kind for a word not in the list
+pub(crate) fn kinds(
+    words: &[String],
+    options: &Options,
+) -> Result<Vec<(WordMatch, Vec<usize>)>, BuildError> {
+    let word_match = word_keys(&options.word_match, words, options.case_fold, "match kind")?;
+    let default = if options.whole_word {
+        WordMatch::WholeWord
+    } else {
+        WordMatch::Substring
+    };
+    Ok(WordMatch::ALL
+        .into_iter()
+        .map(|kind| {
+            let ids: Vec<usize> = (0..words.len())
+                .filter(|i| !words[*i].trim().is_empty())
+                .filter(|i| word_match.get(&words[*i]).map_or(default, |k| **k) == kind)
+                .collect();
+            (kind, ids)
+        })
+        .filter(|(_, ids)| !ids.is_empty())
+        .collect())
+}
+
+impl<E: Engine> Compiled<E> {
+    /// # Errors
+    /// * pattern errors of the backend
+    /// * deny word without word characters in whole word mode
+    /// * exception or match kind for a word not in the list
+    /// * negative or not finite weight
+    ///
+    /// empty and blank deny words are dropped, they would match everything;
+    /// deny words equal to an earlier one after normalization are compiled once;
+    /// with `reversed` each word is compiled spelled backwards as well
+    pub fn new(entries: Vec<String>, options: &Options) -> Result<Self, BuildError> {
+        let words = stored_words(&entries, options.case_fold);
+        let exceptions = word_keys(&options.exceptions, &words, options.case_fold, "exception")?;
+        let mut parts = Vec::new();
+        let mut duplicates = Vec::new();
+        for (kind, ids) in kinds(&words, options)? {
+            let normalizer = Normalizer::new(kind, options);
+            let mut patterns = Vec::with_capacity(ids.len());
+            let mut unique = Vec::with_capacity(ids.len());
+            let mut seen = HashSet::new();
+            for i in ids {
+                let pattern = normalizer.pattern(&words[i])?;
+                // the first of equal patterns is kept, the automata reject duplicates
+                if seen.insert(pattern.clone()) {
+                    patterns.push(pattern);
+                    unique.push(i);
+                } else {
+                    duplicates.push(i);
+                }
+            }
+            let mut ids = unique;
+            let mut part_exceptions = ids
+                .iter()
+                .map(|i| exceptions.get(&words[*i]).map_or(&[][..], |p| p.as_slice()))
+                .collect::<Vec<_>>();
+            let forward = patterns.len();
+            if options.reversed {
+                // reversed spellings report their deny word, palindromes and reversals of
+                // other deny words are not added twice
+                for j in 0..forward {
+                    let pattern = reversed(&patterns[j]);
+                    if seen.insert(pattern.clone()) {
+                        patterns.push(pattern);
+                        ids.push(ids[j]);
+                        part_exceptions.push(&[]);
+                    }
+                }
+            }
+            parts.push(Part {
+                engine: E::build(&patterns, options.match_kind)?,
+                exceptions: Exceptions::new(&part_exceptions, &normalizer, options)?,
+                normalizer,
+                words: ids,
+                patterns,
+                forward,
+                overlapping: OnceLock::new(),
+            });
+        }
+        Ok(Self {
+            entries,
+            words,
+            duplicates,
+            parts,
+            proximity: Proximity::new(options)?,
+            decoder: Decoder::new(options),
+            match_kind: options.match_kind,
+            limits: Limits::new(options),
+            keys: KeyFilter::new(options)?,
+            weights: Weights::new(options)?,
+            metadata: options.metadata(),
+            report: OnceLock::new(),
+            cache: ResultCache::from_options(options),
+        })
+    }
+
+    /// graphviz digraph of the normalized pattern tries, for small lists
+    #[cfg(feature = "dot")]
+    #[must_use]
+    pub fn to_dot(&self) -> String {
+        crate::dot::tries(self.parts.iter().map(|part| {
+            let patterns = part
+                .patterns
+                .iter()
+                .zip(&part.words)
+                .map(|(pattern, i)| (pattern.as_str(), self.words[*i].as_str()))
+                .collect();
+            (part.normalizer.kind(), patterns)
+        }))
+    }
+
+    /// pattern and automaton sizes of all parts
+    #[must_use]
+    pub fn stats(&self) -> Stats {
+        let mut stats = Stats::default();
+        for part in &self.parts {
+            stats.add(&part.patterns, part.engine.heap_bytes());
+        }
+        stats
+    }
+
+    /// dropped, duplicate, altered and subsuming deny words, computed on first use;
+    /// reversed spellings are left out
+    pub fn build_report(&self) -> &BuildReport {
+        self.report.get_or_init(|| {
+            BuildReport::new(
+                &self.entries,
+                &self.duplicates,
+                self.parts
+                    .iter()
+                    .map(|part| (&part.words[..part.forward], &part.patterns[..part.forward])),
+            )
+        })
+    }
+
+    #[must_use]
+    pub fn metadata(&self) -> &ListMetadata {
+        &self.metadata
+    }
+
+    #[must_use]
+    pub fn limits(&self) -> &Limits {
+        &self.limits
+    }
+
+    #[must_use]
+    pub fn ignored_keys(&self) -> &KeyFilter {
+        &self.keys
+    }
+
+    #[must_use]
+    pub fn weights(&self) -> &Weights {
+        &self.weights
+    }
+
+    #[must_use]
+    pub fn result_cache(&self) -> Option<&ResultCache> {
+        self.cache.as_deref()
+    }
+
+    /// deny words in lowercase, as given with `CaseFold::None`
+    #[must_use]
+    pub fn words(&self) -> &[String] {
+        &self.words
+    }
+
+    /// whether `s` itself or one of its decodings has a deny word
+    #[must_use]
+    pub fn is_match(&self, s: &str) -> bool {
+        self.is_match_in(s)
+            || self
+                .decoder
+                .decode(s)
+                .iter()
+                .any(|d| self.is_match_in(&d.text))
+    }
+
+    fn is_match_in(&self, s: &str) -> bool {
+        self.parts.iter().any(|part| {
+            part.normalizer.inputs(s).iter().any(|text| {
+                if part.exceptions.is_empty() {
+                    part.engine.is_match(text)
+                } else {
+                    part.hits(text).next().is_some()
+                }
+            })
+        }) || self.proximity.first_match(s).is_some()
+    }
+
+    /// deny word of the first hit (parts in `WordMatch::ALL` order), then proximity rules,
+    /// in `s` and then in its decodings
+    #[must_use]
+    pub fn first_match(&self, s: &str) -> Option<&str> {
+        self.first_match_in(s).or_else(|| {
+            self.decoder
+                .decode(s)
+                .iter()
+                .find_map(|d| self.first_match_in(&d.text))
+        })
+    }
+
+    fn first_match_in(&self, s: &str) -> Option<&str> {
+        self.parts
+            .iter()
+            .find_map(|part| {
+                part.normalizer.inputs(s).iter().find_map(|text| {
+                    let hit = part.hits(text).next()?;
+                    Some(self.words[part.words[hit.pattern]].as_str())
+                })
+            })
+            .or_else(|| self.proximity.first_match(s))
+    }
+
+    /// the `first_match` decision with its offsets, decodings and normalizations
+    #[must_use]
+    pub fn explain(&self, s: &str) -> Option<Explanation> {
+        self.explain_in(s, None).or_else(|| {
+            self.decoder
+                .decode(s)
+                .iter()
+                .find_map(|d| self.explain_in(s, Some(d)))
+        })
+    }
+
+    fn explain_in(&self, s: &str, decoded: Option<&Decoded>) -> Option<Explanation> {
+        let input = decoded.map_or(s, |d| d.text.as_str());
+        let layers = decoded
+            .into_iter()
+            .flat_map(|d| &d.layers)
+            .map(ToString::to_string);
+        for part in &self.parts {
+            for (text, map) in part.normalizer.inputs_mapped(input) {
+                let Some(hit) = part.hits(&text).next() else {
+                    continue;
+                };
+                let mut found = [self.to_match(part, &map, hit, decoded)];
+                set_char_offsets(s, &mut found);
+                let [found] = found;
+                return Some(Explanation {
+                    word: found.word.clone(),
+                    hit: Some(found),
+                    pattern: bare(&part.patterns[hit.pattern]).to_string(),
+                    normalized: bare(&text[hit.start..hit.end]).to_string(),
+                    steps: layers.chain(part.normalizer.steps()).collect(),
+                    path: None,
+                });
+            }
+        }
+        let label = self.proximity.first_match(input)?;
+        Some(Explanation {
+            word: label.to_string(),
+            hit: None,
+            pattern: label.to_string(),
+            normalized: String::new(),
+            steps: layers.chain(self.proximity.steps()).collect(),
+            path: None,
+        })
+    }
+
+    fn to_match(
+        &self,
+        part: &Part<E>,
+        map: &OffsetMap,
+        hit: Span,
+        decoded: Option<&Decoded>,
+    ) -> Match {
+        let mut range = Normalizer::original(map, hit.start, hit.end);
+        if let Some(decoded) = decoded {
+            range = decoded.original(range);
+        }
+        Match {
+            word: self.words[part.words[hit.pattern]].clone(),
+            start: range.start,
+            end: range.end,
+            char_start: 0,
+            char_end: 0,
+        }
+    }
+
+    /// leftmost non-overlapping deny word occurrences by the match kind, ordered by position,
+    /// hits in decodings spanning the decoded input; suppressed hits are left out, proximity
+    /// rules are not occurrences
+    #[must_use]
+    pub fn find_all(&self, s: &str) -> Vec<Match> {
+        let mut found = Vec::new();
+        let mut searched = 0;
+        let decodings = self.decoder.decode(s);
+        for decoded in iter::once(None).chain(decodings.iter().map(Some)) {
+            let input = decoded.map_or(s, |d| d.text.as_str());
+            for part in &self.parts {
+                for (text, map) in part.normalizer.inputs_mapped(input) {
+                    searched += 1;
+                    found.extend(
+                        part.hits(&text)
+                            .map(|hit| self.to_match(part, &map, hit, decoded)),
+                    );
+                }
+            }
+        }
+        if searched > 1 {
+            // parts, decodings and collapsed forms are searched separately, their hits may
+            // overlap each other
+            match self.match_kind {
+                MatchKind::LeftmostFirst => found.sort_by_key(|m| m.start),
+                MatchKind::LeftmostLongest => found.sort_by_key(|m| (m.start, Reverse(m.end))),
+            }
+            let mut end = 0;
+            found.retain(|m| {
+                let keep = m.start >= end;
+                if keep {
+                    end = m.end;
+                }
+                keep
+            });
+        }
+        set_char_offsets(s, &mut found);
+        found
+    }
+
+    /// labels of the proximity rules satisfied by `s` or one of its decodings, in rule order
+    #[must_use]
+    pub fn proximity_matches(&self, s: &str) -> Vec<&str> {
+        let mut found: Vec<&str> = self.proximity.matches(s).collect();
+        for decoded in self.decoder.decode(s) {
+            for label in self.proximity.matches(&decoded.text) {
+                if !found.contains(&label) {
+                    found.push(label);
+                }
+            }
+        }
+        found
+    }
+
+    /// every deny word occurrence including overlapping ones, ordered by position, the ones
+    /// in decodings too; suppressed hits are left out, proximity rules are not occurrences
+    /// # Errors
+    /// * the overlapping automaton cannot be built
+    pub fn find_overlapping(&self, s: &str) -> Result<Vec<Match>, BuildError> {
+        let mut found = Vec::new();
+        let decodings = self.decoder.decode(s);
+        for decoded in iter::once(None).chain(decodings.iter().map(Some)) {
+            let input = decoded.map_or(s, |d| d.text.as_str());
+            for part in &self.parts {
+                let ac = part.overlapping()?;
+                for (text, map) in part.normalizer.inputs_mapped(input) {
+                    for m in ac.find_overlapping_iter(&text) {
+                        let hit = Span {
+                            pattern: m.pattern().as_usize(),
+                            start: m.start(),
+                            end: m.end(),
+                        };
+                        if !part
+                            .exceptions
+                            .suppressed(&text, hit.pattern, hit.start, hit.end)
+                        {
+                            found.push(self.to_match(part, &map, hit, decoded));
+                        }
+                    }
+                }
+            }
+        }
+        found.sort_by(|a, b| (a.start, a.end, &a.word).cmp(&(b.start, b.end, &b.word)));
+        // a word found in the input and in a decoding or collapsed form of it is one occurrence
+        found.dedup_by(|a, b| (a.start, a.end, &a.word) == (b.start, b.end, &b.word));
+        set_char_offsets(s, &mut found);
+        Ok(found)
+    }
+}
@@ -0,0 +1,368 @@
+use std::collections::{BTreeMap, HashSet};
+use std::iter;
+use std::ops::Range;
+
+use crate::normalize::{OffsetMap, remap};
+use crate::options::Options;
+
+/// nested decodings when `decode_depth` is not set
+pub const DEFAULT_DECODE_DEPTH: usize = 2;
+
+/// value of a base64 digit, standard and url-safe alphabets
+fn base64_digit(c: u8) -> Option<u32> {
+    match c {
+        b'A'..=b'Z' => Some(u32::from(c - b'A')),
+        b'a'..=b'z' => Some(u32::from(c - b'a') + 26),
+        b'0'..=b'9' => Some(u32::from(c - b'0') + 52),
+        b'+' | b'-' => Some(62),
+        b'/' | b'_' => Some(63),
+        _ => None,
+    }
+}
+
+/// bytes of a base64 string, standard or url-safe, padding optional; None for other
+/// characters or a length no encoder produces
+#[must_use]
+pub fn decode_base64(s: &str) -> Option<Vec<u8>> {
+    let s = s.trim().trim_end_matches('=');
+    if s.len() % 4 == 1 {
+        return None;
+    }
+    let mut out = Vec::with_capacity(s.len() * 3 / 4);
+    for chunk in s.as_bytes().chunks(4) {
+        let mut n = 0;
+        for (i, c) in chunk.iter().enumerate() {
+            n |= base64_digit(*c)? << (18 - 6 * i);
+        }
+        let bytes = n.to_be_bytes();
+        out.extend_from_slice(&bytes[1..chunk.len()]);
+    }
+    Some(out)
+}
+
+/// bytes of a hex string of even length, either case
+#[must_use]
+pub fn decode_hex(s: &str) -> Option<Vec<u8>> {
+    let s = s.trim();
+    if s.len() % 2 == 1 || !s.bytes().all(|b| b.is_ascii_hexdigit()) {
+        return None;
+    }
+    (0..s.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&s[i..i + 2], 16).ok())
+        .collect()
+}
+
+/// value of a base32 digit, RFC 4648 alphabet in either case
+fn base32_digit(c: u8) -> Option<u64> {
+    match c.to_ascii_uppercase() {
+        c @ b'A'..=b'Z' => Some(u64::from(c - b'A')),
+        c @ b'2'..=b'7' => Some(u64::from(c - b'2') + 26),
+        _ => None,
+    }
+}
+
+/// bytes of a base32 string, padding optional; None for other characters or a length
+/// no encoder produces
+#[must_use]
+pub fn decode_base32(s: &str) -> Option<Vec<u8>> {
+    let s = s.trim().trim_end_matches('=');
+    if matches!(s.len() % 8, 1 | 3 | 6) {
+        return None;
+    }
+    let mut out = Vec::with_capacity(s.len() * 5 / 8);
+    for chunk in s.as_bytes().chunks(8) {
+        let mut n = 0;
+        for (i, c) in chunk.iter().enumerate() {
+            n |= base32_digit(*c)? << (35 - 5 * i);
+        }
+        let bytes = n.to_be_bytes();
+        out.extend_from_slice(&bytes[3..3 + chunk.len() * 5 / 8]);
+    }
+    Some(out)
+}
+
+/// utf-8 text of decoded bytes without control characters
+fn text(bytes: Vec<u8>) -> Option<String> {
+    let text = String::from_utf8(bytes).ok()?;
+    (!text.chars().any(|c| c.is_control() && !c.is_whitespace())).then_some(text)
+}
+
+/// encoded tokens shorter than this are left alone, plain words often look encoded
+const MIN_ENCODED_LEN: usize = 8;
+
+/// text a token that looks like base64 decodes to: at least 8 base64 characters
+/// decoding to utf-8 without control characters, so plain words are left alone
+#[must_use]
+pub fn base64_text(token: &str) -> Option<String> {
+    if token.len() < MIN_ENCODED_LEN {
+        return None;
+    }
+    text(decode_base64(token)?)
+}
+
+/// text a token that looks like hex decodes to, e.g. "766f696c61", as `base64_text`
+#[must_use]
+pub fn hex_text(token: &str) -> Option<String> {
+    if token.len() < MIN_ENCODED_LEN {
+        return None;
+    }
+    text(decode_hex(token)?)
+}
+
+/// text a token that looks like base32 decodes to, as `base64_text`
+#[must_use]
+pub fn base32_text(token: &str) -> Option<String> {
+    if token.len() < MIN_ENCODED_LEN {
+        return None;
+    }
+    text(decode_base32(token)?)
+}
+
+/// key and string value texts of a json value, depth first
+fn json_strings(value: &serde_json::Value, out: &mut Vec<String>) {
+    match value {
+        serde_json::Value::String(s) => out.push(s.clone()),
+        serde_json::Value::Array(items) => items.iter().for_each(|v| json_strings(v, out)),
+        serde_json::Value::Object(map) => {
+            for (key, value) in map {
+                out.push(key.clone());
+                json_strings(value, out);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// keys and string values of the header and claims of a JWT-shaped token
+/// `header.claims.signature`, e.g. "eyJhbGciOiJub25lIn0.eyJzdWIiOiJ2b2lsYSJ9.", joined
+/// by spaces; both parts must be base64url json objects, the signature is not verified
+#[must_use]
+pub fn jwt_text(token: &str) -> Option<String> {
+    let mut parts = token.split('.');
+    let (header, claims, _signature) = (parts.next()?, parts.next()?, parts.next()?);
+    if parts.next().is_some() || !header.starts_with("eyJ") {
+        return None;
+    }
+    let mut strings = Vec::new();
+    for part in [header, claims] {
+        let value: serde_json::Value = serde_json::from_slice(&decode_base64(part)?).ok()?;
+        if !value.is_object() {
+            return None;
+        }
+        json_strings(&value, &mut strings);
+    }
+    Some(strings.join(" "))
+}
+
+/// `s` with the runs of `alphabet` chars that `decode` takes replaced by their text,
+/// the decoded bytes spanning the whole run; None when no run decodes
+fn decode_tokens(
+    s: &str,
+    alphabet: fn(char) -> bool,
+    decode: fn(&str) -> Option<String>,
+) -> Option<(String, OffsetMap)> {
+    let mut text = String::with_capacity(s.len());
+    let mut map = Vec::with_capacity(s.len());
+    let mut decoded = false;
+    let mut rest = 0;
+    let runs = s.split(|c| !alphabet(c)).filter(|run| !run.is_empty());
+    for run in runs {
+        let start = run.as_ptr() as usize - s.as_ptr() as usize;
+        let Some(plain) = decode(run) else {
+            continue;
+        };
+        text.push_str(&s[rest..start]);
+        map.extend((rest..start).map(|i| (i, i + 1)));
+        text.push_str(&plain);
+        map.extend(iter::repeat_n((start, start + run.len()), plain.len()));
+        rest = start + run.len();
+        decoded = true;
+    }
+    text.push_str(&s[rest..]);
+    map.extend((rest..s.len()).map(|i| (i, i + 1)));
+    decoded.then_some((text, map))
+}
+
+/// a decoding of the whole input text
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Layer {
+    Rot13,
+    Substitutions,
+    Hex,
+    Base32,
+    Base64,
+    Jwt,
+}
+
+impl Layer {
+    fn name(self) -> &'static str {
+        match self {
+            Layer::Rot13 => "rot13",
+            Layer::Substitutions => "substitutions",
+            Layer::Hex => "hex",
+            Layer::Base32 => "base32",
+            Layer::Base64 => "base64",
+            Layer::Jwt => "jwt",
+        }
+    }
+}
+
+/// text decoded from the input with the input byte span of each of its bytes
+#[derive(Clone, Debug)]
+pub struct Decoded {
+    pub text: String,
+    pub map: OffsetMap,
+    /// layers applied, innermost first
+    pub layers: Vec<&'static str>,
+    /// length of the input
+    len: usize,
+}
+
+impl Decoded {
+    fn plain(s: &str) -> Self {
+        Self {
+            text: s.to_string(),
+            map: (0..s.len()).map(|i| (i, i + 1)).collect(),
+            layers: Vec::new(),
+            len: s.len(),
+        }
+    }
+
+    /// input byte range of the decoded `range`
+    #[must_use]
+    pub fn original(&self, range: Range<usize>) -> Range<usize> {
+        if range.start < range.end {
+            self.map[range.start].0..self.map[range.end - 1].1
+        } else {
+            let at = self.map.get(range.start).map_or(self.len, |span| span.0);
+            at..at
+        }
+    }
+}
+
+/// `s` with every char replaced by `f`, mapped char by char
+fn per_char(s: &str, f: impl Fn(char) -> char) -> (String, OffsetMap) {
+    let mut text = String::with_capacity(s.len());
+    let mut map = Vec::with_capacity(s.len());
+    for (i, c) in s.char_indices() {
+        let r = f(c);
+        text.push(r);
+        map.extend(iter::repeat_n((i, i + c.len_utf8()), r.len_utf8()));
+    }
+    (text, map)
+}
+
+fn rot13(c: char) -> char {
+    match c {
+        'a'..='z' => char::from((c as u8 - b'a' + 13) % 26 + b'a'),
+        'A'..='Z' => char::from((c as u8 - b'A' + 13) % 26 + b'A'),
+        _ => c,
+    }
+}
+
+/// decoding layers scanned in addition to the input, e.g. ROT13, a leetspeak map,
+/// base64 tokens or JWT claims
+#[derive(Clone, Debug, Default)]
+pub struct Decoder {
+    layers: Vec<Layer>,
+    substitutions: BTreeMap<char, char>,
+    depth: usize,
+}
+
+impl Decoder {
+    #[must_use]
+    pub fn new(options: &Options) -> Self {
+        let mut layers = Vec::new();
+        if options.rot13 {
+            layers.push(Layer::Rot13);
+        }
+        if !options.substitutions.is_empty() {
+            layers.push(Layer::Substitutions);
+        }
+        if options.hex {
+            layers.push(Layer::Hex);
+        }
+        if options.base32 {
+            layers.push(Layer::Base32);
+        }
+        if options.base64 {
+            layers.push(Layer::Base64);
+        }
+        if options.jwt {
+            layers.push(Layer::Jwt);
+        }
+        Self {
+            layers,
+            substitutions: options.substitutions.clone(),
+            depth: options.decode_depth.unwrap_or(DEFAULT_DECODE_DEPTH),
+        }
+    }
+
+    /// decoding of `s` by one layer, None when the layer changes nothing
+    fn apply(&self, layer: Layer, s: &str) -> Option<(String, OffsetMap)> {
+        match layer {
+            Layer::Rot13 => s
+                .bytes()
+                .any(|b| b.is_ascii_alphabetic())
+                .then(|| per_char(s, rot13)),
+            Layer::Substitutions => s
+                .chars()
+                .any(|c| self.substitutions.contains_key(&c))
+                .then(|| per_char(s, |c| self.substitutions.get(&c).copied().unwrap_or(c))),
+            Layer::Hex => decode_tokens(s, |c| c.is_ascii_hexdigit(), hex_text),
+            Layer::Base32 => decode_tokens(
+                s,
+                |c| c.is_ascii_alphabetic() || ('2'..='7').contains(&c) || c == '=',
+                base32_text,
+            ),
+            Layer::Base64 => decode_tokens(
+                s,
+                |c| c.is_ascii_alphanumeric() || "+/-_=".contains(c),
+                base64_text,
+            ),
+            Layer::Jwt => decode_tokens(
+                s,
+                |c| c.is_ascii_alphanumeric() || "-_.".contains(c),
+                jwt_text,
+            ),
+        }
+    }
+
+    /// distinct decodings of `s` other than `s` itself, up to `depth` layers nested,
+    /// shallow ones first; none without layers
+    #[must_use]
+    pub fn decode(&self, s: &str) -> Vec<Decoded> {
+        if self.layers.is_empty() {
+            return Vec::new();
+        }
+        let mut seen = HashSet::from([s.to_string()]);
+        let mut found = vec![Decoded::plain(s)];
+        let mut level = 0..1;
+        for _ in 0..self.depth {
+            let mut next = Vec::new();
+            for parent in &found[level.clone()] {
+                for layer in &self.layers {
+                    let Some((text, mut map)) = self.apply(*layer, &parent.text) else {
+                        continue;
+                    };
+                    if seen.insert(text.clone()) {
+                        remap(&mut map, &parent.map, s.len());
+                        let mut layers = parent.layers.clone();
+                        layers.push(layer.name());
+                        next.push(Decoded {
+                            text,
+                            map,
+                            layers,
+                            len: s.len(),
+                        });
+                    }
+                }
+            }
+            level = found.len()..found.len() + next.len();
+            found.extend(next);
+        }
+        found.remove(0);
+        found
+    }
+}
@@ -0,0 +1,51 @@
+use flate2::read::{MultiGzDecoder, ZlibDecoder};
+use std::borrow::Cow;
+use std::io::Read;
+
+use crate::traverse::Error;
+
+/// decompressed size limit of `scan_msgpack_compressed` when none is given, 64 MiB
+pub const DEFAULT_MAX_DECOMPRESSED: usize = 64 << 20;
+
+/// compression format of a payload, sniffed by its magic bytes
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Compression {
+    Gzip,
+    Zlib,
+}
+
+impl Compression {
+    /// gzip (`1f 8b`) or zlib (deflate with a valid header checksum), None otherwise
+    #[must_use]
+    pub fn sniff(buf: &[u8]) -> Option<Self> {
+        match buf {
+            [0x1f, 0x8b, ..] => Some(Self::Gzip),
+            [cmf, flg, ..] if cmf & 0x0f == 8 && cmf >> 4 <= 7 => {
+                (u16::from_be_bytes([*cmf, *flg]) % 31 == 0).then_some(Self::Zlib)
+            }
+            _ => None,
+        }
+    }
+}
+
+/// `buf` decompressed when it is gzip or zlib, as is otherwise
+/// # Errors
+/// * corrupt compressed data
+/// * more than `limit` decompressed bytes
+pub fn decompress(buf: &[u8], limit: usize) -> Result<Cow<'_, [u8]>, Error> {
+    let reader: Box<dyn Read + '_> = match Compression::sniff(buf) {
+        Some(Compression::Gzip) => Box::new(MultiGzDecoder::new(buf)),
+        Some(Compression::Zlib) => Box::new(ZlibDecoder::new(buf)),
+        None => return Ok(Cow::Borrowed(buf)),
+    };
+    let mut out = Vec::new();
+    // one byte over the limit tells a full buffer from a truncated one
+    reader
+        .take(limit as u64 + 1)
+        .read_to_end(&mut out)
+        .map_err(|e| Error::Decompress(e.to_string()))?;
+    if out.len() > limit {
+        return Err(Error::TooLarge(limit));
+    }
+    Ok(Cow::Owned(out))
+}
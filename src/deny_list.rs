@@ -1,61 +1,248 @@
-use aho_corasick::{AhoCorasick, MatchKind};
+use aho_corasick::AhoCorasick;
+#[cfg(feature = "python")]
 use pyo3::prelude::*;
-use pyo3_stub_gen::derive::{gen_stub_pyclass, gen_stub_pymethods};
+#[cfg(feature = "python")]
+use pyo3_stub_gen::derive::gen_stub_pyclass;
 
-use crate::build_error::build_error;
+use crate::build_error::{BuildError, invalid};
+use crate::cache::ResultCache;
+use crate::compiled::Compiled;
+use crate::engine::{Engine, Span};
+#[cfg(feature = "python")]
+use crate::evaluate::Evaluation;
+use crate::explain::Explanation;
+use crate::keys::KeyFilter;
+use crate::limits::Limits;
 use crate::matcher::Matcher;
-use pyo3::pyclass;
+use crate::matches::Match;
+use crate::metrics::{self, BackendMetrics};
+#[cfg(feature = "python")]
+use crate::on_match::OnMatch;
+use crate::options::{ListMetadata, MatchKind, Options};
+use crate::pymethods::deny_list_pymethods;
+use crate::report::BuildReport;
+use crate::score::Weights;
+use crate::stats::Stats;
+#[cfg(feature = "python")]
 use pyo3::types::PyDict;
+use std::sync::Arc;
 
-#[gen_stub_pyclass]
-#[pyclass(skip_from_py_object)]
+impl Engine for AhoCorasick {
+    fn build(patterns: &[String], kind: MatchKind) -> Result<Self, BuildError> {
+        AhoCorasick::builder()
+            .match_kind(match kind {
+                MatchKind::LeftmostFirst => aho_corasick::MatchKind::LeftmostFirst,
+                MatchKind::LeftmostLongest => aho_corasick::MatchKind::LeftmostLongest,
+            })
+            .build(patterns)
+            .map_err(invalid)
+    }
+
+    fn is_match(&self, text: &str) -> bool {
+        AhoCorasick::is_match(self, text)
+    }
+
+    fn find_iter<'a>(&'a self, text: &'a str) -> Box<dyn Iterator<Item = Span> + 'a> {
+        Box::new(AhoCorasick::find_iter(self, text).map(|m| Span {
+            pattern: m.pattern().as_usize(),
+            start: m.start(),
+            end: m.end(),
+        }))
+    }
+
+    fn heap_bytes(&self) -> usize {
+        self.memory_usage()
+    }
+}
+
+#[cfg_attr(feature = "python", gen_stub_pyclass)]
+#[cfg_attr(
+    feature = "python",
+    pyclass(module = "deny_filter.matchers", skip_from_py_object)
+)]
 #[derive(Clone)]
 pub struct DenyList {
-    pub ac: AhoCorasick,
+    compiled: Compiled<AhoCorasick>,
+    metrics: Arc<BackendMetrics>,
+    #[cfg(feature = "python")]
+    on_match: OnMatch,
 }
 
 impl Matcher for DenyList {
     /// implements match with aho-corasic
     fn is_match(&self, s: &str) -> bool {
-        // Convert input to lowercase for case-insensitive matching
-        self.ac.is_match(&s.to_lowercase())
+        self.compiled.is_match(s)
+    }
+
+    fn first_match(&self, s: &str) -> Option<&str> {
+        self.compiled.first_match(s)
+    }
+
+    fn find_all(&self, s: &str) -> Vec<Match> {
+        self.compiled.find_all(s)
+    }
+
+    fn find_overlapping(&self, s: &str) -> Result<Vec<Match>, BuildError> {
+        self.compiled.find_overlapping(s)
+    }
+
+    fn explain(&self, s: &str) -> Option<Explanation> {
+        self.compiled.explain(s)
+    }
+
+    fn metadata(&self) -> &ListMetadata {
+        self.compiled.metadata()
+    }
+
+    fn build_report(&self) -> &BuildReport {
+        self.compiled.build_report()
+    }
+
+    fn stats(&self) -> Stats {
+        self.compiled.stats()
+    }
+
+    #[cfg(feature = "dot")]
+    fn to_dot(&self) -> String {
+        self.compiled.to_dot()
+    }
+
+    fn metrics(&self) -> &BackendMetrics {
+        &self.metrics
+    }
+
+    fn limits(&self) -> &Limits {
+        self.compiled.limits()
+    }
+
+    fn result_cache(&self) -> Option<&ResultCache> {
+        self.compiled.result_cache()
+    }
+
+    fn ignored_keys(&self) -> &KeyFilter {
+        self.compiled.ignored_keys()
+    }
+
+    fn weights(&self) -> &Weights {
+        self.compiled.weights()
+    }
+
+    fn proximity_matches(&self, s: &str) -> Vec<&str> {
+        self.compiled.proximity_matches(s)
     }
 }
 
-#[gen_stub_pymethods]
-#[pymethods]
 impl DenyList {
     /// constructor
     /// # Errors
     /// * aho-corasic errors (too long patterns)
-    #[new]
-    pub fn new(words: Vec<String>) -> PyResult<Self> {
-        // Store deny words in lowercase for case-insensitive matching
-        let words_lower: Vec<String> = words.into_iter().map(|w| w.to_lowercase()).collect();
-
-        let ac = AhoCorasick::builder()
-            .match_kind(MatchKind::LeftmostFirst)
-            .build(words_lower)
-            .map_err(build_error)?;
-
-        Ok(Self { ac })
+    pub fn new(words: Vec<String>) -> Result<Self, BuildError> {
+        Self::with_options(words, &Options::default())
     }
 
-    #[must_use]
-    pub fn is_match(&self, s: &str) -> bool {
-        Matcher::is_match(self, s)
-    }
-    #[must_use]
-    pub fn scan_str(&self, txt: &str) -> bool {
-        Matcher::scan_str(self, txt)
-    }
-    #[must_use]
-    pub fn scan(&self, args: &Bound<'_, PyDict>) -> bool {
-        Matcher::scan(self, args)
+    /// constructor with matching options
+    /// # Errors
+    /// * aho-corasic errors (too long patterns)
+    /// * invalid options, see `Compiled::new`
+    pub fn with_options(words: Vec<String>, options: &Options) -> Result<Self, BuildError> {
+        Ok(Self {
+            compiled: Compiled::new(words, options)?,
+            metrics: metrics::backend("aho_corasick"),
+            #[cfg(feature = "python")]
+            on_match: OnMatch::default(),
+        })
     }
-    /// scans dict,str,list
-    #[must_use]
-    pub fn scan_any(&self, value: &Bound<'_, PyAny>) -> bool {
-        Matcher::scan_any(self, value)
+}
+
+deny_list_pymethods! {
+    impl DenyList {
+        /// constructor, keyword options: see `Options`
+        /// # Errors
+        /// * aho-corasic errors (too long patterns)
+        /// * unknown option or invalid value
+        #[new]
+        #[pyo3(signature = (words, **options))]
+        fn py_new(words: Vec<String>, options: Option<&Bound<'_, PyDict>>) -> PyResult<Self> {
+            Ok(Self::with_options(words, &Options::from_kwargs(options)?)?)
+        }
+
+        #[must_use]
+        pub fn is_match(&self, s: &str) -> bool {
+            Matcher::is_match(self, s)
+        }
+        /// leftmost non-overlapping deny word occurrences, byte offsets
+        #[pyo3(name = "find_all")]
+        fn py_find_all(&self, text: &str) -> Vec<Match> {
+            Matcher::find_all(self, text)
+        }
+
+        /// why a str, or the first matching string of a dict/list with its path, matched
+        #[pyo3(name = "explain")]
+        fn py_explain(&self, value: &Bound<'_, PyAny>) -> Option<Explanation> {
+            match value.extract::<&str>() {
+                Ok(s) => Matcher::explain(self, s),
+                Err(_) => Matcher::explain_value(self, value, &mut String::from("$")),
+            }
+        }
+
+        /// precision, recall and false positive words on a labeled corpus
+        /// # Errors
+        /// * samples and labels differ in length
+        #[pyo3(name = "evaluate")]
+        #[allow(clippy::needless_pass_by_value)] // python passes owned lists
+        fn py_evaluate(&self, samples: Vec<String>, labels: Vec<bool>) -> PyResult<Evaluation> {
+            if samples.len() != labels.len() {
+                return Err(pyo3::exceptions::PyValueError::new_err(
+                    "samples and labels differ in length",
+                ));
+            }
+            let pairs: Vec<(&str, bool)> = samples.iter().map(String::as_str).zip(labels).collect();
+            Ok(Matcher::evaluate(self, &pairs))
+        }
+
+        /// version, source and timestamp options of the list
+        #[getter(metadata)]
+        fn py_metadata(&self) -> ListMetadata {
+            Matcher::metadata(self).clone()
+        }
+
+        /// dropped, duplicate, altered and subsuming deny words
+        #[pyo3(name = "build_report")]
+        fn py_build_report(&self) -> BuildReport {
+            Matcher::build_report(self).clone()
+        }
+
+        /// pattern count, trie states, pattern lengths and heap bytes
+        #[pyo3(name = "stats")]
+        fn py_stats(&self) -> Stats {
+            Matcher::stats(self)
+        }
+
+        /// graphviz digraph of the pattern tries, debug builds with the `dot` feature
+        #[cfg(feature = "dot")]
+        #[pyo3(name = "to_dot")]
+        fn py_to_dot(&self) -> String {
+            Matcher::to_dot(self)
+        }
+
+        /// masks matches grapheme by grapheme
+        #[pyo3(name = "redact", signature = (text, mask = "*"))]
+        fn py_redact(&self, text: &str, mask: &str) -> String {
+            Matcher::redact(self, text, mask)
+        }
+
+        /// wraps matches, spans snapped to whole graphemes
+        #[pyo3(name = "highlight", signature = (text, open = "<mark>", close = "</mark>"))]
+        fn py_highlight(&self, text: &str, open: &str, close: &str) -> String {
+            Matcher::highlight(self, text, open, close)
+        }
+
+        /// every deny word occurrence including overlapping ones, byte offsets
+        /// # Errors
+        /// * the overlapping automaton cannot be built
+        #[pyo3(name = "find_overlapping")]
+        fn py_find_overlapping(&self, text: &str) -> PyResult<Vec<Match>> {
+            Ok(Matcher::find_overlapping(self, text)?)
+        }
     }
 }
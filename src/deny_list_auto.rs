@@ -0,0 +1,298 @@
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
+#[cfg(feature = "python")]
+use pyo3::types::PyDict;
+#[cfg(feature = "python")]
+use pyo3_stub_gen::derive::gen_stub_pyclass;
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use crate::backend::Backend;
+use crate::build_error::BuildError;
+use crate::cache::ResultCache;
+#[cfg(feature = "python")]
+use crate::evaluate::Evaluation;
+use crate::explain::Explanation;
+use crate::keys::KeyFilter;
+use crate::limits::Limits;
+use crate::matcher::Matcher;
+use crate::matches::Match;
+use crate::metrics::BackendMetrics;
+#[cfg(feature = "python")]
+use crate::on_match::OnMatch;
+use crate::options::{ListMetadata, Options};
+use crate::pymethods::deny_list_pymethods;
+use crate::report::BuildReport;
+use crate::score::Weights;
+use crate::stats::Stats;
+
+/// backends timed by `DenyListAuto::build`
+pub const CANDIDATES: [Backend; 4] = [
+    Backend::AhoCorasick,
+    Backend::Regex,
+    Backend::Daachorse,
+    Backend::Packed,
+];
+
+/// timed passes over the samples per backend, the fastest pass counts
+const ROUNDS: usize = 3;
+
+/// delegates to the backend that scanned a sample of inputs fastest at build time
+#[cfg_attr(feature = "python", gen_stub_pyclass)]
+#[cfg_attr(
+    feature = "python",
+    pyclass(module = "deny_filter.matchers", skip_from_py_object)
+)]
+pub struct DenyListAuto {
+    inner: Box<dyn Matcher + Send + Sync>,
+    backend: Backend,
+    /// fastest pass of each candidate, empty without samples
+    timings: Vec<(Backend, Duration)>,
+    #[cfg(feature = "python")]
+    on_match: OnMatch,
+}
+
+impl DenyListAuto {
+    /// builds every candidate backend, times `is_match` over `samples` and keeps the
+    /// fastest; without samples aho-corasick is kept untimed
+    /// # Errors
+    /// * pattern errors of a backend, invalid options
+    pub fn build(
+        words: &[String],
+        samples: &[String],
+        options: &Options,
+    ) -> Result<Self, BuildError> {
+        if samples.is_empty() {
+            return Ok(Self {
+                inner: Backend::AhoCorasick.build_with(words.to_vec(), options)?,
+                backend: Backend::AhoCorasick,
+                timings: Vec::new(),
+                #[cfg(feature = "python")]
+                on_match: OnMatch::default(),
+            });
+        }
+        let mut fastest = (Backend::AhoCorasick, Duration::MAX);
+        let mut timings = Vec::with_capacity(CANDIDATES.len());
+        for backend in CANDIDATES {
+            let matcher = backend.build_with(words.to_vec(), options)?;
+            let elapsed = (0..ROUNDS)
+                .map(|_| {
+                    let start = Instant::now();
+                    for sample in samples {
+                        black_box(matcher.is_match(black_box(sample)));
+                    }
+                    start.elapsed()
+                })
+                .min()
+                .unwrap_or_default();
+            timings.push((backend, elapsed));
+            if elapsed < fastest.1 {
+                fastest = (backend, elapsed);
+            }
+        }
+        // rebuilt so only one candidate is held at a time
+        Ok(Self {
+            inner: fastest.0.build_with(words.to_vec(), options)?,
+            backend: fastest.0,
+            timings,
+            #[cfg(feature = "python")]
+            on_match: OnMatch::default(),
+        })
+    }
+
+    /// backend the matcher delegates to
+    #[must_use]
+    pub fn backend(&self) -> Backend {
+        self.backend
+    }
+
+    /// fastest pass over the samples of each candidate, in `CANDIDATES` order
+    #[must_use]
+    pub fn timings(&self) -> &[(Backend, Duration)] {
+        &self.timings
+    }
+}
+
+impl Matcher for DenyListAuto {
+    fn is_match(&self, s: &str) -> bool {
+        self.inner.is_match(s)
+    }
+
+    fn first_match(&self, s: &str) -> Option<&str> {
+        self.inner.first_match(s)
+    }
+
+    fn find_all(&self, s: &str) -> Vec<Match> {
+        self.inner.find_all(s)
+    }
+
+    fn find_overlapping(&self, s: &str) -> Result<Vec<Match>, BuildError> {
+        self.inner.find_overlapping(s)
+    }
+
+    fn explain(&self, s: &str) -> Option<Explanation> {
+        self.inner.explain(s)
+    }
+
+    fn metadata(&self) -> &ListMetadata {
+        self.inner.metadata()
+    }
+
+    fn build_report(&self) -> &BuildReport {
+        self.inner.build_report()
+    }
+
+    /// stats of the chosen backend, named in `backend`
+    fn stats(&self) -> Stats {
+        Stats {
+            backend: Some(self.backend.name().to_string()),
+            ..self.inner.stats()
+        }
+    }
+
+    #[cfg(feature = "dot")]
+    fn to_dot(&self) -> String {
+        self.inner.to_dot()
+    }
+
+    fn metrics(&self) -> &BackendMetrics {
+        self.inner.metrics()
+    }
+
+    fn limits(&self) -> &Limits {
+        self.inner.limits()
+    }
+
+    fn result_cache(&self) -> Option<&ResultCache> {
+        self.inner.result_cache()
+    }
+
+    fn ignored_keys(&self) -> &KeyFilter {
+        self.inner.ignored_keys()
+    }
+
+    fn weights(&self) -> &Weights {
+        self.inner.weights()
+    }
+
+    fn proximity_matches(&self, s: &str) -> Vec<&str> {
+        self.inner.proximity_matches(s)
+    }
+}
+
+deny_list_pymethods! {
+    impl DenyListAuto {
+        /// times every backend on `sample_inputs` and keeps the fastest,
+        /// keyword options: see `Options`
+        /// # Errors
+        /// * pattern errors of a backend
+        /// * unknown option or invalid value
+        #[staticmethod]
+        #[pyo3(name = "build", signature = (words, sample_inputs, **options))]
+        #[allow(clippy::needless_pass_by_value)] // python passes owned lists
+        fn py_build(
+            py: Python<'_>,
+            words: Vec<String>,
+            sample_inputs: Vec<String>,
+            options: Option<&Bound<'_, PyDict>>,
+        ) -> PyResult<Self> {
+            let options = Options::from_kwargs(options)?;
+            Ok(py.detach(|| Self::build(&words, &sample_inputs, &options))?)
+        }
+
+        /// name of the chosen backend
+        #[getter(backend)]
+        fn py_backend(&self) -> &'static str {
+            self.backend.name()
+        }
+
+        /// (backend, seconds) of the fastest pass of each candidate
+        #[pyo3(name = "timings")]
+        fn py_timings(&self) -> Vec<(&'static str, f64)> {
+            self.timings
+                .iter()
+                .map(|(backend, elapsed)| (backend.name(), elapsed.as_secs_f64()))
+                .collect()
+        }
+
+        #[must_use]
+        pub fn is_match(&self, s: &str) -> bool {
+            Matcher::is_match(self, s)
+        }
+
+        /// leftmost non-overlapping deny word occurrences, byte offsets
+        #[pyo3(name = "find_all")]
+        fn py_find_all(&self, text: &str) -> Vec<Match> {
+            Matcher::find_all(self, text)
+        }
+
+        /// why a str, or the first matching string of a dict/list with its path, matched
+        #[pyo3(name = "explain")]
+        fn py_explain(&self, value: &Bound<'_, PyAny>) -> Option<Explanation> {
+            match value.extract::<&str>() {
+                Ok(s) => Matcher::explain(self, s),
+                Err(_) => Matcher::explain_value(self, value, &mut String::from("$")),
+            }
+        }
+
+        /// precision, recall and false positive words on a labeled corpus
+        /// # Errors
+        /// * samples and labels differ in length
+        #[pyo3(name = "evaluate")]
+        #[allow(clippy::needless_pass_by_value)] // python passes owned lists
+        fn py_evaluate(&self, samples: Vec<String>, labels: Vec<bool>) -> PyResult<Evaluation> {
+            if samples.len() != labels.len() {
+                return Err(pyo3::exceptions::PyValueError::new_err(
+                    "samples and labels differ in length",
+                ));
+            }
+            let pairs: Vec<(&str, bool)> = samples.iter().map(String::as_str).zip(labels).collect();
+            Ok(Matcher::evaluate(self, &pairs))
+        }
+
+        /// version, source and timestamp options of the list
+        #[getter(metadata)]
+        fn py_metadata(&self) -> ListMetadata {
+            Matcher::metadata(self).clone()
+        }
+
+        /// dropped, duplicate, altered and subsuming deny words
+        #[pyo3(name = "build_report")]
+        fn py_build_report(&self) -> BuildReport {
+            Matcher::build_report(self).clone()
+        }
+
+        /// stats of the chosen backend, with its name in `backend`
+        #[pyo3(name = "stats")]
+        fn py_stats(&self) -> Stats {
+            Matcher::stats(self)
+        }
+
+        /// graphviz digraph of the chosen backend, debug builds with the `dot` feature
+        #[cfg(feature = "dot")]
+        #[pyo3(name = "to_dot")]
+        fn py_to_dot(&self) -> String {
+            Matcher::to_dot(self)
+        }
+
+        /// masks matches grapheme by grapheme
+        #[pyo3(name = "redact", signature = (text, mask = "*"))]
+        fn py_redact(&self, text: &str, mask: &str) -> String {
+            Matcher::redact(self, text, mask)
+        }
+
+        /// wraps matches, spans snapped to whole graphemes
+        #[pyo3(name = "highlight", signature = (text, open = "<mark>", close = "</mark>"))]
+        fn py_highlight(&self, text: &str, open: &str, close: &str) -> String {
+            Matcher::highlight(self, text, open, close)
+        }
+
+        /// every deny word occurrence including overlapping ones, byte offsets
+        /// # Errors
+        /// * the overlapping automaton cannot be built
+        #[pyo3(name = "find_overlapping")]
+        fn py_find_overlapping(&self, text: &str) -> PyResult<Vec<Match>> {
+            Ok(Matcher::find_overlapping(self, text)?)
+        }
+    }
+}
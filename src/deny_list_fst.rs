@@ -0,0 +1,334 @@
+use fst::Set;
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
+#[cfg(feature = "python")]
+use pyo3::types::{PyBytes, PyDict};
+#[cfg(feature = "python")]
+use pyo3_stub_gen::derive::gen_stub_pyclass;
+use std::borrow::Cow;
+use std::sync::Arc;
+
+use crate::build_error::{BuildError, invalid};
+use crate::cache::ResultCache;
+use crate::explain::Explanation;
+use crate::keys::KeyFilter;
+use crate::limits::Limits;
+use crate::matcher::Matcher;
+use crate::matches::{self, Match};
+use crate::metrics::{self, BackendMetrics};
+use crate::normalize::{self, Normalizer};
+#[cfg(feature = "python")]
+use crate::on_match::OnMatch;
+use crate::options::{ListMetadata, Options, WordMatch};
+use crate::pymethods::deny_list_pymethods;
+use crate::report::BuildReport;
+use crate::score::Weights;
+use crate::stats::Stats;
+
+/// pattern name of all hits, the set keeps no strings to borrow
+pub const FST_LABEL: &str = "fst:token";
+
+/// exact token membership in a finite state transducer set, for very large lists
+/// (e.g. leaked credentials) where substring matching is not needed; the set shares
+/// prefixes and suffixes and takes a fraction of the memory of an automaton
+#[cfg_attr(feature = "python", gen_stub_pyclass)]
+#[cfg_attr(
+    feature = "python",
+    pyclass(module = "deny_filter.matchers", skip_from_py_object)
+)]
+#[derive(Clone)]
+pub struct DenyListFst {
+    set: Set<Vec<u8>>,
+    /// case folded and normalized words and tokens, None for exact bytes
+    normalizer: Option<Normalizer>,
+    limits: Limits,
+    keys: KeyFilter,
+    weights: Weights,
+    metadata: ListMetadata,
+    report: BuildReport,
+    metrics: Arc<BackendMetrics>,
+    cache: Option<Arc<ResultCache>>,
+    #[cfg(feature = "python")]
+    on_match: OnMatch,
+}
+
+impl DenyListFst {
+    /// exact byte membership with default options
+    /// # Errors
+    /// * fst construction errors
+    pub fn new(words: Vec<String>) -> Result<Self, BuildError> {
+        Self::with_options(words, false, &Options::default())
+    }
+
+    /// builds the set from deny words, with `normalized` words and tokens are
+    /// case folded and normalized as set in `options`
+    /// # Errors
+    /// * fst construction errors
+    pub fn with_options(
+        words: Vec<String>,
+        normalized: bool,
+        options: &Options,
+    ) -> Result<Self, BuildError> {
+        let form = normalized.then(|| Normalizer::new(WordMatch::Substring, options));
+        let mut report = BuildReport::default();
+        let mut keys: Vec<String> = Vec::with_capacity(words.len());
+        for (i, word) in words.into_iter().enumerate() {
+            let word = word.trim();
+            if word.is_empty() {
+                report.empty.push(i);
+                continue;
+            }
+            keys.push(match &form {
+                Some(form) => form.input(word),
+                None => word.to_string(),
+            });
+        }
+        keys.sort_unstable();
+        keys.dedup();
+        Ok(Self {
+            set: Set::from_iter(keys).map_err(invalid)?,
+            normalizer: form,
+            limits: Limits::new(options),
+            keys: KeyFilter::new(options)?,
+            weights: Weights::new(options)?,
+            metadata: options.metadata(),
+            report,
+            metrics: metrics::backend("fst"),
+            cache: ResultCache::from_options(options),
+            #[cfg(feature = "python")]
+            on_match: OnMatch::default(),
+        })
+    }
+
+    /// loads a set serialized with `as_bytes`, `normalized` must match how it was built
+    /// # Errors
+    /// * not a valid fst
+    pub fn from_bytes(
+        bytes: Vec<u8>,
+        normalized: bool,
+        options: &Options,
+    ) -> Result<Self, BuildError> {
+        Ok(Self {
+            set: Set::new(bytes).map_err(invalid)?,
+            normalizer: normalized.then(|| Normalizer::new(WordMatch::Substring, options)),
+            limits: Limits::new(options),
+            keys: KeyFilter::new(options)?,
+            weights: Weights::new(options)?,
+            metadata: options.metadata(),
+            report: BuildReport::default(),
+            metrics: metrics::backend("fst"),
+            cache: ResultCache::from_options(options),
+            #[cfg(feature = "python")]
+            on_match: OnMatch::default(),
+        })
+    }
+
+    /// serialized set, build large lists once and load them with `from_bytes`
+    #[must_use]
+    pub fn as_bytes(&self) -> &[u8] {
+        self.set.as_fst().as_bytes()
+    }
+
+    /// number of distinct words in the set
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.set.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.set.is_empty()
+    }
+
+    /// `token` as found in the set, its first listed normalized form
+    fn listed<'a>(&self, token: &'a str) -> Option<Cow<'a, str>> {
+        match &self.normalizer {
+            Some(normalizer) => normalizer
+                .inputs(token)
+                .into_iter()
+                .find(|key| self.set.contains(key.as_bytes()))
+                .map(Cow::Owned),
+            None => self
+                .set
+                .contains(token.as_bytes())
+                .then_some(Cow::Borrowed(token)),
+        }
+    }
+}
+
+impl Matcher for DenyListFst {
+    fn is_match(&self, s: &str) -> bool {
+        normalize::exact_tokens(s)
+            .into_iter()
+            .any(|(_, token)| self.listed(token).is_some())
+    }
+
+    fn first_match(&self, s: &str) -> Option<&str> {
+        self.is_match(s).then_some(FST_LABEL)
+    }
+
+    /// listed tokens, `word` is the token as looked up; a matching whole value hides its tokens
+    fn find_all(&self, s: &str) -> Vec<Match> {
+        let mut found: Vec<Match> = Vec::new();
+        for (start, token) in normalize::exact_tokens(s) {
+            let end = start + token.len();
+            if found.iter().any(|m| m.start < end && start < m.end) {
+                continue;
+            }
+            if let Some(key) = self.listed(token) {
+                found.push(Match {
+                    word: key.into_owned(),
+                    start,
+                    end,
+                    char_start: 0,
+                    char_end: 0,
+                });
+            }
+        }
+        found.sort_by_key(|m| m.start);
+        matches::set_char_offsets(s, &mut found);
+        found
+    }
+
+    /// exact tokens cannot overlap apart from the whole value, same as `find_all`
+    fn find_overlapping(&self, s: &str) -> Result<Vec<Match>, BuildError> {
+        Ok(self.find_all(s))
+    }
+
+    fn explain(&self, s: &str) -> Option<Explanation> {
+        let hit = self.find_all(s).into_iter().next()?;
+        let mut steps = self
+            .normalizer
+            .as_ref()
+            .map(Normalizer::steps)
+            .unwrap_or_default();
+        steps.push("exact_token".to_string());
+        Some(Explanation {
+            word: hit.word.clone(),
+            pattern: hit.word.clone(),
+            normalized: hit.word.clone(),
+            hit: Some(hit),
+            steps,
+            path: None,
+        })
+    }
+
+    fn metadata(&self) -> &ListMetadata {
+        &self.metadata
+    }
+
+    fn build_report(&self) -> &BuildReport {
+        &self.report
+    }
+
+    /// the set does not expose its states or key lengths, only words and bytes are counted
+    fn stats(&self) -> Stats {
+        Stats {
+            patterns: self.set.len(),
+            heap_bytes: self.set.as_fst().size(),
+            ..Stats::default()
+        }
+    }
+
+    #[cfg(feature = "dot")]
+    fn to_dot(&self) -> String {
+        "digraph deny {\n}\n".to_string()
+    }
+
+    fn metrics(&self) -> &BackendMetrics {
+        &self.metrics
+    }
+
+    fn limits(&self) -> &Limits {
+        &self.limits
+    }
+
+    fn result_cache(&self) -> Option<&ResultCache> {
+        self.cache.as_deref()
+    }
+
+    fn ignored_keys(&self) -> &KeyFilter {
+        &self.keys
+    }
+
+    fn weights(&self) -> &Weights {
+        &self.weights
+    }
+}
+
+deny_list_pymethods! {
+    impl DenyListFst {
+        /// constructor from deny words, keyword options: see `Options`
+        /// # Errors
+        /// * fst construction errors
+        /// * unknown option or invalid value
+        #[new]
+        #[pyo3(signature = (words, normalized = false, **options))]
+        fn py_new(
+            words: Vec<String>,
+            normalized: bool,
+            options: Option<&Bound<'_, PyDict>>,
+        ) -> PyResult<Self> {
+            Ok(Self::with_options(
+                words,
+                normalized,
+                &Options::from_kwargs(options)?,
+            )?)
+        }
+
+        /// loads a set serialized with `to_bytes`
+        /// # Errors
+        /// * not a valid fst, unknown option or invalid value
+        #[staticmethod]
+        #[pyo3(name = "from_bytes", signature = (data, normalized = false, **options))]
+        fn py_from_bytes(
+            data: &Bound<'_, PyBytes>,
+            normalized: bool,
+            options: Option<&Bound<'_, PyDict>>,
+        ) -> PyResult<Self> {
+            Ok(Self::from_bytes(
+                data.as_bytes().to_vec(),
+                normalized,
+                &Options::from_kwargs(options)?,
+            )?)
+        }
+
+        /// serialized set
+        fn to_bytes<'py>(&self, py: Python<'py>) -> Bound<'py, PyBytes> {
+            PyBytes::new(py, self.as_bytes())
+        }
+
+        fn __len__(&self) -> usize {
+            self.len()
+        }
+
+        #[must_use]
+        pub fn is_match(&self, s: &str) -> bool {
+            Matcher::is_match(self, s)
+        }
+
+        /// listed tokens with byte offsets
+        #[pyo3(name = "find_all")]
+        fn py_find_all(&self, text: &str) -> Vec<Match> {
+            Matcher::find_all(self, text)
+        }
+
+        /// masks listed tokens grapheme by grapheme
+        #[pyo3(name = "redact", signature = (text, mask = "*"))]
+        fn py_redact(&self, text: &str, mask: &str) -> String {
+            Matcher::redact(self, text, mask)
+        }
+
+        /// version, source and timestamp options of the list
+        #[getter(metadata)]
+        fn py_metadata(&self) -> ListMetadata {
+            Matcher::metadata(self).clone()
+        }
+
+        /// word count and fst bytes
+        #[pyo3(name = "stats")]
+        fn py_stats(&self) -> Stats {
+            Matcher::stats(self)
+        }
+    }
+}
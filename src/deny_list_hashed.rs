@@ -0,0 +1,305 @@
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
+#[cfg(feature = "python")]
+use pyo3::types::PyDict;
+#[cfg(feature = "python")]
+use pyo3_stub_gen::derive::gen_stub_pyclass;
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::collections::hash_map::Entry;
+use std::fmt::Write as _;
+use std::sync::Arc;
+
+use crate::build_error::{BuildError, invalid};
+use crate::cache::ResultCache;
+use crate::explain::Explanation;
+use crate::keys::KeyFilter;
+use crate::limits::Limits;
+use crate::matcher::Matcher;
+use crate::matches::{self, Match};
+use crate::metrics::{self, BackendMetrics};
+use crate::normalize::{self, Normalizer};
+#[cfg(feature = "python")]
+use crate::on_match::OnMatch;
+use crate::options::{ListMetadata, Options, WordMatch};
+use crate::pymethods::deny_list_pymethods;
+use crate::report::BuildReport;
+use crate::score::Weights;
+use crate::signed::decode_hex;
+use crate::stats::Stats;
+
+/// lowercase hex sha-256 of `s`
+fn hex_digest(s: &str) -> String {
+    Sha256::digest(s.as_bytes())
+        .iter()
+        .fold(String::with_capacity(64), |mut out, b| {
+            let _ = write!(out, "{b:02x}");
+            out
+        })
+}
+
+/// exact match deny list of sha-256 digests, for secrets that must not be stored in plaintext;
+/// tokens of the input are hashed and looked up, the list itself reveals nothing
+#[cfg_attr(feature = "python", gen_stub_pyclass)]
+#[cfg_attr(
+    feature = "python",
+    pyclass(module = "deny_filter.matchers", skip_from_py_object)
+)]
+#[derive(Clone)]
+pub struct DenyListHashed {
+    /// digest to its `sha256:<hex>` label, reported instead of the secret
+    digests: HashMap<String, String>,
+    /// case folded and normalized tokens are hashed, see `digest`
+    normalizer: Option<Normalizer>,
+    limits: Limits,
+    keys: KeyFilter,
+    weights: Weights,
+    metadata: ListMetadata,
+    report: BuildReport,
+    metrics: Arc<BackendMetrics>,
+    cache: Option<Arc<ResultCache>>,
+    #[cfg(feature = "python")]
+    on_match: OnMatch,
+}
+
+impl DenyListHashed {
+    /// builds from hex sha-256 digests, with `normalized` tokens are case folded and
+    /// normalized like deny words before hashing
+    /// # Errors
+    /// * a digest is not 64 hex digits
+    pub fn new(digests: Vec<String>, normalized: bool) -> Result<Self, BuildError> {
+        Self::with_options(digests, normalized, &Options::default())
+    }
+
+    /// constructor with the folding, normalization and metadata of `options`
+    /// # Errors
+    /// * a digest is not 64 hex digits
+    pub fn with_options(
+        digests: Vec<String>,
+        normalized: bool,
+        options: &Options,
+    ) -> Result<Self, BuildError> {
+        let mut map = HashMap::with_capacity(digests.len());
+        let mut report = BuildReport::default();
+        for (i, digest) in digests.into_iter().enumerate() {
+            let digest = digest.trim().to_ascii_lowercase();
+            if digest.is_empty() {
+                report.empty.push(i);
+                continue;
+            }
+            if digest.len() != 64 || decode_hex(&digest).is_none() {
+                return Err(invalid(format!("{digest:?} is not a hex sha-256 digest")));
+            }
+            let label = format!("sha256:{digest}");
+            match map.entry(digest) {
+                Entry::Occupied(_) => report.duplicates.push(label),
+                Entry::Vacant(entry) => {
+                    entry.insert(label);
+                }
+            }
+        }
+        Ok(Self {
+            digests: map,
+            normalizer: normalized.then(|| Normalizer::new(WordMatch::Substring, options)),
+            limits: Limits::new(options),
+            keys: KeyFilter::new(options)?,
+            weights: Weights::new(options)?,
+            metadata: options.metadata(),
+            report,
+            metrics: metrics::backend("sha256"),
+            cache: ResultCache::from_options(options),
+            #[cfg(feature = "python")]
+            on_match: OnMatch::default(),
+        })
+    }
+
+    /// hex digest to put on the list for `secret`, normalized like the tokens of a
+    /// `normalized` list with default options
+    #[must_use]
+    pub fn digest(secret: &str, normalized: bool) -> String {
+        if normalized {
+            hex_digest(&Normalizer::new(WordMatch::Substring, &Options::default()).input(secret))
+        } else {
+            hex_digest(secret)
+        }
+    }
+
+    /// label of the listed digest of `candidate`
+    fn lookup(&self, candidate: &str) -> Option<&str> {
+        match &self.normalizer {
+            Some(normalizer) => normalizer
+                .inputs(candidate)
+                .iter()
+                .find_map(|input| self.digests.get(&hex_digest(input))),
+            None => self.digests.get(&hex_digest(candidate)),
+        }
+        .map(String::as_str)
+    }
+}
+
+impl Matcher for DenyListHashed {
+    fn is_match(&self, s: &str) -> bool {
+        self.first_match(s).is_some()
+    }
+
+    fn first_match(&self, s: &str) -> Option<&str> {
+        normalize::exact_tokens(s)
+            .into_iter()
+            .find_map(|(_, c)| self.lookup(c))
+    }
+
+    /// listed tokens, a matching whole value hides the tokens inside it
+    fn find_all(&self, s: &str) -> Vec<Match> {
+        let mut found: Vec<Match> = Vec::new();
+        for (start, candidate) in normalize::exact_tokens(s) {
+            let end = start + candidate.len();
+            if found.iter().any(|m| m.start < end && start < m.end) {
+                continue;
+            }
+            if let Some(label) = self.lookup(candidate) {
+                found.push(Match {
+                    word: label.to_string(),
+                    start,
+                    end,
+                    char_start: 0,
+                    char_end: 0,
+                });
+            }
+        }
+        found.sort_by_key(|m| m.start);
+        matches::set_char_offsets(s, &mut found);
+        found
+    }
+
+    /// exact tokens cannot overlap apart from the whole value, same as `find_all`
+    fn find_overlapping(&self, s: &str) -> Result<Vec<Match>, BuildError> {
+        Ok(self.find_all(s))
+    }
+
+    /// the normalized text is omitted, it is the secret
+    fn explain(&self, s: &str) -> Option<Explanation> {
+        let hit = self.find_all(s).into_iter().next()?;
+        let mut steps = self
+            .normalizer
+            .as_ref()
+            .map(Normalizer::steps)
+            .unwrap_or_default();
+        steps.push("sha256".to_string());
+        Some(Explanation {
+            word: hit.word.clone(),
+            pattern: hit.word.clone(),
+            hit: Some(hit),
+            normalized: String::new(),
+            steps,
+            path: None,
+        })
+    }
+
+    fn metadata(&self) -> &ListMetadata {
+        &self.metadata
+    }
+
+    fn build_report(&self) -> &BuildReport {
+        &self.report
+    }
+
+    /// a hash set has no automaton states, only digests are counted
+    fn stats(&self) -> Stats {
+        Stats {
+            patterns: self.digests.len(),
+            states: 0,
+            min_pattern_len: 64,
+            max_pattern_len: 64,
+            heap_bytes: self
+                .digests
+                .iter()
+                .map(|(digest, label)| digest.capacity() + label.capacity())
+                .sum::<usize>()
+                + self.digests.capacity() * 2 * size_of::<String>(),
+            backend: None,
+        }
+    }
+
+    #[cfg(feature = "dot")]
+    fn to_dot(&self) -> String {
+        "digraph deny {\n}\n".to_string()
+    }
+
+    fn metrics(&self) -> &BackendMetrics {
+        &self.metrics
+    }
+
+    fn limits(&self) -> &Limits {
+        &self.limits
+    }
+
+    fn result_cache(&self) -> Option<&ResultCache> {
+        self.cache.as_deref()
+    }
+
+    fn ignored_keys(&self) -> &KeyFilter {
+        &self.keys
+    }
+
+    fn weights(&self) -> &Weights {
+        &self.weights
+    }
+}
+
+deny_list_pymethods! {
+    impl DenyListHashed {
+        /// constructor from hex sha-256 digests, keyword options: see `Options`
+        /// # Errors
+        /// * a digest is not 64 hex digits
+        /// * unknown option or invalid value
+        #[new]
+        #[pyo3(signature = (digests, normalized = false, **options))]
+        fn py_new(
+            digests: Vec<String>,
+            normalized: bool,
+            options: Option<&Bound<'_, PyDict>>,
+        ) -> PyResult<Self> {
+            Ok(Self::with_options(
+                digests,
+                normalized,
+                &Options::from_kwargs(options)?,
+            )?)
+        }
+
+        /// hex digest to put on the list for a secret
+        #[staticmethod]
+        #[pyo3(name = "digest", signature = (secret, normalized = false))]
+        fn py_digest(secret: &str, normalized: bool) -> String {
+            Self::digest(secret, normalized)
+        }
+
+        #[must_use]
+        pub fn is_match(&self, s: &str) -> bool {
+            Matcher::is_match(self, s)
+        }
+
+        /// listed tokens with byte offsets, `word` is the `sha256:<hex>` label
+        #[pyo3(name = "find_all")]
+        fn py_find_all(&self, text: &str) -> Vec<Match> {
+            Matcher::find_all(self, text)
+        }
+
+        /// masks listed tokens grapheme by grapheme
+        #[pyo3(name = "redact", signature = (text, mask = "*"))]
+        fn py_redact(&self, text: &str, mask: &str) -> String {
+            Matcher::redact(self, text, mask)
+        }
+
+        /// version, source and timestamp options of the list
+        #[getter(metadata)]
+        fn py_metadata(&self) -> ListMetadata {
+            Matcher::metadata(self).clone()
+        }
+
+        /// empty and duplicate digests
+        #[pyo3(name = "build_report")]
+        fn py_build_report(&self) -> BuildReport {
+            Matcher::build_report(self).clone()
+        }
+    }
+}
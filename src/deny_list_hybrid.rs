@@ -0,0 +1,344 @@
+use aho_corasick::AhoCorasick;
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
+#[cfg(feature = "python")]
+use pyo3::types::PyDict;
+#[cfg(feature = "python")]
+use pyo3_stub_gen::derive::gen_stub_pyclass;
+use regex::{Regex, RegexBuilder, RegexSet, RegexSetBuilder};
+use std::cmp::Reverse;
+use std::sync::Arc;
+
+use crate::build_error::{BuildError, invalid};
+use crate::cache::ResultCache;
+use crate::compiled::Compiled;
+#[cfg(feature = "python")]
+use crate::evaluate::Evaluation;
+use crate::explain::Explanation;
+use crate::keys::KeyFilter;
+use crate::limits::Limits;
+use crate::matcher::Matcher;
+use crate::matches::{self, Match};
+use crate::metrics::{self, BackendMetrics};
+#[cfg(feature = "python")]
+use crate::on_match::OnMatch;
+use crate::options::{CaseFold, ListMetadata, Options};
+use crate::pymethods::deny_list_pymethods;
+use crate::report::BuildReport;
+use crate::score::Weights;
+use crate::stats::Stats;
+
+/// prefix of regex entries in a mixed policy list
+pub const REGEX_PREFIX: &str = "re:";
+
+/// splits policy entries into literal words and regexes, regex entries start with `re:`
+#[must_use]
+pub fn split_entries(entries: Vec<String>) -> (Vec<String>, Vec<String>) {
+    let mut words = Vec::new();
+    let mut patterns = Vec::new();
+    for entry in entries {
+        match entry.strip_prefix(REGEX_PREFIX) {
+            Some(pattern) => patterns.push(pattern.to_string()),
+            None => words.push(entry),
+        }
+    }
+    (words, patterns)
+}
+
+/// literal deny words in aho-corasick and a few regexes in a regex set,
+/// scanned together with one result list
+#[cfg_attr(feature = "python", gen_stub_pyclass)]
+#[cfg_attr(
+    feature = "python",
+    pyclass(module = "deny_filter.matchers", skip_from_py_object)
+)]
+#[derive(Clone)]
+pub struct DenyListHybrid {
+    literals: Compiled<AhoCorasick>,
+    set: RegexSet,
+    /// one regex per set pattern, for the offsets the set does not report
+    regexes: Vec<Regex>,
+    metrics: Arc<BackendMetrics>,
+    #[cfg(feature = "python")]
+    on_match: OnMatch,
+}
+
+impl DenyListHybrid {
+    /// constructor, regexes match case insensitively like the literals
+    /// # Errors
+    /// * aho-corasic errors (too long patterns)
+    /// * invalid regex
+    pub fn new(words: Vec<String>, patterns: &[String]) -> Result<Self, BuildError> {
+        Self::with_options(words, patterns, &Options::default())
+    }
+
+    /// constructor with matching options for the literals, regexes are case sensitive
+    /// with `CaseFold::None` like the literals
+    /// # Errors
+    /// * aho-corasic errors (too long patterns)
+    /// * invalid regex or options, see `Compiled::new`
+    pub fn with_options(
+        words: Vec<String>,
+        patterns: &[String],
+        options: &Options,
+    ) -> Result<Self, BuildError> {
+        let insensitive = options.case_fold != CaseFold::None;
+        let regexes = patterns
+            .iter()
+            .map(|p| RegexBuilder::new(p).case_insensitive(insensitive).build())
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(invalid)?;
+        Ok(Self {
+            literals: Compiled::new(words, options)?,
+            set: RegexSetBuilder::new(patterns)
+                .case_insensitive(insensitive)
+                .build()
+                .map_err(invalid)?,
+            regexes,
+            metrics: metrics::backend("hybrid"),
+            #[cfg(feature = "python")]
+            on_match: OnMatch::default(),
+        })
+    }
+
+    /// constructor from a mixed policy list, see `split_entries`
+    /// # Errors
+    /// * see `with_options`
+    pub fn from_entries(entries: Vec<String>, options: &Options) -> Result<Self, BuildError> {
+        let (words, patterns) = split_entries(entries);
+        Self::with_options(words, &patterns, options)
+    }
+
+    /// every regex hit of the patterns the set reports, labeled with the pattern
+    fn regex_matches(&self, s: &str) -> Vec<Match> {
+        self.set
+            .matches(s)
+            .iter()
+            .flat_map(|i| {
+                self.regexes[i]
+                    .find_iter(s)
+                    .filter(|m| !m.is_empty())
+                    .map(move |m| Match {
+                        word: self.regexes[i].as_str().to_string(),
+                        start: m.start(),
+                        end: m.end(),
+                        char_start: 0,
+                        char_end: 0,
+                    })
+            })
+            .collect()
+    }
+}
+
+impl Matcher for DenyListHybrid {
+    fn is_match(&self, s: &str) -> bool {
+        self.literals.is_match(s) || self.set.is_match(s)
+    }
+
+    /// literals first, then the first matching regex
+    fn first_match(&self, s: &str) -> Option<&str> {
+        self.literals
+            .first_match(s)
+            .or_else(|| Some(self.regexes[self.set.matches(s).iter().next()?].as_str()))
+    }
+
+    /// literal and regex hits in text order, the leftmost longest wins where they overlap
+    fn find_all(&self, s: &str) -> Vec<Match> {
+        let mut found = self.literals.find_all(s);
+        let mut regex = self.regex_matches(s);
+        matches::set_char_offsets(s, &mut regex);
+        found.extend(regex);
+        found.sort_by_key(|m| (m.start, Reverse(m.end)));
+        let mut end = 0;
+        found.retain(|m| {
+            let keep = m.start >= end;
+            if keep {
+                end = m.end;
+            }
+            keep
+        });
+        found
+    }
+
+    fn find_overlapping(&self, s: &str) -> Result<Vec<Match>, BuildError> {
+        let mut found = self.literals.find_overlapping(s)?;
+        let mut regex = self.regex_matches(s);
+        matches::set_char_offsets(s, &mut regex);
+        found.extend(regex);
+        found.sort_by_key(|m| (m.start, m.end));
+        Ok(found)
+    }
+
+    /// regex hits are explained with the matched text, regexes are not normalized
+    fn explain(&self, s: &str) -> Option<Explanation> {
+        self.literals.explain(s).or_else(|| {
+            let mut hits = self.regex_matches(s);
+            hits.sort_by_key(|m| m.start);
+            let mut hit = hits.into_iter().next()?;
+            matches::set_char_offsets(s, std::slice::from_mut(&mut hit));
+            Some(Explanation {
+                word: hit.word.clone(),
+                pattern: hit.word.clone(),
+                normalized: s[hit.start..hit.end].to_string(),
+                hit: Some(hit),
+                steps: vec!["regex:case_insensitive".to_string()],
+                path: None,
+            })
+        })
+    }
+
+    fn metadata(&self) -> &ListMetadata {
+        self.literals.metadata()
+    }
+
+    /// report of the literal words, invalid regexes fail construction instead
+    fn build_report(&self) -> &BuildReport {
+        self.literals.build_report()
+    }
+
+    /// literal automaton stats, regexes only add to the pattern count
+    fn stats(&self) -> Stats {
+        let mut stats = self.literals.stats();
+        stats.patterns += self.regexes.len();
+        stats
+    }
+
+    #[cfg(feature = "dot")]
+    fn to_dot(&self) -> String {
+        self.literals.to_dot()
+    }
+
+    fn metrics(&self) -> &BackendMetrics {
+        &self.metrics
+    }
+
+    fn limits(&self) -> &Limits {
+        self.literals.limits()
+    }
+
+    fn result_cache(&self) -> Option<&ResultCache> {
+        self.literals.result_cache()
+    }
+
+    fn ignored_keys(&self) -> &KeyFilter {
+        self.literals.ignored_keys()
+    }
+
+    fn weights(&self) -> &Weights {
+        self.literals.weights()
+    }
+
+    fn proximity_matches(&self, s: &str) -> Vec<&str> {
+        self.literals.proximity_matches(s)
+    }
+}
+
+deny_list_pymethods! {
+    impl DenyListHybrid {
+        /// constructor from literal words and regexes, keyword options: see `Options`
+        /// # Errors
+        /// * aho-corasic errors (too long patterns)
+        /// * invalid regex, unknown option or invalid value
+        #[new]
+        #[pyo3(signature = (words, patterns = Vec::new(), **options))]
+        #[allow(clippy::needless_pass_by_value)] // python passes owned lists
+        fn py_new(
+            words: Vec<String>,
+            patterns: Vec<String>,
+            options: Option<&Bound<'_, PyDict>>,
+        ) -> PyResult<Self> {
+            Ok(Self::with_options(
+                words,
+                &patterns,
+                &Options::from_kwargs(options)?,
+            )?)
+        }
+
+        /// constructor from a mixed policy list, regex entries start with `re:`
+        /// # Errors
+        /// * invalid regex, unknown option or invalid value
+        #[staticmethod]
+        #[pyo3(name = "from_entries", signature = (entries, **options))]
+        fn py_from_entries(
+            entries: Vec<String>,
+            options: Option<&Bound<'_, PyDict>>,
+        ) -> PyResult<Self> {
+            Ok(Self::from_entries(
+                entries,
+                &Options::from_kwargs(options)?,
+            )?)
+        }
+
+        #[must_use]
+        pub fn is_match(&self, s: &str) -> bool {
+            Matcher::is_match(self, s)
+        }
+        /// literal and regex occurrences, byte offsets
+        #[pyo3(name = "find_all")]
+        fn py_find_all(&self, text: &str) -> Vec<Match> {
+            Matcher::find_all(self, text)
+        }
+
+        /// why a str, or the first matching string of a dict/list with its path, matched
+        #[pyo3(name = "explain")]
+        fn py_explain(&self, value: &Bound<'_, PyAny>) -> Option<Explanation> {
+            match value.extract::<&str>() {
+                Ok(s) => Matcher::explain(self, s),
+                Err(_) => Matcher::explain_value(self, value, &mut String::from("$")),
+            }
+        }
+
+        /// precision, recall and false positive words on a labeled corpus
+        /// # Errors
+        /// * samples and labels differ in length
+        #[pyo3(name = "evaluate")]
+        #[allow(clippy::needless_pass_by_value)] // python passes owned lists
+        fn py_evaluate(&self, samples: Vec<String>, labels: Vec<bool>) -> PyResult<Evaluation> {
+            if samples.len() != labels.len() {
+                return Err(pyo3::exceptions::PyValueError::new_err(
+                    "samples and labels differ in length",
+                ));
+            }
+            let pairs: Vec<(&str, bool)> = samples.iter().map(String::as_str).zip(labels).collect();
+            Ok(Matcher::evaluate(self, &pairs))
+        }
+
+        /// version, source and timestamp options of the list
+        #[getter(metadata)]
+        fn py_metadata(&self) -> ListMetadata {
+            Matcher::metadata(self).clone()
+        }
+
+        /// dropped, duplicate, altered and subsuming literal words
+        #[pyo3(name = "build_report")]
+        fn py_build_report(&self) -> BuildReport {
+            Matcher::build_report(self).clone()
+        }
+
+        /// pattern count, trie states, pattern lengths and heap bytes of the literals
+        #[pyo3(name = "stats")]
+        fn py_stats(&self) -> Stats {
+            Matcher::stats(self)
+        }
+
+        /// masks matches grapheme by grapheme
+        #[pyo3(name = "redact", signature = (text, mask = "*"))]
+        fn py_redact(&self, text: &str, mask: &str) -> String {
+            Matcher::redact(self, text, mask)
+        }
+
+        /// wraps matches, spans snapped to whole graphemes
+        #[pyo3(name = "highlight", signature = (text, open = "<mark>", close = "</mark>"))]
+        fn py_highlight(&self, text: &str, open: &str, close: &str) -> String {
+            Matcher::highlight(self, text, open, close)
+        }
+
+        /// every literal and regex occurrence including overlapping ones, byte offsets
+        /// # Errors
+        /// * the overlapping automaton cannot be built
+        #[pyo3(name = "find_overlapping")]
+        fn py_find_overlapping(&self, text: &str) -> PyResult<Vec<Match>> {
+            Ok(Matcher::find_overlapping(self, text)?)
+        }
+    }
+}
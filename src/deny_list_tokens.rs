@@ -0,0 +1,184 @@
+use aho_corasick::{AhoCorasick, MatchKind};
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
+#[cfg(feature = "python")]
+use pyo3_stub_gen::derive::{gen_stub_pyclass, gen_stub_pymethods};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use crate::build_error::{BuildError, invalid};
+use crate::metrics::{self, BackendMetrics, Timer};
+
+/// largest token id that fits into a char once surrogates are skipped
+const MAX_TOKEN_ID: u32 = 0x0010_F7FF;
+
+/// maps a token id to a unique char, so token sequences become utf-8 strings
+/// and automaton matches can only start and end on token boundaries
+fn token_char(id: u32) -> Option<char> {
+    if id > MAX_TOKEN_ID {
+        return None;
+    }
+    let code = if id < 0xD800 { id } else { id + 0x800 };
+    char::from_u32(code)
+}
+
+/// greedy longest-prefix tokenization of `word` with `vocab`,
+/// None when some part of the word is not in the vocabulary
+fn tokenize(vocab: &HashMap<String, u32>, word: &str) -> Option<Vec<u32>> {
+    let mut ids = Vec::new();
+    let mut rest = word;
+    while !rest.is_empty() {
+        let (len, id) = rest
+            .char_indices()
+            .map(|(i, c)| i + c.len_utf8())
+            .rev()
+            .find_map(|end| vocab.get(&rest[..end]).map(|id| (end, *id)))?;
+        ids.push(id);
+        rest = &rest[len..];
+    }
+    Some(ids)
+}
+
+/// token match: pattern index and token positions `[start, end)`
+pub type TokenMatch = (usize, usize, usize);
+
+/// deny list on model token ids, blocks before detokenization
+#[cfg_attr(feature = "python", gen_stub_pyclass)]
+#[cfg_attr(
+    feature = "python",
+    pyclass(module = "deny_filter.matchers", skip_from_py_object)
+)]
+#[derive(Clone)]
+pub struct DenyListTokens {
+    ac: AhoCorasick,
+    patterns: Vec<Vec<u32>>,
+    metrics: Arc<BackendMetrics>,
+}
+
+impl DenyListTokens {
+    /// builds from token id sequences
+    /// # Errors
+    /// * empty pattern, token id above 0x10F7FF or automaton errors
+    pub fn new(patterns: Vec<Vec<u32>>) -> Result<Self, BuildError> {
+        let mut encoded = Vec::with_capacity(patterns.len());
+        for pattern in &patterns {
+            if pattern.is_empty() {
+                return Err(invalid("empty token pattern"));
+            }
+            let s: Option<String> = pattern.iter().map(|id| token_char(*id)).collect();
+            encoded.push(s.ok_or_else(|| invalid("token id above 0x10F7FF"))?);
+        }
+        let ac = AhoCorasick::builder()
+            .match_kind(MatchKind::LeftmostFirst)
+            .build(&encoded)
+            .map_err(invalid)?;
+        Ok(Self {
+            ac,
+            patterns,
+            metrics: metrics::backend("tokens"),
+        })
+    }
+
+    /// precomputes token patterns of deny words with a tokenizer vocabulary,
+    /// each word is also tokenized with every prefix (e.g. "Ġ" or "▁" for a leading space)
+    /// # Errors
+    /// * a word cannot be tokenized with the vocabulary
+    pub fn from_vocab(
+        vocab: &HashMap<String, u32>,
+        words: &[String],
+        prefixes: &[String],
+    ) -> Result<Self, BuildError> {
+        let mut patterns = Vec::new();
+        for word in words {
+            let ids = tokenize(vocab, word).ok_or_else(|| {
+                invalid(format!("{word:?} cannot be tokenized with the vocabulary"))
+            })?;
+            patterns.push(ids);
+            for prefix in prefixes {
+                if let Some(ids) = tokenize(vocab, &format!("{prefix}{word}")) {
+                    patterns.push(ids);
+                }
+            }
+        }
+        patterns.dedup();
+        Self::new(patterns)
+    }
+
+    /// first match in a token sequence
+    #[must_use]
+    pub fn find(&self, ids: &[u32]) -> Option<TokenMatch> {
+        let timer = Timer::start();
+        let found = self.find_segments(ids);
+        self.metrics.record_scan(timer.elapsed(), found.is_some());
+        found
+    }
+
+    /// ids that cannot be encoded never occur in patterns, they split the input
+    fn find_segments(&self, ids: &[u32]) -> Option<TokenMatch> {
+        let mut offset = 0;
+        for segment in ids.split(|id| token_char(*id).is_none()) {
+            let text: String = segment.iter().filter_map(|id| token_char(*id)).collect();
+            if let Some(m) = self.ac.find(&text) {
+                // token position = number of chars before the byte offset
+                let start = text[..m.start()].chars().count();
+                let end = start + text[m.start()..m.end()].chars().count();
+                return Some((m.pattern().as_usize(), offset + start, offset + end));
+            }
+            offset += segment.len() + 1;
+        }
+        None
+    }
+
+    #[must_use]
+    pub fn patterns(&self) -> &[Vec<u32>] {
+        &self.patterns
+    }
+}
+
+// python passes owned lists
+#[cfg(feature = "python")]
+#[allow(clippy::needless_pass_by_value)]
+#[gen_stub_pymethods]
+#[pymethods]
+impl DenyListTokens {
+    /// constructor from token id sequences
+    /// # Errors
+    /// * empty pattern or token id above 0x10F7FF
+    #[new]
+    fn py_new(patterns: Vec<Vec<u32>>) -> PyResult<Self> {
+        Ok(Self::new(patterns)?)
+    }
+
+    /// tokenizes deny words with a vocabulary (greedy longest match),
+    /// prefixes add variants such as "Ġ"/"▁" for a leading space
+    /// # Errors
+    /// * a word cannot be tokenized with the vocabulary
+    #[staticmethod]
+    #[pyo3(signature = (vocab, words, prefixes = Vec::new()))]
+    fn with_vocab(
+        vocab: HashMap<String, u32>,
+        words: Vec<String>,
+        prefixes: Vec<String>,
+    ) -> PyResult<Self> {
+        Ok(Self::from_vocab(&vocab, &words, &prefixes)?)
+    }
+
+    #[must_use]
+    pub fn is_match(&self, ids: Vec<u32>) -> bool {
+        self.find(&ids).is_some()
+    }
+
+    /// (pattern index, start, end) in token positions of the first match
+    #[pyo3(name = "find")]
+    #[must_use]
+    fn py_find(&self, ids: Vec<u32>) -> Option<TokenMatch> {
+        self.find(&ids)
+    }
+
+    /// token id patterns, including those computed from a vocabulary
+    #[pyo3(name = "patterns")]
+    #[must_use]
+    fn py_patterns(&self) -> Vec<Vec<u32>> {
+        self.patterns.clone()
+    }
+}
@@ -0,0 +1,48 @@
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+
+use crate::options::WordMatch;
+
+/// visible edge label of a pattern char: separators and anchor marks would render blank
+fn label(c: char) -> String {
+    match c {
+        ' ' => "␣".to_string(),
+        '\u{2}' => "^".to_string(),
+        '\u{3}' => "$".to_string(),
+        c => format!("{:?}", c.to_string()).trim_matches('"').to_string(),
+    }
+}
+
+/// graphviz digraph of one char trie per match kind; a node ending a pattern
+/// is a double circle labelled with its deny word
+#[must_use]
+pub fn tries<'a>(parts: impl IntoIterator<Item = (WordMatch, Vec<(&'a str, &'a str)>)>) -> String {
+    let mut out =
+        String::from("digraph deny_list {\n  rankdir=LR;\n  node [shape=circle, label=\"\"];\n");
+    // every node but the roots is the target of exactly one edge
+    let mut edges: BTreeMap<(usize, char), usize> = BTreeMap::new();
+    let mut roots = 0;
+    for (kind, patterns) in parts {
+        let root = edges.len() + roots;
+        roots += 1;
+        let _ = writeln!(out, "  {root} [shape=box, label=\"{kind:?}\"];");
+        for (pattern, word) in patterns {
+            let mut node = root;
+            for c in pattern.chars() {
+                let next = edges.len() + roots;
+                let child = *edges.entry((node, c)).or_insert(next);
+                if child == next {
+                    let _ = writeln!(out, "  {node} -> {child} [label=\"{}\"];", label(c));
+                }
+                node = child;
+            }
+            let _ = writeln!(
+                out,
+                "  {node} [shape=doublecircle, xlabel={:?}];",
+                word.replace('"', "'")
+            );
+        }
+    }
+    out.push_str("}\n");
+    out
+}
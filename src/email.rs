@@ -0,0 +1,381 @@
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
+#[cfg(feature = "python")]
+use pyo3_stub_gen::derive::{gen_stub_pyclass, gen_stub_pymethods};
+
+use crate::decode::decode_base64;
+use crate::matcher::Matcher;
+use crate::metrics::Timer;
+use crate::trace::ScanSpan;
+
+/// headers of a message whose values are scanned, address and subject lines;
+/// signatures and relay headers are left out, their base64 would match short words
+const SCANNED_HEADERS: [&str; 7] = ["subject", "from", "to", "cc", "bcc", "reply-to", "sender"];
+
+/// multipart and attached message parts nested at most
+const MAX_DEPTH: usize = 8;
+
+/// html tags separating words, other tags are dropped without a space, e.g. `<b>`
+const BLOCK_TAGS: [&str; 17] = [
+    "br", "p", "div", "li", "ul", "ol", "tr", "td", "th", "table", "h1", "h2", "h3", "h4", "h5",
+    "h6", "hr",
+];
+
+/// the part of an email with a deny word
+#[cfg_attr(feature = "python", gen_stub_pyclass)]
+#[cfg_attr(
+    feature = "python",
+    pyclass(module = "deny_filter.plugin", frozen, get_all, skip_from_py_object)
+)]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct EmailHit {
+    /// header name in lowercase, or `text/plain` or `text/html` for a body part
+    pub part: String,
+    /// deny word that matched first
+    pub word: String,
+}
+
+#[cfg(feature = "python")]
+#[gen_stub_pymethods]
+#[pymethods]
+impl EmailHit {
+    fn __repr__(&self) -> String {
+        format!("EmailHit(part={:?}, word={:?})", self.part, self.word)
+    }
+}
+
+/// header block and body of a message, split at the first empty line
+fn split_head(raw: &[u8]) -> (&[u8], &[u8]) {
+    for blank in [&b"\r\n"[..], b"\n"] {
+        if let Some(body) = raw.strip_prefix(blank) {
+            return (&[], body);
+        }
+    }
+    for (i, byte) in raw.iter().enumerate() {
+        if *byte == b'\n' {
+            for blank in [&b"\r\n"[..], b"\n"] {
+                if let Some(body) = raw[i + 1..].strip_prefix(blank) {
+                    return (&raw[..=i], body);
+                }
+            }
+        }
+    }
+    (raw, &[])
+}
+
+/// lowercase names and unfolded values of a header block
+fn headers(head: &[u8]) -> Vec<(String, String)> {
+    let mut headers: Vec<(String, String)> = Vec::new();
+    for line in String::from_utf8_lossy(head).lines() {
+        if line.starts_with([' ', '\t']) {
+            if let Some((_, value)) = headers.last_mut() {
+                value.push(' ');
+                value.push_str(line.trim());
+            }
+        } else if let Some((name, value)) = line.split_once(':') {
+            headers.push((name.trim().to_ascii_lowercase(), value.trim().to_string()));
+        }
+    }
+    headers
+}
+
+/// text of bytes in `charset`: latin-1 byte by byte, utf-8 (and ascii) otherwise
+fn decode_charset(bytes: &[u8], charset: &str) -> String {
+    match charset.to_ascii_lowercase().as_str() {
+        "iso-8859-1" | "latin1" | "latin-1" | "windows-1252" => {
+            bytes.iter().map(|b| char::from(*b)).collect()
+        }
+        _ => String::from_utf8_lossy(bytes).into_owned(),
+    }
+}
+
+/// bytes of quoted-printable text, soft line breaks removed; invalid escapes are kept
+#[must_use]
+pub fn decode_quoted_printable(s: &[u8]) -> Vec<u8> {
+    let mut out = Vec::with_capacity(s.len());
+    let mut i = 0;
+    while i < s.len() {
+        if s[i] == b'=' {
+            if s[i + 1..].starts_with(b"\r\n") {
+                i += 3;
+                continue;
+            }
+            if s[i + 1..].starts_with(b"\n") {
+                i += 2;
+                continue;
+            }
+            let escaped = s
+                .get(i + 1..i + 3)
+                .and_then(|hex| std::str::from_utf8(hex).ok())
+                .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+            if let Some(byte) = escaped {
+                out.push(byte);
+                i += 3;
+                continue;
+            }
+        }
+        out.push(s[i]);
+        i += 1;
+    }
+    out
+}
+
+/// text and length of the RFC 2047 encoded word `=?charset?B|Q?text?=` at the start of `s`
+fn encoded_word(s: &str) -> Option<(String, usize)> {
+    let inner = s.strip_prefix("=?")?;
+    let (charset, inner) = inner.split_once('?')?;
+    let (encoding, inner) = inner.split_once('?')?;
+    let end = inner.find("?=")?;
+    let text = &inner[..end];
+    let bytes = match encoding {
+        "B" | "b" => decode_base64(text)?,
+        "Q" | "q" => decode_quoted_printable(text.replace('_', " ").as_bytes()),
+        _ => return None,
+    };
+    let len = charset.len() + encoding.len() + end + 6;
+    // RFC 2231 language suffix, e.g. utf-8*en
+    let charset = charset.split('*').next().unwrap_or_default();
+    Some((decode_charset(&bytes, charset), len))
+}
+
+/// header value with its encoded words decoded, the whitespace between two of them dropped
+#[must_use]
+pub fn decode_header(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+    let mut rest = value;
+    let mut after_word = false;
+    while let Some(start) = rest.find("=?") {
+        let Some((text, len)) = encoded_word(&rest[start..]) else {
+            out.push_str(&rest[..start + 2]);
+            rest = &rest[start + 2..];
+            after_word = false;
+            continue;
+        };
+        let between = &rest[..start];
+        if !(after_word && between.trim().is_empty()) {
+            out.push_str(between);
+        }
+        out.push_str(&text);
+        rest = &rest[start + len..];
+        after_word = true;
+    }
+    out.push_str(rest);
+    out
+}
+
+/// mime type in lowercase and parameters of a content type, `text/plain` when missing
+fn content_type(value: Option<&str>) -> (String, Vec<(String, String)>) {
+    let mut parts = value.unwrap_or_default().split(';');
+    let mime = parts.next().unwrap_or_default().trim().to_ascii_lowercase();
+    let params = parts
+        .filter_map(|param| {
+            let (key, value) = param.split_once('=')?;
+            Some((
+                key.trim().to_ascii_lowercase(),
+                value.trim().trim_matches('"').to_string(),
+            ))
+        })
+        .collect();
+    let mime = if mime.is_empty() {
+        "text/plain".to_string()
+    } else {
+        mime
+    };
+    (mime, params)
+}
+
+/// parts of a multipart body between its `--boundary` lines
+fn multipart<'a>(body: &'a [u8], boundary: &str) -> Vec<&'a [u8]> {
+    let delimiter = format!("--{boundary}");
+    let mut parts = Vec::new();
+    let mut start = None;
+    let mut at = 0;
+    for line in body.split_inclusive(|b| *b == b'\n') {
+        if let Some(rest) = line.trim_ascii_end().strip_prefix(delimiter.as_bytes())
+            && (rest.is_empty() || rest == b"--")
+        {
+            if let Some(start) = start {
+                // the line break before a delimiter belongs to it
+                let part: &[u8] = &body[start..at];
+                let part = part.strip_suffix(b"\n").unwrap_or(part);
+                parts.push(part.strip_suffix(b"\r").unwrap_or(part));
+            }
+            if rest == b"--" {
+                return parts;
+            }
+            start = Some(at + line.len());
+        }
+        at += line.len();
+    }
+    // an unterminated last part
+    if let Some(start) = start {
+        parts.push(&body[start..]);
+    }
+    parts
+}
+
+/// decoded html entity, named or numeric
+fn entity(name: &str) -> Option<char> {
+    match name {
+        "amp" => Some('&'),
+        "lt" => Some('<'),
+        "gt" => Some('>'),
+        "quot" => Some('"'),
+        "apos" => Some('\''),
+        "nbsp" => Some(' '),
+        _ => {
+            let code = name.strip_prefix('#')?;
+            let n = match code.strip_prefix(['x', 'X']) {
+                Some(hex) => u32::from_str_radix(hex, 16).ok()?,
+                None => code.parse().ok()?,
+            };
+            char::from_u32(n)
+        }
+    }
+}
+
+fn push_entities(out: &mut String, text: &str) {
+    let mut rest = text;
+    while let Some(amp) = rest.find('&') {
+        out.push_str(&rest[..amp]);
+        rest = &rest[amp..];
+        let decoded = rest
+            .find(';')
+            .filter(|end| *end <= 10)
+            .and_then(|end| Some((entity(&rest[1..end])?, end)));
+        if let Some((c, end)) = decoded {
+            out.push(c);
+            rest = &rest[end + 1..];
+        } else {
+            out.push('&');
+            rest = &rest[1..];
+        }
+    }
+    out.push_str(rest);
+}
+
+/// text of html: tags and comments dropped, block tags as spaces, scripts and styles
+/// left out, entities decoded
+#[must_use]
+pub fn html_text(html: &str) -> String {
+    let mut out = String::with_capacity(html.len());
+    let mut rest = html;
+    while let Some(open) = rest.find('<') {
+        push_entities(&mut out, &rest[..open]);
+        let tag = &rest[open..];
+        if let Some(comment) = tag.strip_prefix("<!--") {
+            rest = comment.find("-->").map_or("", |end| &comment[end + 3..]);
+            continue;
+        }
+        let Some(close) = tag.find('>') else {
+            return out;
+        };
+        let closing = tag.starts_with("</");
+        let name = tag[1..close]
+            .trim_start_matches('/')
+            .split(|c: char| c.is_whitespace() || c == '/')
+            .next()
+            .unwrap_or_default()
+            .to_ascii_lowercase();
+        rest = &tag[close + 1..];
+        if !closing && (name == "script" || name == "style") {
+            let end = format!("</{name}");
+            rest = rest
+                .to_ascii_lowercase()
+                .find(&end)
+                .map_or("", |i| &rest[i..]);
+        }
+        if BLOCK_TAGS.contains(&name.as_str()) {
+            out.push(' ');
+        }
+    }
+    push_entities(&mut out, rest);
+    out
+}
+
+/// body of a part decoded from its `Content-Transfer-Encoding`
+fn transfer_decode(body: &[u8], encoding: Option<&str>) -> Vec<u8> {
+    match encoding.map(str::to_ascii_lowercase).as_deref() {
+        Some("base64") => {
+            let compact: String = String::from_utf8_lossy(body)
+                .chars()
+                .filter(|c| !c.is_whitespace())
+                .collect();
+            decode_base64(&compact).unwrap_or_default()
+        }
+        Some("quoted-printable") => decode_quoted_printable(body),
+        _ => body.to_vec(),
+    }
+}
+
+/// texts of a message, or of a multipart part with `message` false: the headers of a
+/// part describe its content only
+fn walk(raw: &[u8], message: bool, depth: usize, texts: &mut Vec<(String, String)>) {
+    let (head, body) = split_head(raw);
+    let headers = headers(head);
+    let header = |name: &str| {
+        headers
+            .iter()
+            .find(|(n, _)| n == name)
+            .map(|(_, value)| value.as_str())
+    };
+    for (name, value) in headers.iter().filter(|_| message) {
+        if SCANNED_HEADERS.contains(&name.as_str()) {
+            texts.push((name.clone(), decode_header(value)));
+        }
+    }
+    let (mime, params) = content_type(header("content-type"));
+    let param = |name: &str| {
+        params
+            .iter()
+            .find(|(n, _)| n == name)
+            .map(|(_, value)| value.as_str())
+    };
+    let body = transfer_decode(body, header("content-transfer-encoding"));
+    if mime.starts_with("multipart/") {
+        if let Some(boundary) = param("boundary")
+            && depth < MAX_DEPTH
+        {
+            for part in multipart(&body, boundary) {
+                walk(part, false, depth + 1, texts);
+            }
+        }
+    } else if mime == "message/rfc822" {
+        if depth < MAX_DEPTH {
+            walk(&body, true, depth + 1, texts);
+        }
+    } else if mime == "text/plain" || mime == "text/html" {
+        let text = decode_charset(&body, param("charset").unwrap_or("utf-8"));
+        let text = if mime == "text/html" {
+            html_text(&text)
+        } else {
+            text
+        };
+        texts.push((mime, text));
+    }
+}
+
+/// decoded subject and address headers and text bodies of an RFC 822 / MIME message, in
+/// order, labeled with the lowercase header name or the content type; multipart bodies
+/// and attached messages are walked, `base64` and `quoted-printable` bodies and encoded
+/// header words decoded, html reduced to its text; other parts are skipped
+#[must_use]
+pub fn email_texts(raw: &[u8]) -> Vec<(String, String)> {
+    let mut texts = Vec::new();
+    walk(raw, true, 0, &mut texts);
+    texts
+}
+
+/// first header or text body of an email with a deny word, see `email_texts`
+pub fn scan_email<M: Matcher + ?Sized>(matcher: &M, raw: &[u8]) -> Option<EmailHit> {
+    let span = ScanSpan::enter(matcher.metrics(), "scan_email");
+    let timer = Timer::start();
+    let found = email_texts(raw).into_iter().find_map(|(part, text)| {
+        matcher.check(&text).then(|| EmailHit {
+            part,
+            word: matcher.first_match(&text).unwrap_or_default().to_string(),
+        })
+    });
+    span.finish(timer.elapsed(), raw.len(), found.is_some());
+    found
+}
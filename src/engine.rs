@@ -0,0 +1,25 @@
+use crate::build_error::BuildError;
+use crate::options::MatchKind;
+
+/// hit of pattern `pattern` at byte range `start..end` of the searched text
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Span {
+    pub pattern: usize,
+    pub start: usize,
+    pub end: usize,
+}
+
+/// multi-pattern automaton of one backend, built from normalized deny words
+pub trait Engine: Sized {
+    /// # Errors
+    /// * pattern errors of the backend
+    fn build(patterns: &[String], kind: MatchKind) -> Result<Self, BuildError>;
+
+    fn is_match(&self, text: &str) -> bool;
+
+    /// leftmost non-overlapping hits
+    fn find_iter<'a>(&'a self, text: &'a str) -> Box<dyn Iterator<Item = Span> + 'a>;
+
+    /// heap memory of the automaton
+    fn heap_bytes(&self) -> usize;
+}
@@ -0,0 +1,82 @@
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
+#[cfg(feature = "python")]
+use pyo3_stub_gen::derive::{gen_stub_pyclass, gen_stub_pymethods};
+use std::collections::BTreeMap;
+
+/// quality of a matcher on a labeled corpus
+#[cfg_attr(feature = "python", gen_stub_pyclass)]
+#[cfg_attr(
+    feature = "python",
+    pyclass(module = "deny_filter.matchers", frozen, get_all, skip_from_py_object)
+)]
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Evaluation {
+    pub true_positives: usize,
+    pub false_positives: usize,
+    pub true_negatives: usize,
+    pub false_negatives: usize,
+    /// matched samples labeled as deny, 1.0 when nothing matched
+    pub precision: f64,
+    /// deny labeled samples matched, 1.0 without deny labels
+    pub recall: f64,
+    /// deny word (or proximity rule) and its false positive count, most frequent first
+    pub false_positive_words: Vec<(String, usize)>,
+}
+
+impl Evaluation {
+    /// counts `(matched word, label)` outcomes, the word is None for a sample that did not match
+    pub(crate) fn new<'a>(outcomes: impl IntoIterator<Item = (Option<&'a str>, bool)>) -> Self {
+        let mut eval = Self::default();
+        let mut words: BTreeMap<&str, usize> = BTreeMap::new();
+        for (word, label) in outcomes {
+            match (word, label) {
+                (Some(_), true) => eval.true_positives += 1,
+                (Some(word), false) => {
+                    eval.false_positives += 1;
+                    *words.entry(word).or_default() += 1;
+                }
+                (None, false) => eval.true_negatives += 1,
+                (None, true) => eval.false_negatives += 1,
+            }
+        }
+        eval.precision = ratio(eval.true_positives, eval.false_positives);
+        eval.recall = ratio(eval.true_positives, eval.false_negatives);
+        eval.false_positive_words = words
+            .into_iter()
+            .map(|(word, n)| (word.to_string(), n))
+            .collect();
+        eval.false_positive_words
+            .sort_by_key(|(_, n)| std::cmp::Reverse(*n));
+        eval
+    }
+}
+
+/// `hits / (hits + misses)`, 1.0 for no samples
+#[allow(clippy::cast_precision_loss)] // corpora are far below 2^52 samples
+fn ratio(hits: usize, misses: usize) -> f64 {
+    if hits + misses == 0 {
+        1.0
+    } else {
+        hits as f64 / (hits + misses) as f64
+    }
+}
+
+#[cfg(feature = "python")]
+#[gen_stub_pymethods]
+#[pymethods]
+impl Evaluation {
+    fn __repr__(&self) -> String {
+        format!(
+            "Evaluation(precision={:.4}, recall={:.4}, true_positives={}, false_positives={}, \
+             true_negatives={}, false_negatives={}, false_positive_words={:?})",
+            self.precision,
+            self.recall,
+            self.true_positives,
+            self.false_positives,
+            self.true_negatives,
+            self.false_negatives,
+            self.false_positive_words
+        )
+    }
+}
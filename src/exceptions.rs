@@ -0,0 +1,101 @@
+use regex::{Regex, RegexBuilder};
+
+use crate::build_error::{BuildError, invalid};
+use crate::normalize::Normalizer;
+use crate::options::{CaseFold, Options};
+
+/// negative context of deny words: a hit is suppressed
+/// when one of the word's exception patterns or an allow entry overlaps it
+#[derive(Clone, Debug, Default)]
+pub struct Exceptions {
+    /// normalized exception patterns by pattern index, empty without exceptions
+    by_pattern: Vec<Vec<String>>,
+    /// normalized `allow` phrases and `allow_patterns`, for the hits of every pattern
+    allowed: Vec<Regex>,
+    /// adjacent whole words share their separator, which is no overlap
+    shared: usize,
+}
+
+impl Exceptions {
+    /// `patterns[i]` are the exceptions of the i-th deny word pattern, the allow entries
+    /// of `options` apply to all of them
+    /// # Errors
+    /// * empty exception pattern or allow entry, or without words in whole word mode
+    /// * invalid allow regex
+    pub fn new(
+        patterns: &[&[String]],
+        normalizer: &Normalizer,
+        options: &Options,
+    ) -> Result<Self, BuildError> {
+        let insensitive = options.case_fold != CaseFold::None;
+        let allowed = options
+            .allow
+            .iter()
+            .map(|p| match normalizer.pattern(p)? {
+                p if p.is_empty() => Err(invalid("empty allow entry")),
+                p => Regex::new(&regex::escape(&p)).map_err(invalid),
+            })
+            .chain(options.allow_patterns.iter().map(|p| {
+                RegexBuilder::new(p)
+                    .case_insensitive(insensitive)
+                    .build()
+                    .map_err(invalid)
+            }))
+            .collect::<Result<Vec<_>, _>>()?;
+        let shared = usize::from(normalizer.words_only());
+        if patterns.iter().all(|p| p.is_empty()) {
+            return Ok(Self {
+                by_pattern: Vec::new(),
+                allowed,
+                shared,
+            });
+        }
+        let by_pattern = patterns
+            .iter()
+            .map(|patterns| {
+                patterns
+                    .iter()
+                    .map(|p| match normalizer.pattern(p)? {
+                        p if p.is_empty() => Err(invalid("empty exception pattern")),
+                        p => Ok(p),
+                    })
+                    .collect::<Result<Vec<_>, _>>()
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Self {
+            by_pattern,
+            allowed,
+            shared,
+        })
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.by_pattern.is_empty() && self.allowed.is_empty()
+    }
+
+    /// hit of `pattern` at `start..end` of the normalized text overlaps an exception
+    /// or an allow entry
+    #[must_use]
+    pub fn suppressed(&self, text: &str, pattern: usize, start: usize, end: usize) -> bool {
+        let overlaps =
+            |at: usize, len: usize| at + self.shared < end && start + self.shared < at + len;
+        let excepted = self.by_pattern.get(pattern).is_some_and(|exceptions| {
+            exceptions.iter().any(|exception| {
+                // only occurrences starting in this window can overlap the hit
+                let from = text.floor_char_boundary((start + 1).saturating_sub(exception.len()));
+                let to = text.ceil_char_boundary(end + exception.len() - 1);
+                text[from..to]
+                    .match_indices(exception.as_str())
+                    .any(|(i, p)| overlaps(from + i, p.len()))
+            })
+        });
+        excepted
+            || self.allowed.iter().any(|allow| {
+                allow
+                    .find_iter(text)
+                    .take_while(|m| m.start() < end)
+                    .any(|m| !m.is_empty() && overlaps(m.start(), m.len()))
+            })
+    }
+}
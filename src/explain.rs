@@ -0,0 +1,49 @@
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
+#[cfg(feature = "python")]
+use pyo3_stub_gen::derive::{gen_stub_pyclass, gen_stub_pymethods};
+
+use crate::matches::Match;
+
+/// why a text matched, to justify a block in review and appeals
+#[cfg_attr(feature = "python", gen_stub_pyclass)]
+#[cfg_attr(
+    feature = "python",
+    pyclass(module = "deny_filter.matchers", frozen, get_all, skip_from_py_object)
+)]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Explanation {
+    /// deny word in lowercase, or the `first NEAR/n second` label of a proximity rule
+    pub word: String,
+    /// offsets of the deny word hit in the text, None for proximity rules
+    pub hit: Option<Match>,
+    /// the deny word after normalization
+    pub pattern: String,
+    /// the normalized text that matched the pattern
+    pub normalized: String,
+    /// normalizations applied to the deny word and the text, in order
+    pub steps: Vec<String>,
+    /// `$.key[0]` path of the matched string for scans, None for plain text
+    pub path: Option<String>,
+}
+
+#[cfg(feature = "python")]
+#[gen_stub_pymethods]
+#[pymethods]
+impl Explanation {
+    fn __repr__(&self) -> String {
+        format!(
+            "Explanation(word={:?}, hit={}, pattern={:?}, normalized={:?}, steps={:?}, path={})",
+            self.word,
+            self.hit
+                .as_ref()
+                .map_or("None".to_string(), Match::__repr__),
+            self.pattern,
+            self.normalized,
+            self.steps,
+            self.path
+                .as_ref()
+                .map_or("None".to_string(), |p| format!("{p:?}"))
+        )
+    }
+}
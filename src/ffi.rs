@@ -0,0 +1,130 @@
+use std::ffi::{CStr, c_char, c_int};
+use std::slice;
+
+use crate::backend::Backend;
+use crate::matcher::Matcher;
+
+/// opaque handle owned by the C caller
+pub struct DenyFilter {
+    matcher: Box<dyn Matcher + Send + Sync>,
+}
+
+/// 1 for a match, 0 for no match, -1 for invalid input
+fn verdict(result: Option<bool>) -> c_int {
+    match result {
+        Some(true) => 1,
+        Some(false) => 0,
+        None => -1,
+    }
+}
+
+/// builds a deny list, returns NULL on invalid input or patterns
+/// # Safety
+/// * `words` points to `len` NUL terminated UTF-8 strings
+/// * `backend` is NULL (aho-corasick) or a NUL terminated backend name
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn deny_new(
+    words: *const *const c_char,
+    len: usize,
+    backend: *const c_char,
+) -> *mut DenyFilter {
+    if words.is_null() && len > 0 {
+        return std::ptr::null_mut();
+    }
+    let backend = if backend.is_null() {
+        Backend::default()
+    } else {
+        // SAFETY: caller passes a NUL terminated string
+        match unsafe { CStr::from_ptr(backend) }.to_str().map(str::parse) {
+            Ok(Ok(backend)) => backend,
+            _ => return std::ptr::null_mut(),
+        }
+    };
+    let ptrs = if len == 0 {
+        &[]
+    } else {
+        // SAFETY: caller passes `len` string pointers
+        unsafe { slice::from_raw_parts(words, len) }
+    };
+    let mut list = Vec::with_capacity(len);
+    for &ptr in ptrs {
+        if ptr.is_null() {
+            return std::ptr::null_mut();
+        }
+        // SAFETY: caller passes NUL terminated strings
+        match unsafe { CStr::from_ptr(ptr) }.to_str() {
+            Ok(word) => list.push(word.to_string()),
+            Err(_) => return std::ptr::null_mut(),
+        }
+    }
+    match backend.build(list) {
+        Ok(matcher) => Box::into_raw(Box::new(DenyFilter { matcher })),
+        Err(_) => std::ptr::null_mut(),
+    }
+}
+
+/// checks UTF-8 text of `len` bytes, returns 1 on match, 0 if clean, -1 on invalid input
+/// # Safety
+/// * `filter` comes from `deny_new` and was not freed
+/// * `text` points to `len` readable bytes
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn deny_is_match(
+    filter: *const DenyFilter,
+    text: *const u8,
+    len: usize,
+) -> c_int {
+    // SAFETY: caller passes a live handle and a readable buffer
+    let (Some(filter), Some(bytes)) = (unsafe { filter.as_ref() }, unsafe { bytes(text, len) })
+    else {
+        return -1;
+    };
+    verdict(
+        std::str::from_utf8(bytes)
+            .ok()
+            .map(|s| filter.matcher.scan_str(s)),
+    )
+}
+
+/// scans every string value of a msgpack document of `len` bytes,
+/// returns 1 on match, 0 if clean, -1 on malformed msgpack or invalid input
+/// # Safety
+/// * `filter` comes from `deny_new` and was not freed
+/// * `buf` points to `len` readable bytes
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn deny_scan_msgpack(
+    filter: *const DenyFilter,
+    buf: *const u8,
+    len: usize,
+) -> c_int {
+    // SAFETY: caller passes a live handle and a readable buffer
+    let (Some(filter), Some(bytes)) = (unsafe { filter.as_ref() }, unsafe { bytes(buf, len) })
+    else {
+        return -1;
+    };
+    verdict(filter.matcher.scan_msgpack(bytes).ok())
+}
+
+/// releases a deny list, NULL is ignored
+/// # Safety
+/// * `filter` comes from `deny_new` and is freed only once
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn deny_free(filter: *mut DenyFilter) {
+    if !filter.is_null() {
+        // SAFETY: handle was created by Box::into_raw in deny_new
+        drop(unsafe { Box::from_raw(filter) });
+    }
+}
+
+/// borrowed buffer, None for NULL with a non zero length
+/// # Safety
+/// * `ptr` points to `len` readable bytes
+unsafe fn bytes<'a>(ptr: *const u8, len: usize) -> Option<&'a [u8]> {
+    if len == 0 {
+        return Some(&[]);
+    }
+    if ptr.is_null() {
+        return None;
+    }
+    // SAFETY: caller guarantees `len` readable bytes
+    Some(unsafe { slice::from_raw_parts(ptr, len) })
+}
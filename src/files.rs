@@ -0,0 +1,266 @@
+use memmap2::Mmap;
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
+#[cfg(feature = "python")]
+use pyo3_stub_gen::derive::{gen_stub_pyclass, gen_stub_pymethods};
+use std::fs::File;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Mutex, PoisonError};
+use std::thread;
+
+use crate::hits::{self, Format, Hit};
+use crate::matcher::Matcher;
+use crate::matches::Match;
+use crate::stream::StreamScanner;
+
+/// bytes of a mapped file scanned at a time when no window is given, 1 MiB
+pub const DEFAULT_MMAP_WINDOW: usize = 1 << 20;
+
+/// hits of one file, an unreadable or malformed file has an error and no hits
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FileReport {
+    pub path: PathBuf,
+    pub hits: Vec<Hit>,
+    pub error: Option<String>,
+}
+
+/// files below `path` in name order, directories are walked recursively;
+/// a file is returned as is
+/// # Errors
+/// * `path` does not exist, a directory cannot be read
+pub fn walk(path: &Path) -> io::Result<Vec<PathBuf>> {
+    let mut files = Vec::new();
+    walk_into(path, &mut files)?;
+    Ok(files)
+}
+
+fn walk_into(path: &Path, files: &mut Vec<PathBuf>) -> io::Result<()> {
+    if std::fs::metadata(path)?.is_dir() {
+        let mut entries: Vec<PathBuf> = std::fs::read_dir(path)?
+            .map(|entry| entry.map(|e| e.path()))
+            .collect::<io::Result<_>>()?;
+        entries.sort();
+        for entry in entries {
+            walk_into(&entry, files)?;
+        }
+    } else {
+        files.push(path.to_path_buf());
+    }
+    Ok(())
+}
+
+/// whether `path` (`/` separated) matches `glob`: `*` and `?` within a path segment,
+/// `**` across segments; a glob without `/` matches the file name, e.g. `*.log`
+#[must_use]
+pub fn glob_match(glob: &str, path: &str) -> bool {
+    if !glob.contains('/') {
+        let name = path.rsplit('/').next().unwrap_or(path);
+        return segments_match(glob.as_bytes(), name.as_bytes());
+    }
+    let glob: Vec<&str> = glob.split('/').collect();
+    let path: Vec<&str> = path.split('/').collect();
+    path_match(&glob, &path)
+}
+
+fn path_match(glob: &[&str], path: &[&str]) -> bool {
+    match glob.split_first() {
+        None => path.is_empty(),
+        Some((&"**", rest)) => (0..=path.len()).any(|i| path_match(rest, &path[i..])),
+        Some((segment, rest)) => path.split_first().is_some_and(|(name, path)| {
+            segments_match(segment.as_bytes(), name.as_bytes()) && path_match(rest, path)
+        }),
+    }
+}
+
+/// `*` and `?` wildcard match of one segment
+fn segments_match(glob: &[u8], name: &[u8]) -> bool {
+    match glob.split_first() {
+        None => name.is_empty(),
+        Some((b'*', rest)) => (0..=name.len()).any(|i| segments_match(rest, &name[i..])),
+        Some((b'?', rest)) => !name.is_empty() && segments_match(rest, &name[1..]),
+        Some((c, rest)) => name.first() == Some(c) && segments_match(rest, &name[1..]),
+    }
+}
+
+/// scans one file, `format` None picks it by extension
+pub fn scan_file(matcher: &dyn Matcher, path: &Path, format: Option<Format>) -> FileReport {
+    let format = format.unwrap_or_else(|| Format::from_path(&path.to_string_lossy()));
+    let result = std::fs::read(path)
+        .map_err(|e| e.to_string())
+        .and_then(|data| hits::collect(matcher, &data, format));
+    let (hits, error) = match result {
+        Ok(hits) => (hits, None),
+        Err(e) => (Vec::new(), Some(e)),
+    };
+    FileReport {
+        path: path.to_path_buf(),
+        hits,
+        error,
+    }
+}
+
+/// scans the files below `root` whose path relative to it matches `glob` (all when None)
+/// on `workers` threads; reports are in path order, a failing file is reported and the
+/// scan goes on
+/// # Errors
+/// * `root` does not exist, a directory cannot be read
+pub fn scan_dir(
+    matcher: &(dyn Matcher + Sync),
+    root: &Path,
+    glob: Option<&str>,
+    format: Option<Format>,
+    workers: usize,
+) -> io::Result<Vec<FileReport>> {
+    let files: Vec<PathBuf> = walk(root)?
+        .into_iter()
+        .filter(|file| {
+            glob.is_none_or(|glob| {
+                let relative = file.strip_prefix(root).unwrap_or(file);
+                glob_match(glob, &relative.to_string_lossy().replace('\\', "/"))
+            })
+        })
+        .collect();
+    let next = AtomicUsize::new(0);
+    let reports = Mutex::new(Vec::with_capacity(files.len()));
+    thread::scope(|scope| {
+        for _ in 0..workers.clamp(1, files.len().max(1)) {
+            scope.spawn(|| {
+                loop {
+                    let i = next.fetch_add(1, Ordering::Relaxed);
+                    let Some(file) = files.get(i) else {
+                        break;
+                    };
+                    let report = scan_file(matcher, file, format);
+                    reports
+                        .lock()
+                        .unwrap_or_else(PoisonError::into_inner)
+                        .push((i, report));
+                }
+            });
+        }
+    });
+    let mut reports = reports.into_inner().unwrap_or_else(PoisonError::into_inner);
+    reports.sort_by_key(|(i, _)| *i);
+    Ok(reports.into_iter().map(|(_, report)| report).collect())
+}
+
+/// every deny word occurrence of a file of any size as text, the file is mapped into memory
+/// and scanned `window` bytes at a time, so memory stays flat; words straddling two windows
+/// are found once, offsets count from the start of the file
+/// # Errors
+/// * the file cannot be opened or mapped
+pub fn scan_mmap(matcher: &dyn Matcher, path: &Path, window: usize) -> io::Result<Vec<Match>> {
+    let file = File::open(path)?;
+    // SAFETY: the mapping is only read; a file truncated by another process while mapped
+    // is the caller's concern, as with any mmap based reader
+    let map = unsafe { Mmap::map(&file)? };
+    #[cfg(unix)]
+    map.advise(memmap2::Advice::Sequential)?;
+    let mut scanner = StreamScanner::for_matcher(matcher);
+    let mut found = Vec::new();
+    for chunk in map.chunks(window.max(1)) {
+        found.extend(scanner.feed_bytes(matcher, chunk));
+    }
+    found.extend(scanner.finish(matcher));
+    Ok(found)
+}
+
+/// threads of `scan_dir` when not given
+#[must_use]
+pub fn default_workers() -> usize {
+    thread::available_parallelism().map_or(1, usize::from)
+}
+
+/// `FileReport` as returned to python, hits are `(location, pattern)` pairs
+#[cfg(feature = "python")]
+#[gen_stub_pyclass]
+#[pyclass(module = "deny_filter.plugin", frozen, name = "FileReport", get_all)]
+pub struct PyFileReport {
+    pub path: String,
+    pub hits: Vec<(String, String)>,
+    pub error: Option<String>,
+}
+
+#[cfg(feature = "python")]
+#[gen_stub_pymethods]
+#[pymethods]
+impl PyFileReport {
+    fn __repr__(&self) -> String {
+        format!(
+            "FileReport(path={:?}, hits={:?}, error={})",
+            self.path,
+            self.hits,
+            self.error
+                .as_ref()
+                .map_or("None".to_string(), |e| format!("{e:?}"))
+        )
+    }
+}
+
+#[cfg(feature = "python")]
+impl From<FileReport> for PyFileReport {
+    fn from(report: FileReport) -> Self {
+        Self {
+            path: report.path.to_string_lossy().into_owned(),
+            hits: report
+                .hits
+                .into_iter()
+                .map(|hit| (hit.location, hit.pattern))
+                .collect(),
+            error: report.error,
+        }
+    }
+}
+
+/// `scan_file` of the python matchers, without the GIL
+/// # Errors
+/// * unknown format
+#[cfg(feature = "python")]
+pub fn py_scan_file<M: Matcher + Sync>(
+    py: Python<'_>,
+    matcher: &M,
+    path: PathBuf,
+    format: Option<&str>,
+) -> PyResult<PyFileReport> {
+    let format = format.map(str::parse).transpose().map_err(invalid_format)?;
+    Ok(py.detach(move || scan_file(matcher, &path, format)).into())
+}
+
+/// `scan_dir` of the python matchers, without the GIL
+/// # Errors
+/// * unknown format, a directory cannot be read
+#[cfg(feature = "python")]
+pub fn py_scan_dir<M: Matcher + Sync>(
+    py: Python<'_>,
+    matcher: &M,
+    path: PathBuf,
+    glob: Option<&str>,
+    format: Option<&str>,
+    workers: Option<usize>,
+) -> PyResult<Vec<PyFileReport>> {
+    let format = format.map(str::parse).transpose().map_err(invalid_format)?;
+    let workers = workers.unwrap_or_else(default_workers);
+    let reports = py.detach(move || scan_dir(matcher, &path, glob, format, workers))?;
+    Ok(reports.into_iter().map(PyFileReport::from).collect())
+}
+
+/// `scan_mmap` of the python matchers, without the GIL
+/// # Errors
+/// * the file cannot be opened or mapped
+#[cfg(feature = "python")]
+pub fn py_scan_mmap<M: Matcher + Sync>(
+    py: Python<'_>,
+    matcher: &M,
+    path: PathBuf,
+    window: Option<usize>,
+) -> PyResult<Vec<Match>> {
+    let window = window.unwrap_or(DEFAULT_MMAP_WINDOW);
+    Ok(py.detach(move || scan_mmap(matcher, &path, window))?)
+}
+
+#[cfg(feature = "python")]
+fn invalid_format(e: String) -> PyErr {
+    pyo3::exceptions::PyValueError::new_err(e)
+}
@@ -0,0 +1,106 @@
+use std::pin::Pin;
+use std::sync::{Arc, PoisonError, RwLock};
+
+use tokio_stream::{Stream, StreamExt};
+use tonic::{Request, Response, Status, Streaming};
+
+use crate::backend::Backend;
+use crate::hits::{self, Format};
+use crate::matcher::Matcher;
+
+/// generated protocol types, client and server
+#[allow(clippy::pedantic)]
+pub mod proto {
+    tonic::include_proto!("deny_filter.v1");
+}
+
+use proto::deny_filter_server::{DenyFilter, DenyFilterServer};
+use proto::scan_request::Payload;
+use proto::{Hit, ReloadConfigRequest, ReloadConfigResponse, ScanRequest, ScanResponse};
+
+type SharedMatcher = Arc<dyn Matcher + Send + Sync>;
+
+/// grpc service scanning with a reloadable matcher
+#[derive(Clone)]
+pub struct DenyFilterService {
+    matcher: Arc<RwLock<SharedMatcher>>,
+}
+
+impl DenyFilterService {
+    #[must_use]
+    pub fn new(matcher: Box<dyn Matcher + Send + Sync>) -> Self {
+        Self {
+            matcher: Arc::new(RwLock::new(Arc::from(matcher))),
+        }
+    }
+
+    #[must_use]
+    pub fn into_server(self) -> DenyFilterServer<Self> {
+        DenyFilterServer::new(self)
+    }
+
+    /// matcher of the current config, reloads do not affect scans holding it
+    fn current(&self) -> SharedMatcher {
+        Arc::clone(&self.matcher.read().unwrap_or_else(PoisonError::into_inner))
+    }
+
+    fn scan_one(matcher: &dyn Matcher, request: &ScanRequest) -> Result<ScanResponse, Status> {
+        let (data, format) = match &request.payload {
+            Some(Payload::Text(text)) => (text.as_bytes(), Format::Text),
+            Some(Payload::Json(json)) => (json.as_bytes(), Format::Json),
+            Some(Payload::Msgpack(bytes)) => (bytes.as_slice(), Format::Msgpack),
+            None => return Err(Status::invalid_argument("empty scan request")),
+        };
+        let hits = hits::collect(matcher, data, format).map_err(Status::invalid_argument)?;
+        Ok(ScanResponse {
+            matched: !hits.is_empty(),
+            hits: hits
+                .into_iter()
+                .map(|hit| Hit {
+                    location: hit.location,
+                    pattern: hit.pattern,
+                })
+                .collect(),
+        })
+    }
+}
+
+#[tonic::async_trait]
+impl DenyFilter for DenyFilterService {
+    async fn scan(&self, request: Request<ScanRequest>) -> Result<Response<ScanResponse>, Status> {
+        let matcher = self.current();
+        Self::scan_one(matcher.as_ref(), request.get_ref()).map(Response::new)
+    }
+
+    type ScanStreamStream = Pin<Box<dyn Stream<Item = Result<ScanResponse, Status>> + Send>>;
+
+    async fn scan_stream(
+        &self,
+        request: Request<Streaming<ScanRequest>>,
+    ) -> Result<Response<Self::ScanStreamStream>, Status> {
+        let service = self.clone();
+        let responses = request.into_inner().map(move |request| {
+            let matcher = service.current();
+            Self::scan_one(matcher.as_ref(), &request?)
+        });
+        Ok(Response::new(Box::pin(responses)))
+    }
+
+    async fn reload_config(
+        &self,
+        request: Request<ReloadConfigRequest>,
+    ) -> Result<Response<ReloadConfigResponse>, Status> {
+        let request = request.into_inner();
+        let backend = if request.backend.is_empty() {
+            Backend::default()
+        } else {
+            request.backend.parse().map_err(Status::invalid_argument)?
+        };
+        let words = request.words.len() as u64;
+        let matcher = backend
+            .build(request.words)
+            .map_err(|e| Status::invalid_argument(e.to_string()))?;
+        *self.matcher.write().unwrap_or_else(PoisonError::into_inner) = Arc::from(matcher);
+        Ok(Response::new(ReloadConfigResponse { words }))
+    }
+}
@@ -0,0 +1,102 @@
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
+#[cfg(feature = "python")]
+use pyo3_stub_gen::derive::{gen_stub_pyclass, gen_stub_pymethods};
+
+use crate::decode::base64_text;
+use crate::matcher::Matcher;
+use crate::metrics::Timer;
+
+/// the http header with a deny word
+#[cfg_attr(feature = "python", gen_stub_pyclass)]
+#[cfg_attr(
+    feature = "python",
+    pyclass(module = "deny_filter.plugin", frozen, get_all, skip_from_py_object)
+)]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct HeaderHit {
+    /// header name in lowercase
+    pub name: String,
+    /// comma separated element of the value with the deny word, decoded when `decoded`
+    pub value: String,
+    /// whether the deny word was found in the base64 decoded text of a token
+    pub decoded: bool,
+    /// deny word that matched first
+    pub word: String,
+}
+
+#[cfg(feature = "python")]
+#[gen_stub_pymethods]
+#[pymethods]
+impl HeaderHit {
+    fn __repr__(&self) -> String {
+        format!(
+            "HeaderHit(name={:?}, value={:?}, decoded={}, word={:?})",
+            self.name,
+            self.value,
+            if self.decoded { "True" } else { "False" },
+            self.word
+        )
+    }
+}
+
+/// hit of one element of a header value: the element itself, then the base64 looking
+/// tokens of it, e.g. the credentials of `Basic dXNlcjpoYWNr`
+fn element_hit<M: Matcher + ?Sized>(matcher: &M, name: &str, element: &str) -> Option<HeaderHit> {
+    let hit = |value: String, decoded: bool| {
+        matcher.check(&value).then(|| HeaderHit {
+            name: name.to_string(),
+            word: matcher.first_match(&value).unwrap_or_default().to_string(),
+            value,
+            decoded,
+        })
+    };
+    hit(element.to_string(), false).or_else(|| {
+        element
+            .split_whitespace()
+            .filter_map(base64_text)
+            .find_map(|text| hit(text, true))
+    })
+}
+
+/// first header with a deny word in an element of its comma separated value, whatever
+/// the case of its name; names are reported in lowercase
+pub fn scan_headers<'a, M: Matcher + ?Sized>(
+    matcher: &M,
+    headers: impl IntoIterator<Item = (&'a str, &'a str)>,
+) -> Option<HeaderHit> {
+    let timer = Timer::start();
+    let found = headers.into_iter().find_map(|(name, value)| {
+        let name = name.trim().to_ascii_lowercase();
+        value
+            .split(',')
+            .map(str::trim)
+            .filter(|element| !element.is_empty())
+            .find_map(|element| element_hit(matcher, &name, element))
+    });
+    matcher
+        .metrics()
+        .record_scan(timer.elapsed(), found.is_some());
+    found
+}
+
+/// `scan_headers` of the python matchers: a dict or any object with `items()` of str
+/// name and value pairs, e.g. starlette `Headers`
+/// # Errors
+/// * no `items()` or items that are not str pairs
+#[cfg(feature = "python")]
+pub fn py_scan_headers<M: Matcher + ?Sized>(
+    matcher: &M,
+    headers: &Bound<'_, PyAny>,
+) -> PyResult<Option<HeaderHit>> {
+    let mut pairs: Vec<(String, String)> = Vec::new();
+    for item in headers.call_method0("items")?.try_iter()? {
+        pairs.push(item?.extract()?);
+    }
+    Ok(scan_headers(
+        matcher,
+        pairs
+            .iter()
+            .map(|(name, value)| (name.as_str(), value.as_str())),
+    ))
+}
@@ -0,0 +1,122 @@
+use std::str::FromStr;
+
+use crate::matcher::Matcher;
+use crate::traverse::{self, Segment};
+
+/// how raw input bytes are interpreted
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Format {
+    /// utf-8 text, scanned line by line
+    #[default]
+    Text,
+    Json,
+    /// one json document per line
+    Ndjson,
+    Msgpack,
+}
+
+impl Format {
+    /// format by file extension: json, ndjson/jsonl, msgpack/mpk/mp, text otherwise
+    #[must_use]
+    pub fn from_path(path: &str) -> Self {
+        match path.rsplit_once('.').map(|(_, ext)| ext) {
+            Some("json") => Self::Json,
+            Some("ndjson" | "jsonl") => Self::Ndjson,
+            Some("msgpack" | "mpk" | "mp") => Self::Msgpack,
+            _ => Self::Text,
+        }
+    }
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(Self::Text),
+            "json" => Ok(Self::Json),
+            "ndjson" => Ok(Self::Ndjson),
+            "msgpack" => Ok(Self::Msgpack),
+            other => Err(format!(
+                "unknown format {other:?}, expected text, json, ndjson or msgpack"
+            )),
+        }
+    }
+}
+
+/// one deny word found in an input
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Hit {
+    /// line number for text, `$.json.path` for json and msgpack, `line:$.path` for ndjson
+    pub location: String,
+    pub pattern: String,
+}
+
+/// all deny words in one input, each string reports its first match
+/// # Errors
+/// * input is not valid json / msgpack
+pub fn collect(matcher: &dyn Matcher, data: &[u8], format: Format) -> Result<Vec<Hit>, String> {
+    let mut hits = Vec::new();
+    let mut visit = |path: &[Segment<'_>], s: &str| {
+        if let Some(pattern) = matcher.first_match(s) {
+            hits.push(Hit {
+                location: traverse::format_path(path),
+                pattern: pattern.to_string(),
+            });
+        }
+        false
+    };
+    match format {
+        Format::Json => {
+            let value: serde_json::Value =
+                serde_json::from_slice(data).map_err(|e| e.to_string())?;
+            traverse::json(&value, &mut visit);
+        }
+        Format::Msgpack => {
+            traverse::msgpack(data, &mut visit).map_err(|e| e.to_string())?;
+        }
+        Format::Text | Format::Ndjson => {
+            let text = String::from_utf8_lossy(data);
+            for (i, line) in text.lines().enumerate() {
+                collect_line(matcher, i + 1, line, format, &mut hits)?;
+            }
+        }
+    }
+    Ok(hits)
+}
+
+/// hits of line `number` (from 1) of a text or ndjson input, blank ndjson lines are skipped
+/// # Errors
+/// * an ndjson line is not valid json
+pub fn collect_line(
+    matcher: &dyn Matcher,
+    number: usize,
+    line: &str,
+    format: Format,
+    hits: &mut Vec<Hit>,
+) -> Result<(), String> {
+    if format != Format::Ndjson {
+        if let Some(pattern) = matcher.first_match(line) {
+            hits.push(Hit {
+                location: number.to_string(),
+                pattern: pattern.to_string(),
+            });
+        }
+        return Ok(());
+    }
+    if line.trim().is_empty() {
+        return Ok(());
+    }
+    let value: serde_json::Value =
+        serde_json::from_str(line).map_err(|e| format!("line {number}: {e}"))?;
+    traverse::json(&value, &mut |path, s| {
+        if let Some(pattern) = matcher.first_match(s) {
+            hits.push(Hit {
+                location: format!("{number}:{}", traverse::format_path(path)),
+                pattern: pattern.to_string(),
+            });
+        }
+        false
+    });
+    Ok(())
+}
@@ -0,0 +1,377 @@
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
+#[cfg(feature = "python")]
+use pyo3::types::PyDict;
+#[cfg(feature = "python")]
+use pyo3_stub_gen::derive::{gen_stub_pyclass, gen_stub_pymethods};
+use std::cmp::Reverse;
+use std::sync::Arc;
+#[cfg(feature = "python")]
+use std::sync::{Mutex, PoisonError, RwLock};
+
+use crate::backend::Backend;
+use crate::build_error::BuildError;
+use crate::compiled::{stored_word, stored_words};
+use crate::explain::Explanation;
+use crate::keys::KeyFilter;
+use crate::limits::Limits;
+use crate::matcher::Matcher;
+use crate::matches::Match;
+use crate::metrics::BackendMetrics;
+use crate::options::{ListMetadata, Options};
+use crate::report::BuildReport;
+use crate::score::Weights;
+use crate::stats::Stats;
+
+type SharedMatcher = Arc<dyn Matcher + Send + Sync>;
+
+/// delta words merged into the base when `merge_at` is not given
+pub const DEFAULT_MERGE_AT: usize = 1024;
+
+/// deny list of a large base automaton and a small delta automaton of the words added
+/// since the last merge: `add_words` rebuilds the delta only, the base is rebuilt with
+/// all words once the delta reaches `merge_at` words or on `merge`; clones share the
+/// base, so a copy can take the next words while the original keeps scanning
+#[derive(Clone)]
+pub struct IncrementalMatcher {
+    backend: Backend,
+    options: Options,
+    merge_at: usize,
+    base: SharedMatcher,
+    base_words: Arc<Vec<String>>,
+    delta: Option<SharedMatcher>,
+    delta_words: Vec<String>,
+}
+
+impl IncrementalMatcher {
+    /// # Errors
+    /// * pattern errors of the backend, invalid options
+    pub fn new(
+        backend: Backend,
+        words: Vec<String>,
+        options: &Options,
+        merge_at: usize,
+    ) -> Result<Self, BuildError> {
+        Ok(Self {
+            backend,
+            options: options.clone(),
+            merge_at: merge_at.max(1),
+            base: Arc::from(backend.build_with(words.clone(), options)?),
+            base_words: Arc::new(words),
+            delta: None,
+            delta_words: Vec::new(),
+        })
+    }
+
+    /// adds deny words, returns whether they were merged into the base; on an error the
+    /// matcher is unchanged
+    /// # Errors
+    /// * pattern errors of the backend
+    pub fn add_words(&mut self, words: Vec<String>) -> Result<bool, BuildError> {
+        let mut delta_words = self.delta_words.clone();
+        for word in words {
+            if !delta_words.contains(&word) {
+                delta_words.push(word);
+            }
+        }
+        if delta_words.len() == self.delta_words.len() {
+            return Ok(false);
+        }
+        if delta_words.len() >= self.merge_at {
+            self.rebuild(delta_words)?;
+            return Ok(true);
+        }
+        let delta = self
+            .backend
+            .build_with(delta_words.clone(), &self.delta_options(&delta_words))?;
+        self.delta = Some(Arc::from(delta));
+        self.delta_words = delta_words;
+        Ok(false)
+    }
+
+    /// options of a delta of `words`: `exceptions` and `word_match` keyed by the words of
+    /// the base are left out, the delta build rejects them as unknown deny words
+    fn delta_options(&self, words: &[String]) -> Options {
+        let case_fold = self.options.case_fold;
+        let words = stored_words(words, case_fold);
+        let known = |word: &String| words.contains(&stored_word(word, case_fold));
+        Options {
+            exceptions: self
+                .options
+                .exceptions
+                .iter()
+                .filter(|(word, _)| known(word))
+                .map(|(word, patterns)| (word.clone(), patterns.clone()))
+                .collect(),
+            word_match: self
+                .options
+                .word_match
+                .iter()
+                .filter(|(word, _)| known(word))
+                .map(|(word, kind)| (word.clone(), *kind))
+                .collect(),
+            ..self.options.clone()
+        }
+    }
+
+    /// rebuilds the base with the delta words, a no-op without them
+    /// # Errors
+    /// * pattern errors of the backend
+    pub fn merge(&mut self) -> Result<(), BuildError> {
+        if self.delta_words.is_empty() {
+            return Ok(());
+        }
+        self.rebuild(self.delta_words.clone())
+    }
+
+    fn rebuild(&mut self, delta_words: Vec<String>) -> Result<(), BuildError> {
+        let mut words = Vec::with_capacity(self.base_words.len() + delta_words.len());
+        words.extend(self.base_words.iter().cloned());
+        words.extend(delta_words);
+        self.base = Arc::from(self.backend.build_with(words.clone(), &self.options)?);
+        self.base_words = Arc::new(words);
+        self.delta = None;
+        self.delta_words.clear();
+        Ok(())
+    }
+
+    /// deny words of the base and the delta
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.base_words.len() + self.delta_words.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// deny words added since the last merge
+    #[must_use]
+    pub fn delta_len(&self) -> usize {
+        self.delta_words.len()
+    }
+}
+
+/// leftmost non-overlapping matches of both automata, the longer one where two start at
+/// the same offset
+fn leftmost(mut found: Vec<Match>) -> Vec<Match> {
+    found.sort_by_key(|m| (m.start, Reverse(m.end)));
+    let mut end = 0;
+    found.retain(|m| {
+        let keep = m.start >= end;
+        if keep {
+            end = m.end;
+        }
+        keep
+    });
+    found
+}
+
+impl Matcher for IncrementalMatcher {
+    fn is_match(&self, s: &str) -> bool {
+        self.base.is_match(s) || self.delta.as_ref().is_some_and(|delta| delta.is_match(s))
+    }
+
+    fn first_match(&self, s: &str) -> Option<&str> {
+        self.base
+            .first_match(s)
+            .or_else(|| self.delta.as_ref()?.first_match(s))
+    }
+
+    fn find_all(&self, s: &str) -> Vec<Match> {
+        let found = self.base.find_all(s);
+        match &self.delta {
+            Some(delta) => leftmost(found.into_iter().chain(delta.find_all(s)).collect()),
+            None => found,
+        }
+    }
+
+    fn find_overlapping(&self, s: &str) -> Result<Vec<Match>, BuildError> {
+        let mut found = self.base.find_overlapping(s)?;
+        if let Some(delta) = &self.delta {
+            found.extend(delta.find_overlapping(s)?);
+            found.sort_by_key(|m| (m.start, m.end));
+        }
+        Ok(found)
+    }
+
+    fn explain(&self, s: &str) -> Option<Explanation> {
+        self.base
+            .explain(s)
+            .or_else(|| self.delta.as_ref()?.explain(s))
+    }
+
+    fn metadata(&self) -> &ListMetadata {
+        self.base.metadata()
+    }
+
+    /// report of the last base build
+    fn build_report(&self) -> &BuildReport {
+        self.base.build_report()
+    }
+
+    /// sizes of both automata added up
+    fn stats(&self) -> Stats {
+        let mut stats = self.base.stats();
+        if let Some(delta) = &self.delta {
+            let delta = delta.stats();
+            stats.min_pattern_len = if stats.patterns == 0 {
+                delta.min_pattern_len
+            } else {
+                stats.min_pattern_len.min(delta.min_pattern_len)
+            };
+            stats.max_pattern_len = stats.max_pattern_len.max(delta.max_pattern_len);
+            stats.patterns += delta.patterns;
+            stats.states += delta.states;
+            stats.heap_bytes += delta.heap_bytes;
+        }
+        stats
+    }
+
+    /// tries of the base
+    #[cfg(feature = "dot")]
+    fn to_dot(&self) -> String {
+        self.base.to_dot()
+    }
+
+    fn metrics(&self) -> &BackendMetrics {
+        self.base.metrics()
+    }
+
+    fn limits(&self) -> &Limits {
+        self.base.limits()
+    }
+
+    fn ignored_keys(&self) -> &KeyFilter {
+        self.base.ignored_keys()
+    }
+
+    fn weights(&self) -> &Weights {
+        self.base.weights()
+    }
+
+    /// the rules are options of the base, the delta adds words only
+    fn proximity_matches(&self, s: &str) -> Vec<&str> {
+        self.base.proximity_matches(s)
+    }
+}
+
+/// python handle of an `IncrementalMatcher`: scans run on the current matcher while
+/// `add_words` builds the next one without the GIL, one update at a time
+#[cfg(feature = "python")]
+#[gen_stub_pyclass]
+#[pyclass(module = "deny_filter.plugin", frozen, name = "IncrementalDenyList")]
+pub struct PyIncrementalDenyList {
+    current: RwLock<Arc<IncrementalMatcher>>,
+    updating: Mutex<()>,
+}
+
+#[cfg(feature = "python")]
+impl PyIncrementalDenyList {
+    fn current(&self) -> Arc<IncrementalMatcher> {
+        self.current
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
+    }
+
+    /// applies `update` to a copy of the current matcher and swaps it in; the GIL is
+    /// released before waiting for another update, which may need it to finish
+    fn update<T: Send>(
+        &self,
+        py: Python<'_>,
+        update: impl FnOnce(&mut IncrementalMatcher) -> Result<T, BuildError> + Send,
+    ) -> PyResult<T> {
+        Ok(py.detach(|| {
+            let _updating = self.updating.lock().unwrap_or_else(PoisonError::into_inner);
+            let mut next = IncrementalMatcher::clone(&self.current());
+            let result = update(&mut next)?;
+            *self.current.write().unwrap_or_else(PoisonError::into_inner) = Arc::new(next);
+            Ok::<_, BuildError>(result)
+        })?)
+    }
+}
+
+#[cfg(feature = "python")]
+#[gen_stub_pymethods]
+#[pymethods]
+impl PyIncrementalDenyList {
+    /// keyword options: see `Options`, used by the base and the delta
+    /// # Errors
+    /// * unknown backend, invalid patterns or options
+    #[new]
+    #[pyo3(signature = (words, merge_at = DEFAULT_MERGE_AT, backend = "aho-corasick", **options))]
+    fn py_new(
+        py: Python<'_>,
+        words: Vec<String>,
+        merge_at: usize,
+        backend: &str,
+        options: Option<&Bound<'_, PyDict>>,
+    ) -> PyResult<Self> {
+        let backend: Backend = backend
+            .parse()
+            .map_err(pyo3::exceptions::PyValueError::new_err)?;
+        let options = Options::from_kwargs(options)?;
+        let matcher = py.detach(|| IncrementalMatcher::new(backend, words, &options, merge_at))?;
+        Ok(Self {
+            current: RwLock::new(Arc::new(matcher)),
+            updating: Mutex::new(()),
+        })
+    }
+
+    /// adds deny words to the delta, True when that reached `merge_at` and was merged
+    /// # Errors
+    /// * invalid patterns, the list is unchanged
+    fn add_words(&self, py: Python<'_>, words: Vec<String>) -> PyResult<bool> {
+        self.update(py, |matcher| matcher.add_words(words))
+    }
+
+    /// rebuilds the base with the delta words, e.g. from a timer in a quiet period
+    /// # Errors
+    /// * invalid patterns, the list is unchanged
+    fn merge(&self, py: Python<'_>) -> PyResult<()> {
+        self.update(py, IncrementalMatcher::merge)
+    }
+
+    fn is_match(&self, s: &str) -> bool {
+        self.current().is_match(s)
+    }
+
+    fn find_all(&self, text: &str) -> Vec<Match> {
+        self.current().find_all(text)
+    }
+
+    /// # Errors
+    /// * over a length limit with `on_overflow="raise"`
+    fn scan_str(&self, txt: &str) -> PyResult<bool> {
+        Ok(self.current().try_scan_str(txt)?)
+    }
+
+    /// # Errors
+    /// * over a length limit with `on_overflow="raise"`
+    fn scan(&self, args: &Bound<'_, PyDict>) -> PyResult<bool> {
+        self.current().scan(args)
+    }
+
+    /// # Errors
+    /// * over a length limit with `on_overflow="raise"`
+    fn scan_any(&self, value: &Bound<'_, PyAny>) -> PyResult<bool> {
+        self.current().scan_any(value)
+    }
+
+    /// sizes of the base and delta automata added up
+    fn stats(&self) -> Stats {
+        self.current().stats()
+    }
+
+    /// deny words added since the last merge
+    #[getter]
+    fn delta_len(&self) -> usize {
+        self.current().delta_len()
+    }
+
+    fn __len__(&self) -> usize {
+        self.current().len()
+    }
+}
@@ -0,0 +1,56 @@
+use regex::RegexSet;
+use std::collections::BTreeSet;
+
+use crate::build_error::{BuildError, invalid};
+use crate::options::Options;
+use crate::traverse::Segment;
+
+/// map keys whose values are not scanned, by name or by regex, e.g. `.*_b64$`
+#[derive(Clone, Debug, Default)]
+pub struct KeyFilter {
+    names: BTreeSet<String>,
+    patterns: Option<RegexSet>,
+}
+
+/// no key skipped
+pub static NO_KEYS: KeyFilter = KeyFilter {
+    names: BTreeSet::new(),
+    patterns: None,
+};
+
+impl KeyFilter {
+    /// filter of `ignore_keys` and `ignore_key_patterns`
+    /// # Errors
+    /// * invalid regex
+    pub fn new(options: &Options) -> Result<Self, BuildError> {
+        let patterns = if options.ignore_key_patterns.is_empty() {
+            None
+        } else {
+            Some(RegexSet::new(&options.ignore_key_patterns).map_err(invalid)?)
+        };
+        Ok(Self {
+            names: options.ignore_keys.iter().cloned().collect(),
+            patterns,
+        })
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty() && self.patterns.is_none()
+    }
+
+    /// whether the value of `key` is skipped
+    #[must_use]
+    pub fn skips(&self, key: &str) -> bool {
+        self.names.contains(key) || self.patterns.as_ref().is_some_and(|p| p.is_match(key))
+    }
+
+    /// whether a string at `path` is below a skipped key
+    #[must_use]
+    pub fn skips_path(&self, path: &[Segment<'_>]) -> bool {
+        !self.is_empty()
+            && path
+                .iter()
+                .any(|segment| matches!(segment, Segment::Key(key) if self.skips(key)))
+    }
+}
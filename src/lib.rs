@@ -1,6 +1,81 @@
+pub mod analyze;
+#[cfg(feature = "arrow")]
+pub mod arrow;
+pub mod backend;
+pub mod batch;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod bench;
 pub mod build_error;
+pub mod cache;
+pub mod chat;
+pub mod compiled;
+pub mod decode;
+pub mod decompress;
 pub mod deny_list;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod deny_list_auto;
 pub mod deny_list_daac;
+pub mod deny_list_fst;
+pub mod deny_list_hashed;
+pub mod deny_list_hybrid;
+pub mod deny_list_packed;
 pub mod deny_list_rs;
+pub mod deny_list_tokens;
+#[cfg(feature = "dot")]
+pub mod dot;
+pub mod email;
+pub mod engine;
+pub mod evaluate;
+pub mod exceptions;
+pub mod explain;
+#[cfg(feature = "cdylib")]
+pub mod ffi;
+pub mod files;
+#[cfg(feature = "grpc")]
+pub mod grpc;
+pub mod headers;
+pub mod hits;
+pub mod incremental;
+pub mod keys;
+pub mod limits;
+pub mod lines;
+#[cfg(feature = "python")]
+pub mod logs;
 pub mod matcher;
+pub mod matches;
+pub mod mcp;
+pub mod metrics;
+pub mod normalize;
+#[cfg(feature = "object-store")]
+pub mod object_scan;
+pub mod on_match;
+pub mod options;
+pub mod pool;
+pub mod prelude;
+pub mod proximity;
+#[cfg(feature = "python")]
+pub mod py_matcher;
+mod pymethods;
+#[cfg(feature = "python")]
 pub mod pymodule;
+pub mod redact;
+pub mod registry;
+pub mod report;
+#[cfg(feature = "python")]
+pub mod scan_async;
+pub mod score;
+pub mod select;
+pub mod shadow;
+pub mod signed;
+pub mod sql;
+pub mod stats;
+pub mod stream;
+pub mod trace;
+#[cfg(feature = "python")]
+pub mod tracebacks;
+pub mod traverse;
+pub mod urls;
+pub mod violations;
+#[cfg(feature = "wasm")]
+pub mod wasm;
+pub mod word_list;
@@ -0,0 +1,354 @@
+use std::collections::HashSet;
+use std::fmt;
+
+use crate::cache::{Key, Keyer};
+use crate::options::{Options, Overflow};
+
+/// containers nested in a msgpack document at most without `max_msgpack_depth` and
+/// `max_depth`, so a hostile document of nested arrays cannot overflow the stack of the
+/// recursive walk
+pub const DEFAULT_MSGPACK_DEPTH: usize = 512;
+
+/// length limits of scanned input, so a huge field cannot stall a scan
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Limits {
+    /// bytes of one string
+    pub max_string_len: Option<usize>,
+    /// string bytes of one payload
+    pub max_payload_len: Option<usize>,
+    /// bytes of a msgpack document
+    pub max_msgpack_len: Option<usize>,
+    /// containers nested in a msgpack document
+    pub max_msgpack_depth: Option<usize>,
+    /// values of a msgpack document, containers and map values included
+    pub max_msgpack_elements: Option<usize>,
+    /// containers nested in one payload of any kind
+    pub max_depth: Option<usize>,
+    /// strings of one payload
+    pub max_strings: Option<usize>,
+    pub on_overflow: Overflow,
+}
+
+impl Limits {
+    /// no limits
+    pub const NONE: Limits = Limits {
+        max_string_len: None,
+        max_payload_len: None,
+        max_msgpack_len: None,
+        max_msgpack_depth: None,
+        max_msgpack_elements: None,
+        max_depth: None,
+        max_strings: None,
+        on_overflow: Overflow::ScanPrefix,
+    };
+
+    #[must_use]
+    pub fn new(options: &Options) -> Self {
+        Self {
+            max_string_len: options.max_string_len,
+            max_payload_len: options.max_payload_len,
+            max_msgpack_len: options.max_msgpack_len,
+            max_msgpack_depth: options.max_msgpack_depth,
+            max_msgpack_elements: options.max_msgpack_elements,
+            max_depth: options.max_depth,
+            max_strings: options.max_strings,
+            on_overflow: options.on_overflow,
+        }
+    }
+
+    /// verdict of input past a limit when nothing more of it is scanned: clean for
+    /// `scan_prefix` and `allow`, a match for `block`
+    /// # Errors
+    /// * `error` with `Overflow::Raise`
+    pub fn overflow(&self, error: LimitError) -> Result<bool, LimitError> {
+        match self.on_overflow {
+            Overflow::ScanPrefix | Overflow::Allow => Ok(false),
+            Overflow::Block => Ok(true),
+            Overflow::Raise => Err(error),
+        }
+    }
+
+    /// containers nested in a msgpack document at most, the lower of `max_msgpack_depth`
+    /// and `max_depth`, `DEFAULT_MSGPACK_DEPTH` when neither is set
+    #[must_use]
+    pub fn msgpack_depth(&self) -> usize {
+        self.max_msgpack_depth
+            .into_iter()
+            .chain(self.max_depth)
+            .min()
+            .unwrap_or(DEFAULT_MSGPACK_DEPTH)
+    }
+
+    /// budget of one payload
+    #[must_use]
+    pub fn budget(&self) -> Budget {
+        self.budget_with(Some(Seen::default()))
+    }
+
+    /// budget of a payload of a single string, e.g. of `scan_str`: it cannot repeat, so it
+    /// is not hashed to recognize repeats
+    #[must_use]
+    pub fn string_budget(&self) -> Budget {
+        self.budget_with(None)
+    }
+
+    fn budget_with(&self, seen: Option<Seen>) -> Budget {
+        Budget {
+            limits: *self,
+            left: self.max_payload_len,
+            scanned: 0,
+            strings: 0,
+            depth: 0,
+            seen,
+            recorded: None,
+            visited: HashSet::new(),
+        }
+    }
+}
+
+/// input over a length limit with `Overflow::Raise`
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LimitError {
+    /// a string of `len` bytes over `limit`
+    String { len: usize, limit: usize },
+    /// a payload over `limit` string bytes
+    Payload { limit: usize },
+    /// a msgpack document of `len` bytes over `limit`
+    Buffer { len: usize, limit: usize },
+    /// containers nested deeper than `limit`
+    Depth { limit: usize },
+    /// a payload of more than `limit` strings
+    Strings { limit: usize },
+    /// more than `limit` values
+    Elements { limit: usize },
+}
+
+impl fmt::Display for LimitError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LimitError::String { len, limit } => {
+                write!(f, "string of {len} bytes over the {limit} byte limit")
+            }
+            LimitError::Payload { limit } => {
+                write!(f, "payload over the {limit} byte limit")
+            }
+            LimitError::Buffer { len, limit } => {
+                write!(
+                    f,
+                    "msgpack document of {len} bytes over the {limit} byte limit"
+                )
+            }
+            LimitError::Depth { limit } => {
+                write!(f, "containers nested deeper than {limit}")
+            }
+            LimitError::Strings { limit } => {
+                write!(f, "payload of more than {limit} strings")
+            }
+            LimitError::Elements { limit } => {
+                write!(f, "msgpack document of more than {limit} values")
+            }
+        }
+    }
+}
+
+impl std::error::Error for LimitError {}
+
+#[cfg(feature = "python")]
+impl From<LimitError> for pyo3::PyErr {
+    fn from(e: LimitError) -> Self {
+        pyo3::exceptions::PyValueError::new_err(e.to_string())
+    }
+}
+
+/// what to do with a string of a payload
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Guarded<'a> {
+    /// scan this text, the string or its prefix
+    Scan(&'a str),
+    /// do not scan, the verdict is given
+    Skip(bool),
+}
+
+/// strings of a payload shorter than this are scanned again when repeated: hashing them
+/// to recognize the repeat costs about as much as their scan
+pub const REPEAT_MIN_LEN: usize = 64;
+
+/// keys of the strings of a payload scanned so far; the first one is kept apart, so a scan
+/// of a single string does not allocate or copy it
+#[derive(Clone, Debug, Default)]
+struct Seen {
+    keyer: Keyer,
+    first: Option<Key>,
+    rest: HashSet<Key>,
+}
+
+impl Seen {
+    /// records `key`, false when it was recorded before
+    fn insert(&mut self, key: Key) -> bool {
+        if self.first == Some(key) || self.rest.contains(&key) {
+            return false;
+        }
+        match self.first {
+            None => self.first = Some(key),
+            Some(_) => {
+                self.rest.insert(key);
+            }
+        }
+        true
+    }
+
+    fn remove(&mut self, key: Key) {
+        if self.first == Some(key) {
+            self.first = None;
+        } else {
+            self.rest.remove(&key);
+        }
+    }
+}
+
+/// string bytes and strings left to scan in one payload
+#[derive(Clone, Debug)]
+pub struct Budget {
+    limits: Limits,
+    left: Option<usize>,
+    scanned: usize,
+    strings: usize,
+    /// containers entered with `enter`
+    depth: usize,
+    /// strings scanned without a match, so their repeats are not scanned again; None for a
+    /// payload of a single string
+    seen: Option<Seen>,
+    /// key of the string the last `repeated` recorded
+    recorded: Option<Key>,
+    /// ids of the objects scanned with `visit`, e.g. exceptions referencing each other
+    visited: HashSet<usize>,
+}
+
+impl Budget {
+    /// the part of the next string of the payload to scan, or its verdict when it is over
+    /// a limit and `on_overflow` is `allow` or `block`; scanned bytes are taken from the
+    /// payload budget
+    /// # Errors
+    /// * over a limit with `Overflow::Raise`
+    pub fn take<'a>(&mut self, s: &'a str) -> Result<Guarded<'a>, LimitError> {
+        self.strings += 1;
+        if let Some(limit) = self.limits.max_strings
+            && self.strings > limit
+        {
+            return self
+                .limits
+                .overflow(LimitError::Strings { limit })
+                .map(Guarded::Skip);
+        }
+        let mut cut = None;
+        if let Some(limit) = self.limits.max_string_len
+            && s.len() > limit
+        {
+            cut = Some((
+                limit,
+                LimitError::String {
+                    len: s.len(),
+                    limit,
+                },
+            ));
+        }
+        if let (Some(left), Some(limit)) = (self.left, self.limits.max_payload_len)
+            && cut.map_or(s.len(), |(len, _)| len) > left
+        {
+            cut = Some((left, LimitError::Payload { limit }));
+        }
+        let Some((len, error)) = cut else {
+            self.spend(s.len());
+            return Ok(Guarded::Scan(s));
+        };
+        match self.limits.on_overflow {
+            Overflow::ScanPrefix => {
+                let prefix = &s[..s.floor_char_boundary(len)];
+                self.spend(prefix.len());
+                Ok(if prefix.is_empty() {
+                    Guarded::Skip(false)
+                } else {
+                    Guarded::Scan(prefix)
+                })
+            }
+            _ => self.limits.overflow(error).map(Guarded::Skip),
+        }
+    }
+
+    /// verdict of a value nested in `depth` containers when that is over `max_depth`:
+    /// not scanned for `scan_prefix` and `allow`, a match for `block`; None to scan it
+    /// # Errors
+    /// * over the limit with `Overflow::Raise`
+    pub fn nested(&self, depth: usize) -> Result<Option<bool>, LimitError> {
+        match self.limits.max_depth {
+            Some(limit) if depth > limit => {
+                self.limits.overflow(LimitError::Depth { limit }).map(Some)
+            }
+            _ => Ok(None),
+        }
+    }
+
+    /// enters a container of a payload walked without paths: its verdict when it is
+    /// nested too deep, see `nested`, otherwise None and `leave` follows its items
+    /// # Errors
+    /// * over the depth limit with `Overflow::Raise`
+    pub fn enter(&mut self) -> Result<Option<bool>, LimitError> {
+        let verdict = self.nested(self.depth + 1)?;
+        if verdict.is_none() {
+            self.depth += 1;
+        }
+        Ok(verdict)
+    }
+
+    /// leaves a container entered with `enter`
+    pub fn leave(&mut self) {
+        self.depth -= 1;
+    }
+
+    /// whether the text `take` gave to scan was scanned before in this payload without a
+    /// match; otherwise it is recorded, and `matched` takes it back when its scan matches.
+    /// Strings shorter than `REPEAT_MIN_LEN` and the string of a `string_budget` are not
+    /// hashed and never repeated
+    pub fn repeated(&mut self, s: &str) -> bool {
+        self.recorded = None;
+        let Some(seen) = &mut self.seen else {
+            return false;
+        };
+        if s.len() < REPEAT_MIN_LEN {
+            return false;
+        }
+        let key = seen.keyer.key(s);
+        if !seen.insert(key) {
+            return true;
+        }
+        self.recorded = Some(key);
+        false
+    }
+
+    /// takes back the string the last `repeated` recorded, as its scan matched, so its
+    /// repeats are scanned again, e.g. to record their matches
+    pub fn matched(&mut self) {
+        if let (Some(seen), Some(key)) = (&mut self.seen, self.recorded.take()) {
+            seen.remove(key);
+        }
+    }
+
+    /// whether the object of `id` is visited the first time in this payload, so objects
+    /// referencing each other, e.g. in causes and notes, are scanned once
+    pub fn visit(&mut self, id: usize) -> bool {
+        self.visited.insert(id)
+    }
+
+    /// string bytes taken to scan so far
+    #[must_use]
+    pub fn scanned(&self) -> usize {
+        self.scanned
+    }
+
+    fn spend(&mut self, len: usize) {
+        self.scanned += len;
+        if let Some(left) = &mut self.left {
+            *left -= len;
+        }
+    }
+}
@@ -0,0 +1,163 @@
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
+#[cfg(feature = "python")]
+use pyo3::types::PyBytes;
+#[cfg(feature = "python")]
+use pyo3_stub_gen::derive::{gen_stub_pyclass, gen_stub_pymethods};
+use std::collections::VecDeque;
+use std::io::{self, BufRead};
+#[cfg(feature = "python")]
+use std::sync::{Mutex, PoisonError};
+
+use crate::matcher::Matcher;
+use crate::matches::Match;
+
+/// a deny word occurrence in a line
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LineHit {
+    /// line number from 1
+    pub line: usize,
+    pub word: String,
+    /// byte offsets in the line, without its line ending
+    pub start: usize,
+    pub end: usize,
+}
+
+/// reads `reader` one line at a time and yields its deny word occurrences in order;
+/// invalid utf-8 is replaced, `\n` and `\r\n` endings are stripped
+pub struct LineScanner<R> {
+    reader: R,
+    line: usize,
+    buf: Vec<u8>,
+    pending: VecDeque<LineHit>,
+}
+
+impl<R: BufRead> LineScanner<R> {
+    pub fn new(reader: R) -> Self {
+        Self {
+            reader,
+            line: 0,
+            buf: Vec::new(),
+            pending: VecDeque::new(),
+        }
+    }
+
+    /// next occurrence, None at the end of the input
+    /// # Errors
+    /// * the reader fails
+    pub fn next_hit<M: Matcher + ?Sized>(&mut self, matcher: &M) -> io::Result<Option<LineHit>> {
+        self.next_with(|text| matcher.find_all(text))
+    }
+
+    fn next_with(
+        &mut self,
+        mut find_all: impl FnMut(&str) -> Vec<Match>,
+    ) -> io::Result<Option<LineHit>> {
+        while self.pending.is_empty() {
+            self.buf.clear();
+            if self.reader.read_until(b'\n', &mut self.buf)? == 0 {
+                return Ok(None);
+            }
+            self.line += 1;
+            let mut bytes = self.buf.as_slice();
+            bytes = bytes.strip_suffix(b"\n").unwrap_or(bytes);
+            bytes = bytes.strip_suffix(b"\r").unwrap_or(bytes);
+            let text = String::from_utf8_lossy(bytes);
+            self.pending
+                .extend(find_all(&text).into_iter().map(|m| LineHit {
+                    line: self.line,
+                    word: m.word,
+                    start: m.start,
+                    end: m.end,
+                }));
+        }
+        Ok(self.pending.pop_front())
+    }
+
+    /// all occurrences until the end of the input
+    /// # Errors
+    /// * the reader fails
+    pub fn collect<M: Matcher + ?Sized>(mut self, matcher: &M) -> io::Result<Vec<LineHit>> {
+        let mut hits = Vec::new();
+        while let Some(hit) = self.next_hit(matcher)? {
+            hits.push(hit);
+        }
+        Ok(hits)
+    }
+}
+
+/// `(line_number, word, (start, end))` of a hit as yielded to python
+#[cfg(feature = "python")]
+type PyLineHit = (usize, String, (usize, usize));
+
+/// `M::find_all` through a python object of class `M`
+#[cfg(feature = "python")]
+type FindAll = fn(&Bound<'_, PyAny>, &str) -> PyResult<Vec<Match>>;
+
+#[cfg(feature = "python")]
+fn find_all_as<M: Matcher + pyo3::PyClass>(
+    matcher: &Bound<'_, PyAny>,
+    text: &str,
+) -> PyResult<Vec<Match>> {
+    Ok(matcher.cast::<M>()?.borrow().find_all(text))
+}
+
+/// iterator of `(line_number, word, (start, end))` of a file or bytes, lines are read and
+/// scanned as the iteration goes
+#[cfg(feature = "python")]
+#[gen_stub_pyclass]
+#[pyclass(module = "deny_filter.matchers", frozen)]
+pub struct LineScan {
+    matcher: Py<PyAny>,
+    find_all: FindAll,
+    scanner: Mutex<LineScanner<Box<dyn BufRead + Send + Sync>>>,
+}
+
+#[cfg(feature = "python")]
+#[gen_stub_pymethods]
+#[pymethods]
+impl LineScan {
+    fn __iter__(slf: PyRef<'_, Self>) -> PyRef<'_, Self> {
+        slf
+    }
+
+    /// # Errors
+    /// * the file cannot be read
+    fn __next__(&self, py: Python<'_>) -> PyResult<Option<PyLineHit>> {
+        let matcher = self.matcher.bind(py);
+        let mut scanner = self.scanner.lock().unwrap_or_else(PoisonError::into_inner);
+        let mut failed = None;
+        let hit = scanner.next_with(|text| {
+            (self.find_all)(matcher, text).unwrap_or_else(|e| {
+                failed = Some(e);
+                Vec::new()
+            })
+        })?;
+        if let Some(e) = failed {
+            return Err(e);
+        }
+        Ok(hit.map(|hit| (hit.line, hit.word, (hit.start, hit.end))))
+    }
+}
+
+/// `scan_lines` of the python matchers, `source` is the bytes to scan or a file path
+/// # Errors
+/// * the file cannot be opened
+#[cfg(feature = "python")]
+pub fn py_scan_lines<M: Matcher + pyo3::PyClass>(
+    matcher: PyRef<'_, M>,
+    source: &Bound<'_, PyAny>,
+) -> PyResult<LineScan> {
+    let reader: Box<dyn BufRead + Send + Sync> = if let Ok(bytes) = source.cast::<PyBytes>() {
+        Box::new(io::Cursor::new(bytes.as_bytes().to_vec()))
+    } else {
+        let path: std::path::PathBuf = source.extract()?;
+        Box::new(io::BufReader::new(std::fs::File::open(path)?))
+    };
+    let py = matcher.py();
+    Ok(LineScan {
+        matcher: matcher.into_pyobject(py)?.into_any().unbind(),
+        find_all: find_all_as::<M>,
+        scanner: Mutex::new(LineScanner::new(reader)),
+    })
+}
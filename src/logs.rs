@@ -0,0 +1,188 @@
+use pyo3::prelude::*;
+use pyo3::types::{PyDict, PyTuple};
+use pyo3_stub_gen::derive::{gen_stub_pyclass, gen_stub_pymethods};
+
+use crate::limits::{Budget, LimitError};
+use crate::matcher::Matcher;
+use crate::metrics::Timer;
+use crate::trace::ScanSpan;
+
+/// attributes every `logging.LogRecord` has, the others are `extra` fields
+const RECORD_ATTRIBUTES: [&str; 23] = [
+    "name",
+    "msg",
+    "args",
+    "levelname",
+    "levelno",
+    "pathname",
+    "filename",
+    "module",
+    "exc_info",
+    "exc_text",
+    "stack_info",
+    "lineno",
+    "funcName",
+    "created",
+    "msecs",
+    "relativeCreated",
+    "thread",
+    "threadName",
+    "processName",
+    "process",
+    "taskName",
+    "message",
+    "asctime",
+];
+
+/// whether the formatted message of a record has a deny word, `msg % args` as
+/// `LogRecord.getMessage` formats it, or `msg` and each of `args` when that fails
+fn scan_message<M: Matcher + ?Sized>(
+    matcher: &M,
+    record: &Bound<'_, PyDict>,
+    budget: &mut Budget,
+) -> Result<bool, LimitError> {
+    let Ok(Some(msg)) = record.get_item("msg") else {
+        return Ok(false);
+    };
+    let args = record.get_item("args").ok().flatten();
+    let args = args.filter(|args| args.is_truthy().unwrap_or(false));
+    let Some(args) = args else {
+        return matcher.scan_value(&msg, budget);
+    };
+    if let Ok(message) = msg.str().and_then(|msg| msg.rem(&args))
+        && let Ok(message) = message.extract::<&str>()
+    {
+        return matcher.check_within(message, budget);
+    }
+    if matcher.scan_value(&msg, budget)? {
+        return Ok(true);
+    }
+    if let Ok(args) = args.cast::<PyTuple>() {
+        for arg in args {
+            if matcher.scan_value(&arg, budget)? {
+                return Ok(true);
+            }
+        }
+        return Ok(false);
+    }
+    matcher.scan_value(&args, budget)
+}
+
+fn scan_record<M: Matcher + ?Sized>(
+    matcher: &M,
+    record: &Bound<'_, PyDict>,
+    budget: &mut Budget,
+) -> Result<bool, LimitError> {
+    if scan_message(matcher, record, budget)? {
+        return Ok(true);
+    }
+    if let Ok(Some(exc_info)) = record.get_item("exc_info")
+        && let Ok(exc_info) = exc_info.cast::<PyTuple>()
+        && let Ok(exception) = exc_info.get_item(1)
+        && matcher.scan_value(&exception, budget)?
+    {
+        return Ok(true);
+    }
+    for name in ["exc_text", "stack_info"] {
+        if let Ok(Some(text)) = record.get_item(name)
+            && matcher.scan_value(&text, budget)?
+        {
+            return Ok(true);
+        }
+    }
+    for (key, value) in record {
+        let Ok(key) = key.extract::<&str>() else {
+            continue;
+        };
+        if RECORD_ATTRIBUTES.contains(&key) || matcher.ignored_keys().skips(key) {
+            continue;
+        }
+        if matcher.scan_value(&value, budget)? {
+            return Ok(true);
+        }
+    }
+    Ok(false)
+}
+
+/// whether a `logging.LogRecord`, or its `__dict__`, has a deny word in its formatted
+/// message, its exception and stack texts or an `extra` field
+/// # Errors
+/// * a record without `__dict__` that is not a dict
+/// * over a length limit with `Overflow::Raise`
+pub fn py_scan_log_record<M: Matcher + ?Sized>(
+    matcher: &M,
+    record: &Bound<'_, PyAny>,
+) -> PyResult<bool> {
+    let span = ScanSpan::enter(matcher.metrics(), "scan_log_record");
+    let timer = Timer::start();
+    let record = match record.cast::<PyDict>() {
+        Ok(record) => record.clone(),
+        Err(_) => record.getattr("__dict__")?.cast_into::<PyDict>()?,
+    };
+    let mut budget = matcher.limits().budget();
+    let found = scan_record(matcher, &record, &mut budget)?;
+    span.finish(timer.elapsed(), budget.scanned(), found);
+    Ok(found)
+}
+
+/// `logging.Filter` of a deny list: a record with a deny word is dropped, or logged with
+/// `replacement` as its message and its `extra` fields, without arguments and exception
+#[gen_stub_pyclass]
+#[pyclass(module = "deny_filter.plugin", frozen, skip_from_py_object)]
+pub struct LogFilter {
+    deny_list: Py<PyAny>,
+    replacement: Option<String>,
+}
+
+impl LogFilter {
+    #[must_use]
+    pub fn new(deny_list: Py<PyAny>, replacement: Option<String>) -> Self {
+        Self {
+            deny_list,
+            replacement,
+        }
+    }
+}
+
+#[gen_stub_pymethods]
+#[pymethods]
+impl LogFilter {
+    /// whether `logging` emits the record, see `scan_log_record`
+    /// # Errors
+    /// * over a length limit with `on_overflow="raise"`
+    fn filter(&self, record: &Bound<'_, PyAny>) -> PyResult<bool> {
+        let py = record.py();
+        let found = self
+            .deny_list
+            .bind(py)
+            .call_method1("scan_log_record", (record,))?
+            .is_truthy()?;
+        if !found {
+            return Ok(true);
+        }
+        let Some(replacement) = &self.replacement else {
+            return Ok(false);
+        };
+        record.setattr("msg", replacement)?;
+        record.setattr("args", PyTuple::empty(py))?;
+        for name in ["exc_info", "exc_text", "stack_info"] {
+            record.setattr(name, py.None())?;
+        }
+        let extra: Vec<String> = record
+            .getattr("__dict__")?
+            .cast_into::<PyDict>()?
+            .keys()
+            .iter()
+            .filter_map(|key| key.extract().ok())
+            .filter(|key: &String| !RECORD_ATTRIBUTES.contains(&key.as_str()))
+            .collect();
+        for name in extra {
+            record.setattr(name, replacement)?;
+        }
+        Ok(true)
+    }
+
+    fn __repr__(&self) -> String {
+        format!("LogFilter(replacement={:?})", self.replacement)
+    }
+}
@@ -1,52 +1,437 @@
+#[cfg(feature = "python")]
 use pyo3::prelude::*;
+#[cfg(feature = "python")]
 use pyo3::types::{PyDict, PyList};
+use serde::Serialize;
+use serde_json::Value;
+#[cfg(feature = "python")]
+use std::fmt::Write as _;
+use std::sync::Arc;
+
+use crate::build_error::BuildError;
+use crate::cache::ResultCache;
+use crate::decompress::decompress;
+use crate::evaluate::Evaluation;
+use crate::explain::Explanation;
+use crate::keys::{KeyFilter, NO_KEYS};
+use crate::limits::{Budget, Guarded, LimitError, Limits};
+use crate::matches::Match;
+use crate::metrics::{BackendMetrics, Timer};
+use crate::options::ListMetadata;
+use crate::redact;
+use crate::report::BuildReport;
+use crate::score::{NO_WEIGHTS, Weights};
+use crate::stats::Stats;
+use crate::trace::{self, ScanSpan};
+use crate::traverse;
 
 pub trait Matcher {
     fn is_match(&self, s: &str) -> bool;
 
+    /// deny word that matched first, None if no match
+    fn first_match(&self, s: &str) -> Option<&str>;
+
+    /// leftmost non-overlapping deny word occurrences
+    fn find_all(&self, s: &str) -> Vec<Match>;
+
+    /// every deny word occurrence, overlapping ones included
+    /// # Errors
+    /// * the overlapping automaton cannot be built
+    fn find_overlapping(&self, s: &str) -> Result<Vec<Match>, BuildError>;
+
+    /// text with every grapheme of the `find_all` matches replaced by `mask`
+    fn redact(&self, s: &str, mask: &str) -> String {
+        redact::redact(s, &self.find_all(s), mask)
+    }
+
+    /// text with the `find_all` matches wrapped in `open` and `close`
+    fn highlight(&self, s: &str, open: &str, close: &str) -> String {
+        redact::highlight(s, &self.find_all(s), open, close)
+    }
+
+    /// msgpack document with every string value redacted, see `redact::redact_msgpack`
+    /// # Errors
+    /// * truncated or malformed msgpack
+    /// * over the depth or element count limit
+    fn redact_msgpack(&self, buf: &[u8], mask: &str) -> Result<Vec<u8>, traverse::Error> {
+        redact::redact_msgpack(self, buf, mask)
+    }
+
+    /// json document with every string value redacted, see `redact::redact_json`
+    /// # Errors
+    /// * invalid json
+    fn redact_json(
+        &self,
+        buf: &[u8],
+        mask: &str,
+        preserve_order: bool,
+    ) -> Result<Vec<u8>, serde_json::Error> {
+        redact::redact_json(self, buf, mask, preserve_order)
+    }
+
+    /// which deny word matched, where and after which normalizations
+    fn explain(&self, s: &str) -> Option<Explanation>;
+
+    /// `explain` of the first matching string value of a json document, with its path
+    fn explain_json(&self, value: &Value) -> Option<Explanation> {
+        let mut found = None;
+        traverse::json(value, &mut |path, s| {
+            found = self.explain(s).map(|e| Explanation {
+                path: Some(traverse::format_path(path)),
+                ..e
+            });
+            found.is_some()
+        });
+        found
+    }
+
+    /// `explain` of the first matching string of a python dict/list/str, `path` is the
+    /// `$.key[0]` path of `value`
+    #[cfg(feature = "python")]
+    fn explain_value(&self, value: &Bound<'_, PyAny>, path: &mut String) -> Option<Explanation> {
+        let len = path.len();
+        if let Ok(s) = value.extract::<&str>() {
+            return self.explain(s).map(|e| Explanation {
+                path: Some(path.clone()),
+                ..e
+            });
+        } else if let Ok(dict) = value.cast::<PyDict>() {
+            for (key, item) in dict {
+                let _ = write!(path, ".{key}");
+                let found = self.explain_value(&item, path);
+                path.truncate(len);
+                if found.is_some() {
+                    return found;
+                }
+            }
+        } else if let Ok(list) = value.cast::<PyList>() {
+            for (i, item) in list.iter().enumerate() {
+                let _ = write!(path, "[{i}]");
+                let found = self.explain_value(&item, path);
+                path.truncate(len);
+                if found.is_some() {
+                    return found;
+                }
+            }
+        }
+        None
+    }
+
+    /// precision, recall and false positive words on `(sample, deny label)` pairs
+    fn evaluate(&self, samples: &[(&str, bool)]) -> Evaluation {
+        Evaluation::new(
+            samples
+                .iter()
+                .map(|(sample, label)| (self.first_match(sample), *label)),
+        )
+    }
+
+    /// version, source and timestamp of the list
+    fn metadata(&self) -> &ListMetadata;
+
+    /// what construction did to the deny words
+    fn build_report(&self) -> &BuildReport;
+
+    /// pattern and automaton sizes
+    fn stats(&self) -> Stats;
+
+    /// graphviz digraph of the pattern tries
+    #[cfg(feature = "dot")]
+    fn to_dot(&self) -> String;
+
+    /// counters shared by all matchers of the same backend
+    fn metrics(&self) -> &BackendMetrics;
+
+    /// length limits of the scanned input, none unless overridden
+    fn limits(&self) -> &Limits {
+        &Limits::NONE
+    }
+
+    /// map keys whose values are not scanned, none unless overridden
+    fn ignored_keys(&self) -> &KeyFilter {
+        &NO_KEYS
+    }
+
+    /// verdicts of recently scanned strings, see `Options::cache_size`; none unless
+    /// overridden
+    fn result_cache(&self) -> Option<&ResultCache> {
+        None
+    }
+
+    /// weights of the deny words in `score`, 1 each unless overridden
+    fn weights(&self) -> &Weights {
+        &NO_WEIGHTS
+    }
+
+    /// labels (`first NEAR/n second`) of the proximity rules satisfied by `s`, none for
+    /// backends without proximity rules
+    fn proximity_matches(&self, s: &str) -> Vec<&str> {
+        let _ = s;
+        Vec::new()
+    }
+
+    /// what `score` weighs in `s`: the deny words of `find_all` and the labels of
+    /// `proximity_matches`, repeats included
+    fn scored_words(&self, s: &str) -> Vec<String> {
+        let mut words: Vec<String> = self.find_all(s).into_iter().map(|m| m.word).collect();
+        words.extend(self.proximity_matches(s).into_iter().map(str::to_string));
+        words
+    }
+
+    /// risk score of `s`: the aggregated weights of the distinct deny words and proximity
+    /// rules matched
+    fn score(&self, s: &str) -> f64 {
+        let words = self.scored_words(s);
+        let score = self.weights().score(words.iter().map(String::as_str));
+        self.metrics().record_score(score);
+        score
+    }
+
+    /// `score` of all string values of a json document, each deny word counted once
+    fn score_json(&self, value: &Value) -> f64 {
+        let mut words = Vec::new();
+        traverse::json(value, &mut |path, s| {
+            if !self.ignored_keys().skips_path(path) {
+                words.extend(self.scored_words(s));
+            }
+            false
+        });
+        let score = self.weights().score(words.iter().map(String::as_str));
+        self.metrics().record_score(score);
+        score
+    }
+
+    /// `check` of one string of a payload, within the length limits left in `budget`
+    /// # Errors
+    /// * over a length limit with `Overflow::Raise`
+    fn check_within(&self, s: &str, budget: &mut Budget) -> Result<bool, LimitError> {
+        let s = match budget.take(s)? {
+            Guarded::Scan(s) => s,
+            Guarded::Skip(verdict) => return Ok(verdict),
+        };
+        // identical strings of one payload, e.g. a system prompt fanned out to every
+        // message, are scanned once; only clean ones are skipped
+        if budget.repeated(s) {
+            return Ok(false);
+        }
+        let found = if let Some(cache) = self.result_cache() {
+            let (found, pattern) = cache.verdict(s, || {
+                let found = self.is_match(s);
+                (
+                    found,
+                    found.then(|| self.first_match(s)).flatten().map(Arc::from),
+                )
+            });
+            if let Some(pattern) = pattern {
+                self.metrics().record_pattern(&pattern);
+                trace::matched(&pattern);
+            }
+            found
+        } else if self.is_match(s) {
+            if let Some(pattern) = self.first_match(s) {
+                self.metrics().record_pattern(pattern);
+                trace::matched(pattern);
+            }
+            true
+        } else {
+            false
+        };
+        if found {
+            budget.matched();
+        }
+        Ok(found)
+    }
+
+    /// `check_within` of a string of a document at `path`: clean below an ignored key,
+    /// the `on_overflow` verdict when nested deeper than `max_depth`
+    /// # Errors
+    /// * over a limit with `Overflow::Raise`
+    fn check_at(
+        &self,
+        path: &[traverse::Segment<'_>],
+        s: &str,
+        budget: &mut Budget,
+    ) -> Result<bool, LimitError> {
+        if self.ignored_keys().skips_path(path) {
+            return Ok(false);
+        }
+        if let Some(verdict) = budget.nested(path.len())? {
+            return Ok(verdict);
+        }
+        self.check_within(s, budget)
+    }
+
+    /// Shared logic: checks one string and records the matched pattern;
+    /// over a length limit with `Overflow::Raise` it counts as a match
+    fn check(&self, s: &str) -> bool {
+        self.check_within(s, &mut self.limits().string_budget())
+            .unwrap_or(true)
+    }
+
+    /// `scan_str` failing over a length limit with `Overflow::Raise`
+    /// # Errors
+    /// * over a length limit with `Overflow::Raise`
+    fn try_scan_str(&self, txt: &str) -> Result<bool, LimitError> {
+        let span = ScanSpan::enter(self.metrics(), "scan_str");
+        let timer = Timer::start();
+        let mut budget = self.limits().string_budget();
+        let found = self.check_within(txt, &mut budget)?;
+        span.finish(timer.elapsed(), budget.scanned(), found);
+        Ok(found)
+    }
+
     /// Shared logic: Scans a string and returns true if match found
     fn scan_str(&self, txt: &str) -> bool {
-        self.is_match(txt)
+        self.try_scan_str(txt).unwrap_or(true)
     }
 
     /// Shared logic: Scans single level dictionary
-    fn scan(&self, args: &Bound<'_, PyDict>) -> bool {
-        for value in args.values() {
+    /// # Errors
+    /// * over a length limit with `Overflow::Raise`
+    #[cfg(feature = "python")]
+    fn scan(&self, args: &Bound<'_, PyDict>) -> PyResult<bool> {
+        let span = ScanSpan::enter(self.metrics(), "scan");
+        let timer = Timer::start();
+        let mut budget = self.limits().budget();
+        let mut found = false;
+        for (key, value) in args {
             if let Ok(value_str) = value.extract::<&str>()
-                && self.is_match(value_str)
+                && !key
+                    .extract::<&str>()
+                    .is_ok_and(|key| self.ignored_keys().skips(key))
+                && self.check_within(value_str, &mut budget)?
             {
-                return true;
+                found = true;
+                break;
             }
         }
-        false
+        span.finish(timer.elapsed(), budget.scanned(), found);
+        Ok(found)
+    }
+
+    /// Shared logic: scans any Python object and records metrics
+    /// # Errors
+    /// * over a length limit with `Overflow::Raise`
+    #[cfg(feature = "python")]
+    fn scan_any(&self, value: &Bound<'_, PyAny>) -> PyResult<bool> {
+        let span = ScanSpan::enter(self.metrics(), "scan_any");
+        let timer = Timer::start();
+        let mut budget = self.limits().budget();
+        let found = self.scan_value(value, &mut budget)?;
+        span.finish(timer.elapsed(), budget.scanned(), found);
+        Ok(found)
     }
 
     /// Shared logic: The recursive engine for any Python object
     /// # Errors
-    /// * too deep dictionaries, too long patterns probably
-    fn scan_any(&self, value: &Bound<'_, PyAny>) -> bool {
+    /// * over a length limit with `Overflow::Raise`
+    #[cfg(feature = "python")]
+    fn scan_value(
+        &self,
+        value: &Bound<'_, PyAny>,
+        budget: &mut Budget,
+    ) -> Result<bool, LimitError> {
         // 1. Check for String
         if let Ok(s) = value.extract::<&str>() {
-            if self.is_match(s) {
-                return true;
+            if self.check_within(s, budget)? {
+                return Ok(true);
             }
         }
         // 2. Check for Dictionary (using downcast for speed)
         else if let Ok(dict) = value.cast::<PyDict>() {
-            for item_value in dict.values() {
-                if self.scan_any(&item_value) {
-                    return true;
+            if let Some(verdict) = budget.enter()? {
+                return Ok(verdict);
+            }
+            let mut found = false;
+            for (key, item_value) in dict {
+                if key
+                    .extract::<&str>()
+                    .is_ok_and(|key| self.ignored_keys().skips(key))
+                {
+                    continue;
+                }
+                if self.scan_value(&item_value, budget)? {
+                    found = true;
+                    break;
                 }
             }
+            budget.leave();
+            return Ok(found);
         }
         // 3. Check for List
         else if let Ok(list) = value.cast::<PyList>() {
+            if let Some(verdict) = budget.enter()? {
+                return Ok(verdict);
+            }
+            let mut found = false;
             for item in list {
-                if self.scan_any(&item) {
-                    return true;
+                if self.scan_value(&item, budget)? {
+                    found = true;
+                    break;
                 }
             }
+            budget.leave();
+            return Ok(found);
         }
-        false
+        // 4. Exceptions, tracebacks and frames of an error report
+        else if crate::tracebacks::is_error(value) {
+            return crate::tracebacks::scan_error(self, value, budget);
+        }
+        Ok(false)
     }
+
+    /// Shared logic: scans every string value of a parsed json document;
+    /// over a length limit with `Overflow::Raise` it counts as a match
+    fn scan_json(&self, value: &Value) -> bool {
+        let span = ScanSpan::enter(self.metrics(), "scan_json");
+        let timer = Timer::start();
+        let mut budget = self.limits().budget();
+        let found = traverse::json(value, &mut |path, s| {
+            self.check_at(path, s, &mut budget).unwrap_or(true)
+        });
+        span.finish(timer.elapsed(), budget.scanned(), found);
+        found
+    }
+
+    /// Shared logic: scans every string value of a msgpack document
+    /// # Errors
+    /// * truncated or malformed msgpack
+    /// * over a length limit with `Overflow::Raise`
+    fn scan_msgpack(&self, buf: &[u8]) -> Result<bool, traverse::Error> {
+        let span = ScanSpan::enter(self.metrics(), "scan_msgpack");
+        let timer = Timer::start();
+        let mut budget = self.limits().budget();
+        let mut failed = None;
+        let found = traverse::msgpack_limited(buf, self.limits(), &mut |path, s| {
+            self.check_at(path, s, &mut budget).unwrap_or_else(|e| {
+                failed = Some(e);
+                true
+            })
+        })?;
+        if let Some(e) = failed {
+            return Err(e.into());
+        }
+        span.finish(timer.elapsed(), budget.scanned(), found);
+        Ok(found)
+    }
+
+    /// `scan_msgpack` of a gzip or zlib compressed document, sniffed by its magic bytes,
+    /// of at most `limit` bytes decompressed; uncompressed documents are scanned as is
+    /// # Errors
+    /// * corrupt compressed data or more than `limit` decompressed bytes
+    /// * truncated or malformed msgpack
+    fn scan_msgpack_compressed(&self, buf: &[u8], limit: usize) -> Result<bool, traverse::Error> {
+        self.scan_msgpack(&decompress(buf, limit)?)
+    }
+}
+
+/// `scan_json` of any serializable value, e.g. a request type of a rust service, through
+/// its `serde_json` representation
+/// # Errors
+/// * the value has no json representation, e.g. a map with non-string keys
+pub fn scan_serialize<M: Matcher + ?Sized, T: Serialize + ?Sized>(
+    matcher: &M,
+    value: &T,
+) -> Result<bool, serde_json::Error> {
+    Ok(matcher.scan_json(&serde_json::to_value(value)?))
 }
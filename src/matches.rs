@@ -0,0 +1,67 @@
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
+#[cfg(feature = "python")]
+use pyo3_stub_gen::derive::{gen_stub_pyclass, gen_stub_pymethods};
+
+/// occurrence of a deny word in the scanned text
+#[cfg_attr(feature = "python", gen_stub_pyclass)]
+#[cfg_attr(
+    feature = "python",
+    pyclass(module = "deny_filter.matchers", frozen, get_all, skip_from_py_object)
+)]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Match {
+    /// deny word in lowercase
+    pub word: String,
+    /// byte offset of the first byte in the original text
+    pub start: usize,
+    /// byte offset after the last byte in the original text
+    pub end: usize,
+    /// code point offset of `start`, for slicing python strings
+    pub char_start: usize,
+    /// code point offset of `end`
+    pub char_end: usize,
+}
+
+/// fills the code point offsets of matches in `text` with one pass over the text
+pub fn set_char_offsets(text: &str, matches: &mut [Match]) {
+    if text.is_ascii() {
+        for m in matches {
+            (m.char_start, m.char_end) = (m.start, m.end);
+        }
+        return;
+    }
+    let mut offsets: Vec<(usize, usize)> = Vec::with_capacity(matches.len() * 2);
+    for (i, m) in matches.iter().enumerate() {
+        offsets.push((m.start, 2 * i));
+        offsets.push((m.end, 2 * i + 1));
+    }
+    offsets.sort_unstable();
+    let (mut byte, mut chars) = (0, 0);
+    let mut char_indices = text.char_indices().map(|(i, _)| i).peekable();
+    for (offset, slot) in offsets {
+        while byte < offset {
+            char_indices.next();
+            chars += 1;
+            byte = char_indices.peek().copied().unwrap_or(text.len());
+        }
+        let m = &mut matches[slot / 2];
+        if slot % 2 == 0 {
+            m.char_start = chars;
+        } else {
+            m.char_end = chars;
+        }
+    }
+}
+
+#[cfg(feature = "python")]
+#[gen_stub_pymethods]
+#[pymethods]
+impl Match {
+    pub(crate) fn __repr__(&self) -> String {
+        format!(
+            "Match(word={:?}, start={}, end={}, char_start={}, char_end={})",
+            self.word, self.start, self.end, self.char_start, self.char_end
+        )
+    }
+}
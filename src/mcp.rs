@@ -0,0 +1,202 @@
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
+#[cfg(feature = "python")]
+use pyo3::types::{PyBytes, PyString};
+#[cfg(feature = "python")]
+use pyo3_stub_gen::derive::{gen_stub_pyclass, gen_stub_pymethods};
+use serde_json::Value;
+
+use crate::matcher::Matcher;
+use crate::metrics::Timer;
+use crate::traverse;
+
+/// verdict of an MCP JSON-RPC message in the shape of a gateway plugin result
+#[cfg_attr(feature = "python", gen_stub_pyclass)]
+#[cfg_attr(
+    feature = "python",
+    pyclass(module = "deny_filter.plugin", frozen, get_all, skip_from_py_object)
+)]
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct McpVerdict {
+    /// false when a deny word was found
+    pub continue_processing: bool,
+    /// method of the envelope with the deny word, None for responses
+    pub method: Option<String>,
+    /// `$.params.arguments.key` path of the string with the deny word
+    pub path: Option<String>,
+    /// deny word that matched first
+    pub word: Option<String>,
+}
+
+#[cfg(feature = "python")]
+#[gen_stub_pymethods]
+#[pymethods]
+impl McpVerdict {
+    fn __repr__(&self) -> String {
+        let show = |v: &Option<String>| v.as_ref().map_or("None".to_string(), |v| format!("{v:?}"));
+        format!(
+            "McpVerdict(continue_processing={}, method={}, path={}, word={})",
+            if self.continue_processing {
+                "True"
+            } else {
+                "False"
+            },
+            show(&self.method),
+            show(&self.path),
+            show(&self.word)
+        )
+    }
+}
+
+/// a field of an envelope to scan, all string values below it
+type Field<'a> = (String, &'a Value);
+
+/// text of an MCP content block, or of the resource it embeds; images, audio, blobs
+/// and resource links have none
+fn content_block<'a>(path: String, block: &'a Value, fields: &mut Vec<Field<'a>>) {
+    match block.get("type").and_then(Value::as_str) {
+        Some("text") => fields.extend(block.get("text").map(|t| (path + ".text", t))),
+        Some("resource") => fields.extend(
+            block
+                .pointer("/resource/text")
+                .map(|t| (path + ".resource.text", t)),
+        ),
+        _ => {}
+    }
+}
+
+/// every element of the array at `key` of `value` with its path
+fn items<'a>(path: &str, value: &'a Value, key: &str) -> Vec<(String, &'a Value)> {
+    value
+        .get(key)
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .enumerate()
+        .map(|(i, item)| (format!("{path}.{key}[{i}]"), item))
+        .collect()
+}
+
+/// the semantically relevant fields of one request, notification or response: tool and
+/// prompt arguments, completion values, sampling messages, resource uris and contents,
+/// tool results; other methods and errors have none
+fn fields<'a>(path: &str, envelope: &'a Value) -> Vec<Field<'a>> {
+    let mut fields = Vec::new();
+    let at = |key: &str| format!("{path}.{key}");
+    if let Some(params) = envelope.get("params") {
+        let params_path = at("params");
+        match envelope.get("method").and_then(Value::as_str) {
+            Some("tools/call" | "prompts/get") => {
+                fields.extend(params.get("arguments").map(|a| (at("params.arguments"), a)));
+            }
+            Some("completion/complete") => fields.extend(
+                params
+                    .pointer("/argument/value")
+                    .map(|v| (at("params.argument.value"), v)),
+            ),
+            Some("resources/read") => {
+                fields.extend(params.get("uri").map(|u| (at("params.uri"), u)));
+            }
+            Some("sampling/createMessage") => {
+                fields.extend(
+                    params
+                        .get("systemPrompt")
+                        .map(|p| (at("params.systemPrompt"), p)),
+                );
+                for (message_path, message) in items(&params_path, params, "messages") {
+                    if let Some(content) = message.get("content") {
+                        content_block(message_path + ".content", content, &mut fields);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+    if let Some(result) = envelope.get("result") {
+        let result_path = at("result");
+        for (content_path, content) in items(&result_path, result, "contents") {
+            fields.extend(content.get("text").map(|t| (content_path + ".text", t)));
+        }
+        for (block_path, block) in items(&result_path, result, "content") {
+            content_block(block_path, block, &mut fields);
+        }
+        for (message_path, message) in items(&result_path, result, "messages") {
+            if let Some(content) = message.get("content") {
+                content_block(message_path + ".content", content, &mut fields);
+            }
+        }
+        fields.extend(
+            result
+                .get("structuredContent")
+                .map(|c| (at("result.structuredContent"), c)),
+        );
+    }
+    fields
+}
+
+/// scans an MCP JSON-RPC message, a single envelope or a batch, in the fields that carry
+/// user or tool content; the envelope itself (`jsonrpc`, `id`, `method`, `_meta`) is not
+/// scanned
+pub fn scan_mcp_request<M: Matcher + ?Sized>(matcher: &M, message: &Value) -> McpVerdict {
+    let timer = Timer::start();
+    let envelopes: Vec<(String, &Value)> = match message {
+        Value::Array(batch) => batch
+            .iter()
+            .enumerate()
+            .map(|(i, envelope)| (format!("$[{i}]"), envelope))
+            .collect(),
+        _ => vec![("$".to_string(), message)],
+    };
+    let mut verdict = McpVerdict {
+        continue_processing: true,
+        ..McpVerdict::default()
+    };
+    'envelopes: for (path, envelope) in envelopes {
+        for (field_path, value) in fields(&path, envelope) {
+            let mut found = None;
+            traverse::json(value, &mut |path, s| {
+                if matcher.check(s) {
+                    found = Some((path.to_vec(), s));
+                }
+                found.is_some()
+            });
+            if let Some((inner, s)) = found {
+                verdict = McpVerdict {
+                    continue_processing: false,
+                    method: envelope
+                        .get("method")
+                        .and_then(Value::as_str)
+                        .map(ToString::to_string),
+                    path: Some(field_path + &traverse::format_path(&inner)[1..]),
+                    word: matcher.first_match(s).map(ToString::to_string),
+                };
+                break 'envelopes;
+            }
+        }
+    }
+    matcher
+        .metrics()
+        .record_scan(timer.elapsed(), !verdict.continue_processing);
+    verdict
+}
+
+/// `scan_mcp_request` of the python matchers, `message` is the raw json as bytes or str,
+/// or the decoded dict/list
+/// # Errors
+/// * invalid json
+#[cfg(feature = "python")]
+pub fn py_scan_mcp_request<M: Matcher + ?Sized>(
+    matcher: &M,
+    message: &Bound<'_, PyAny>,
+) -> PyResult<McpVerdict> {
+    let invalid =
+        |e: &dyn std::fmt::Display| pyo3::exceptions::PyValueError::new_err(e.to_string());
+    let message: Value = if let Ok(bytes) = message.cast::<PyBytes>() {
+        serde_json::from_slice(bytes.as_bytes()).map_err(|e| invalid(&e))?
+    } else if let Ok(text) = message.cast::<PyString>() {
+        serde_json::from_str(text.to_str()?).map_err(|e| invalid(&e))?
+    } else {
+        pythonize::depythonize(message).map_err(|e| invalid(&e))?
+    };
+    Ok(scan_mcp_request(matcher, &message))
+}
@@ -0,0 +1,485 @@
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::io::{Read, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream};
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering::Relaxed};
+use std::sync::{Arc, Mutex, PoisonError};
+use std::thread;
+use std::time::Duration;
+#[cfg(not(target_arch = "wasm32"))]
+use std::time::Instant;
+
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
+#[cfg(feature = "python")]
+use pyo3_stub_gen::derive::gen_stub_pyfunction;
+
+/// upper bounds (seconds) of the scan latency histogram buckets
+const LATENCY_BUCKETS: [f64; 10] = [1e-6, 5e-6, 1e-5, 5e-5, 1e-4, 5e-4, 1e-3, 5e-3, 1e-2, 1e-1];
+
+/// measures scan latency, wasm32 has no clock so latency is always zero there
+pub struct Timer {
+    #[cfg(not(target_arch = "wasm32"))]
+    start: Instant,
+}
+
+impl Timer {
+    #[must_use]
+    pub fn start() -> Self {
+        Self {
+            #[cfg(not(target_arch = "wasm32"))]
+            start: Instant::now(),
+        }
+    }
+
+    #[must_use]
+    pub fn elapsed(&self) -> Duration {
+        #[cfg(not(target_arch = "wasm32"))]
+        return self.start.elapsed();
+        #[cfg(target_arch = "wasm32")]
+        return Duration::ZERO;
+    }
+}
+
+/// counters of one matcher backend, shared by all instances of that backend
+#[derive(Default)]
+pub struct BackendMetrics {
+    name: &'static str,
+    scans: AtomicU64,
+    matches: AtomicU64,
+    bytes: AtomicU64,
+    latency_buckets: [AtomicU64; LATENCY_BUCKETS.len()],
+    latency_nanos: AtomicU64,
+    patterns: Mutex<BTreeMap<String, u64>>,
+    scores: AtomicU64,
+    /// bits of the f64 sum of the scores
+    score_sum: AtomicU64,
+}
+
+impl BackendMetrics {
+    /// backend the counters belong to
+    #[must_use]
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// records one finished scan call
+    pub fn record_scan(&self, elapsed: Duration, matched: bool) {
+        self.scans.fetch_add(1, Relaxed);
+        if matched {
+            self.matches.fetch_add(1, Relaxed);
+        }
+        let secs = elapsed.as_secs_f64();
+        if let Some(i) = LATENCY_BUCKETS.iter().position(|le| secs <= *le) {
+            self.latency_buckets[i].fetch_add(1, Relaxed);
+        }
+        let nanos = u64::try_from(elapsed.as_nanos()).unwrap_or(u64::MAX);
+        self.latency_nanos.fetch_add(nanos, Relaxed);
+    }
+
+    /// counts the string bytes scanned by one scan call
+    pub fn record_bytes(&self, bytes: usize) {
+        self.bytes
+            .fetch_add(u64::try_from(bytes).unwrap_or(u64::MAX), Relaxed);
+    }
+
+    /// scans, matches and scanned bytes so far
+    #[must_use]
+    pub fn counts(&self) -> ScanCounts {
+        ScanCounts {
+            scans: self.scans.load(Relaxed),
+            matches: self.matches.load(Relaxed),
+            bytes: self.bytes.load(Relaxed),
+        }
+    }
+
+    /// sets every counter back to zero
+    pub fn reset(&self) {
+        for counter in [
+            &self.scans,
+            &self.matches,
+            &self.bytes,
+            &self.latency_nanos,
+            &self.scores,
+            &self.score_sum,
+        ]
+        .into_iter()
+        .chain(&self.latency_buckets)
+        {
+            counter.store(0, Relaxed);
+        }
+        self.patterns
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clear();
+    }
+
+    /// counts a hit of a single pattern, only called on the match path
+    pub fn record_pattern(&self, pattern: &str) {
+        let mut patterns = self.patterns.lock().unwrap_or_else(PoisonError::into_inner);
+        *patterns.entry(pattern.to_string()).or_default() += 1;
+    }
+
+    /// records the risk score of one `score` call
+    pub fn record_score(&self, score: f64) {
+        if !score.is_finite() {
+            // a block by a limit, kept out of the summary
+            return;
+        }
+        self.scores.fetch_add(1, Relaxed);
+        let _ = self.score_sum.fetch_update(Relaxed, Relaxed, |bits| {
+            Some((f64::from_bits(bits) + score).to_bits())
+        });
+    }
+}
+
+/// scan counters of one backend, or summed over backends
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ScanCounts {
+    /// scan calls
+    pub scans: u64,
+    /// scan calls that found a deny word
+    pub matches: u64,
+    /// string bytes scanned by `scan_str`, `scan`, `scan_any`, `scan_json`, `scan_msgpack`
+    /// and `scan_selected`
+    pub bytes: u64,
+}
+
+impl std::iter::Sum for ScanCounts {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::default(), |a, b| Self {
+            scans: a.scans + b.scans,
+            matches: a.matches + b.matches,
+            bytes: a.bytes + b.bytes,
+        })
+    }
+}
+
+/// registry of backend counters, one entry per backend name
+static REGISTRY: Mutex<Vec<(&'static str, Arc<BackendMetrics>)>> = Mutex::new(Vec::new());
+
+/// returns the shared counters for a backend, registering it on first use
+#[must_use]
+pub fn backend(name: &'static str) -> Arc<BackendMetrics> {
+    let mut registry = REGISTRY.lock().unwrap_or_else(PoisonError::into_inner);
+    if let Some((_, m)) = registry.iter().find(|(n, _)| *n == name) {
+        return Arc::clone(m);
+    }
+    let m = Arc::new(BackendMetrics {
+        name,
+        ..BackendMetrics::default()
+    });
+    registry.push((name, Arc::clone(&m)));
+    m
+}
+
+/// counters of every backend used so far, by backend name
+#[must_use]
+pub fn scan_counts() -> BTreeMap<&'static str, ScanCounts> {
+    REGISTRY
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .iter()
+        .map(|(name, m)| (*name, m.counts()))
+        .collect()
+}
+
+/// sets the counters of every backend back to zero, shadow counters are kept
+pub fn reset() {
+    for (_, m) in REGISTRY
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .iter()
+    {
+        m.reset();
+    }
+}
+
+/// verdict differences of a shadow candidate list, one entry per shadow name
+#[derive(Default)]
+pub struct ShadowMetrics {
+    sampled: AtomicU64,
+    /// the candidate would block what the active list allows
+    blocks: AtomicU64,
+    /// the candidate would allow what the active list blocks
+    allows: AtomicU64,
+}
+
+impl ShadowMetrics {
+    /// records one sampled scan with both verdicts
+    pub fn record(&self, active: bool, candidate: bool) {
+        self.sampled.fetch_add(1, Relaxed);
+        match (active, candidate) {
+            (false, true) => self.blocks.fetch_add(1, Relaxed),
+            (true, false) => self.allows.fetch_add(1, Relaxed),
+            _ => 0,
+        };
+    }
+
+    /// scans evaluated by the candidate
+    #[must_use]
+    pub fn sampled(&self) -> u64 {
+        self.sampled.load(Relaxed)
+    }
+
+    /// sampled scans the candidate would block but the active list allows
+    #[must_use]
+    pub fn blocks(&self) -> u64 {
+        self.blocks.load(Relaxed)
+    }
+
+    /// sampled scans the candidate would allow but the active list blocks
+    #[must_use]
+    pub fn allows(&self) -> u64 {
+        self.allows.load(Relaxed)
+    }
+}
+
+static SHADOWS: Mutex<Vec<(String, Arc<ShadowMetrics>)>> = Mutex::new(Vec::new());
+
+/// returns the shared counters of a shadow name, registering it on first use
+#[must_use]
+pub fn shadow(name: &str) -> Arc<ShadowMetrics> {
+    let mut shadows = SHADOWS.lock().unwrap_or_else(PoisonError::into_inner);
+    if let Some((_, m)) = shadows.iter().find(|(n, _)| n == name) {
+        return Arc::clone(m);
+    }
+    let m = Arc::new(ShadowMetrics::default());
+    shadows.push((name.to_string(), Arc::clone(&m)));
+    m
+}
+
+/// escapes a prometheus label value
+fn escape_label(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+/// metric name, help and value of a per-backend counter
+type Counter = (&'static str, &'static str, fn(&ScanCounts) -> u64);
+
+const COUNTERS: [Counter; 3] = [
+    ("scans_total", "Number of scan calls.", |c| c.scans),
+    (
+        "matches_total",
+        "Number of scan calls that found a deny word.",
+        |c| c.matches,
+    ),
+    (
+        "scanned_bytes_total",
+        "String bytes scanned by scan calls.",
+        |c| c.bytes,
+    ),
+];
+
+/// renders all counters in the prometheus text exposition format
+#[must_use]
+pub fn render() -> String {
+    let registry = REGISTRY.lock().unwrap_or_else(PoisonError::into_inner);
+    let mut out = String::new();
+
+    for (metric, help, value) in COUNTERS {
+        let _ = writeln!(out, "# HELP deny_filter_{metric} {help}");
+        let _ = writeln!(out, "# TYPE deny_filter_{metric} counter");
+        for (name, m) in registry.iter() {
+            let _ = writeln!(
+                out,
+                "deny_filter_{metric}{{backend=\"{name}\"}} {}",
+                value(&m.counts())
+            );
+        }
+    }
+
+    out.push_str("# HELP deny_filter_pattern_matches_total Number of hits per deny word.\n");
+    out.push_str("# TYPE deny_filter_pattern_matches_total counter\n");
+    for (name, m) in registry.iter() {
+        let patterns = m.patterns.lock().unwrap_or_else(PoisonError::into_inner);
+        for (pattern, count) in patterns.iter() {
+            let _ = writeln!(
+                out,
+                "deny_filter_pattern_matches_total{{backend=\"{name}\",pattern=\"{}\"}} {count}",
+                escape_label(pattern)
+            );
+        }
+    }
+
+    out.push_str("# HELP deny_filter_scan_duration_seconds Latency of scan calls.\n");
+    out.push_str("# TYPE deny_filter_scan_duration_seconds histogram\n");
+    for (name, m) in registry.iter() {
+        let mut cumulative = 0;
+        for (le, bucket) in LATENCY_BUCKETS.iter().zip(&m.latency_buckets) {
+            cumulative += bucket.load(Relaxed);
+            let _ = writeln!(
+                out,
+                "deny_filter_scan_duration_seconds_bucket{{backend=\"{name}\",le=\"{le}\"}} {cumulative}"
+            );
+        }
+        let count = m.scans.load(Relaxed);
+        let sum = Duration::from_nanos(m.latency_nanos.load(Relaxed)).as_secs_f64();
+        let _ = writeln!(
+            out,
+            "deny_filter_scan_duration_seconds_bucket{{backend=\"{name}\",le=\"+Inf\"}} {count}"
+        );
+        let _ = writeln!(
+            out,
+            "deny_filter_scan_duration_seconds_sum{{backend=\"{name}\"}} {sum}"
+        );
+        let _ = writeln!(
+            out,
+            "deny_filter_scan_duration_seconds_count{{backend=\"{name}\"}} {count}"
+        );
+    }
+
+    out.push_str("# HELP deny_filter_score Risk scores of score calls.\n");
+    out.push_str("# TYPE deny_filter_score summary\n");
+    for (name, m) in registry.iter() {
+        let sum = f64::from_bits(m.score_sum.load(Relaxed));
+        let _ = writeln!(out, "deny_filter_score_sum{{backend=\"{name}\"}} {sum}");
+        let _ = writeln!(
+            out,
+            "deny_filter_score_count{{backend=\"{name}\"}} {}",
+            m.scores.load(Relaxed)
+        );
+    }
+    drop(registry);
+
+    render_shadows(&mut out);
+    out
+}
+
+/// shadow list counters of `render`
+fn render_shadows(out: &mut String) {
+    let shadows = SHADOWS.lock().unwrap_or_else(PoisonError::into_inner);
+    out.push_str("# HELP deny_filter_shadow_scans_total Number of scans also evaluated by a candidate list.\n");
+    out.push_str("# TYPE deny_filter_shadow_scans_total counter\n");
+    for (name, m) in shadows.iter() {
+        let _ = writeln!(
+            out,
+            "deny_filter_shadow_scans_total{{shadow=\"{}\"}} {}",
+            escape_label(name),
+            m.sampled()
+        );
+    }
+    out.push_str(
+        "# HELP deny_filter_shadow_diffs_total Sampled scans the candidate list would decide differently.\n",
+    );
+    out.push_str("# TYPE deny_filter_shadow_diffs_total counter\n");
+    for (name, m) in shadows.iter() {
+        let name = escape_label(name);
+        let _ = writeln!(
+            out,
+            "deny_filter_shadow_diffs_total{{shadow=\"{name}\",candidate=\"block\"}} {}",
+            m.blocks()
+        );
+        let _ = writeln!(
+            out,
+            "deny_filter_shadow_diffs_total{{shadow=\"{name}\",candidate=\"allow\"}} {}",
+            m.allows()
+        );
+    }
+}
+
+/// starts a background thread answering every http request with the metrics, each
+/// connection on its own thread so a stalled client holds up only itself until
+/// `IO_TIMEOUT` drops it; connections over `MAX_CONNECTIONS` are closed unanswered
+/// # Errors
+/// * address cannot be bound
+pub fn serve(addr: &str) -> std::io::Result<SocketAddr> {
+    let listener = TcpListener::bind(addr)?;
+    let local = listener.local_addr()?;
+    let open = Arc::new(AtomicUsize::new(0));
+    thread::spawn(move || {
+        for stream in listener.incoming().flatten() {
+            if open.fetch_add(1, Relaxed) >= MAX_CONNECTIONS {
+                open.fetch_sub(1, Relaxed);
+                continue;
+            }
+            let open = Arc::clone(&open);
+            thread::spawn(move || {
+                respond(stream);
+                open.fetch_sub(1, Relaxed);
+            });
+        }
+    });
+    Ok(local)
+}
+
+/// connections `serve` answers at once
+pub const MAX_CONNECTIONS: usize = 8;
+
+/// read and write timeout of a metrics connection
+const IO_TIMEOUT: Duration = Duration::from_secs(5);
+
+fn respond(mut stream: TcpStream) {
+    if stream.set_read_timeout(Some(IO_TIMEOUT)).is_err()
+        || stream.set_write_timeout(Some(IO_TIMEOUT)).is_err()
+    {
+        return;
+    }
+    // the request itself is irrelevant, every path serves the metrics
+    let mut buf = [0u8; 1024];
+    let _ = stream.read(&mut buf);
+    let body = render();
+    let _ = write!(
+        stream,
+        "HTTP/1.1 200 OK\r\nContent-Type: text/plain; version=0.0.4\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+        body.len()
+    );
+}
+
+/// prometheus text exposition of the scan metrics
+#[cfg(feature = "python")]
+#[gen_stub_pyfunction]
+#[pyfunction]
+#[must_use]
+pub fn render_prometheus() -> String {
+    render()
+}
+
+/// serves the scan metrics over http, returns the bound address
+/// # Errors
+/// * address cannot be bound
+#[cfg(feature = "python")]
+#[gen_stub_pyfunction]
+#[pyfunction]
+#[pyo3(signature = (address = "127.0.0.1:9464"))]
+pub fn serve_metrics(address: &str) -> PyResult<String> {
+    Ok(serve(address)?.to_string())
+}
+
+/// scan counters of this process: `scans`, `matches` and scanned `bytes` in total and
+/// per backend under `backends`
+/// # Errors
+/// * the dict cannot be built
+#[cfg(feature = "python")]
+#[gen_stub_pyfunction]
+#[pyfunction]
+#[pyo3(name = "stats")]
+pub fn py_stats(py: Python<'_>) -> PyResult<Bound<'_, pyo3::types::PyDict>> {
+    fn counts_dict(py: Python<'_>, c: ScanCounts) -> PyResult<Bound<'_, pyo3::types::PyDict>> {
+        let dict = pyo3::types::PyDict::new(py);
+        dict.set_item("scans", c.scans)?;
+        dict.set_item("matches", c.matches)?;
+        dict.set_item("bytes", c.bytes)?;
+        Ok(dict)
+    }
+    let counts = scan_counts();
+    let stats = counts_dict(py, counts.values().copied().sum())?;
+    let backends = pyo3::types::PyDict::new(py);
+    for (name, c) in counts {
+        backends.set_item(name, counts_dict(py, c)?)?;
+    }
+    stats.set_item("backends", backends)?;
+    Ok(stats)
+}
+
+/// sets the scan counters of every backend back to zero, for `stats` and the
+/// prometheus metrics alike
+#[cfg(feature = "python")]
+#[gen_stub_pyfunction]
+#[pyfunction]
+pub fn reset_stats() {
+    reset();
+}
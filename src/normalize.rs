@@ -0,0 +1,538 @@
+use caseless::Caseless;
+use rust_stemmers::Stemmer;
+use std::borrow::Cow;
+use std::iter;
+use std::ops::Range;
+use unicode_normalization::char::is_combining_mark;
+use unicode_normalization::{IsNormalized, UnicodeNormalization, is_nfc_quick, is_nfd_quick};
+use unicode_segmentation::UnicodeSegmentation;
+
+use crate::build_error::{BuildError, invalid};
+use crate::options::{CaseFold, Language, Normalization, Options, WordMatch};
+
+/// marks the start and end of the input for anchored deny words
+const ANCHOR_START: char = '\u{2}';
+const ANCHOR_END: char = '\u{3}';
+
+/// original byte span of every normalized byte, empty for inserted separators and marks
+pub type OffsetMap = Vec<(usize, usize)>;
+
+/// `s` in normalization `form`, with the original byte span of every byte when mapped;
+/// graphemes are normalized one by one, canonical composition never crosses them
+fn normal_form(s: &str, form: Normalization, mapped: bool) -> (Cow<'_, str>, Option<OffsetMap>) {
+    let quick = match form {
+        Normalization::None => IsNormalized::Yes,
+        _ if s.is_ascii() => IsNormalized::Yes,
+        Normalization::Nfc => is_nfc_quick(s.chars()),
+        Normalization::Nfd => is_nfd_quick(s.chars()),
+    };
+    if quick == IsNormalized::Yes {
+        return (Cow::Borrowed(s), None);
+    }
+    let mut text = String::with_capacity(s.len());
+    let mut map = mapped.then(|| Vec::with_capacity(s.len()));
+    for (i, g) in s.grapheme_indices(true) {
+        let len = text.len();
+        if form == Normalization::Nfc {
+            text.extend(g.nfc());
+        } else {
+            text.extend(g.nfd());
+        }
+        if let Some(map) = &mut map {
+            map.extend(iter::repeat_n((i, i + g.len()), text.len() - len));
+        }
+    }
+    (Cow::Owned(text), map)
+}
+
+/// spans of `map` into the normal form translated to the original text of length `len`
+pub(crate) fn remap(map: &mut OffsetMap, form_map: &OffsetMap, len: usize) {
+    for span in map {
+        *span = if span.0 < span.1 {
+            (form_map[span.0].0, form_map[span.1 - 1].1)
+        } else {
+            let at = form_map.get(span.0).map_or(len, |s| s.0);
+            (at, at)
+        };
+    }
+}
+
+/// pattern without word separators and anchor marks
+#[must_use]
+pub fn bare(pattern: &str) -> &str {
+    pattern.trim_matches([' ', ANCHOR_START, ANCHOR_END])
+}
+
+/// pattern with its graphemes in reverse order, word separators and anchor marks kept
+#[must_use]
+pub fn reversed(pattern: &str) -> String {
+    let word = bare(pattern);
+    let start = pattern.len()
+        - pattern
+            .trim_start_matches([' ', ANCHOR_START, ANCHOR_END])
+            .len();
+    let mut out = String::with_capacity(pattern.len());
+    out.push_str(&pattern[..start]);
+    out.extend(word.graphemes(true).rev());
+    out.push_str(&pattern[start + word.len()..]);
+    out
+}
+
+/// exact match candidates of `s` with their byte offsets: the trimmed value,
+/// every whitespace separated token and the token without surrounding punctuation
+#[must_use]
+pub fn exact_tokens(s: &str) -> Vec<(usize, &str)> {
+    let mut found = Vec::new();
+    let trimmed = s.trim();
+    if !trimmed.is_empty() {
+        found.push((s.len() - s.trim_start().len(), trimmed));
+    }
+    let mut start = None;
+    for (i, c) in s.char_indices().chain([(s.len(), ' ')]) {
+        if !c.is_whitespace() {
+            start.get_or_insert(i);
+            continue;
+        }
+        let Some(at) = start.take() else { continue };
+        let token = &s[at..i];
+        if token != trimmed {
+            found.push((at, token));
+        }
+        let bare = token.trim_matches(|c: char| c.is_ascii_punctuation());
+        if !bare.is_empty() && bare != token && bare != trimmed {
+            let offset = token.len()
+                - token
+                    .trim_start_matches(|c: char| c.is_ascii_punctuation())
+                    .len();
+            found.push((at + offset, bare));
+        }
+    }
+    found
+}
+
+/// case folded `s`, e.g. full folding maps `ß` to `ss` and final sigma to `σ`,
+/// so deny words match all their case variants
+#[must_use]
+pub fn fold(s: &str, mode: CaseFold) -> String {
+    if mode == CaseFold::None {
+        return s.to_string();
+    }
+    // turkish folds the ascii `I` to `ı`
+    if mode == CaseFold::Ascii || (s.is_ascii() && mode != CaseFold::Turkish) {
+        return s.to_ascii_lowercase();
+    }
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        push_folded(&mut out, c, mode);
+    }
+    out
+}
+
+fn push_folded(out: &mut String, c: char, mode: CaseFold) {
+    match (mode, c) {
+        (CaseFold::None, c) => out.push(c),
+        (CaseFold::Ascii, c) => out.push(c.to_ascii_lowercase()),
+        // the only common one to one folding that is no lowercase mapping
+        (CaseFold::Simple, 'ς') => out.push('σ'),
+        (CaseFold::Simple, c) => {
+            let mut lower = c.to_lowercase();
+            match (lower.next(), lower.next()) {
+                (Some(l), None) => out.push(l),
+                _ => out.push(c),
+            }
+        }
+        (CaseFold::Turkish, 'I') => out.push('ı'),
+        (CaseFold::Turkish, 'İ') => out.push('i'),
+        (CaseFold::Full | CaseFold::Turkish, c) => out.extend(iter::once(c).default_case_fold()),
+    }
+}
+
+/// original byte span of every byte of `fold(s, mode)`, folding is per char
+fn fold_map(s: &str, mode: CaseFold) -> OffsetMap {
+    let mut map = Vec::with_capacity(s.len());
+    let mut folded = String::new();
+    for (i, c) in s.char_indices() {
+        folded.clear();
+        push_folded(&mut folded, c, mode);
+        map.extend(iter::repeat_n((i, i + c.len_utf8()), folded.len()));
+    }
+    map
+}
+
+/// letter, digit or combining mark
+fn is_word(c: char) -> bool {
+    c.is_alphanumeric() || is_combining_mark(c)
+}
+
+/// punctuation or symbol, neither word char nor whitespace
+fn is_gap(c: char) -> bool {
+    !(is_word(c) || c.is_whitespace())
+}
+
+/// `chars` of `s` except the dropped ones, with the map entries of the kept chars
+fn without(
+    chars: &[(usize, char)],
+    dropped: &[bool],
+    map: Option<&OffsetMap>,
+) -> (String, Option<OffsetMap>) {
+    let mut text = String::with_capacity(chars.len());
+    let mut out_map = map.map(|_| Vec::with_capacity(chars.len()));
+    for (&(start, c), _) in chars.iter().zip(dropped).filter(|(_, dropped)| !**dropped) {
+        text.push(c);
+        if let (Some(out), Some(map)) = (&mut out_map, map) {
+            out.extend_from_slice(&map[start..start + c.len_utf8()]);
+        }
+    }
+    (text, out_map)
+}
+
+/// `s` without runs of punctuation and symbols between two word chars, e.g. `v-o.i*l_a`
+/// becomes `voila`
+fn drop_punctuation_gaps(s: &str, map: Option<&OffsetMap>) -> (String, Option<OffsetMap>) {
+    let chars: Vec<(usize, char)> = s.char_indices().collect();
+    let mut dropped = vec![false; chars.len()];
+    let mut i = 0;
+    while i < chars.len() {
+        let run = chars[i..].iter().take_while(|(_, c)| is_gap(*c)).count();
+        if run > 0
+            && i > 0
+            && is_word(chars[i - 1].1)
+            && chars.get(i + run).is_some_and(|(_, c)| is_word(*c))
+        {
+            dropped[i..i + run].fill(true);
+        }
+        i += run.max(1);
+    }
+    without(&chars, &dropped, map)
+}
+
+/// fewest single letters in a row `squeeze` joins, so the `a` and `I` of sentences stay
+const SQUEEZE_MIN_LETTERS: usize = 3;
+/// most whitespace chars between the joined letters
+const SQUEEZE_MAX_GAP: usize = 2;
+
+/// `s` with the whitespace between single letters dropped where at least
+/// `SQUEEZE_MIN_LETTERS` of them follow each other, e.g. `v o i l a` becomes `voila`
+fn squeeze(s: &str, map: Option<&OffsetMap>) -> (String, Option<OffsetMap>) {
+    let chars: Vec<(usize, char)> = s.char_indices().collect();
+    // char ranges of the words
+    let mut words = Vec::new();
+    let mut i = 0;
+    while i < chars.len() {
+        let len = chars[i..].iter().take_while(|(_, c)| is_word(*c)).count();
+        if len > 0 {
+            words.push(i..i + len);
+        }
+        i += len.max(1);
+    }
+    let letter = |w: &Range<usize>| {
+        chars[w.start + 1..w.end]
+            .iter()
+            .all(|(_, c)| is_combining_mark(*c))
+    };
+    let joined = |a: &Range<usize>, b: &Range<usize>| {
+        b.start - a.end <= SQUEEZE_MAX_GAP
+            && chars[a.end..b.start].iter().all(|(_, c)| c.is_whitespace())
+    };
+    let mut dropped = vec![false; chars.len()];
+    let mut first = 0;
+    while first < words.len() {
+        let mut last = first;
+        while letter(&words[last])
+            && words
+                .get(last + 1)
+                .is_some_and(|next| letter(next) && joined(&words[last], next))
+        {
+            last += 1;
+        }
+        if last + 1 - first >= SQUEEZE_MIN_LETTERS {
+            for pair in words[first..=last].windows(2) {
+                dropped[pair[0].end..pair[1].start].fill(true);
+            }
+        }
+        first = last + 1;
+    }
+    without(&chars, &dropped, map)
+}
+
+/// `s` with runs of 3 or more of the same char shortened to `keep` chars, which span the
+/// whole run in the original text when `map` is given
+fn collapse_repeats(s: &str, keep: usize, map: Option<&OffsetMap>) -> (String, Option<OffsetMap>) {
+    let mut text = String::with_capacity(s.len());
+    let mut out_map = map.map(|_| Vec::with_capacity(s.len()));
+    let mut chars = s.char_indices().peekable();
+    while let Some((start, c)) = chars.next() {
+        let mut run = 1;
+        while chars.next_if(|&(_, next)| next == c).is_some() {
+            run += 1;
+        }
+        let end = start + run * c.len_utf8();
+        let collapsed = run >= 3;
+        text.extend(iter::repeat_n(c, if collapsed { keep } else { run }));
+        if let (Some(out), Some(map)) = (&mut out_map, map) {
+            if collapsed {
+                let span = (map[start].0, map[end - 1].1);
+                out.extend(iter::repeat_n(span, keep * c.len_utf8()));
+            } else {
+                out.extend_from_slice(&map[start..end]);
+            }
+        }
+    }
+    (text, out_map)
+}
+
+/// normalized text with the offset map of the case folded input, when requested
+struct Builder {
+    text: String,
+    map: Option<OffsetMap>,
+    lower_map: OffsetMap,
+    len: usize,
+}
+
+impl Builder {
+    /// original position of byte `i` of the case folded input
+    fn position(&self, i: usize) -> usize {
+        self.lower_map.get(i).map_or(self.len, |span| span.0)
+    }
+
+    fn push_lower(&mut self, lower: &str, range: Range<usize>) {
+        self.text.push_str(&lower[range.clone()]);
+        if let Some(map) = &mut self.map {
+            map.extend_from_slice(&self.lower_map[range]);
+        }
+    }
+
+    fn push_inserted(&mut self, c: char, at: usize) {
+        self.text.push(c);
+        let position = self.position(at);
+        if let Some(map) = &mut self.map {
+            map.extend(iter::repeat_n((position, position), c.len_utf8()));
+        }
+    }
+
+    /// `s` replaces `range` of the case folded input, e.g. a stemmed word
+    fn push_replacement(&mut self, s: &str, range: Range<usize>) {
+        self.text.push_str(s);
+        if let Some(map) = &mut self.map {
+            let span = (
+                self.lower_map[range.start].0,
+                self.lower_map[range.end - 1].1,
+            );
+            map.extend(iter::repeat_n(span, s.len()));
+        }
+    }
+}
+
+/// text transformation applied the same way to deny words and scanned input
+#[derive(Clone, Debug, Default)]
+pub struct Normalizer {
+    kind: WordMatch,
+    stem: Option<Language>,
+    case_fold: CaseFold,
+    form: Normalization,
+    collapse_repeats: bool,
+    punctuation_gaps: bool,
+    squeeze: bool,
+}
+
+impl Normalizer {
+    /// normalizer of `kind` deny words with the stemming and folding of `options`
+    #[must_use]
+    pub fn new(kind: WordMatch, options: &Options) -> Self {
+        Self {
+            kind,
+            stem: options.stem,
+            case_fold: options.case_fold,
+            form: options.normalization,
+            collapse_repeats: options.collapse_repeats,
+            punctuation_gaps: options.punctuation_gaps,
+            squeeze: options.squeeze,
+        }
+    }
+
+    #[must_use]
+    pub fn kind(&self) -> WordMatch {
+        self.kind
+    }
+
+    /// names of the applied normalizations in order, e.g. `["nfc", "case_fold:full", "whole_word"]`
+    #[must_use]
+    pub fn steps(&self) -> Vec<String> {
+        let mut steps = Vec::new();
+        if self.form != Normalization::None {
+            steps.push(format!("{:?}", self.form).to_lowercase());
+        }
+        steps.push(format!("case_fold:{:?}", self.case_fold).to_lowercase());
+        if self.punctuation_gaps {
+            steps.push("punctuation_gaps".to_string());
+        }
+        if self.squeeze {
+            steps.push("squeeze".to_string());
+        }
+        if self.collapse_repeats {
+            steps.push("collapse_repeats".to_string());
+        }
+        match self.kind {
+            WordMatch::Substring if self.words_only() => steps.push("whole_word".to_string()),
+            WordMatch::Substring => {}
+            WordMatch::WholeWord => steps.push("whole_word".to_string()),
+            WordMatch::Anchored => steps.push("anchored".to_string()),
+        }
+        if let Some(language) = self.stem {
+            steps.push(format!("stem:{language:?}").to_lowercase());
+        }
+        steps
+    }
+
+    /// input is reduced to words separated by single spaces
+    #[must_use]
+    pub fn words_only(&self) -> bool {
+        self.kind == WordMatch::WholeWord || self.stem.is_some()
+    }
+
+    /// case folded input; in whole word mode every word is surrounded by single spaces,
+    /// so a plain substring match of a normalized pattern is a whole word match;
+    /// anchored input is wrapped in start/end marks only a whole value can match
+    #[must_use]
+    pub fn input(&self, s: &str) -> String {
+        self.build(s, false, 1).0
+    }
+
+    /// normalized forms of the input to search: `input`, and with `collapse_repeats` the
+    /// input with runs kept at two chars as well, so "freeeee" matches "free" like "baaaad"
+    /// matches "bad"
+    #[must_use]
+    pub fn inputs(&self, s: &str) -> Vec<String> {
+        let text = self.input(s);
+        if !self.collapse_repeats {
+            return vec![text];
+        }
+        let pairs = self.build(s, false, 2).0;
+        if pairs == text {
+            vec![text]
+        } else {
+            vec![text, pairs]
+        }
+    }
+
+    /// normalized input with the original byte span of every normalized byte
+    #[must_use]
+    pub fn input_mapped(&self, s: &str) -> (String, OffsetMap) {
+        let (text, map) = self.build(s, true, 1);
+        (text, map.unwrap_or_default())
+    }
+
+    /// `inputs` with the original byte span of every normalized byte
+    #[must_use]
+    pub fn inputs_mapped(&self, s: &str) -> Vec<(String, OffsetMap)> {
+        let mapped = self.input_mapped(s);
+        if !self.collapse_repeats {
+            return vec![mapped];
+        }
+        let (pairs, map) = self.build(s, true, 2);
+        if pairs == mapped.0 {
+            vec![mapped]
+        } else {
+            vec![mapped, (pairs, map.unwrap_or_default())]
+        }
+    }
+
+    /// original byte range of the normalized `start..end`, inserted separators and marks excluded
+    #[must_use]
+    pub fn original(map: &OffsetMap, start: usize, end: usize) -> Range<usize> {
+        let spans = &map[start..end];
+        let first = spans.iter().position(|(a, b)| a < b);
+        let last = spans.iter().rposition(|(a, b)| a < b);
+        if let (Some(first), Some(last)) = (first, last) {
+            return spans[first].0..spans[last].1;
+        }
+        // empty match or inserted chars only
+        let at = map.get(start).or(map.last()).map_or(0, |span| span.0);
+        at..at
+    }
+
+    /// normalized `s`, runs of `collapse_repeats` kept at `keep` chars
+    fn build(&self, s: &str, mapped: bool, keep: usize) -> (String, Option<OffsetMap>) {
+        let (normal, form_map) = normal_form(s, self.form, mapped);
+        let (text, mut map) = self.build_folded(&normal, mapped, keep);
+        if let (Some(map), Some(form_map)) = (&mut map, &form_map) {
+            remap(map, form_map, s.len());
+        }
+        (text, map)
+    }
+
+    /// `build` of input already in normal form
+    fn build_folded(&self, s: &str, mapped: bool, keep: usize) -> (String, Option<OffsetMap>) {
+        let mut lower = fold(s, self.case_fold);
+        let mut lower_map = mapped.then(|| fold_map(s, self.case_fold));
+        if self.punctuation_gaps {
+            (lower, lower_map) = drop_punctuation_gaps(&lower, lower_map.as_ref());
+        }
+        if self.squeeze {
+            (lower, lower_map) = squeeze(&lower, lower_map.as_ref());
+        }
+        if self.collapse_repeats {
+            (lower, lower_map) = collapse_repeats(&lower, keep, lower_map.as_ref());
+        }
+        if !self.words_only() && self.kind != WordMatch::Anchored {
+            return (lower, lower_map);
+        }
+        let mut out = Builder {
+            text: String::with_capacity(lower.len() + 2),
+            map: mapped.then(|| Vec::with_capacity(lower.len() + 2)),
+            lower_map: lower_map.unwrap_or_default(),
+            len: s.len(),
+        };
+        if self.kind == WordMatch::Anchored {
+            out.push_inserted(ANCHOR_START, 0);
+        }
+        if self.words_only() {
+            self.push_words(&mut out, &lower);
+        } else {
+            let trimmed = lower.trim();
+            let offset = lower.len() - lower.trim_start().len();
+            for (i, c) in trimmed.char_indices() {
+                if c != ANCHOR_START && c != ANCHOR_END {
+                    out.push_lower(&lower, offset + i..offset + i + c.len_utf8());
+                }
+            }
+        }
+        if self.kind == WordMatch::Anchored {
+            out.push_inserted(ANCHOR_END, lower.len());
+        }
+        (out.text, out.map)
+    }
+
+    fn push_words(&self, out: &mut Builder, lower: &str) {
+        let stemmer = self.stem.map(|language| Stemmer::create(language.into()));
+        out.push_inserted(' ', 0);
+        let mut word_start = None;
+        // a trailing separator ends the last word
+        for (i, c) in lower.char_indices().chain(iter::once((lower.len(), ' '))) {
+            // folding can produce combining marks, e.g. the dot of `İ`
+            if c.is_alphanumeric() || is_combining_mark(c) {
+                word_start.get_or_insert(i);
+                continue;
+            }
+            let Some(start) = word_start.take() else {
+                continue;
+            };
+            match &stemmer {
+                Some(stemmer) => out.push_replacement(&stemmer.stem(&lower[start..i]), start..i),
+                None => out.push_lower(lower, start..i),
+            }
+            out.push_inserted(' ', i);
+        }
+    }
+
+    /// deny word in the form matched against normalized input
+    /// # Errors
+    /// * whole word mode and the deny word has no word characters
+    pub fn pattern(&self, word: &str) -> Result<String, BuildError> {
+        let pattern = self.input(word);
+        if self.words_only() && bare(&pattern).is_empty() {
+            return Err(invalid(format!("{word:?} has no words to match")));
+        }
+        Ok(pattern)
+    }
+}
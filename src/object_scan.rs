@@ -0,0 +1,261 @@
+use futures::StreamExt;
+use object_store::path::Path;
+use object_store::{ObjectStore, ObjectStoreExt};
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
+#[cfg(feature = "python")]
+use pyo3::types::PyDict;
+#[cfg(feature = "python")]
+use pyo3_stub_gen::derive::{gen_stub_pyclass, gen_stub_pymethods};
+
+#[cfg(feature = "python")]
+use crate::backend::Backend;
+#[cfg(feature = "python")]
+use crate::build_error::invalid;
+use crate::hits::{self, Format, Hit};
+use crate::matcher::Matcher;
+#[cfg(feature = "python")]
+use crate::options::Options;
+
+/// hits of one object, an unreadable or malformed object keeps the hits found before the error
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ObjectReport {
+    pub path: String,
+    pub hits: Vec<Hit>,
+    pub error: Option<String>,
+}
+
+/// scans one object; text and ndjson are streamed line by line, json and msgpack
+/// documents are read whole
+/// # Errors
+/// * the object cannot be read
+/// * invalid json / msgpack, invalid ndjson line
+pub async fn scan_object(
+    store: &dyn ObjectStore,
+    path: &Path,
+    matcher: &dyn Matcher,
+    format: Format,
+) -> Result<Vec<Hit>, String> {
+    let mut report = ObjectReport {
+        path: path.to_string(),
+        hits: Vec::new(),
+        error: None,
+    };
+    stream_object(store, path, matcher, format, &mut report).await;
+    match report.error {
+        Some(e) => Err(e),
+        None => Ok(report.hits),
+    }
+}
+
+/// scans one object into `report`, stops at the first error
+async fn stream_object(
+    store: &dyn ObjectStore,
+    path: &Path,
+    matcher: &dyn Matcher,
+    format: Format,
+    report: &mut ObjectReport,
+) {
+    let mut chunks = match store.get(path).await {
+        Ok(object) => object.into_stream(),
+        Err(e) => {
+            report.error = Some(e.to_string());
+            return;
+        }
+    };
+    let mut pending: Vec<u8> = Vec::new();
+    let mut line = 0;
+    while let Some(chunk) = chunks.next().await {
+        let chunk = match chunk {
+            Ok(chunk) => chunk,
+            Err(e) => {
+                report.error = Some(e.to_string());
+                return;
+            }
+        };
+        pending.extend_from_slice(&chunk);
+        if matches!(format, Format::Json | Format::Msgpack) {
+            continue;
+        }
+        // complete lines only, the rest waits for the next chunk
+        let Some(last) = pending.iter().rposition(|&b| b == b'\n') else {
+            continue;
+        };
+        let rest = pending.split_off(last + 1);
+        if let Err(e) = scan_lines(matcher, &pending, format, &mut line, &mut report.hits) {
+            report.error = Some(e);
+            return;
+        }
+        pending = rest;
+    }
+    let result = match format {
+        Format::Json | Format::Msgpack => {
+            hits::collect(matcher, &pending, format).map(|mut found| {
+                report.hits.append(&mut found);
+            })
+        }
+        Format::Text | Format::Ndjson => {
+            scan_lines(matcher, &pending, format, &mut line, &mut report.hits)
+        }
+    };
+    report.error = result.err();
+}
+
+/// text lines of `data`, `line` counts lines across chunks
+fn scan_lines(
+    matcher: &dyn Matcher,
+    data: &[u8],
+    format: Format,
+    line: &mut usize,
+    hits: &mut Vec<Hit>,
+) -> Result<(), String> {
+    for text in String::from_utf8_lossy(data).lines() {
+        *line += 1;
+        hits::collect_line(matcher, *line, text, format, hits)?;
+    }
+    Ok(())
+}
+
+/// scans all objects below `prefix` (the whole store when None) in path order,
+/// `format` None picks each object's format by extension; a failing object is
+/// reported and the scan goes on
+/// # Errors
+/// * the objects cannot be listed
+pub async fn scan_prefix(
+    store: &dyn ObjectStore,
+    prefix: Option<&Path>,
+    matcher: &dyn Matcher,
+    format: Option<Format>,
+) -> Result<Vec<ObjectReport>, String> {
+    let mut paths: Vec<Path> = store
+        .list(prefix)
+        .map(|meta| meta.map(|meta| meta.location))
+        .collect::<Vec<_>>()
+        .await
+        .into_iter()
+        .collect::<Result<_, _>>()
+        .map_err(|e| e.to_string())?;
+    paths.sort();
+    let mut reports = Vec::with_capacity(paths.len());
+    for path in paths {
+        let format = format.unwrap_or_else(|| Format::from_path(path.as_ref()));
+        let mut report = ObjectReport {
+            path: path.to_string(),
+            hits: Vec::new(),
+            error: None,
+        };
+        stream_object(store, &path, matcher, format, &mut report).await;
+        reports.push(report);
+    }
+    Ok(reports)
+}
+
+/// `ObjectReport` as returned to python, hits are `(location, pattern)` pairs
+#[cfg(feature = "python")]
+#[gen_stub_pyclass]
+#[pyclass(
+    module = "deny_filter.matchers",
+    frozen,
+    name = "ObjectReport",
+    get_all
+)]
+pub struct PyObjectReport {
+    pub path: String,
+    pub hits: Vec<(String, String)>,
+    pub error: Option<String>,
+}
+
+#[cfg(feature = "python")]
+impl From<ObjectReport> for PyObjectReport {
+    fn from(report: ObjectReport) -> Self {
+        Self {
+            path: report.path,
+            hits: report
+                .hits
+                .into_iter()
+                .map(|hit| (hit.location, hit.pattern))
+                .collect(),
+            error: report.error,
+        }
+    }
+}
+
+/// scans objects of an S3, GCS or local store (`s3://bucket/prefix`, `gs://...`, `file:///...`)
+#[cfg(feature = "python")]
+#[gen_stub_pyclass]
+#[pyclass(module = "deny_filter.matchers", frozen)]
+pub struct ObjectScanner {
+    store: Box<dyn ObjectStore>,
+    root: Path,
+    matcher: Box<dyn Matcher + Send + Sync>,
+    format: Option<Format>,
+}
+
+#[cfg(feature = "python")]
+#[gen_stub_pymethods]
+#[pymethods]
+impl ObjectScanner {
+    /// scanner of the objects below `url` with a matcher of `backend`; `format` is text,
+    /// json, ndjson or msgpack, None picks it by extension; `store_options` are passed to
+    /// the store (credentials, region, endpoint), keyword options: see `Options`
+    /// # Errors
+    /// * invalid url or store options, unknown format or backend, invalid patterns or options
+    #[new]
+    #[pyo3(signature = (url, words, format = None, backend = "aho-corasick", store_options = None, **options))]
+    fn py_new(
+        url: &str,
+        words: Vec<String>,
+        format: Option<&str>,
+        backend: &str,
+        store_options: Option<std::collections::HashMap<String, String>>,
+        options: Option<&Bound<'_, PyDict>>,
+    ) -> PyResult<Self> {
+        let url = url::Url::parse(url).map_err(invalid)?;
+        let (store, root) = object_store::parse_url_opts(&url, store_options.unwrap_or_default())
+            .map_err(invalid)?;
+        let backend: Backend = backend
+            .parse()
+            .map_err(pyo3::exceptions::PyValueError::new_err)?;
+        Ok(Self {
+            store,
+            root,
+            matcher: backend.build_with(words, &Options::from_kwargs(options)?)?,
+            format: format
+                .map(str::parse)
+                .transpose()
+                .map_err(pyo3::exceptions::PyValueError::new_err)?,
+        })
+    }
+
+    /// reports of all objects below `prefix` (relative to the url), scanned without the GIL
+    /// # Errors
+    /// * the objects cannot be listed
+    #[pyo3(signature = (prefix = None))]
+    fn scan(&self, py: Python<'_>, prefix: Option<&str>) -> PyResult<Vec<PyObjectReport>> {
+        let root = match prefix {
+            Some(prefix) => self
+                .root
+                .parts()
+                .chain(Path::from(prefix).parts())
+                .collect(),
+            None => self.root.clone(),
+        };
+        let reports = py.detach(|| {
+            let runtime = tokio::runtime::Builder::new_current_thread()
+                .enable_all()
+                .build()
+                .map_err(|e| e.to_string())?;
+            runtime.block_on(scan_prefix(
+                self.store.as_ref(),
+                Some(&root),
+                self.matcher.as_ref(),
+                self.format,
+            ))
+        });
+        Ok(reports
+            .map_err(invalid)?
+            .into_iter()
+            .map(PyObjectReport::from)
+            .collect())
+    }
+}
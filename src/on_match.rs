@@ -0,0 +1,239 @@
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
+#[cfg(feature = "python")]
+use pyo3::types::{PyDict, PyList};
+#[cfg(feature = "python")]
+use std::fmt::Write as _;
+#[cfg(feature = "python")]
+use std::sync::Arc;
+
+#[cfg(feature = "python")]
+use crate::keys::KeyFilter;
+#[cfg(feature = "python")]
+use crate::limits::Budget;
+use crate::limits::LimitError;
+use crate::matcher::Matcher;
+use crate::metrics::Timer;
+use crate::traverse;
+
+/// the match deciding a scan: its pattern, the `$.key[0]` path of the string and the
+/// matched text
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MatchEvent {
+    pub pattern: String,
+    pub path: String,
+    pub snippet: String,
+}
+
+impl MatchEvent {
+    /// event of `text` at `path`, a string `matcher` matched
+    #[must_use]
+    pub fn new<M: Matcher + ?Sized>(matcher: &M, path: String, text: &str) -> Self {
+        let snippet = matcher
+            .find_all(text)
+            .first()
+            .map_or(text, |m| &text[m.start..m.end])
+            .to_string();
+        Self {
+            pattern: matcher.first_match(text).unwrap_or_default().to_string(),
+            path,
+            snippet,
+        }
+    }
+}
+
+/// `Matcher::scan_msgpack` with the deciding match instead of a bool
+/// # Errors
+/// * truncated or malformed msgpack
+/// * over a length limit with `Overflow::Raise`
+pub fn scan_msgpack<M: Matcher + ?Sized>(
+    matcher: &M,
+    buf: &[u8],
+) -> Result<Option<MatchEvent>, traverse::Error> {
+    let timer = Timer::start();
+    let mut budget = matcher.limits().budget();
+    let mut failed = None;
+    let mut event = None;
+    traverse::msgpack_limited(buf, matcher.limits(), &mut |path, s| {
+        match matcher.check_at(path, s, &mut budget) {
+            Ok(true) => event = Some(MatchEvent::new(matcher, traverse::format_path(path), s)),
+            Ok(false) => {}
+            Err(e) => failed = Some(e),
+        }
+        event.is_some() || failed.is_some()
+    })?;
+    if let Some(e) = failed {
+        return Err(e.into());
+    }
+    matcher
+        .metrics()
+        .record_scan(timer.elapsed(), event.is_some());
+    Ok(event)
+}
+
+/// scan of `(path, text)` pairs with the deciding match instead of a bool
+/// # Errors
+/// * over a length limit with `Overflow::Raise`
+pub fn scan_strings<M: Matcher + ?Sized>(
+    matcher: &M,
+    strings: &[(String, String)],
+) -> Result<Option<MatchEvent>, LimitError> {
+    let timer = Timer::start();
+    let mut budget = matcher.limits().budget();
+    let mut event = None;
+    for (path, text) in strings {
+        if matcher.check_within(text, &mut budget)? {
+            event = Some(MatchEvent::new(matcher, path.clone(), text));
+            break;
+        }
+    }
+    matcher
+        .metrics()
+        .record_scan(timer.elapsed(), event.is_some());
+    Ok(event)
+}
+
+/// strings of a python dict/list/str with their `$.key[0]` paths, in `scan_any` order;
+/// values of the keys `keys` skips are left out
+#[cfg(feature = "python")]
+pub(crate) fn strings(
+    value: &Bound<'_, PyAny>,
+    keys: &KeyFilter,
+    path: &mut String,
+    out: &mut Vec<(String, String)>,
+) {
+    let len = path.len();
+    if let Ok(s) = value.extract::<String>() {
+        out.push((path.clone(), s));
+    } else if let Ok(dict) = value.cast::<PyDict>() {
+        for (key, item) in dict {
+            if skipped(keys, &key) {
+                continue;
+            }
+            let _ = write!(path, ".{key}");
+            strings(&item, keys, path, out);
+            path.truncate(len);
+        }
+    } else if let Ok(list) = value.cast::<PyList>() {
+        for (i, item) in list.iter().enumerate() {
+            let _ = write!(path, "[{i}]");
+            strings(&item, keys, path, out);
+            path.truncate(len);
+        }
+    }
+}
+
+/// whether the value of a python dict key is not scanned
+#[cfg(feature = "python")]
+fn skipped(keys: &KeyFilter, key: &Bound<'_, PyAny>) -> bool {
+    key.extract::<&str>().is_ok_and(|key| keys.skips(key))
+}
+
+/// the first matching string of a python dict/list/str, in `scan_value` order
+#[cfg(feature = "python")]
+fn first_event<M: Matcher + ?Sized>(
+    matcher: &M,
+    value: &Bound<'_, PyAny>,
+    path: &mut String,
+    budget: &mut Budget,
+) -> Result<Option<MatchEvent>, LimitError> {
+    let len = path.len();
+    if let Ok(s) = value.extract::<&str>() {
+        return Ok(matcher
+            .check_within(s, budget)?
+            .then(|| MatchEvent::new(matcher, path.clone(), s)));
+    } else if let Ok(dict) = value.cast::<PyDict>() {
+        for (key, item) in dict {
+            if skipped(matcher.ignored_keys(), &key) {
+                continue;
+            }
+            let _ = write!(path, ".{key}");
+            let found = first_event(matcher, &item, path, budget);
+            path.truncate(len);
+            if !matches!(found, Ok(None)) {
+                return found;
+            }
+        }
+    } else if let Ok(list) = value.cast::<PyList>() {
+        for (i, item) in list.iter().enumerate() {
+            let _ = write!(path, "[{i}]");
+            let found = first_event(matcher, &item, path, budget);
+            path.truncate(len);
+            if !matches!(found, Ok(None)) {
+                return found;
+            }
+        }
+    }
+    Ok(None)
+}
+
+/// optional python callable of a matcher, called with `(pattern, path, snippet)` of the
+/// match deciding `scan_any`, `scan_any_async` and `scan_msgpack_async`
+#[cfg(feature = "python")]
+#[derive(Clone, Default)]
+pub struct OnMatch {
+    callback: Option<Arc<Py<PyAny>>>,
+}
+
+#[cfg(feature = "python")]
+impl OnMatch {
+    #[must_use]
+    pub fn get(&self, py: Python<'_>) -> Option<Py<PyAny>> {
+        self.callback
+            .as_ref()
+            .map(|callback| callback.clone_ref(py))
+    }
+
+    /// replaces the callback, None removes it
+    /// # Errors
+    /// * `callback` is not callable
+    pub fn set(&mut self, callback: Option<Bound<'_, PyAny>>) -> PyResult<()> {
+        if callback.as_ref().is_some_and(|c| !c.is_callable()) {
+            return Err(pyo3::exceptions::PyTypeError::new_err(
+                "on_match must be callable or None",
+            ));
+        }
+        self.callback = callback.map(|c| Arc::new(c.unbind()));
+        Ok(())
+    }
+
+    #[must_use]
+    pub fn is_set(&self) -> bool {
+        self.callback.is_some()
+    }
+
+    /// calls back with the event, errors of the callback are reported as unraisable and
+    /// leave the verdict alone
+    pub fn notify(&self, py: Python<'_>, event: &MatchEvent) {
+        let Some(callback) = &self.callback else {
+            return;
+        };
+        let args = (&event.pattern, &event.path, &event.snippet);
+        if let Err(err) = callback.call1(py, args) {
+            err.write_unraisable(py, Some(callback.bind(py)));
+        }
+    }
+
+    /// `Matcher::scan_any`, the deciding match goes to the callback
+    /// # Errors
+    /// * over a length limit with `Overflow::Raise`
+    pub fn scan_any<M: Matcher + ?Sized>(
+        &self,
+        matcher: &M,
+        value: &Bound<'_, PyAny>,
+    ) -> PyResult<bool> {
+        if !self.is_set() {
+            return matcher.scan_any(value);
+        }
+        let timer = Timer::start();
+        let mut budget = matcher.limits().budget();
+        let event = first_event(matcher, value, &mut String::from("$"), &mut budget)?;
+        matcher
+            .metrics()
+            .record_scan(timer.elapsed(), event.is_some());
+        if let Some(event) = &event {
+            self.notify(value.py(), event);
+        }
+        Ok(event.is_some())
+    }
+}
@@ -0,0 +1,289 @@
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
+#[cfg(feature = "python")]
+use pyo3::types::PyDict;
+#[cfg(feature = "python")]
+use pyo3_stub_gen::derive::{gen_stub_pyclass, gen_stub_pymethods};
+use rust_stemmers::Algorithm;
+use serde::Deserialize;
+use std::collections::BTreeMap;
+
+use crate::proximity::ProximityRule;
+
+/// stemming languages (snowball algorithms)
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Language {
+    Arabic,
+    Danish,
+    Dutch,
+    English,
+    French,
+    German,
+    Greek,
+    Hungarian,
+    Italian,
+    Norwegian,
+    Portuguese,
+    Romanian,
+    Russian,
+    Spanish,
+    Swedish,
+    Tamil,
+    Turkish,
+}
+
+impl From<Language> for Algorithm {
+    fn from(language: Language) -> Self {
+        match language {
+            Language::Arabic => Algorithm::Arabic,
+            Language::Danish => Algorithm::Danish,
+            Language::Dutch => Algorithm::Dutch,
+            Language::English => Algorithm::English,
+            Language::French => Algorithm::French,
+            Language::German => Algorithm::German,
+            Language::Greek => Algorithm::Greek,
+            Language::Hungarian => Algorithm::Hungarian,
+            Language::Italian => Algorithm::Italian,
+            Language::Norwegian => Algorithm::Norwegian,
+            Language::Portuguese => Algorithm::Portuguese,
+            Language::Romanian => Algorithm::Romanian,
+            Language::Russian => Algorithm::Russian,
+            Language::Spanish => Algorithm::Spanish,
+            Language::Swedish => Algorithm::Swedish,
+            Language::Tamil => Algorithm::Tamil,
+            Language::Turkish => Algorithm::Turkish,
+        }
+    }
+}
+
+/// how a deny word is matched
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WordMatch {
+    /// anywhere in the text
+    #[default]
+    Substring,
+    /// whole words of the text only
+    WholeWord,
+    /// the whole (trimmed) string value
+    Anchored,
+}
+
+impl WordMatch {
+    pub const ALL: [WordMatch; 3] = [
+        WordMatch::Substring,
+        WordMatch::WholeWord,
+        WordMatch::Anchored,
+    ];
+}
+
+/// which hit wins when deny words overlap at the same position
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MatchKind {
+    /// the word listed first
+    #[default]
+    LeftmostFirst,
+    /// the longest word, e.g. "free money" over "free"
+    LeftmostLongest,
+}
+
+/// case folding of deny words and input
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CaseFold {
+    /// text as given, matching is case sensitive
+    None,
+    /// ascii letters only, the fastest
+    Ascii,
+    /// one to one lowercase mappings, `ß` stays `ß`
+    Simple,
+    /// full unicode case folding, `ß` matches `ss`
+    #[default]
+    Full,
+    /// full folding with turkish dotted and dotless i: `I` to `ı`, `İ` to `i`
+    Turkish,
+}
+
+/// unicode normalization form of deny words and input, so canonically
+/// equivalent strings such as composed and decomposed "café" match
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Normalization {
+    /// text as given
+    None,
+    /// canonical composition
+    #[default]
+    Nfc,
+    /// canonical decomposition
+    Nfd,
+}
+
+/// what a scan does with a string or payload over its length limit
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Overflow {
+    /// the part within the limit is scanned, the rest is not
+    #[default]
+    ScanPrefix,
+    /// nothing over the limit is scanned, it passes (fail open)
+    Allow,
+    /// nothing over the limit is scanned, it counts as a match (fail closed)
+    Block,
+    /// the scan fails, python raises `ValueError`; scans that cannot fail block
+    Raise,
+}
+
+/// how the weights of the matched deny words make a score
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Aggregation {
+    /// the weights added up
+    #[default]
+    Sum,
+    /// the highest weight
+    Max,
+}
+
+/// which policy version a matcher was built from, recorded with its block decisions
+#[cfg_attr(feature = "python", gen_stub_pyclass)]
+#[cfg_attr(
+    feature = "python",
+    pyclass(module = "deny_filter.matchers", frozen, get_all, skip_from_py_object)
+)]
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ListMetadata {
+    pub version: Option<String>,
+    /// where the list came from, e.g. a file path or url
+    pub source: Option<String>,
+    /// when the list was published, e.g. ISO 8601
+    pub timestamp: Option<String>,
+}
+
+#[cfg(feature = "python")]
+#[gen_stub_pymethods]
+#[pymethods]
+impl ListMetadata {
+    fn __repr__(&self) -> String {
+        format!(
+            "ListMetadata(version={:?}, source={:?}, timestamp={:?})",
+            self.version, self.source, self.timestamp
+        )
+    }
+}
+
+/// matching options shared by all backends,
+/// python passes them as keyword arguments: `DenyList(words, whole_word=True)`
+#[derive(Clone, Debug, Default, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+#[allow(clippy::struct_excessive_bools)] // independent keyword arguments
+pub struct Options {
+    /// deny words only match whole words of the input
+    pub whole_word: bool,
+    /// stems patterns and input words, e.g. "hacking" matches "hack"; implies whole words
+    pub stem: Option<Language>,
+    /// co-occurrence rules matched in addition to the deny words
+    pub proximity: Vec<ProximityRule>,
+    /// deny word -> patterns that suppress its hits when overlapping, e.g. `{"shoot": ["photo shoot"]}`
+    pub exceptions: BTreeMap<String, Vec<String>>,
+    /// phrases suppressing the overlapping hits of every deny word, e.g. `["class action"]`
+    pub allow: Vec<String>,
+    /// regexes of the normalized input suppressing the overlapping hits of every deny word,
+    /// e.g. `["scunthorpe\\w*"]`
+    pub allow_patterns: Vec<String>,
+    /// deny word -> match kind overriding `whole_word`, e.g. `{"ass": "whole_word"}`
+    pub word_match: BTreeMap<String, WordMatch>,
+    /// `leftmost_first` or `leftmost_longest` for overlapping deny words
+    pub match_kind: MatchKind,
+    /// `ascii`, `simple`, `full` or `turkish` case folding
+    pub case_fold: CaseFold,
+    /// `nfc`, `nfd` or `none` unicode normalization
+    pub normalization: Normalization,
+    /// runs of 3 or more of the same char count as one or two, e.g. "baaaad" matches "bad"
+    /// and "freeeee" matches "free"
+    pub collapse_repeats: bool,
+    /// punctuation and symbols between letters are dropped, e.g. "v.o.i.l.a" matches "voila"
+    pub punctuation_gaps: bool,
+    /// whitespace between 3 or more single letters in a row is dropped, e.g. "v o i l a"
+    /// matches "voila"
+    pub squeeze: bool,
+    /// deny words also match spelled backwards, e.g. "aliov" matches "voila"
+    pub reversed: bool,
+    /// the ROT13 decoding of the input is scanned as well, e.g. "ibvyn" matches "voila"
+    pub rot13: bool,
+    /// char -> char replacements whose decoding of the input is scanned as well,
+    /// e.g. `{"0": "o", "1": "i"}`
+    pub substitutions: BTreeMap<char, char>,
+    /// hex tokens of the input are scanned decoded as well, e.g. "766f696c61" matches "voila"
+    pub hex: bool,
+    /// base32 tokens of the input are scanned decoded as well
+    pub base32: bool,
+    /// base64 tokens of the input are scanned decoded as well, e.g. "dm9pbGE=" matches "voila"
+    pub base64: bool,
+    /// JWT-shaped tokens of the input are scanned as the keys and string values of their
+    /// header and claims, unverified, e.g. a custom claim `{"note": "voila"}` matches "voila"
+    pub jwt: bool,
+    /// decoding layers nested at most, 2 when not set
+    pub decode_depth: Option<usize>,
+    /// map keys whose values are not scanned, e.g. `["image"]`
+    pub ignore_keys: Vec<String>,
+    /// regexes of map keys whose values are not scanned, e.g. `[".*_embedding$", ".*_b64$"]`
+    pub ignore_key_patterns: Vec<String>,
+    /// strings of more bytes than this are handled by `on_overflow`
+    pub max_string_len: Option<usize>,
+    /// scanned string bytes of one payload, e.g. a dict or msgpack document, over which
+    /// `on_overflow` applies
+    pub max_payload_len: Option<usize>,
+    /// msgpack documents of more bytes than this are handled by `on_overflow`
+    pub max_msgpack_len: Option<usize>,
+    /// msgpack containers nested deeper than this are handled by `on_overflow`
+    pub max_msgpack_depth: Option<usize>,
+    /// msgpack documents of more values than this are handled by `on_overflow`
+    pub max_msgpack_elements: Option<usize>,
+    /// values of a payload of any kind nested in more containers than this are handled by
+    /// `on_overflow`, e.g. dicts and lists, json and msgpack
+    pub max_depth: Option<usize>,
+    /// strings of a payload after this many are handled by `on_overflow`
+    pub max_strings: Option<usize>,
+    /// `scan_prefix`, `allow`, `block` or `raise` for input over the limits
+    pub on_overflow: Overflow,
+    /// verdicts of this many recently scanned strings are cached, so repeated payload
+    /// fields are scanned once; no cache when not set
+    pub cache_size: Option<usize>,
+    /// deny word -> weight of its matches in `score`, 1 when not given, e.g. `{"kill": 5.0}`
+    pub weights: BTreeMap<String, f64>,
+    /// `sum` or `max` of the weights of the distinct deny words matched
+    pub score_aggregation: Aggregation,
+    /// list version, not used for matching
+    pub version: Option<String>,
+    /// list source, not used for matching
+    pub source: Option<String>,
+    /// list publication time, not used for matching
+    pub timestamp: Option<String>,
+}
+
+impl Options {
+    #[must_use]
+    pub fn metadata(&self) -> ListMetadata {
+        ListMetadata {
+            version: self.version.clone(),
+            source: self.source.clone(),
+            timestamp: self.timestamp.clone(),
+        }
+    }
+
+    /// options from python keyword arguments
+    /// # Errors
+    /// * unknown option or invalid value
+    #[cfg(feature = "python")]
+    pub fn from_kwargs(kwargs: Option<&Bound<'_, PyDict>>) -> PyResult<Self> {
+        kwargs.map_or_else(
+            || Ok(Self::default()),
+            |kwargs| {
+                pythonize::depythonize(kwargs)
+                    .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))
+            },
+        )
+    }
+}
@@ -0,0 +1,223 @@
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
+#[cfg(feature = "python")]
+use pyo3::types::{PyDict, PyList};
+#[cfg(feature = "python")]
+use pyo3_stub_gen::derive::{gen_stub_pyclass, gen_stub_pymethods};
+use std::sync::mpsc::{self, Receiver, SyncSender, TrySendError};
+use std::sync::{Arc, Mutex, PoisonError};
+use std::thread::{self, JoinHandle};
+
+#[cfg(feature = "python")]
+use crate::backend::Backend;
+use crate::build_error::{BuildError, invalid};
+use crate::matcher::Matcher;
+#[cfg(feature = "python")]
+use crate::options::Options;
+
+type SharedMatcher = Arc<dyn Matcher + Send + Sync>;
+
+/// text to scan and where its result goes
+type Job = (String, mpsc::Sender<bool>);
+
+/// worker threads scanning with one matcher, fed through a bounded queue;
+/// submitting blocks while the queue is full, so callers cannot outrun the workers
+pub struct ScanPool {
+    sender: Option<SyncSender<Job>>,
+    workers: Vec<JoinHandle<()>>,
+}
+
+impl ScanPool {
+    /// starts `workers` threads behind a queue of `queue_size` pending scans
+    /// # Errors
+    /// * no workers or a zero queue size
+    pub fn new(
+        matcher: Box<dyn Matcher + Send + Sync>,
+        workers: usize,
+        queue_size: usize,
+    ) -> Result<Self, BuildError> {
+        if workers == 0 || queue_size == 0 {
+            return Err(invalid(
+                "scan pool needs at least one worker and queue slot",
+            ));
+        }
+        let (sender, receiver) = mpsc::sync_channel::<Job>(queue_size);
+        let receiver = Arc::new(Mutex::new(receiver));
+        let matcher: SharedMatcher = Arc::from(matcher);
+        let workers = (0..workers)
+            .map(|i| {
+                let receiver = Arc::clone(&receiver);
+                let matcher = Arc::clone(&matcher);
+                thread::Builder::new()
+                    .name(format!("deny-scan-{i}"))
+                    .spawn(move || Self::work(&*matcher, &receiver))
+                    .map_err(invalid)
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(Self {
+            sender: Some(sender),
+            workers,
+        })
+    }
+
+    /// runs jobs until the pool is dropped
+    fn work(matcher: &dyn Matcher, receiver: &Mutex<Receiver<Job>>) {
+        loop {
+            let job = receiver
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner)
+                .recv();
+            let Ok((text, result)) = job else { return };
+            // the caller may have stopped waiting
+            let _ = result.send(matcher.scan_str(&text));
+        }
+    }
+
+    fn sender(&self) -> &SyncSender<Job> {
+        self.sender.as_ref().expect("sender lives until drop")
+    }
+
+    /// queues a scan, blocks while the queue is full
+    #[must_use]
+    pub fn submit(&self, text: String) -> Receiver<bool> {
+        let (result, pending) = mpsc::channel();
+        // workers only stop on drop, the send cannot fail before
+        let _ = self.sender().send((text, result));
+        pending
+    }
+
+    /// queues a scan unless the queue is full
+    /// # Errors
+    /// * the queue is full, the text is handed back
+    pub fn try_submit(&self, text: String) -> Result<Receiver<bool>, String> {
+        let (result, pending) = mpsc::channel();
+        match self.sender().try_send((text, result)) {
+            Ok(()) => Ok(pending),
+            Err(TrySendError::Full((text, _)) | TrySendError::Disconnected((text, _))) => Err(text),
+        }
+    }
+
+    /// scans all texts on the workers, results in input order
+    #[must_use]
+    pub fn scan_many(&self, texts: Vec<String>) -> Vec<bool> {
+        let pending: Vec<Receiver<bool>> = texts.into_iter().map(|t| self.submit(t)).collect();
+        pending
+            .into_iter()
+            .map(|p| p.recv().unwrap_or(false))
+            .collect()
+    }
+
+    #[must_use]
+    pub fn workers(&self) -> usize {
+        self.workers.len()
+    }
+}
+
+impl Drop for ScanPool {
+    /// closes the queue and waits for the workers to finish queued scans
+    fn drop(&mut self) {
+        self.sender.take();
+        for worker in self.workers.drain(..) {
+            let _ = worker.join();
+        }
+    }
+}
+
+/// all string values of a python dict/list/str
+#[cfg(feature = "python")]
+fn strings(value: &Bound<'_, PyAny>, out: &mut Vec<String>) {
+    if let Ok(s) = value.extract::<String>() {
+        out.push(s);
+    } else if let Ok(dict) = value.cast::<PyDict>() {
+        for item in dict.values() {
+            strings(&item, out);
+        }
+    } else if let Ok(list) = value.cast::<PyList>() {
+        for item in list {
+            strings(&item, out);
+        }
+    }
+}
+
+/// scan submitted to a `PyScanPool`
+#[cfg(feature = "python")]
+#[gen_stub_pyclass]
+#[pyclass(module = "deny_filter.matchers", frozen)]
+pub struct PendingScan {
+    receiver: Mutex<Receiver<bool>>,
+}
+
+#[cfg(feature = "python")]
+#[gen_stub_pymethods]
+#[pymethods]
+impl PendingScan {
+    /// waits for the scan without holding the GIL
+    fn result(&self, py: Python<'_>) -> bool {
+        py.detach(|| {
+            self.receiver
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner)
+                .recv()
+                .unwrap_or(false)
+        })
+    }
+}
+
+/// python handle of a `ScanPool`, scans run off the GIL
+#[cfg(feature = "python")]
+#[gen_stub_pyclass]
+#[pyclass(module = "deny_filter.matchers", frozen, name = "ScanPool")]
+pub struct PyScanPool {
+    pool: ScanPool,
+}
+
+#[cfg(feature = "python")]
+#[gen_stub_pymethods]
+#[pymethods]
+impl PyScanPool {
+    /// pool scanning with a matcher of `backend`, keyword options: see `Options`
+    /// # Errors
+    /// * unknown backend, invalid patterns or options, no workers
+    #[new]
+    #[pyo3(signature = (words, workers = 4, queue_size = 64, backend = "aho-corasick", **options))]
+    fn py_new(
+        words: Vec<String>,
+        workers: usize,
+        queue_size: usize,
+        backend: &str,
+        options: Option<&Bound<'_, PyDict>>,
+    ) -> PyResult<Self> {
+        let backend: Backend = backend
+            .parse()
+            .map_err(pyo3::exceptions::PyValueError::new_err)?;
+        let matcher = backend.build_with(words, &Options::from_kwargs(options)?)?;
+        Ok(Self {
+            pool: ScanPool::new(matcher, workers, queue_size)?,
+        })
+    }
+
+    /// queues a scan, waits off the GIL while the queue is full
+    fn submit(&self, py: Python<'_>, text: String) -> PendingScan {
+        let receiver = py.detach(|| self.pool.submit(text));
+        PendingScan {
+            receiver: Mutex::new(receiver),
+        }
+    }
+
+    /// scans texts concurrently, results in input order
+    fn scan_many(&self, py: Python<'_>, texts: Vec<String>) -> Vec<bool> {
+        py.detach(|| self.pool.scan_many(texts))
+    }
+
+    /// scans every string of a dict/list/str concurrently
+    fn scan_any(&self, py: Python<'_>, value: &Bound<'_, PyAny>) -> bool {
+        let mut texts = Vec::new();
+        strings(value, &mut texts);
+        py.detach(|| self.pool.scan_many(texts).into_iter().any(|found| found))
+    }
+
+    #[getter]
+    fn workers(&self) -> usize {
+        self.pool.workers()
+    }
+}
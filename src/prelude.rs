@@ -0,0 +1,10 @@
+// the native api for rust services, without pyo3 with `default-features = false`
+pub use crate::backend::Backend;
+pub use crate::build_error::BuildError;
+pub use crate::deny_list::DenyList;
+pub use crate::limits::{LimitError, Limits};
+pub use crate::matcher::{Matcher, scan_serialize};
+pub use crate::matches::Match;
+pub use crate::options::Options;
+pub use crate::registry::{DenyListRegistry, SharedMatcher};
+pub use crate::traverse;
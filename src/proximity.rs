@@ -0,0 +1,140 @@
+use aho_corasick::{AhoCorasick, MatchKind};
+use serde::Deserialize;
+
+use crate::build_error::{BuildError, invalid};
+use crate::normalize::Normalizer;
+use crate::options::{Options, WordMatch};
+
+/// co-occurrence rule: `first` and `second` at most `within` words apart, in any order
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ProximityRule {
+    pub first: String,
+    pub second: String,
+    pub within: usize,
+    /// weight of the rule in scores, 1 when not set
+    #[serde(default)]
+    pub weight: Option<f64>,
+}
+
+impl ProximityRule {
+    /// `first NEAR/n second`, how matches and scores report the rule
+    #[must_use]
+    pub fn label(&self) -> String {
+        let (first, second) = (self.first.to_lowercase(), self.second.to_lowercase());
+        format!("{first} NEAR/{} {second}", self.within)
+    }
+}
+
+/// word span `[start, end)` of a term occurrence
+type Span = (usize, usize);
+
+/// words between two spans, 0 when they overlap
+fn gap(a: Span, b: Span) -> usize {
+    if a.1 <= b.0 {
+        b.0 - a.1
+    } else {
+        a.0.saturating_sub(b.1)
+    }
+}
+
+/// compiled proximity rules: one automaton finds all rule terms in the input,
+/// then word distances are checked per rule
+#[derive(Clone, Debug, Default)]
+pub struct Proximity {
+    ac: Option<AhoCorasick>,
+    normalizer: Normalizer,
+    /// term indexes and distance of each rule
+    rules: Vec<(usize, usize, usize)>,
+    labels: Vec<String>,
+}
+
+impl Proximity {
+    /// # Errors
+    /// * a rule term without word characters, automaton errors
+    pub fn new(options: &Options) -> Result<Self, BuildError> {
+        let rules = &options.proximity;
+        if rules.is_empty() {
+            return Ok(Self::default());
+        }
+        let normalizer = Normalizer::new(WordMatch::WholeWord, options);
+        let mut terms: Vec<String> = Vec::new();
+        let mut term_index = |word: &str| -> Result<usize, BuildError> {
+            let term = normalizer.pattern(word)?;
+            Ok(terms.iter().position(|t| *t == term).unwrap_or_else(|| {
+                terms.push(term);
+                terms.len() - 1
+            }))
+        };
+        let mut compiled = Vec::with_capacity(rules.len());
+        for rule in rules {
+            compiled.push((
+                term_index(&rule.first)?,
+                term_index(&rule.second)?,
+                rule.within,
+            ));
+        }
+        let ac = AhoCorasick::builder()
+            .match_kind(MatchKind::Standard)
+            .build(&terms)
+            .map_err(invalid)?;
+        Ok(Self {
+            ac: Some(ac),
+            normalizer,
+            rules: compiled,
+            labels: rules.iter().map(ProximityRule::label).collect(),
+        })
+    }
+
+    /// normalizations of the rule terms and the input
+    #[must_use]
+    pub fn steps(&self) -> Vec<String> {
+        self.normalizer.steps()
+    }
+
+    /// label (`first NEAR/n second`) of the first rule satisfied by the input
+    #[must_use]
+    pub fn first_match(&self, s: &str) -> Option<&str> {
+        self.matches(s).next()
+    }
+
+    /// labels of all rules satisfied by the input, in rule order
+    pub fn matches(&self, s: &str) -> impl Iterator<Item = &str> {
+        let inputs: Vec<Vec<Vec<Span>>> = match self.ac {
+            Some(_) => self
+                .normalizer
+                .inputs(s)
+                .iter()
+                .map(|text| self.spans(text))
+                .collect(),
+            None => Vec::new(),
+        };
+        self.rules
+            .iter()
+            .zip(&self.labels)
+            .filter(move |((a, b, within), _)| {
+                inputs.iter().any(|spans| {
+                    spans[*a]
+                        .iter()
+                        .any(|x| spans[*b].iter().any(|y| x != y && gap(*x, *y) <= *within))
+                })
+            })
+            .map(|(_, label)| label.as_str())
+    }
+
+    /// word spans of each rule term in a normalized input
+    fn spans(&self, text: &str) -> Vec<Vec<Span>> {
+        let Some(ac) = &self.ac else {
+            return Vec::new();
+        };
+        // normalized text is " w0 w1 ... ", a term starting at the i-th space starts at word i
+        let spaces: Vec<usize> = text.match_indices(' ').map(|(i, _)| i).collect();
+        let mut spans: Vec<Vec<Span>> = vec![Vec::new(); ac.patterns_len()];
+        for m in ac.find_overlapping_iter(text) {
+            let start = spaces.partition_point(|&i| i < m.start());
+            let end = spaces.partition_point(|&i| i < m.end() - 1);
+            spans[m.pattern().as_usize()].push((start, end));
+        }
+        spans
+    }
+}
@@ -0,0 +1,235 @@
+use pyo3::exceptions::PyNotImplementedError;
+use pyo3::prelude::*;
+use pyo3_stub_gen::derive::{gen_stub_pyclass, gen_stub_pymethods};
+use std::sync::Arc;
+
+use crate::build_error::BuildError;
+use crate::explain::Explanation;
+use crate::limits::Limits;
+use crate::matcher::Matcher;
+use crate::matches::Match;
+use crate::metrics::{self, BackendMetrics};
+use crate::options::ListMetadata;
+use crate::report::BuildReport;
+use crate::stats::Stats;
+
+/// matcher implemented in python: an object with `is_match(text) -> bool` and optionally
+/// `find_all(text)`, returning `Match` objects or `(word, char_start, char_end)` tuples;
+/// python errors are reported as unraisable and count as a match, so a broken matcher
+/// blocks instead of letting text through
+pub struct PyMatcher {
+    object: Py<PyAny>,
+    /// class name of the object, the pattern of hits without `find_all`
+    label: String,
+    metadata: ListMetadata,
+    report: BuildReport,
+    metrics: Arc<BackendMetrics>,
+}
+
+impl PyMatcher {
+    /// wraps a python matcher
+    /// # Errors
+    /// * the object has no callable `is_match`
+    pub fn new(object: &Bound<'_, PyAny>) -> PyResult<Self> {
+        if !object.getattr("is_match").is_ok_and(|m| m.is_callable()) {
+            return Err(pyo3::exceptions::PyTypeError::new_err(
+                "a matcher needs an is_match(text) method",
+            ));
+        }
+        Ok(Self {
+            object: object.clone().unbind(),
+            label: object.get_type().name()?.to_string(),
+            metadata: ListMetadata::default(),
+            report: BuildReport::default(),
+            metrics: metrics::backend("python"),
+        })
+    }
+
+    /// the python error as unraisable, the caller treats the text as matching
+    fn report_error(&self, py: Python<'_>, err: &PyErr) {
+        err.clone_ref(py)
+            .write_unraisable(py, Some(self.object.bind(py)));
+    }
+
+    fn call_find_all(&self, py: Python<'_>, s: &str) -> PyResult<Vec<Match>> {
+        let object = self.object.bind(py);
+        if !object.hasattr("find_all")? {
+            return Ok(if self.call_is_match(py, s)? {
+                vec![whole_text(&self.label, s)]
+            } else {
+                Vec::new()
+            });
+        }
+        let mut found = Vec::new();
+        let mut chars: Option<Vec<usize>> = None;
+        for item in object.call_method1("find_all", (s,))?.try_iter()? {
+            let item = item?;
+            if let Ok(m) = item.cast::<Match>() {
+                found.push(m.get().clone());
+                continue;
+            }
+            let (word, char_start, char_end): (String, usize, usize) = item.extract()?;
+            let bytes = chars.get_or_insert_with(|| {
+                s.char_indices()
+                    .map(|(i, _)| i)
+                    .chain(std::iter::once(s.len()))
+                    .collect()
+            });
+            let byte = |c: usize| {
+                bytes.get(c).copied().ok_or_else(|| {
+                    pyo3::exceptions::PyValueError::new_err(format!(
+                        "match offset {c} is past the end of the text"
+                    ))
+                })
+            };
+            found.push(Match {
+                word,
+                start: byte(char_start)?,
+                end: byte(char_end)?,
+                char_start,
+                char_end,
+            });
+        }
+        Ok(found)
+    }
+
+    fn call_is_match(&self, py: Python<'_>, s: &str) -> PyResult<bool> {
+        self.object
+            .bind(py)
+            .call_method1("is_match", (s,))?
+            .is_truthy()
+    }
+}
+
+/// one match covering all of `text`
+fn whole_text(label: &str, text: &str) -> Match {
+    Match {
+        word: label.to_string(),
+        start: 0,
+        end: text.len(),
+        char_start: 0,
+        char_end: text.chars().count(),
+    }
+}
+
+impl Matcher for PyMatcher {
+    fn is_match(&self, s: &str) -> bool {
+        Python::attach(|py| {
+            self.call_is_match(py, s).unwrap_or_else(|err| {
+                self.report_error(py, &err);
+                true
+            })
+        })
+    }
+
+    /// the class name, python matchers have no pattern names to borrow
+    fn first_match(&self, s: &str) -> Option<&str> {
+        self.is_match(s).then_some(self.label.as_str())
+    }
+
+    fn find_all(&self, s: &str) -> Vec<Match> {
+        Python::attach(|py| {
+            self.call_find_all(py, s).unwrap_or_else(|err| {
+                self.report_error(py, &err);
+                vec![whole_text(&self.label, s)]
+            })
+        })
+    }
+
+    /// python matchers report no overlaps, same as `find_all`
+    fn find_overlapping(&self, s: &str) -> Result<Vec<Match>, BuildError> {
+        Ok(self.find_all(s))
+    }
+
+    fn explain(&self, s: &str) -> Option<Explanation> {
+        let hit = self.find_all(s).into_iter().next()?;
+        Some(Explanation {
+            word: hit.word.clone(),
+            pattern: hit.word.clone(),
+            normalized: s.get(hit.start..hit.end).unwrap_or_default().to_string(),
+            hit: Some(hit),
+            steps: vec!["python".to_string()],
+            path: None,
+        })
+    }
+
+    fn metadata(&self) -> &ListMetadata {
+        &self.metadata
+    }
+
+    fn build_report(&self) -> &BuildReport {
+        &self.report
+    }
+
+    fn stats(&self) -> Stats {
+        Stats::default()
+    }
+
+    #[cfg(feature = "dot")]
+    fn to_dot(&self) -> String {
+        "digraph deny {\n}\n".to_string()
+    }
+
+    fn metrics(&self) -> &BackendMetrics {
+        &self.metrics
+    }
+}
+
+/// base class of python matchers, subclasses implement `is_match` and may override
+/// `find_all`; instances are accepted wherever a matcher is, e.g. `DenyListRegistry.register`
+#[gen_stub_pyclass]
+#[pyclass(module = "deny_filter.matchers", frozen, subclass, name = "Matcher")]
+pub struct MatcherBase;
+
+#[gen_stub_pymethods]
+#[pymethods]
+impl MatcherBase {
+    #[new]
+    fn new() -> Self {
+        Self
+    }
+
+    /// whether `text` has a deny word
+    /// # Errors
+    /// * `NotImplementedError` unless overridden
+    #[allow(clippy::unused_self)] // abstract, subclasses use both
+    fn is_match(&self, text: &str) -> PyResult<bool> {
+        let _ = text;
+        Err(PyNotImplementedError::new_err(
+            "Matcher subclasses implement is_match",
+        ))
+    }
+
+    /// one match covering a matching text, override to report words and offsets
+    /// # Errors
+    /// * errors of `is_match`
+    fn find_all(slf: PyRef<'_, Self>, text: &str) -> PyResult<Vec<Match>> {
+        let py = slf.py();
+        let slf = slf.into_pyobject(py)?;
+        let found = slf.call_method1("is_match", (text,))?.is_truthy()?;
+        let label = slf.get_type().name()?.to_string();
+        Ok(if found {
+            vec![whole_text(&label, text)]
+        } else {
+            Vec::new()
+        })
+    }
+
+    /// `is_match` of a str
+    /// # Errors
+    /// * errors of `is_match`
+    fn scan_str(slf: PyRef<'_, Self>, txt: &str) -> PyResult<bool> {
+        let py = slf.py();
+        let slf = slf.into_pyobject(py)?;
+        slf.call_method1("is_match", (txt,))?.is_truthy()
+    }
+
+    /// `is_match` of every string of a dict/list/str
+    /// # Errors
+    /// * errors of `is_match` are reported as unraisable and count as a match
+    fn scan_any(slf: PyRef<'_, Self>, value: &Bound<'_, PyAny>) -> PyResult<bool> {
+        let py = slf.py();
+        let slf = slf.into_pyobject(py)?;
+        Ok(PyMatcher::new(slf.as_any())?.scan_value(value, &mut Limits::NONE.budget())?)
+    }
+}
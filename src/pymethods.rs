@@ -0,0 +1,353 @@
+/// `#[pymethods]` of a deny list class: the methods given, then the verdict cache, redaction,
+/// payload, file and callback methods every backend shares, so they are written once; the
+/// class needs an `on_match` field and the pyo3 prelude in scope
+macro_rules! deny_list_pymethods {
+    (impl $class:ident { $($methods:tt)* }) => {
+        #[cfg(feature = "python")]
+        #[pyo3_stub_gen::derive::gen_stub_pymethods]
+        #[pyo3::pymethods]
+        impl $class {
+            $($methods)*
+
+            /// hits, misses and size of the `cache_size` verdict cache, None without it
+            fn cache_info(&self) -> Option<$crate::cache::CacheInfo> {
+                $crate::matcher::Matcher::result_cache(self).map($crate::cache::ResultCache::info)
+            }
+
+            /// deep copy of a dict/list/str with the matches of every string masked, other
+            /// values kept as they are, e.g. for a `modified_payload`; limits apply as in
+            /// `scan_any`
+            /// # Errors
+            /// * a dict item cannot be copied
+            /// * over a limit with `on_overflow="raise"`
+            #[pyo3(signature = (value, mask = "*"))]
+            fn redact_any<'py>(
+                &self,
+                value: &Bound<'py, PyAny>,
+                mask: &str,
+            ) -> PyResult<Bound<'py, PyAny>> {
+                $crate::redact::py_redact_any(self, value, mask)
+            }
+
+            /// msgpack document re-encoded with the matches of every string value masked, e.g.
+            /// to forward a sanitized payload instead of dropping it
+            /// # Errors
+            /// * truncated or malformed msgpack
+            #[pyo3(signature = (data, mask = "*"))]
+            fn redact_msgpack<'py>(
+                &self,
+                py: Python<'py>,
+                data: &Bound<'py, pyo3::types::PyBytes>,
+                mask: &str,
+            ) -> PyResult<Bound<'py, pyo3::types::PyBytes>> {
+                let redacted =
+                    $crate::matcher::Matcher::redact_msgpack(self, data.as_bytes(), mask)?;
+                Ok(pyo3::types::PyBytes::new(py, &redacted))
+            }
+
+            /// json document with the matches of every string value masked; with
+            /// `preserve_order` key order and formatting are kept, otherwise it is written back
+            /// compact with sorted keys
+            /// # Errors
+            /// * invalid json
+            #[pyo3(signature = (data, mask = "*", preserve_order = false))]
+            fn redact_json<'py>(
+                &self,
+                py: Python<'py>,
+                data: &Bound<'py, pyo3::types::PyBytes>,
+                mask: &str,
+                preserve_order: bool,
+            ) -> PyResult<Bound<'py, pyo3::types::PyBytes>> {
+                let redacted =
+                    $crate::matcher::Matcher::redact_json(
+                        self,
+                        data.as_bytes(),
+                        mask,
+                        preserve_order,
+                    )
+                    .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))?;
+                Ok(pyo3::types::PyBytes::new(py, &redacted))
+            }
+
+            /// boolean mask of the rows of an arrow record batch or table with a deny word in any
+            /// of `columns` (default: all string columns), or of the values of a string array
+            /// # Errors
+            /// * not arrow data, a missing or non string column
+            #[cfg(feature = "arrow")]
+            #[pyo3(signature = (batch, columns = None))]
+            #[allow(clippy::needless_pass_by_value)] // python passes owned lists
+            fn scan_arrow<'py>(
+                &self,
+                batch: &Bound<'py, PyAny>,
+                columns: Option<Vec<String>>,
+            ) -> PyResult<Bound<'py, PyAny>> {
+                $crate::arrow::py_scan_arrow(self, batch, columns.as_deref())
+            }
+            /// polars boolean series, true where the string series has a deny word
+            /// # Errors
+            /// * not a polars string series
+            #[cfg(feature = "arrow")]
+            fn scan_series<'py>(&self, series: &Bound<'py, PyAny>) -> PyResult<Bound<'py, PyAny>> {
+                $crate::arrow::py_scan_series(self, series)
+            }
+            /// async iterator of verdicts over an async iterator of str/bytes chunks, each verdict
+            /// lists the matches completed by a chunk, words split across chunks are found
+            /// # Errors
+            /// * `chunks` is not an async iterable
+            fn scan_stream(
+                slf: PyRef<'_, Self>,
+                chunks: &Bound<'_, PyAny>,
+            ) -> PyResult<$crate::stream::StreamScan> {
+                $crate::stream::py_scan_stream(slf, chunks)
+            }
+            /// iterator of the `Match`es of `text` found a chunk at a time as the iteration goes,
+            /// for inputs too large to collect all matches of at once
+            fn iter_matches(
+                slf: PyRef<'_, Self>,
+                text: &Bound<'_, pyo3::types::PyString>,
+            ) -> $crate::stream::MatchIter {
+                $crate::stream::py_iter_matches(slf, text)
+            }
+            /// awaitable `scan_any`: the strings are copied, then scanned on a background thread
+            /// without the GIL while the event loop keeps running
+            /// # Errors
+            /// * no running event loop
+            fn scan_any_async<'py>(
+                slf: PyRef<'py, Self>,
+                value: &Bound<'py, PyAny>,
+            ) -> PyResult<Bound<'py, PyAny>> {
+                $crate::scan_async::py_scan_any_async(slf.on_match.clone(), slf, value)
+            }
+            /// awaitable verdict of the string values of a msgpack document, scanned on a
+            /// background thread without the GIL; malformed msgpack raises `ValueError` on await.
+            /// With `decompress` gzip and zlib documents are decompressed first, up to `max_size`
+            /// bytes (64 MiB by default)
+            /// # Errors
+            /// * no running event loop
+            #[pyo3(signature = (data, decompress = false, max_size = None))]
+            fn scan_msgpack_async<'py>(
+                slf: PyRef<'py, Self>,
+                data: &Bound<'py, pyo3::types::PyBytes>,
+                decompress: bool,
+                max_size: Option<usize>,
+            ) -> PyResult<Bound<'py, PyAny>> {
+                let limit = decompress
+                    .then(|| max_size.unwrap_or($crate::decompress::DEFAULT_MAX_DECOMPRESSED));
+                $crate::scan_async::py_scan_msgpack_async(
+                    slf.on_match.clone(),
+                    slf,
+                    data.as_bytes(),
+                    limit,
+                )
+            }
+            /// verdicts of a batch of msgpack documents, e.g. the messages of one consumer poll, in
+            /// order, scanned on `workers` threads (one per cpu) without the GIL
+            /// # Errors
+            /// * a truncated or malformed document, `ValueError` naming its index
+            #[pyo3(signature = (buffers, workers = None))]
+            #[allow(clippy::needless_pass_by_value)] // python passes owned lists
+            fn scan_msgpack_many(
+                &self,
+                py: Python<'_>,
+                buffers: Vec<Bound<'_, pyo3::types::PyBytes>>,
+                workers: Option<usize>,
+            ) -> PyResult<Vec<bool>> {
+                $crate::batch::py_scan_msgpack_many(py, self, &buffers, workers)
+            }
+            /// role and index of the first message of a chat completion message list with a
+            /// deny word in its text content, None when all are clean
+            /// # Errors
+            /// * messages that are not json-like python values
+            fn scan_chat_messages(
+                &self,
+                messages: &Bound<'_, PyAny>,
+            ) -> PyResult<Option<$crate::chat::ChatHit>> {
+                $crate::chat::py_scan_chat_messages(self, messages)
+            }
+            /// `scan_chat_messages` of an anthropic messages list: text, `tool_use` input,
+            /// `tool_result` content and text document blocks are scanned
+            /// # Errors
+            /// * messages that are not json-like python values
+            fn scan_anthropic_messages(
+                &self,
+                messages: &Bound<'_, PyAny>,
+            ) -> PyResult<Option<$crate::chat::ChatHit>> {
+                $crate::chat::py_scan_anthropic_messages(self, messages)
+            }
+            /// verdict of an MCP JSON-RPC message (bytes, str or the decoded dict/list): tool and
+            /// prompt arguments, resource contents, tool results and sampling messages are scanned
+            /// # Errors
+            /// * invalid json
+            fn scan_mcp_request(
+                &self,
+                message: &Bound<'_, PyAny>,
+            ) -> PyResult<$crate::mcp::McpVerdict> {
+                $crate::mcp::py_scan_mcp_request(self, message)
+            }
+            /// first http header with a deny word in a comma separated element of its value or in
+            /// the base64 decoded text of one, e.g. `Authorization: Basic ...`; any object with
+            /// `items()` of str pairs
+            /// # Errors
+            /// * no `items()` or items that are not str pairs
+            fn scan_headers(
+                &self,
+                headers: &Bound<'_, PyAny>,
+            ) -> PyResult<Option<$crate::headers::HeaderHit>> {
+                $crate::headers::py_scan_headers(self, headers)
+            }
+            /// first part of a url with a deny word: scheme, userinfo, host, path segments, query
+            /// keys and values and fragment, each percent decoded, `+` as space in the query
+            fn scan_url(&self, url: &str) -> Option<$crate::urls::UrlHit> {
+                $crate::urls::scan_url(self, url)
+            }
+            /// whether sql text has a deny word as written or with its `/* */` and `--` comments
+            /// stripped and its whitespace collapsed, e.g. `SEL/**/ECT voila`
+            fn scan_sql(&self, text: &str) -> bool {
+                $crate::sql::scan_sql(self, text)
+            }
+            /// header name or body content type of the first part of an RFC 822 / MIME email
+            /// with a deny word: subject and address headers, `text/plain` and `text/html` bodies
+            /// (html reduced to its text) after transfer decoding, None when clean
+            fn scan_email(
+                &self,
+                data: &Bound<'_, pyo3::types::PyBytes>,
+            ) -> Option<$crate::email::EmailHit> {
+                $crate::email::scan_email(self, data.as_bytes())
+            }
+            /// whether a `logging.LogRecord` or its `__dict__` has a deny word in its formatted
+            /// message, its exception and stack texts or an `extra` field
+            /// # Errors
+            /// * a record that is no dict and has no `__dict__`
+            /// * over a length limit with `on_overflow="raise"`
+            fn scan_log_record(&self, record: &Bound<'_, PyAny>) -> PyResult<bool> {
+                $crate::logs::py_scan_log_record(self, record)
+            }
+            /// `logging.Filter` of this deny list, `logger.addFilter(deny_list.log_filter())`:
+            /// records with a deny word are dropped, or logged with `replacement` as their message
+            #[pyo3(signature = (replacement = None))]
+            fn log_filter(
+                slf: &Bound<'_, Self>,
+                replacement: Option<String>,
+            ) -> $crate::logs::LogFilter {
+                $crate::logs::LogFilter::new(slf.clone().into_any().unbind(), replacement)
+            }
+            /// whether a string the `JSONPath` `selectors` select in `payload` has a deny word;
+            /// `payload` is a dict/list/str or the bytes of a msgpack document, e.g.
+            /// `scan_selected(body, ["$.messages[*].content", "$.tool.args.*"])`
+            /// # Errors
+            /// * invalid selector, malformed msgpack
+            /// * over a limit with `on_overflow="raise"`
+            #[allow(clippy::needless_pass_by_value)] // python passes owned lists
+            fn scan_selected(
+                &self,
+                payload: &Bound<'_, PyAny>,
+                selectors: Vec<String>,
+            ) -> PyResult<bool> {
+                $crate::select::py_scan_selected(self, payload, &selectors)
+            }
+            /// risk score of a dict/list/str or the bytes of a msgpack document: the weights of
+            /// the distinct deny words matched, summed or their maximum per `score_aggregation`
+            /// # Errors
+            /// * malformed msgpack
+            #[pyo3(name = "score")]
+            fn py_score(&self, payload: &Bound<'_, PyAny>) -> PyResult<f64> {
+                $crate::score::py_score(self, payload)
+            }
+            /// hits of one file as text, json, ndjson or msgpack, None picks the format by
+            /// extension; read errors are in the report
+            /// # Errors
+            /// * unknown format
+            #[pyo3(signature = (path, format = None))]
+            fn scan_file(
+                &self,
+                py: Python<'_>,
+                path: std::path::PathBuf,
+                format: Option<&str>,
+            ) -> PyResult<$crate::files::PyFileReport> {
+                $crate::files::py_scan_file(py, self, path, format)
+            }
+            /// reports of the files below `path` matching `glob` (e.g. `*.log` or
+            /// `logs/**/*.json`), in path order, scanned on `workers` threads (one per cpu)
+            /// without the GIL
+            /// # Errors
+            /// * unknown format, a directory cannot be read
+            #[pyo3(signature = (path, glob = None, format = None, workers = None))]
+            fn scan_dir(
+                &self,
+                py: Python<'_>,
+                path: std::path::PathBuf,
+                glob: Option<&str>,
+                format: Option<&str>,
+                workers: Option<usize>,
+            ) -> PyResult<Vec<$crate::files::PyFileReport>> {
+                $crate::files::py_scan_dir(py, self, path, glob, format, workers)
+            }
+            /// every deny word occurrence of a text file of any size, mapped into memory and
+            /// scanned `window` bytes (1 MiB) at a time without the GIL; offsets count from the
+            /// file start
+            /// # Errors
+            /// * the file cannot be opened or mapped
+            #[pyo3(signature = (path, window = None))]
+            fn scan_mmap(
+                &self,
+                py: Python<'_>,
+                path: std::path::PathBuf,
+                window: Option<usize>,
+            ) -> PyResult<Vec<$crate::matches::Match>> {
+                $crate::files::py_scan_mmap(py, self, path, window)
+            }
+            /// lazy iterator of `(line_number, word, (start, end))` of a file path or bytes, the
+            /// offsets in the line; lines are read as the iteration goes
+            /// # Errors
+            /// * the file cannot be opened
+            fn scan_lines(
+                slf: PyRef<'_, Self>,
+                source: &Bound<'_, PyAny>,
+            ) -> PyResult<$crate::lines::LineScan> {
+                $crate::lines::py_scan_lines(slf, source)
+            }
+            /// callable run with `(pattern, path, snippet)` of the match deciding `scan_any`,
+            /// `scan_any_async` and `scan_msgpack_async`, None when unset
+            #[getter]
+            fn on_match(&self, py: Python<'_>) -> Option<Py<PyAny>> {
+                self.on_match.get(py)
+            }
+            /// # Errors
+            /// * not callable or None
+            #[setter]
+            fn set_on_match(&mut self, callback: Option<Bound<'_, PyAny>>) -> PyResult<()> {
+                self.on_match.set(callback)
+            }
+            /// # Errors
+            /// * over a length limit with `on_overflow="raise"`
+            pub fn scan_str(&self, txt: &str) -> PyResult<bool> {
+                Ok($crate::matcher::Matcher::try_scan_str(self, txt)?)
+            }
+            /// # Errors
+            /// * over a length limit with `on_overflow="raise"`
+            pub fn scan(&self, args: &Bound<'_, pyo3::types::PyDict>) -> PyResult<bool> {
+                $crate::matcher::Matcher::scan(self, args)
+            }
+            /// scans dict,str,list
+            /// # Errors
+            /// * over a length limit with `on_overflow="raise"`
+            pub fn scan_any(&self, value: &Bound<'_, PyAny>) -> PyResult<bool> {
+                self.on_match.scan_any(self, value)
+            }
+            /// every deny word of a dict/list/str with the top level key it is under, walked as
+            /// `scan_any` walks it (nested values, ignored keys, limits), so the list is empty
+            /// exactly when `scan_any` is false; a proximity rule is listed by its label and a
+            /// limit blocking with `on_overflow="block"` with no word
+            /// # Errors
+            /// * over a length limit with `on_overflow="raise"`
+            fn violations(
+                &self,
+                value: &Bound<'_, PyAny>,
+            ) -> PyResult<Vec<$crate::violations::Violation>> {
+                $crate::violations::py_violations(self, value)
+            }
+        }
+    };
+}
+
+pub(crate) use deny_list_pymethods;
@@ -2,18 +2,218 @@ pub use crate::deny_list::DenyList;
 pub use crate::deny_list_rs::DenyListRs;
 use pyo3_stub_gen::define_stub_info_gatherer;
 
+use crate::analyze::{Analysis, py_analyze};
+use crate::bench::{BackendBench, py_benchmark};
+use crate::cache::CacheInfo;
+use crate::chat::ChatHit;
+use crate::deny_list_auto::DenyListAuto;
 use crate::deny_list_daac::DenyListDaac;
+use crate::deny_list_fst::DenyListFst;
+use crate::deny_list_hashed::DenyListHashed;
+use crate::deny_list_hybrid::DenyListHybrid;
+use crate::deny_list_packed::DenyListPacked;
+use crate::deny_list_tokens::DenyListTokens;
+use crate::email::EmailHit;
+use crate::evaluate::Evaluation;
+use crate::explain::Explanation;
+use crate::files::PyFileReport;
+use crate::headers::HeaderHit;
+use crate::incremental::PyIncrementalDenyList;
+use crate::logs::LogFilter;
+use crate::matches::Match;
+use crate::mcp::McpVerdict;
+use crate::metrics::{render_prometheus, serve_metrics};
+use crate::options::ListMetadata;
+use crate::pool::{PendingScan, PyScanPool};
+use crate::py_matcher::MatcherBase;
+use crate::registry::{PyDenyListRegistry, RegisteredList};
+use crate::report::BuildReport;
+use crate::shadow::PyShadowDenyList;
+use crate::signed::SignedLoader;
+use crate::stats::Stats;
+use crate::urls::UrlHit;
+use crate::violations::Violation;
+use pyo3::ffi;
+use pyo3::panic::PanicException;
 use pyo3::prelude::*;
+use pyo3::types::PyString;
+use std::cell::UnsafeCell;
+use std::ffi::{c_int, c_void};
+use std::panic::{self, AssertUnwindSafe};
+use std::ptr;
 
-#[pymodule]
-/// python module compose
+/// `PyModuleDef` of the package, with multi-phase init so each interpreter importing it
+/// gets its own module object
+struct ModuleDef(UnsafeCell<ffi::PyModuleDef>);
+
+// SAFETY: only python reads it, in `PyModuleDef_Init` and the imports
+unsafe impl Sync for ModuleDef {}
+
+struct Slots([ffi::PyModuleDef_Slot; SLOTS_LEN]);
+
+// SAFETY: the slots are never written
+unsafe impl Sync for Slots {}
+
+#[cfg(Py_3_12)]
+const SLOTS_LEN: usize = 3;
+#[cfg(not(Py_3_12))]
+const SLOTS_LEN: usize = 2;
+
+/// `PyO3` creates each class type object once per process, so an interpreter with its own
+/// GIL must not import the package: `Py_mod_multiple_interpreters` makes that an
+/// `ImportError`, while interpreters sharing the main GIL import it as before
+static SLOTS: Slots = Slots([
+    ffi::PyModuleDef_Slot {
+        slot: ffi::Py_mod_exec,
+        value: exec as *mut c_void,
+    },
+    #[cfg(Py_3_12)]
+    ffi::PyModuleDef_Slot {
+        slot: ffi::Py_mod_multiple_interpreters,
+        value: ffi::Py_MOD_MULTIPLE_INTERPRETERS_NOT_SUPPORTED,
+    },
+    ffi::PyModuleDef_Slot {
+        slot: 0,
+        value: ptr::null_mut(),
+    },
+]);
+
+static MODULE: ModuleDef = ModuleDef(UnsafeCell::new(ffi::PyModuleDef {
+    m_base: ffi::PyModuleDef_HEAD_INIT,
+    m_name: c"deny_filter".as_ptr(),
+    m_doc: c"deny word matching of strings and payloads".as_ptr(),
+    m_size: 0,
+    m_methods: ptr::null_mut(),
+    m_slots: ptr::addr_of!(SLOTS.0)
+        .cast::<ffi::PyModuleDef_Slot>()
+        .cast_mut(),
+    m_traverse: None,
+    m_clear: None,
+    m_free: None,
+}));
+
+/// entry point of `import deny_filter`
+/// # Safety
+/// called by the python import machinery, with the GIL held
+#[allow(non_snake_case)]
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn PyInit_deny_filter() -> *mut ffi::PyObject {
+    // SAFETY: `MODULE` is a valid, static module definition
+    unsafe { ffi::PyModuleDef_Init(MODULE.0.get()) }
+}
+
+/// `Py_mod_exec` slot: fills the module an interpreter created from `MODULE`
+unsafe extern "C" fn exec(module: *mut ffi::PyObject) -> c_int {
+    // SAFETY: python runs the exec slot with the GIL of the importing interpreter held,
+    // which `Python::attach` would not find through `PyGILState` in a subinterpreter
+    let py = unsafe { Python::assume_attached() };
+    // SAFETY: python passes the module object being initialized
+    let module = unsafe { Bound::from_borrowed_ptr(py, module) };
+    let filled = panic::catch_unwind(AssertUnwindSafe(|| deny_filter(module.cast()?)))
+        .unwrap_or_else(|_| Err(PanicException::new_err("deny_filter init panicked")));
+    match filled {
+        Ok(()) => 0,
+        Err(e) => {
+            e.restore(py);
+            -1
+        }
+    }
+}
+
+/// python module compose: the classes in the `matchers` and `plugin` submodules, each
+/// registered once and re-exported by the package, the functions at the top level
 /// # Errors
 /// * methods not found
 pub fn deny_filter(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    add_submodule(m, "matchers", matchers)?;
+    add_submodule(m, "plugin", plugin)?;
+    m.add_function(wrap_pyfunction!(render_prometheus, m)?)?;
+    m.add_function(wrap_pyfunction!(serve_metrics, m)?)?;
+    m.add_function(wrap_pyfunction!(crate::metrics::py_stats, m)?)?;
+    m.add_function(wrap_pyfunction!(crate::metrics::reset_stats, m)?)?;
+    m.add_function(wrap_pyfunction!(py_analyze, m)?)?;
+    m.add_function(wrap_pyfunction!(py_benchmark, m)?)?;
+    #[cfg(feature = "tracing")]
+    m.add_function(wrap_pyfunction!(crate::trace::enable_tracing, m)?)?;
+    Ok(())
+}
+
+/// `deny_filter.matchers`: the deny lists, their matches, reports and scanners
+fn matchers(m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_class::<DenyList>()?;
     m.add_class::<DenyListRs>()?;
     m.add_class::<DenyListDaac>()?;
+    m.add_class::<DenyListPacked>()?;
+    m.add_class::<DenyListAuto>()?;
+    m.add_class::<DenyListTokens>()?;
+    m.add_class::<DenyListHashed>()?;
+    m.add_class::<DenyListHybrid>()?;
+    m.add_class::<DenyListFst>()?;
+    m.add_class::<MatcherBase>()?;
+    m.add_class::<Match>()?;
+    m.add_class::<Explanation>()?;
+    m.add_class::<Evaluation>()?;
+    m.add_class::<ListMetadata>()?;
+    m.add_class::<BuildReport>()?;
+    m.add_class::<Analysis>()?;
+    m.add_class::<BackendBench>()?;
+    m.add_class::<Stats>()?;
+    m.add_class::<CacheInfo>()?;
+    #[cfg(feature = "arrow")]
+    m.add_class::<crate::arrow::BooleanMask>()?;
+    #[cfg(feature = "object-store")]
+    m.add_class::<crate::object_scan::ObjectScanner>()?;
+    #[cfg(feature = "object-store")]
+    m.add_class::<crate::object_scan::PyObjectReport>()?;
+    m.add_class::<PyScanPool>()?;
+    m.add_class::<PendingScan>()?;
+    m.add_class::<crate::stream::StreamScan>()?;
+    m.add_class::<crate::stream::NextVerdict>()?;
+    m.add_class::<crate::stream::MatchIter>()?;
+    m.add_class::<crate::lines::LineScan>()?;
     Ok(())
 }
 
+/// `deny_filter.plugin`: verdicts of the gateway hook payloads and the lifecycle of the
+/// lists a plugin serves
+fn plugin(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_class::<Violation>()?;
+    m.add_class::<ChatHit>()?;
+    m.add_class::<McpVerdict>()?;
+    m.add_class::<HeaderHit>()?;
+    m.add_class::<UrlHit>()?;
+    m.add_class::<EmailHit>()?;
+    m.add_class::<LogFilter>()?;
+    m.add_class::<PyFileReport>()?;
+    m.add_class::<SignedLoader>()?;
+    m.add_class::<PyDenyListRegistry>()?;
+    m.add_class::<RegisteredList>()?;
+    m.add_class::<PyShadowDenyList>()?;
+    m.add_class::<PyIncrementalDenyList>()?;
+    Ok(())
+}
+
+/// `<package>.<name>` filled by `add`, importable through `sys.modules` and with its
+/// classes re-exported by the package, so `from deny_filter import DenyList` still works
+fn add_submodule<'py>(
+    m: &Bound<'py, PyModule>,
+    name: &str,
+    add: fn(&Bound<'py, PyModule>) -> PyResult<()>,
+) -> PyResult<()> {
+    let py = m.py();
+    let full_name = format!("{}.{name}", m.name()?);
+    let submodule = PyModule::new(py, &full_name)?;
+    add(&submodule)?;
+    for item in submodule.index()? {
+        let item = item.cast_into::<PyString>()?;
+        m.add(&item, submodule.getattr(&item)?)?;
+    }
+    m.add(name, &submodule)?;
+    py.import("sys")?
+        .getattr("modules")?
+        .set_item(full_name, submodule)
+}
+
 define_stub_info_gatherer!(stub_info);
+pyo3_stub_gen::reexport_module_members!("deny_filter", "deny_filter.matchers");
+pyo3_stub_gen::reexport_module_members!("deny_filter", "deny_filter.plugin");
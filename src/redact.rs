@@ -0,0 +1,337 @@
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
+#[cfg(feature = "python")]
+use pyo3::types::{PyDict, PyList, PyString};
+use serde::de::IgnoredAny;
+use serde_json::Value;
+#[cfg(feature = "python")]
+use std::collections::HashMap;
+use std::ops::Range;
+use unicode_segmentation::UnicodeSegmentation;
+
+#[cfg(feature = "python")]
+use crate::limits::{Budget, Guarded};
+use crate::matcher::Matcher;
+use crate::matches::Match;
+use crate::traverse::{self, Segment};
+
+/// grapheme cluster boundaries of a text, so spans never split
+/// emoji zwj sequences or combining marks
+pub struct Graphemes {
+    /// start offset of every grapheme and the text length
+    bounds: Vec<usize>,
+}
+
+impl Graphemes {
+    #[must_use]
+    pub fn new(text: &str) -> Self {
+        let mut bounds: Vec<usize> = text.grapheme_indices(true).map(|(i, _)| i).collect();
+        bounds.push(text.len());
+        Self { bounds }
+    }
+
+    /// byte range widened to whole graphemes
+    #[must_use]
+    pub fn snap(&self, start: usize, end: usize) -> Range<usize> {
+        let first = self.bounds.partition_point(|&b| b <= start) - 1;
+        let last = self.bounds.partition_point(|&b| b < end);
+        self.bounds[first]..self.bounds[last.min(self.bounds.len() - 1)]
+    }
+
+    /// number of graphemes in a snapped range
+    #[must_use]
+    pub fn count(&self, range: &Range<usize>) -> usize {
+        self.bounds.partition_point(|&b| b < range.end)
+            - self.bounds.partition_point(|&b| b < range.start)
+    }
+}
+
+/// match spans snapped to graphemes, overlapping and touching spans merged
+fn spans(graphemes: &Graphemes, matches: &[Match]) -> Vec<Range<usize>> {
+    let mut spans: Vec<Range<usize>> = matches
+        .iter()
+        .map(|m| graphemes.snap(m.start, m.end))
+        .collect();
+    spans.sort_by_key(|r| r.start);
+    let mut merged: Vec<Range<usize>> = Vec::with_capacity(spans.len());
+    for span in spans {
+        match merged.last_mut() {
+            Some(last) if span.start <= last.end => last.end = last.end.max(span.end),
+            _ => merged.push(span),
+        }
+    }
+    merged
+}
+
+/// replaces every grapheme of the matches with `mask`
+#[must_use]
+pub fn redact(text: &str, matches: &[Match], mask: &str) -> String {
+    let graphemes = Graphemes::new(text);
+    let mut out = String::with_capacity(text.len());
+    let mut at = 0;
+    for span in spans(&graphemes, matches) {
+        out.push_str(&text[at..span.start]);
+        out.push_str(&mask.repeat(graphemes.count(&span)));
+        at = span.end;
+    }
+    out.push_str(&text[at..]);
+    out
+}
+
+/// wraps the matches in `open` and `close` markers
+#[must_use]
+pub fn highlight(text: &str, matches: &[Match], open: &str, close: &str) -> String {
+    let graphemes = Graphemes::new(text);
+    let mut out = String::with_capacity(text.len());
+    let mut at = 0;
+    for span in spans(&graphemes, matches) {
+        out.push_str(&text[at..span.start]);
+        out.push_str(open);
+        out.push_str(&text[span.clone()]);
+        out.push_str(close);
+        at = span.end;
+    }
+    out.push_str(&text[at..]);
+    out
+}
+
+/// msgpack document re-encoded with the matches of every string value masked, keys,
+/// other values and the values of ignored keys are copied byte for byte
+/// # Errors
+/// * truncated or malformed msgpack
+/// * over the depth or element count limit
+pub fn redact_msgpack<M: Matcher + ?Sized>(
+    matcher: &M,
+    buf: &[u8],
+    mask: &str,
+) -> Result<Vec<u8>, traverse::Error> {
+    let mut edits = Vec::new();
+    traverse::msgpack_spans(buf, matcher.limits(), &mut |path, s, span| {
+        if !matcher.ignored_keys().skips_path(path) {
+            let found = matcher.find_all(s);
+            if !found.is_empty() {
+                edits.push((span, redact(s, &found, mask)));
+            }
+        }
+        false
+    })?;
+    let mut out = Vec::with_capacity(buf.len());
+    let mut at = 0;
+    for (span, text) in edits {
+        out.extend_from_slice(&buf[at..span.start]);
+        // writing to a vec cannot fail
+        let _ = rmp::encode::write_str(&mut out, &text);
+        at = span.end;
+    }
+    out.extend_from_slice(&buf[at..]);
+    Ok(out)
+}
+
+/// json document with the matches of every string value masked; with `preserve_order` only
+/// the masked strings are rewritten and key order, whitespace and number formats are kept,
+/// otherwise the document is written back compact with its keys sorted
+/// # Errors
+/// * invalid json
+pub fn redact_json<M: Matcher + ?Sized>(
+    matcher: &M,
+    buf: &[u8],
+    mask: &str,
+    preserve_order: bool,
+) -> Result<Vec<u8>, serde_json::Error> {
+    if preserve_order {
+        serde_json::from_slice::<IgnoredAny>(buf)?;
+        return redact_json_text(matcher, buf, mask);
+    }
+    let mut value: Value = serde_json::from_slice(buf)?;
+    redact_value(matcher, &mut value, mask);
+    serde_json::to_vec(&value)
+}
+
+fn redact_value<M: Matcher + ?Sized>(matcher: &M, value: &mut Value, mask: &str) {
+    match value {
+        Value::String(s) => {
+            let found = matcher.find_all(s);
+            if !found.is_empty() {
+                *s = redact(s, &found, mask);
+            }
+        }
+        Value::Array(items) => {
+            for item in items {
+                redact_value(matcher, item, mask);
+            }
+        }
+        Value::Object(map) => {
+            for (key, item) in map {
+                if !matcher.ignored_keys().skips(key) {
+                    redact_value(matcher, item, mask);
+                }
+            }
+        }
+        Value::Null | Value::Bool(_) | Value::Number(_) => {}
+    }
+}
+
+/// an open container of a json text
+enum Frame {
+    /// the key of the current member, None before it is read
+    Object(Option<String>),
+    Array(usize),
+}
+
+/// rewrites the masked string values of a valid json text, the other bytes are copied
+fn redact_json_text<M: Matcher + ?Sized>(
+    matcher: &M,
+    buf: &[u8],
+    mask: &str,
+) -> Result<Vec<u8>, serde_json::Error> {
+    let mut out = Vec::with_capacity(buf.len());
+    let mut frames: Vec<Frame> = Vec::new();
+    let mut at = 0;
+    let mut i = 0;
+    while i < buf.len() {
+        match buf[i] {
+            b'{' => frames.push(Frame::Object(None)),
+            b'[' => frames.push(Frame::Array(0)),
+            b'}' | b']' => {
+                frames.pop();
+            }
+            b',' => match frames.last_mut() {
+                Some(Frame::Object(key)) => *key = None,
+                Some(Frame::Array(index)) => *index += 1,
+                None => {}
+            },
+            b'"' => {
+                let start = i;
+                i += 1;
+                while buf[i] != b'"' {
+                    i += if buf[i] == b'\\' { 2 } else { 1 };
+                }
+                let text: String = serde_json::from_slice(&buf[start..=i])?;
+                if let Some(Frame::Object(key @ None)) = frames.last_mut() {
+                    *key = Some(text);
+                } else {
+                    let path: Vec<Segment<'_>> = frames
+                        .iter()
+                        .map(|frame| match frame {
+                            Frame::Object(key) => Segment::Key(key.as_deref().unwrap_or("")),
+                            Frame::Array(index) => Segment::Index(*index),
+                        })
+                        .collect();
+                    if !matcher.ignored_keys().skips_path(&path) {
+                        let found = matcher.find_all(&text);
+                        if !found.is_empty() {
+                            out.extend_from_slice(&buf[at..start]);
+                            serde_json::to_writer(&mut out, &redact(&text, &found, mask))?;
+                            at = i + 1;
+                        }
+                    }
+                }
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+    out.extend_from_slice(&buf[at..]);
+    Ok(out)
+}
+
+/// copy of a python dict/list/str with the matches of every string masked, in the
+/// structures `scan_any` walks and within its limits; keys, other values and the values
+/// of ignored keys are kept as they are, unchanged strings are not copied. A string cut
+/// at a limit is masked in the part a scan reads, a string or container over a limit is
+/// masked whole or emptied with `on_overflow="block"` and kept with `allow`. A dict or
+/// list met again, e.g. a list holding itself, maps to the same copy
+/// # Errors
+/// * a new dict item cannot be set, e.g. an unhashable key of a dict subclass
+/// * over a limit with `on_overflow="raise"`
+#[cfg(feature = "python")]
+pub fn py_redact_any<'py, M: Matcher + ?Sized>(
+    matcher: &M,
+    value: &Bound<'py, PyAny>,
+    mask: &str,
+) -> PyResult<Bound<'py, PyAny>> {
+    PyRedactor {
+        matcher,
+        mask,
+        budget: matcher.limits().budget(),
+        copies: HashMap::new(),
+    }
+    .value(value)
+}
+
+/// walk of `py_redact_any`
+#[cfg(feature = "python")]
+struct PyRedactor<'m, 'py, M: ?Sized> {
+    matcher: &'m M,
+    mask: &'m str,
+    budget: Budget,
+    /// copies of the dicts and lists visited so far by the id of the original
+    copies: HashMap<usize, Bound<'py, PyAny>>,
+}
+
+#[cfg(feature = "python")]
+impl<'py, M: Matcher + ?Sized> PyRedactor<'_, 'py, M> {
+    fn value(&mut self, value: &Bound<'py, PyAny>) -> PyResult<Bound<'py, PyAny>> {
+        let py = value.py();
+        if let Ok(s) = value.cast::<PyString>() {
+            return self.string(s);
+        }
+        let empty = if value.is_instance_of::<PyDict>() {
+            PyDict::new(py).into_any()
+        } else if value.is_instance_of::<PyList>() {
+            PyList::empty(py).into_any()
+        } else {
+            return Ok(value.clone());
+        };
+        let id = value.as_ptr() as usize;
+        if !self.budget.visit(id) {
+            return Ok(self.copies[&id].clone());
+        }
+        let verdict = self.budget.enter()?;
+        let copy = if verdict == Some(false) {
+            value.clone()
+        } else {
+            empty
+        };
+        // known before its items, so an item holding the container gets the copy
+        self.copies.insert(id, copy.clone());
+        if verdict.is_some() {
+            return Ok(copy);
+        }
+        if let Ok(dict) = value.cast::<PyDict>() {
+            let copied = copy.cast::<PyDict>()?;
+            for (key, item) in dict {
+                let ignored = key
+                    .extract::<&str>()
+                    .is_ok_and(|key| self.matcher.ignored_keys().skips(key));
+                let item = if ignored { item } else { self.value(&item)? };
+                copied.set_item(key, item)?;
+            }
+        } else if let Ok(list) = value.cast::<PyList>() {
+            let copied = copy.cast::<PyList>()?;
+            for item in list {
+                copied.append(self.value(&item)?)?;
+            }
+        }
+        self.budget.leave();
+        Ok(copy)
+    }
+
+    fn string(&mut self, s: &Bound<'py, PyString>) -> PyResult<Bound<'py, PyAny>> {
+        let text = s.to_cow()?;
+        let redacted = match self.budget.take(&text)? {
+            Guarded::Scan(part) => {
+                let found = self.matcher.find_all(part);
+                if found.is_empty() {
+                    return Ok(s.clone().into_any());
+                }
+                redact(part, &found, self.mask) + &text[part.len()..]
+            }
+            Guarded::Skip(false) => return Ok(s.clone().into_any()),
+            Guarded::Skip(true) => self
+                .mask
+                .repeat(Graphemes::new(&text).count(&(0..text.len()))),
+        };
+        Ok(PyString::new(s.py(), &redacted).into_any())
+    }
+}
@@ -0,0 +1,386 @@
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
+#[cfg(feature = "python")]
+use pyo3::types::PyBytes;
+#[cfg(feature = "python")]
+use pyo3::types::PyDict;
+#[cfg(feature = "python")]
+use pyo3_stub_gen::derive::{gen_stub_pyclass, gen_stub_pymethods};
+use serde_json::Value;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{Arc, PoisonError, RwLock};
+
+use crate::backend::Backend;
+use crate::build_error::BuildError;
+use crate::limits::{Budget, LimitError};
+use crate::matcher::Matcher;
+#[cfg(feature = "python")]
+use crate::matches::Match;
+use crate::metrics::Timer;
+#[cfg(feature = "python")]
+use crate::options::ListMetadata;
+use crate::options::Options;
+#[cfg(feature = "python")]
+use crate::stats::Stats;
+use crate::traverse::{self, Segment};
+
+pub type SharedMatcher = Arc<dyn Matcher + Send + Sync>;
+
+/// named matchers, e.g. one per tenant or policy; a reload builds the new matcher
+/// before swapping it in, scans holding the old one finish on it
+#[derive(Default)]
+pub struct DenyListRegistry {
+    lists: RwLock<HashMap<String, SharedMatcher>>,
+}
+
+impl DenyListRegistry {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// matcher registered as `name`
+    #[must_use]
+    pub fn get(&self, name: &str) -> Option<SharedMatcher> {
+        self.lists
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .get(name)
+            .cloned()
+    }
+
+    /// registers `matcher` as `name`, returns the matcher it replaces
+    pub fn insert(&self, name: impl Into<String>, matcher: SharedMatcher) -> Option<SharedMatcher> {
+        self.lists
+            .write()
+            .unwrap_or_else(PoisonError::into_inner)
+            .insert(name.into(), matcher)
+    }
+
+    /// builds a matcher of `backend` and registers it as `name`, a failed build keeps
+    /// the current matcher
+    /// # Errors
+    /// * pattern errors of the backend
+    pub fn reload(
+        &self,
+        name: impl Into<String>,
+        backend: Backend,
+        words: Vec<String>,
+        options: &Options,
+    ) -> Result<(), BuildError> {
+        let matcher = backend.build_with(words, options)?;
+        self.insert(name, Arc::from(matcher));
+        Ok(())
+    }
+
+    /// unregisters `name`, returns its matcher
+    pub fn remove(&self, name: &str) -> Option<SharedMatcher> {
+        self.lists
+            .write()
+            .unwrap_or_else(PoisonError::into_inner)
+            .remove(name)
+    }
+
+    /// registered names in sorted order
+    #[must_use]
+    pub fn names(&self) -> Vec<String> {
+        let mut names: Vec<String> = self
+            .lists
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .keys()
+            .cloned()
+            .collect();
+        names.sort_unstable();
+        names
+    }
+
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.lists
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// registered matchers in name order, as they are now
+    #[must_use]
+    pub fn snapshot(&self) -> Vec<(String, SharedMatcher)> {
+        let mut lists: Vec<(String, SharedMatcher)> = self
+            .lists
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .iter()
+            .map(|(name, matcher)| (name.clone(), Arc::clone(matcher)))
+            .collect();
+        lists.sort_unstable_by(|a, b| a.0.cmp(&b.0));
+        lists
+    }
+
+    /// verdict of every list on a json payload, walked once: each string is checked
+    /// against the lists without a match yet, with their own ignored keys and limits
+    /// # Errors
+    /// * over a length limit of a list with `Overflow::Raise`
+    pub fn scan_all_json(&self, value: &Value) -> Result<BTreeMap<String, bool>, LimitError> {
+        let lists = self.snapshot();
+        let mut scan = ScanAll::new(&lists);
+        let mut failed = None;
+        traverse::json(value, &mut |path, s| {
+            scan.visit(path, s).unwrap_or_else(|e| {
+                failed = Some(e);
+                true
+            })
+        });
+        match failed {
+            Some(e) => Err(e),
+            None => Ok(scan.verdicts()),
+        }
+    }
+
+    /// `scan_all_json` of a msgpack document; the string limits of each list apply, the
+    /// document length, depth and element limits do not
+    /// # Errors
+    /// * truncated or malformed msgpack
+    /// * over a length limit of a list with `Overflow::Raise`
+    pub fn scan_all_msgpack(&self, buf: &[u8]) -> Result<BTreeMap<String, bool>, traverse::Error> {
+        let lists = self.snapshot();
+        let mut scan = ScanAll::new(&lists);
+        let mut failed = None;
+        traverse::msgpack(buf, &mut |path, s| {
+            scan.visit(path, s).unwrap_or_else(|e| {
+                failed = Some(e);
+                true
+            })
+        })?;
+        match failed {
+            Some(e) => Err(e.into()),
+            None => Ok(scan.verdicts()),
+        }
+    }
+}
+
+/// verdicts of several lists filled in one walk of a payload
+struct ScanAll<'a> {
+    lists: &'a [(String, SharedMatcher)],
+    budgets: Vec<Budget>,
+    found: Vec<bool>,
+    left: usize,
+    timer: Timer,
+}
+
+impl<'a> ScanAll<'a> {
+    fn new(lists: &'a [(String, SharedMatcher)]) -> Self {
+        Self {
+            lists,
+            budgets: lists.iter().map(|(_, m)| m.limits().budget()).collect(),
+            found: vec![false; lists.len()],
+            left: lists.len(),
+            timer: Timer::start(),
+        }
+    }
+
+    /// checks the string at `path` with every list without a match yet, returns true
+    /// once all of them matched
+    fn visit(&mut self, path: &[Segment<'_>], s: &str) -> Result<bool, LimitError> {
+        for (i, (_, matcher)) in self.lists.iter().enumerate() {
+            if !self.found[i] && matcher.check_at(path, s, &mut self.budgets[i])? {
+                self.found[i] = true;
+                self.left -= 1;
+            }
+        }
+        Ok(self.left == 0)
+    }
+
+    /// list name to verdict, each scan recorded in the metrics of its list
+    fn verdicts(self) -> BTreeMap<String, bool> {
+        let elapsed = self.timer.elapsed();
+        self.lists
+            .iter()
+            .zip(self.found)
+            .map(|((name, matcher), found)| {
+                matcher.metrics().record_scan(elapsed, found);
+                (name.clone(), found)
+            })
+            .collect()
+    }
+}
+
+/// matcher of a registry entry as it was when fetched, later reloads do not change it
+#[cfg(feature = "python")]
+#[gen_stub_pyclass]
+#[pyclass(module = "deny_filter.plugin", frozen)]
+pub struct RegisteredList {
+    matcher: SharedMatcher,
+}
+
+#[cfg(feature = "python")]
+#[gen_stub_pymethods]
+#[pymethods]
+impl RegisteredList {
+    fn is_match(&self, s: &str) -> bool {
+        self.matcher.is_match(s)
+    }
+
+    /// non-overlapping matches with byte and code point offsets
+    fn find_all(&self, text: &str) -> Vec<Match> {
+        self.matcher.find_all(text)
+    }
+
+    /// masks every match grapheme by grapheme
+    #[pyo3(signature = (text, mask = "*"))]
+    fn redact(&self, text: &str, mask: &str) -> String {
+        self.matcher.redact(text, mask)
+    }
+
+    /// # Errors
+    /// * over a length limit with `on_overflow="raise"`
+    fn scan_str(&self, txt: &str) -> PyResult<bool> {
+        Ok(self.matcher.try_scan_str(txt)?)
+    }
+
+    /// # Errors
+    /// * over a length limit with `on_overflow="raise"`
+    fn scan(&self, args: &Bound<'_, PyDict>) -> PyResult<bool> {
+        self.matcher.scan(args)
+    }
+
+    /// # Errors
+    /// * over a length limit with `on_overflow="raise"`
+    fn scan_any(&self, value: &Bound<'_, PyAny>) -> PyResult<bool> {
+        self.matcher.scan_any(value)
+    }
+
+    /// version, source and timestamp options of the list
+    #[getter]
+    fn metadata(&self) -> ListMetadata {
+        self.matcher.metadata().clone()
+    }
+
+    fn stats(&self) -> Stats {
+        self.matcher.stats()
+    }
+}
+
+/// matcher of a python config: `words`, optional `backend` and matching options,
+/// built without the GIL
+#[cfg(feature = "python")]
+fn build_config(config: &Bound<'_, PyDict>) -> PyResult<SharedMatcher> {
+    let config = config.copy()?;
+    let words: Vec<String> = config
+        .get_item("words")?
+        .ok_or_else(|| pyo3::exceptions::PyValueError::new_err("config needs words"))?
+        .extract()?;
+    config.del_item("words")?;
+    let backend: Backend = match config.get_item("backend")? {
+        Some(backend) => {
+            config.del_item("backend")?;
+            backend
+                .extract::<String>()?
+                .parse()
+                .map_err(pyo3::exceptions::PyValueError::new_err)?
+        }
+        None => Backend::default(),
+    };
+    let options = Options::from_kwargs(Some(&config))?;
+    let matcher = config.py().detach(|| backend.build_with(words, &options))?;
+    Ok(Arc::from(matcher))
+}
+
+#[cfg(feature = "python")]
+#[gen_stub_pyclass]
+#[pyclass(module = "deny_filter.plugin", frozen, name = "DenyListRegistry")]
+pub struct PyDenyListRegistry {
+    registry: DenyListRegistry,
+}
+
+#[cfg(feature = "python")]
+#[gen_stub_pymethods]
+#[pymethods]
+impl PyDenyListRegistry {
+    /// registry of `configs`: name to `{"words": [...], "backend": ..., **options}`
+    /// # Errors
+    /// * a config without words, unknown backend, invalid patterns or options
+    #[new]
+    #[pyo3(signature = (configs = None))]
+    fn py_new(configs: Option<&Bound<'_, PyDict>>) -> PyResult<Self> {
+        let registry = DenyListRegistry::new();
+        if let Some(configs) = configs {
+            for (name, config) in configs {
+                registry.insert(name.extract::<String>()?, build_config(config.cast()?)?);
+            }
+        }
+        Ok(Self { registry })
+    }
+
+    /// matcher of `name`
+    /// # Errors
+    /// * `KeyError` for unknown names
+    fn get(&self, name: &str) -> PyResult<RegisteredList> {
+        self.registry
+            .get(name)
+            .map(|matcher| RegisteredList { matcher })
+            .ok_or_else(|| pyo3::exceptions::PyKeyError::new_err(name.to_string()))
+    }
+
+    /// builds `config` and swaps it in as `name`, lists fetched before keep the old
+    /// matcher; on errors the current matcher stays
+    /// # Errors
+    /// * a config without words, unknown backend, invalid patterns or options
+    fn reload(&self, name: String, config: &Bound<'_, PyDict>) -> PyResult<()> {
+        self.registry.insert(name, build_config(config)?);
+        Ok(())
+    }
+
+    /// registers a python matcher (a `Matcher` subclass or any object with `is_match`)
+    /// as `name`, scans call back into python
+    /// # Errors
+    /// * the object has no callable `is_match`
+    fn register(&self, name: String, matcher: &Bound<'_, PyAny>) -> PyResult<()> {
+        let matcher = crate::py_matcher::PyMatcher::new(matcher)?;
+        self.registry.insert(name, Arc::new(matcher));
+        Ok(())
+    }
+
+    /// unregisters `name`
+    /// # Errors
+    /// * `KeyError` for unknown names
+    fn remove(&self, name: &str) -> PyResult<()> {
+        self.registry
+            .remove(name)
+            .map(|_| ())
+            .ok_or_else(|| pyo3::exceptions::PyKeyError::new_err(name.to_string()))
+    }
+
+    /// registered names in sorted order
+    fn names(&self) -> Vec<String> {
+        self.registry.names()
+    }
+
+    /// verdict of every list on a dict/list/str, or bytes of a msgpack document, walked
+    /// once; a dict of list name to whether it matched
+    /// # Errors
+    /// * malformed msgpack
+    /// * over a length limit of a list with `on_overflow="raise"`
+    fn scan_all(&self, payload: &Bound<'_, PyAny>) -> PyResult<BTreeMap<String, bool>> {
+        if let Ok(bytes) = payload.cast::<PyBytes>() {
+            return Ok(self.registry.scan_all_msgpack(bytes.as_bytes())?);
+        }
+        let lists = self.registry.snapshot();
+        let mut scan = ScanAll::new(&lists);
+        traverse::python(payload, &mut |path, s| Ok(scan.visit(path, s)?))?;
+        Ok(scan.verdicts())
+    }
+
+    fn __contains__(&self, name: &str) -> bool {
+        self.registry.get(name).is_some()
+    }
+
+    fn __len__(&self) -> usize {
+        self.registry.len()
+    }
+}
@@ -0,0 +1,100 @@
+use aho_corasick::AhoCorasick;
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
+#[cfg(feature = "python")]
+use pyo3_stub_gen::derive::{gen_stub_pyclass, gen_stub_pymethods};
+
+use crate::normalize::bare;
+
+/// `(i, j, prefix)` for every pattern `j` contained in another pattern `i`,
+/// `prefix` when `i` starts with it
+pub(crate) fn contained(patterns: &[String]) -> Vec<(usize, usize, bool)> {
+    let Ok(ac) = AhoCorasick::new(patterns) else {
+        return Vec::new();
+    };
+    let mut found = Vec::new();
+    for (i, pattern) in patterns.iter().enumerate() {
+        for m in ac.find_overlapping_iter(pattern) {
+            if m.pattern().as_usize() != i {
+                found.push((i, m.pattern().as_usize(), m.start() == 0));
+            }
+        }
+    }
+    found
+}
+
+/// what construction did to the deny word entries, for cleaning up word list sources
+#[cfg_attr(feature = "python", gen_stub_pyclass)]
+#[cfg_attr(
+    feature = "python",
+    pyclass(module = "deny_filter.matchers", frozen, get_all, skip_from_py_object)
+)]
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct BuildReport {
+    /// indexes of empty or blank entries, dropped
+    pub empty: Vec<usize>,
+    /// entries repeating an earlier entry after normalization
+    pub duplicates: Vec<String>,
+    /// entry and its normalized form, when case folding or normalization changed it
+    pub altered: Vec<(String, String)>,
+    /// entry and a shorter entry contained in it, e.g. `("free money", "free")`
+    pub subsumes: Vec<(String, String)>,
+}
+
+impl BuildReport {
+    /// report of `entries` compiled into parts of (entry index, pattern) pairs,
+    /// `duplicates` are the indexes of entries left out as repeated patterns
+    pub(crate) fn new<'a>(
+        entries: &[String],
+        duplicates: &[usize],
+        parts: impl IntoIterator<Item = (&'a [usize], &'a [String])>,
+    ) -> Self {
+        let mut report = Self {
+            empty: (0..entries.len())
+                .filter(|i| entries[*i].trim().is_empty())
+                .collect(),
+            ..Self::default()
+        };
+        let (mut altered, mut subsumes) = (Vec::new(), Vec::new());
+        for (ids, patterns) in parts {
+            for (id, pattern) in ids.iter().zip(patterns) {
+                if bare(pattern) != entries[*id].trim() {
+                    altered.push((*id, bare(pattern).to_string()));
+                }
+            }
+            // compiled patterns are unique, a contained one is shorter
+            subsumes.extend(
+                contained(patterns)
+                    .into_iter()
+                    .map(|(i, j, _)| (ids[i], ids[j])),
+            );
+        }
+        let mut duplicates = duplicates.to_vec();
+        duplicates.sort_unstable();
+        altered.sort();
+        subsumes.sort_unstable();
+        subsumes.dedup();
+        report.duplicates = duplicates.into_iter().map(|i| entries[i].clone()).collect();
+        report.altered = altered
+            .into_iter()
+            .map(|(i, normalized)| (entries[i].clone(), normalized))
+            .collect();
+        report.subsumes = subsumes
+            .into_iter()
+            .map(|(i, j)| (entries[i].clone(), entries[j].clone()))
+            .collect();
+        report
+    }
+}
+
+#[cfg(feature = "python")]
+#[gen_stub_pymethods]
+#[pymethods]
+impl BuildReport {
+    fn __repr__(&self) -> String {
+        format!(
+            "BuildReport(empty={:?}, duplicates={:?}, altered={:?}, subsumes={:?})",
+            self.empty, self.duplicates, self.altered, self.subsumes
+        )
+    }
+}
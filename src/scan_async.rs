@@ -0,0 +1,132 @@
+use pyo3::PyClass;
+use pyo3::exceptions::{PyRuntimeError, PyValueError};
+use pyo3::prelude::*;
+use pyo3::types::{PyCFunction, PyDict, PyTuple};
+use std::borrow::Cow;
+use std::thread;
+
+use crate::decompress::decompress;
+use crate::matcher::Matcher;
+use crate::on_match::{self, MatchEvent, OnMatch, strings};
+
+/// data copied out of python objects, so the scan needs no GIL
+enum Snapshot {
+    /// `(path, text)` of the string values of a dict/list/str in `scan_any` order
+    Strings(Vec<(String, String)>),
+    /// msgpack document, decompressed up to the size limit first when set
+    Msgpack(Vec<u8>, Option<usize>),
+}
+
+impl Snapshot {
+    fn scan(&self, matcher: &dyn Matcher) -> Result<Option<MatchEvent>, String> {
+        match self {
+            Self::Strings(texts) => {
+                on_match::scan_strings(matcher, texts).map_err(|err| err.to_string())
+            }
+            Self::Msgpack(buf, limit) => {
+                let buf = match limit {
+                    Some(limit) => decompress(buf, *limit).map_err(|err| err.to_string())?,
+                    None => Cow::Borrowed(buf.as_slice()),
+                };
+                on_match::scan_msgpack(matcher, &buf).map_err(|err| err.to_string())
+            }
+        }
+    }
+}
+
+/// future of the running event loop, resolved with the verdict of a background thread
+fn spawn<M: Matcher + PyClass + Sync>(
+    on_match: OnMatch,
+    matcher: PyRef<'_, M>,
+    snapshot: Snapshot,
+) -> PyResult<Bound<'_, PyAny>> {
+    let py = matcher.py();
+    let event_loop = py.import("asyncio")?.call_method0("get_running_loop")?;
+    let future = event_loop.call_method0("create_future")?;
+    let matcher = matcher.into_pyobject(py)?.unbind();
+    let (event_loop, waiting) = (event_loop.unbind(), future.clone().unbind());
+    thread::Builder::new()
+        .name("deny-scan-async".to_string())
+        .spawn(move || {
+            Python::attach(|py| {
+                let verdict = {
+                    let matcher = matcher.bind(py).borrow();
+                    let matcher: &M = &matcher;
+                    py.detach(|| snapshot.scan(matcher))
+                };
+                resolve(py, &event_loop, waiting, on_match, verdict);
+            });
+        })
+        .map_err(|err| PyRuntimeError::new_err(err.to_string()))?;
+    Ok(future)
+}
+
+/// on the event loop thread: reports the deciding match to `on_match`, then sets the
+/// verdict unless the future was cancelled meanwhile
+fn resolve(
+    py: Python<'_>,
+    event_loop: &Py<PyAny>,
+    future: Py<PyAny>,
+    on_match: OnMatch,
+    verdict: Result<Option<MatchEvent>, String>,
+) {
+    let settle = PyCFunction::new_closure(
+        py,
+        None,
+        None,
+        move |args: &Bound<'_, PyTuple>, _: Option<&Bound<'_, PyDict>>| -> PyResult<()> {
+            let py = args.py();
+            if let Ok(Some(event)) = &verdict {
+                on_match.notify(py, event);
+            }
+            let future = future.bind(py);
+            if future.call_method0("done")?.is_truthy()? {
+                return Ok(());
+            }
+            match &verdict {
+                Ok(event) => future.call_method1("set_result", (event.is_some(),))?,
+                Err(message) => future.call_method1(
+                    "set_exception",
+                    (PyValueError::new_err(message.clone()).into_value(py),),
+                )?,
+            };
+            Ok(())
+        },
+    );
+    // a closed loop has no one left awaiting the verdict
+    if let Ok(settle) = settle {
+        let _ = event_loop
+            .bind(py)
+            .call_method1("call_soon_threadsafe", (settle,));
+    }
+}
+
+/// `scan_any_async` of the python matchers
+/// # Errors
+/// * no running event loop
+pub fn py_scan_any_async<'py, M: Matcher + PyClass + Sync>(
+    on_match: OnMatch,
+    matcher: PyRef<'py, M>,
+    value: &Bound<'py, PyAny>,
+) -> PyResult<Bound<'py, PyAny>> {
+    let mut texts = Vec::new();
+    strings(
+        value,
+        matcher.ignored_keys(),
+        &mut String::from("$"),
+        &mut texts,
+    );
+    spawn(on_match, matcher, Snapshot::Strings(texts))
+}
+
+/// `scan_msgpack_async` of the python matchers, decompressing up to `limit` bytes when set
+/// # Errors
+/// * no running event loop
+pub fn py_scan_msgpack_async<'py, M: Matcher + PyClass + Sync>(
+    on_match: OnMatch,
+    matcher: PyRef<'py, M>,
+    data: &[u8],
+    limit: Option<usize>,
+) -> PyResult<Bound<'py, PyAny>> {
+    spawn(on_match, matcher, Snapshot::Msgpack(data.to_vec(), limit))
+}
@@ -0,0 +1,97 @@
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
+#[cfg(feature = "python")]
+use pyo3::types::PyBytes;
+use std::collections::{BTreeMap, BTreeSet};
+
+use crate::build_error::{BuildError, invalid};
+use crate::compiled::stored_words;
+#[cfg(feature = "python")]
+use crate::matcher::Matcher;
+use crate::options::{Aggregation, Options};
+#[cfg(feature = "python")]
+use crate::violations;
+
+/// weights of deny words and proximity rules, for a risk score of the matches instead of a
+/// yes or no
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Weights {
+    /// stored deny word or proximity rule label -> weight, others weigh 1
+    weights: BTreeMap<String, f64>,
+    aggregation: Aggregation,
+}
+
+/// every deny word weighs 1, scores sum them
+pub static NO_WEIGHTS: Weights = Weights {
+    weights: BTreeMap::new(),
+    aggregation: Aggregation::Sum,
+};
+
+impl Weights {
+    /// `weights`, the weights of the `proximity` rules and `score_aggregation` of
+    /// `options`, keys in the form of `stored_words` and `ProximityRule::label`
+    /// # Errors
+    /// * a weight is negative, infinite or NaN
+    pub fn new(options: &Options) -> Result<Self, BuildError> {
+        let words: Vec<String> = options.weights.keys().cloned().collect();
+        let rules = options
+            .proximity
+            .iter()
+            .filter_map(|rule| Some((rule.label(), rule.weight?)));
+        let weights = stored_words(&words, options.case_fold)
+            .into_iter()
+            .zip(options.weights.values().copied())
+            .chain(rules)
+            .map(|(word, weight)| {
+                if weight.is_finite() && weight >= 0.0 {
+                    Ok((word, weight))
+                } else {
+                    Err(invalid(format!(
+                        "weight {weight} of {word:?} is not a finite number >= 0"
+                    )))
+                }
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(Self {
+            weights,
+            aggregation: options.score_aggregation,
+        })
+    }
+
+    /// weight of a deny word as reported in `Match::word`, or of a proximity rule label
+    #[must_use]
+    pub fn weight(&self, word: &str) -> f64 {
+        self.weights.get(word).copied().unwrap_or(1.0)
+    }
+
+    /// aggregated weights of the distinct `words`, 0 without any
+    #[must_use]
+    pub fn score<'a>(&self, words: impl IntoIterator<Item = &'a str>) -> f64 {
+        let words: BTreeSet<&str> = words.into_iter().collect();
+        let weights = words.into_iter().map(|word| self.weight(word));
+        match self.aggregation {
+            // `sum` of no floats is -0.0
+            Aggregation::Sum => weights.fold(0.0, |sum, weight| sum + weight),
+            Aggregation::Max => weights.fold(0.0, f64::max),
+        }
+    }
+}
+
+/// `score` of the python matchers: a dict/list/str, or bytes of a msgpack document, walked
+/// within the limits as `scan_any` and `scan_msgpack` walk them; infinite when a limit
+/// blocks with `on_overflow="block"`
+/// # Errors
+/// * malformed msgpack
+/// * over a limit with `on_overflow="raise"`
+#[cfg(feature = "python")]
+pub fn py_score<M: Matcher + ?Sized>(matcher: &M, payload: &Bound<'_, PyAny>) -> PyResult<f64> {
+    let found = if let Ok(bytes) = payload.cast::<PyBytes>() {
+        violations::msgpack_violations(matcher, bytes.as_bytes())?
+    } else {
+        violations::walk(matcher, payload, &mut matcher.limits().budget())?
+    };
+    let words: Option<Vec<&str>> = found.iter().map(|v| v.word.as_deref()).collect();
+    let score = words.map_or(f64::INFINITY, |words| matcher.weights().score(words));
+    matcher.metrics().record_score(score);
+    Ok(score)
+}
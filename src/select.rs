@@ -0,0 +1,263 @@
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
+#[cfg(feature = "python")]
+use pyo3::types::{PyBytes, PyDict, PyList};
+use serde_json::Value;
+use std::str::FromStr;
+
+#[cfg(feature = "python")]
+use crate::limits::Budget;
+use crate::matcher::Matcher;
+use crate::metrics::Timer;
+use crate::trace::ScanSpan;
+use crate::traverse::{self, Segment};
+
+/// one step of a selector
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Step {
+    /// `.name` or `['name']`
+    Key(String),
+    /// `[0]`
+    Index(usize),
+    /// `.*` or `[*]`, any one key or index
+    Any,
+    /// `..`, any number of keys and indexes
+    Descend,
+}
+
+impl Step {
+    fn matches(&self, segment: &Segment<'_>) -> bool {
+        match (self, segment) {
+            (Step::Key(name), Segment::Key(key)) => name == key,
+            (Step::Index(i), Segment::Index(j)) => i == j,
+            (Step::Any, _) => true,
+            _ => false,
+        }
+    }
+}
+
+/// a `JSONPath` subset selecting values of a document: `$`, `.name`, `['name']`, `[0]`,
+/// `.*` or `[*]` for any child and `..` for any descendant, e.g. `$.messages[*].content`;
+/// the strings below a selected value are all selected
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Selector {
+    steps: Vec<Step>,
+}
+
+impl Selector {
+    /// whether the string at `path` is at or below a value the selector selects
+    #[must_use]
+    pub fn selects(&self, path: &[Segment<'_>]) -> bool {
+        selects(&self.steps, path)
+    }
+}
+
+fn selects(steps: &[Step], path: &[Segment<'_>]) -> bool {
+    match steps.split_first() {
+        None => true,
+        Some((Step::Descend, rest)) => (0..=path.len()).any(|i| selects(rest, &path[i..])),
+        Some((step, rest)) => path
+            .split_first()
+            .is_some_and(|(segment, path)| step.matches(segment) && selects(rest, path)),
+    }
+}
+
+/// the name after a `.`, up to the next `.` or `[`
+fn name(rest: &str) -> (Step, &str) {
+    let end = rest.find(['.', '[']).unwrap_or(rest.len());
+    let step = match &rest[..end] {
+        "*" => Step::Any,
+        name => Step::Key(name.to_string()),
+    };
+    (step, &rest[end..])
+}
+
+/// the step of a `[...]` at the start of `rest` and what follows it
+fn bracket(rest: &str) -> Result<(Step, &str), String> {
+    let inner = &rest[1..];
+    if let Some(quote) = inner.chars().next().filter(|c| matches!(c, '\'' | '"')) {
+        let end = inner[1..]
+            .find(quote)
+            .ok_or_else(|| format!("unterminated quote in {rest:?}"))?;
+        let tail = inner[end + 2..]
+            .strip_prefix(']')
+            .ok_or_else(|| format!("missing ] in {rest:?}"))?;
+        return Ok((Step::Key(inner[1..=end].to_string()), tail));
+    }
+    let end = inner
+        .find(']')
+        .ok_or_else(|| format!("missing ] in {rest:?}"))?;
+    let step = match inner[..end].trim() {
+        "*" => Step::Any,
+        index => Step::Index(index.parse().map_err(|_| {
+            format!("invalid index {index:?}, expected a number, * or a quoted key")
+        })?),
+    };
+    Ok((step, &inner[end + 1..]))
+}
+
+impl FromStr for Selector {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut rest = s
+            .trim()
+            .strip_prefix('$')
+            .ok_or_else(|| format!("selector {s:?} does not start with $"))?;
+        let mut steps = Vec::new();
+        while !rest.is_empty() {
+            let (step, tail) = if let Some(tail) = rest.strip_prefix("..") {
+                steps.push(Step::Descend);
+                if tail.starts_with('[') {
+                    bracket(tail)?
+                } else {
+                    name(tail)
+                }
+            } else if let Some(tail) = rest.strip_prefix('.') {
+                name(tail)
+            } else if rest.starts_with('[') {
+                bracket(rest)?
+            } else {
+                return Err(format!("unexpected {rest:?} in selector {s:?}"));
+            };
+            if step == Step::Key(String::new()) {
+                return Err(format!("empty name in selector {s:?}"));
+            }
+            steps.push(step);
+            rest = tail;
+        }
+        Ok(Self { steps })
+    }
+}
+
+/// parses each selector
+/// # Errors
+/// * a selector is not valid
+pub fn parse_selectors<S: AsRef<str>>(selectors: &[S]) -> Result<Vec<Selector>, String> {
+    selectors.iter().map(|s| s.as_ref().parse()).collect()
+}
+
+fn selected(selectors: &[Selector], path: &[Segment<'_>]) -> bool {
+    selectors.iter().any(|selector| selector.selects(path))
+}
+
+/// `scan_json` of the strings the selectors select only;
+/// over a length limit with `Overflow::Raise` it counts as a match
+pub fn scan_json<M: Matcher + ?Sized>(matcher: &M, value: &Value, selectors: &[Selector]) -> bool {
+    let span = ScanSpan::enter(matcher.metrics(), "scan_selected");
+    let timer = Timer::start();
+    let mut budget = matcher.limits().budget();
+    let found = traverse::json(value, &mut |path, s| {
+        selected(selectors, path) && matcher.check_at(path, s, &mut budget).unwrap_or(true)
+    });
+    span.finish(timer.elapsed(), budget.scanned(), found);
+    found
+}
+
+/// `scan_msgpack` of the strings the selectors select only
+/// # Errors
+/// * truncated or malformed msgpack
+/// * over a limit with `Overflow::Raise`
+pub fn scan_msgpack<M: Matcher + ?Sized>(
+    matcher: &M,
+    buf: &[u8],
+    selectors: &[Selector],
+) -> Result<bool, traverse::Error> {
+    let span = ScanSpan::enter(matcher.metrics(), "scan_selected");
+    let timer = Timer::start();
+    let mut budget = matcher.limits().budget();
+    let mut failed = None;
+    let found = traverse::msgpack_limited(buf, matcher.limits(), &mut |path, s| {
+        selected(selectors, path)
+            && matcher.check_at(path, s, &mut budget).unwrap_or_else(|e| {
+                failed = Some(e);
+                true
+            })
+    })?;
+    if let Some(e) = failed {
+        return Err(e.into());
+    }
+    span.finish(timer.elapsed(), budget.scanned(), found);
+    Ok(found)
+}
+
+/// key or index of a python dict/list item on the path of a string
+#[cfg(feature = "python")]
+enum PyStep {
+    Key(String),
+    Index(usize),
+}
+
+/// whether a selected string of a python dict/list/str matches, in `scan_any` order
+#[cfg(feature = "python")]
+fn scan_value<M: Matcher + ?Sized>(
+    matcher: &M,
+    value: &Bound<'_, PyAny>,
+    path: &mut Vec<PyStep>,
+    selectors: &[Selector],
+    budget: &mut Budget,
+) -> PyResult<bool> {
+    if let Ok(s) = value.extract::<&str>() {
+        let segments: Vec<Segment<'_>> = path
+            .iter()
+            .map(|step| match step {
+                PyStep::Key(key) => Segment::Key(key),
+                PyStep::Index(i) => Segment::Index(*i),
+            })
+            .collect();
+        return Ok(selected(selectors, &segments) && matcher.check_within(s, budget)?);
+    }
+    if (value.is_instance_of::<PyDict>() || value.is_instance_of::<PyList>())
+        && let Some(verdict) = budget.nested(path.len() + 1)?
+    {
+        return Ok(verdict);
+    }
+    let items: Vec<(PyStep, Bound<'_, PyAny>)> = if let Ok(dict) = value.cast::<PyDict>() {
+        dict.iter()
+            .filter(|(key, _)| {
+                !key.extract::<&str>()
+                    .is_ok_and(|key| matcher.ignored_keys().skips(key))
+            })
+            .map(|(key, item)| Ok((PyStep::Key(key.str()?.to_string()), item)))
+            .collect::<PyResult<_>>()?
+    } else if let Ok(list) = value.cast::<PyList>() {
+        list.iter()
+            .enumerate()
+            .map(|(i, item)| (PyStep::Index(i), item))
+            .collect()
+    } else {
+        return Ok(false);
+    };
+    for (step, item) in items {
+        path.push(step);
+        let found = scan_value(matcher, &item, path, selectors, budget);
+        path.pop();
+        if found? {
+            return Ok(true);
+        }
+    }
+    Ok(false)
+}
+
+/// `scan_selected` of the python matchers: a dict/list/str, or bytes of a msgpack document
+/// # Errors
+/// * invalid selector, malformed msgpack
+/// * over a limit with `on_overflow="raise"`
+#[cfg(feature = "python")]
+pub fn py_scan_selected<M: Matcher + ?Sized>(
+    matcher: &M,
+    payload: &Bound<'_, PyAny>,
+    selectors: &[String],
+) -> PyResult<bool> {
+    let selectors = parse_selectors(selectors).map_err(pyo3::exceptions::PyValueError::new_err)?;
+    if let Ok(bytes) = payload.cast::<PyBytes>() {
+        return scan_msgpack(matcher, bytes.as_bytes(), &selectors)
+            .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()));
+    }
+    let span = ScanSpan::enter(matcher.metrics(), "scan_selected");
+    let timer = Timer::start();
+    let mut budget = matcher.limits().budget();
+    let found = scan_value(matcher, payload, &mut Vec::new(), &selectors, &mut budget)?;
+    span.finish(timer.elapsed(), budget.scanned(), found);
+    Ok(found)
+}
@@ -0,0 +1,300 @@
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
+#[cfg(feature = "python")]
+use pyo3::types::{PyDict, PyList};
+#[cfg(feature = "python")]
+use pyo3_stub_gen::derive::{gen_stub_pyclass, gen_stub_pymethods};
+use serde_json::Value;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering::Relaxed};
+
+#[cfg(feature = "python")]
+use crate::backend::Backend;
+use crate::build_error::{BuildError, invalid};
+use crate::explain::Explanation;
+use crate::keys::KeyFilter;
+use crate::limits::{LimitError, Limits};
+use crate::matcher::Matcher;
+use crate::matches::Match;
+use crate::metrics::{self, BackendMetrics, ShadowMetrics};
+use crate::options::ListMetadata;
+#[cfg(feature = "python")]
+use crate::options::Options;
+use crate::report::BuildReport;
+use crate::score::Weights;
+use crate::stats::Stats;
+use crate::traverse;
+
+type BoxedMatcher = Box<dyn Matcher + Send + Sync>;
+
+/// whether any string of a python dict/list/str satisfies `f`, in `scan_value` order
+#[cfg(feature = "python")]
+fn any_string(value: &Bound<'_, PyAny>, f: &dyn Fn(&str) -> bool) -> bool {
+    if let Ok(s) = value.extract::<&str>() {
+        f(s)
+    } else if let Ok(dict) = value.cast::<PyDict>() {
+        dict.values().iter().any(|item| any_string(&item, f))
+    } else if let Ok(list) = value.cast::<PyList>() {
+        list.iter().any(|item| any_string(&item, f))
+    } else {
+        false
+    }
+}
+
+/// the active matcher decides every scan; a candidate list, e.g. the next version of the
+/// list, also scans an evenly spaced `sample_rate` share of them and the verdicts it would
+/// have given differently are counted in the metrics of `name`
+pub struct ShadowMatcher {
+    active: BoxedMatcher,
+    candidate: BoxedMatcher,
+    sample_rate: f64,
+    scans: AtomicU64,
+    shadow: Arc<ShadowMetrics>,
+}
+
+impl ShadowMatcher {
+    /// # Errors
+    /// * `sample_rate` outside 0..=1
+    pub fn new(
+        active: BoxedMatcher,
+        candidate: BoxedMatcher,
+        sample_rate: f64,
+        name: &str,
+    ) -> Result<Self, BuildError> {
+        if !(0.0..=1.0).contains(&sample_rate) {
+            return Err(invalid(format!(
+                "sample rate {sample_rate} is not between 0 and 1"
+            )));
+        }
+        Ok(Self {
+            active,
+            candidate,
+            sample_rate,
+            scans: AtomicU64::new(0),
+            shadow: metrics::shadow(name),
+        })
+    }
+
+    /// counters of the candidate, shared by all shadows of the same name
+    #[must_use]
+    pub fn shadow_metrics(&self) -> &ShadowMetrics {
+        &self.shadow
+    }
+
+    #[must_use]
+    pub fn sample_rate(&self) -> f64 {
+        self.sample_rate
+    }
+
+    /// whether the current scan is in the sample: the n-th scan is when n * rate
+    /// crosses an integer, so the share is exact without randomness
+    #[allow(
+        clippy::cast_precision_loss,
+        clippy::cast_possible_truncation,
+        clippy::cast_sign_loss
+    )]
+    fn sampled(&self) -> bool {
+        let n = self.scans.fetch_add(1, Relaxed) as f64;
+        ((n + 1.0) * self.sample_rate).floor() as u64 > (n * self.sample_rate).floor() as u64
+    }
+
+    /// records the candidate verdict of a sampled scan, `active` stays the result
+    fn compare(&self, active: bool, candidate: impl FnOnce() -> bool) -> bool {
+        if self.sampled() {
+            self.shadow.record(active, candidate());
+        }
+        active
+    }
+}
+
+impl Matcher for ShadowMatcher {
+    fn is_match(&self, s: &str) -> bool {
+        self.active.is_match(s)
+    }
+
+    fn first_match(&self, s: &str) -> Option<&str> {
+        self.active.first_match(s)
+    }
+
+    fn find_all(&self, s: &str) -> Vec<Match> {
+        self.active.find_all(s)
+    }
+
+    fn find_overlapping(&self, s: &str) -> Result<Vec<Match>, BuildError> {
+        self.active.find_overlapping(s)
+    }
+
+    fn explain(&self, s: &str) -> Option<Explanation> {
+        self.active.explain(s)
+    }
+
+    fn metadata(&self) -> &ListMetadata {
+        self.active.metadata()
+    }
+
+    fn build_report(&self) -> &BuildReport {
+        self.active.build_report()
+    }
+
+    fn stats(&self) -> Stats {
+        self.active.stats()
+    }
+
+    #[cfg(feature = "dot")]
+    fn to_dot(&self) -> String {
+        self.active.to_dot()
+    }
+
+    fn metrics(&self) -> &BackendMetrics {
+        self.active.metrics()
+    }
+
+    fn limits(&self) -> &Limits {
+        self.active.limits()
+    }
+
+    fn ignored_keys(&self) -> &KeyFilter {
+        self.active.ignored_keys()
+    }
+
+    fn weights(&self) -> &Weights {
+        self.active.weights()
+    }
+
+    fn proximity_matches(&self, s: &str) -> Vec<&str> {
+        self.active.proximity_matches(s)
+    }
+
+    fn try_scan_str(&self, txt: &str) -> Result<bool, LimitError> {
+        let active = self.active.try_scan_str(txt)?;
+        Ok(self.compare(active, || self.candidate.is_match(txt)))
+    }
+
+    #[cfg(feature = "python")]
+    fn scan(&self, args: &Bound<'_, PyDict>) -> PyResult<bool> {
+        let active = self.active.scan(args)?;
+        Ok(self.compare(active, || {
+            args.values().iter().any(|value| {
+                value
+                    .extract::<&str>()
+                    .is_ok_and(|s| self.candidate.is_match(s))
+            })
+        }))
+    }
+
+    #[cfg(feature = "python")]
+    fn scan_any(&self, value: &Bound<'_, PyAny>) -> PyResult<bool> {
+        let active = self.active.scan_any(value)?;
+        Ok(self.compare(active, || {
+            any_string(value, &|s| self.candidate.is_match(s))
+        }))
+    }
+
+    fn scan_json(&self, value: &Value) -> bool {
+        self.compare(self.active.scan_json(value), || {
+            traverse::json(value, &mut |_, s| self.candidate.is_match(s))
+        })
+    }
+
+    /// malformed msgpack fails the active scan first, the candidate never sees it
+    fn scan_msgpack(&self, buf: &[u8]) -> Result<bool, traverse::Error> {
+        let active = self.active.scan_msgpack(buf)?;
+        Ok(self.compare(active, || {
+            traverse::msgpack_limited(buf, self.limits(), &mut |_, s| self.candidate.is_match(s))
+                .unwrap_or(false)
+        }))
+    }
+}
+
+/// python handle of a `ShadowMatcher`, both lists built from words with one backend
+#[cfg(feature = "python")]
+#[gen_stub_pyclass]
+#[pyclass(module = "deny_filter.plugin", frozen, name = "ShadowDenyList")]
+pub struct PyShadowDenyList {
+    matcher: ShadowMatcher,
+}
+
+#[cfg(feature = "python")]
+#[gen_stub_pymethods]
+#[pymethods]
+impl PyShadowDenyList {
+    /// `words` decide, `candidate_words` are evaluated on `sample_rate` of the scans;
+    /// keyword options: see `Options`, used by both lists
+    /// # Errors
+    /// * unknown backend, invalid patterns or options, sample rate outside 0..=1
+    #[new]
+    #[pyo3(signature = (words, candidate_words, sample_rate = 0.1, name = "candidate", backend = "aho-corasick", **options))]
+    fn py_new(
+        py: Python<'_>,
+        words: Vec<String>,
+        candidate_words: Vec<String>,
+        sample_rate: f64,
+        name: &str,
+        backend: &str,
+        options: Option<&Bound<'_, PyDict>>,
+    ) -> PyResult<Self> {
+        let backend: Backend = backend
+            .parse()
+            .map_err(pyo3::exceptions::PyValueError::new_err)?;
+        let options = Options::from_kwargs(options)?;
+        let matcher = py.detach(|| {
+            ShadowMatcher::new(
+                backend.build_with(words, &options)?,
+                backend.build_with(candidate_words, &options)?,
+                sample_rate,
+                name,
+            )
+        })?;
+        Ok(Self { matcher })
+    }
+
+    fn is_match(&self, s: &str) -> bool {
+        self.matcher.is_match(s)
+    }
+
+    /// matches of the active list
+    fn find_all(&self, text: &str) -> Vec<Match> {
+        self.matcher.find_all(text)
+    }
+
+    /// # Errors
+    /// * over a length limit with `on_overflow="raise"`
+    fn scan_str(&self, txt: &str) -> PyResult<bool> {
+        Ok(self.matcher.try_scan_str(txt)?)
+    }
+
+    /// # Errors
+    /// * over a length limit with `on_overflow="raise"`
+    fn scan(&self, args: &Bound<'_, PyDict>) -> PyResult<bool> {
+        self.matcher.scan(args)
+    }
+
+    /// # Errors
+    /// * over a length limit with `on_overflow="raise"`
+    fn scan_any(&self, value: &Bound<'_, PyAny>) -> PyResult<bool> {
+        self.matcher.scan_any(value)
+    }
+
+    #[getter]
+    fn sample_rate(&self) -> f64 {
+        self.matcher.sample_rate()
+    }
+
+    /// scans the candidate evaluated, counted per name
+    #[getter]
+    fn sampled(&self) -> u64 {
+        self.matcher.shadow_metrics().sampled()
+    }
+
+    /// sampled scans the candidate would block but the active list allows
+    #[getter]
+    fn candidate_blocks(&self) -> u64 {
+        self.matcher.shadow_metrics().blocks()
+    }
+
+    /// sampled scans the candidate would allow but the active list blocks
+    #[getter]
+    fn candidate_allows(&self) -> u64 {
+        self.matcher.shadow_metrics().allows()
+    }
+}
@@ -0,0 +1,115 @@
+use ed25519_dalek::{Signature, Verifier, VerifyingKey};
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
+#[cfg(feature = "python")]
+use pyo3::types::PyBytes;
+#[cfg(feature = "python")]
+use pyo3_stub_gen::derive::{gen_stub_pyclass, gen_stub_pymethods};
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::word_list;
+
+fn invalid_data(msg: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg)
+}
+
+/// bytes of a hex string, None for odd length or non hex digits
+#[must_use]
+pub fn decode_hex(s: &str) -> Option<Vec<u8>> {
+    let s = s.trim();
+    if !s.len().is_multiple_of(2) {
+        return None;
+    }
+    (0..s.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(s.get(i..i + 2)?, 16).ok())
+        .collect()
+}
+
+/// loads word lists only when their detached ed25519 signature verifies,
+/// so tampered lists are rejected before an automaton is built
+#[cfg_attr(feature = "python", gen_stub_pyclass)]
+#[cfg_attr(
+    feature = "python",
+    pyclass(module = "deny_filter.plugin", frozen, skip_from_py_object)
+)]
+#[derive(Clone, Debug)]
+pub struct SignedLoader {
+    key: VerifyingKey,
+}
+
+impl SignedLoader {
+    /// # Errors
+    /// * not a 32 byte ed25519 public key
+    pub fn new(public_key: &[u8]) -> io::Result<Self> {
+        let bytes: &[u8; 32] = public_key
+            .try_into()
+            .map_err(|_| invalid_data("ed25519 public key must be 32 bytes"))?;
+        let key = VerifyingKey::from_bytes(bytes)
+            .map_err(|_| invalid_data("invalid ed25519 public key"))?;
+        Ok(Self { key })
+    }
+
+    /// words of `path` (see `word_list::load`) verified with the signature in `<path>.sig`,
+    /// 64 raw bytes or 128 hex digits
+    /// # Errors
+    /// * files cannot be read, malformed or not verifying signature, malformed list
+    pub fn load(&self, path: &Path) -> io::Result<Vec<String>> {
+        let mut sig_path = path.as_os_str().to_owned();
+        sig_path.push(".sig");
+        let signature = std::fs::read(PathBuf::from(sig_path))?;
+        let signature = if signature.len() == Signature::BYTE_SIZE {
+            signature
+        } else {
+            std::str::from_utf8(&signature)
+                .ok()
+                .and_then(decode_hex)
+                .ok_or_else(|| invalid_data("signature is neither 64 bytes nor hex"))?
+        };
+        self.load_with_signature(path, &signature)
+    }
+
+    /// words of `path` verified with a 64 byte `signature` over the file contents
+    /// # Errors
+    /// * file cannot be read, malformed or not verifying signature, malformed list
+    pub fn load_with_signature(&self, path: &Path, signature: &[u8]) -> io::Result<Vec<String>> {
+        let data = std::fs::read(path)?;
+        let signature = Signature::from_slice(signature)
+            .map_err(|_| invalid_data("ed25519 signature must be 64 bytes"))?;
+        self.key
+            .verify(&data, &signature)
+            .map_err(|_| invalid_data("word list signature does not verify"))?;
+        let data = String::from_utf8(data).map_err(|_| invalid_data("word list is not utf-8"))?;
+        word_list::parse(path, &data)
+    }
+}
+
+#[cfg(feature = "python")]
+#[gen_stub_pymethods]
+#[pymethods]
+impl SignedLoader {
+    /// loader verifying with a 32 byte ed25519 public key
+    /// # Errors
+    /// * not a 32 byte ed25519 public key
+    #[new]
+    fn py_new(public_key: &Bound<'_, PyBytes>) -> PyResult<Self> {
+        Ok(Self::new(public_key.as_bytes())?)
+    }
+
+    /// verified words of a word list file, the signature defaults to `<path>.sig`
+    /// # Errors
+    /// * unreadable files, tampered list or malformed signature
+    #[pyo3(name = "load", signature = (path, signature = None))]
+    #[allow(clippy::needless_pass_by_value)] // python passes an owned path
+    fn py_load(
+        &self,
+        path: PathBuf,
+        signature: Option<&Bound<'_, PyBytes>>,
+    ) -> PyResult<Vec<String>> {
+        Ok(match signature {
+            Some(signature) => self.load_with_signature(&path, signature.as_bytes())?,
+            None => self.load(&path)?,
+        })
+    }
+}
@@ -0,0 +1,70 @@
+use crate::matcher::Matcher;
+use crate::metrics::Timer;
+use crate::trace::ScanSpan;
+
+/// sql text without `/* */` and `--` comments, whitespace runs collapsed to one space;
+/// a comment joins the text around it, so `SEL/**/ECT` reads `SELECT`; comment marks in
+/// quoted strings and identifiers are kept
+#[must_use]
+pub fn strip_sql(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut chars = text.chars().peekable();
+    let mut quote = None;
+    let mut space = false;
+    while let Some(c) = chars.next() {
+        if let Some(q) = quote {
+            // a doubled quote is an escaped one and closes and reopens the string
+            if c == q {
+                quote = None;
+            }
+            out.push(c);
+            continue;
+        }
+        match (c, chars.peek()) {
+            ('/', Some('*')) => {
+                chars.next();
+                let mut last = ' ';
+                for c in chars.by_ref() {
+                    if last == '*' && c == '/' {
+                        break;
+                    }
+                    last = c;
+                }
+                continue;
+            }
+            ('-', Some('-')) => {
+                for c in chars.by_ref() {
+                    if c == '\n' {
+                        space = true;
+                        break;
+                    }
+                }
+                continue;
+            }
+            _ => {}
+        }
+        if c.is_whitespace() {
+            space = true;
+            continue;
+        }
+        if space && !out.is_empty() {
+            out.push(' ');
+        }
+        space = false;
+        if matches!(c, '\'' | '"' | '`') {
+            quote = Some(c);
+        }
+        out.push(c);
+    }
+    out
+}
+
+/// whether sql text has a deny word as written or with its comments stripped and its
+/// whitespace collapsed, see `strip_sql`, e.g. `SEL/**/ECT voila`
+pub fn scan_sql<M: Matcher + ?Sized>(matcher: &M, text: &str) -> bool {
+    let span = ScanSpan::enter(matcher.metrics(), "scan_sql");
+    let timer = Timer::start();
+    let found = matcher.check(text) || matcher.check(&strip_sql(text));
+    span.finish(timer.elapsed(), text.len(), found);
+    found
+}
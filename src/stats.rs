@@ -0,0 +1,79 @@
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
+#[cfg(feature = "python")]
+use pyo3_stub_gen::derive::{gen_stub_pyclass, gen_stub_pymethods};
+
+/// size of the compiled automata, summed over the automata of all match kinds
+#[cfg_attr(feature = "python", gen_stub_pyclass)]
+#[cfg_attr(
+    feature = "python",
+    pyclass(module = "deny_filter.matchers", frozen, get_all, skip_from_py_object)
+)]
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Stats {
+    /// compiled (unique, normalized) patterns
+    pub patterns: usize,
+    /// states of the pattern tries, one per distinct pattern prefix plus the roots
+    pub states: usize,
+    /// shortest normalized pattern in bytes, 0 without patterns
+    pub min_pattern_len: usize,
+    /// longest normalized pattern in bytes
+    pub max_pattern_len: usize,
+    /// heap bytes of the automata as reported by the backend crates
+    pub heap_bytes: usize,
+    /// backend picked by `DenyListAuto`, None for the other matchers
+    pub backend: Option<String>,
+}
+
+impl Stats {
+    /// adds one automaton of `patterns` using `heap_bytes`
+    pub(crate) fn add(&mut self, patterns: &[String], heap_bytes: usize) {
+        let min = patterns.iter().map(String::len).min().unwrap_or(0);
+        let max = patterns.iter().map(String::len).max().unwrap_or(0);
+        self.min_pattern_len = if self.patterns == 0 {
+            min
+        } else {
+            self.min_pattern_len.min(min)
+        };
+        self.max_pattern_len = self.max_pattern_len.max(max);
+        self.patterns += patterns.len();
+        self.states += trie_states(patterns);
+        self.heap_bytes += heap_bytes;
+    }
+}
+
+/// nodes of the byte trie of `patterns`: every pattern adds the bytes
+/// after its longest common prefix with the previous one in sorted order
+fn trie_states(patterns: &[String]) -> usize {
+    let mut sorted: Vec<&[u8]> = patterns.iter().map(String::as_bytes).collect();
+    sorted.sort_unstable();
+    let mut states = 1;
+    let mut previous: &[u8] = &[];
+    for pattern in sorted {
+        let common = previous
+            .iter()
+            .zip(pattern)
+            .take_while(|(a, b)| a == b)
+            .count();
+        states += pattern.len() - common;
+        previous = pattern;
+    }
+    states
+}
+
+#[cfg(feature = "python")]
+#[gen_stub_pymethods]
+#[pymethods]
+impl Stats {
+    fn __repr__(&self) -> String {
+        let backend = self
+            .backend
+            .as_ref()
+            .map(|backend| format!(", backend={backend:?}"))
+            .unwrap_or_default();
+        format!(
+            "Stats(patterns={}, states={}, min_pattern_len={}, max_pattern_len={}, heap_bytes={}{backend})",
+            self.patterns, self.states, self.min_pattern_len, self.max_pattern_len, self.heap_bytes
+        )
+    }
+}
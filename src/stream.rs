@@ -0,0 +1,430 @@
+#[cfg(feature = "python")]
+use pyo3::exceptions::{PyStopAsyncIteration, PyStopIteration, PyTypeError};
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
+#[cfg(feature = "python")]
+use pyo3::types::{PyBytes, PyList, PyString};
+#[cfg(feature = "python")]
+use pyo3_stub_gen::derive::{gen_stub_pyclass, gen_stub_pymethods};
+use std::collections::VecDeque;
+#[cfg(feature = "python")]
+use std::sync::{Mutex, PoisonError};
+
+use crate::matcher::Matcher;
+use crate::matches::Match;
+
+/// fewest characters kept between chunks, covers short regexes and proximity rules
+pub const MIN_WINDOW: usize = 64;
+
+/// bytes of a text scanned at a time by `iter_matches`
+pub const ITER_CHUNK: usize = 64 << 10;
+
+/// scans text arriving in chunks, deny words split across chunks are found;
+/// the last `window` characters are rescanned with each chunk, a match touching the
+/// end of the input seen so far is held back until more input shows where it ends
+#[derive(Clone, Debug)]
+pub struct StreamScanner {
+    /// characters kept from earlier chunks
+    window: usize,
+    /// kept characters and the current chunk
+    buffer: String,
+    /// incomplete utf-8 sequence at the end of the last byte chunk
+    pending: Vec<u8>,
+    /// stream byte offset of the buffer start
+    offset: usize,
+    /// stream code point offset of the buffer start
+    char_offset: usize,
+    /// stream byte offset before which matches were reported
+    reported: usize,
+}
+
+impl StreamScanner {
+    /// keeps `window` characters between chunks, at least one
+    #[must_use]
+    pub fn new(window: usize) -> Self {
+        Self {
+            window: window.max(1),
+            buffer: String::new(),
+            pending: Vec::new(),
+            offset: 0,
+            char_offset: 0,
+            reported: 0,
+        }
+    }
+
+    /// window of the longest pattern of `matcher` and its boundary, at least `MIN_WINDOW`
+    #[must_use]
+    pub fn for_matcher<M: Matcher + ?Sized>(matcher: &M) -> Self {
+        Self::new((matcher.stats().max_pattern_len + 1).max(MIN_WINDOW))
+    }
+
+    /// new matches once `chunk` is added, offsets count from the start of the stream
+    pub fn feed<M: Matcher + ?Sized>(&mut self, matcher: &M, chunk: &str) -> Vec<Match> {
+        if !self.pending.is_empty() {
+            // a text chunk ends an incomplete byte sequence
+            self.buffer.push(char::REPLACEMENT_CHARACTER);
+            self.pending.clear();
+        }
+        self.buffer.push_str(chunk);
+        self.scan(matcher, false)
+    }
+
+    /// as `feed` for utf-8 bytes, a sequence split across chunks is joined,
+    /// invalid bytes become U+FFFD
+    pub fn feed_bytes<M: Matcher + ?Sized>(&mut self, matcher: &M, chunk: &[u8]) -> Vec<Match> {
+        self.pending.extend_from_slice(chunk);
+        let rest = std::mem::take(&mut self.pending);
+        let mut data = rest.as_slice();
+        loop {
+            match std::str::from_utf8(data) {
+                Ok(text) => {
+                    self.buffer.push_str(text);
+                    break;
+                }
+                Err(e) => {
+                    let (valid, after) = data.split_at(e.valid_up_to());
+                    self.buffer
+                        .push_str(std::str::from_utf8(valid).unwrap_or_default());
+                    let Some(invalid) = e.error_len() else {
+                        self.pending = after.to_vec();
+                        break;
+                    };
+                    self.buffer.push(char::REPLACEMENT_CHARACTER);
+                    data = &after[invalid..];
+                }
+            }
+        }
+        self.scan(matcher, false)
+    }
+
+    /// matches held back at the end of the stream
+    pub fn finish<M: Matcher + ?Sized>(&mut self, matcher: &M) -> Vec<Match> {
+        if !self.pending.is_empty() {
+            self.buffer.push(char::REPLACEMENT_CHARACTER);
+            self.pending.clear();
+        }
+        self.scan(matcher, true)
+    }
+
+    fn scan<M: Matcher + ?Sized>(&mut self, matcher: &M, last: bool) -> Vec<Match> {
+        let mut found = Vec::new();
+        for m in matcher.find_all(&self.buffer) {
+            if self.offset + m.start < self.reported {
+                continue;
+            }
+            // the word may go on or lose its word boundary in the next chunk
+            if !last && m.end == self.buffer.len() {
+                break;
+            }
+            self.reported = self.offset + m.end;
+            found.push(Match {
+                word: m.word,
+                start: self.offset + m.start,
+                end: self.offset + m.end,
+                char_start: self.char_offset + m.char_start,
+                char_end: self.char_offset + m.char_end,
+            });
+        }
+        let cut = self
+            .buffer
+            .char_indices()
+            .rev()
+            .nth(self.window - 1)
+            .map_or(0, |(i, _)| i);
+        self.char_offset += self.buffer[..cut].chars().count();
+        self.offset += cut;
+        self.buffer.drain(..cut);
+        found
+    }
+}
+
+/// matches of a text scanned `ITER_CHUNK` bytes at a time, the state of `iter_matches`
+#[derive(Clone, Debug)]
+struct Chunked {
+    scanner: StreamScanner,
+    /// byte offset of the text not fed yet
+    fed: usize,
+    pending: VecDeque<Match>,
+    finished: bool,
+}
+
+impl Chunked {
+    fn new(scanner: StreamScanner) -> Self {
+        Self {
+            scanner,
+            fed: 0,
+            pending: VecDeque::new(),
+            finished: false,
+        }
+    }
+
+    /// next match of `text`, `feed` scans a chunk or finishes with None
+    fn next(
+        &mut self,
+        text: &str,
+        mut feed: impl FnMut(&mut StreamScanner, Option<&str>) -> Vec<Match>,
+    ) -> Option<Match> {
+        while self.pending.is_empty() && !self.finished {
+            if self.fed < text.len() {
+                let mut end = (self.fed + ITER_CHUNK).min(text.len());
+                while !text.is_char_boundary(end) {
+                    end += 1;
+                }
+                let found = feed(&mut self.scanner, Some(&text[self.fed..end]));
+                self.pending.extend(found);
+                self.fed = end;
+            } else {
+                self.pending.extend(feed(&mut self.scanner, None));
+                self.finished = true;
+            }
+        }
+        self.pending.pop_front()
+    }
+}
+
+/// lazy `find_all` of a large text: matches are found a chunk at a time as the iteration
+/// goes, so only one chunk's matches are held
+pub fn iter_matches<'a, M: Matcher + ?Sized>(
+    matcher: &'a M,
+    text: &'a str,
+) -> impl Iterator<Item = Match> + 'a {
+    let mut chunked = Chunked::new(StreamScanner::for_matcher(matcher));
+    std::iter::from_fn(move || {
+        chunked.next(text, |scanner, chunk| match chunk {
+            Some(chunk) => scanner.feed(matcher, chunk),
+            None => scanner.finish(matcher),
+        })
+    })
+}
+
+/// `M::find_all` through a python object of class `M`
+#[cfg(feature = "python")]
+type Feed =
+    fn(&Bound<'_, PyAny>, &mut StreamScanner, Option<&Bound<'_, PyAny>>) -> PyResult<Vec<Match>>;
+
+/// feeds a str or bytes chunk, None finishes the stream
+#[cfg(feature = "python")]
+fn feed_as<M: Matcher + pyo3::PyClass>(
+    matcher: &Bound<'_, PyAny>,
+    scanner: &mut StreamScanner,
+    chunk: Option<&Bound<'_, PyAny>>,
+) -> PyResult<Vec<Match>> {
+    let matcher = matcher.cast::<M>()?.borrow();
+    let Some(chunk) = chunk else {
+        return Ok(scanner.finish(&*matcher));
+    };
+    if let Ok(bytes) = chunk.cast::<PyBytes>() {
+        return Ok(scanner.feed_bytes(&*matcher, bytes.as_bytes()));
+    }
+    match chunk.extract::<&str>() {
+        Ok(text) => Ok(scanner.feed(&*matcher, text)),
+        Err(_) => Err(PyTypeError::new_err("stream chunks must be str or bytes")),
+    }
+}
+
+/// `feed_as` of a text chunk
+#[cfg(feature = "python")]
+type FeedText = fn(&Bound<'_, PyAny>, &mut StreamScanner, Option<&str>) -> PyResult<Vec<Match>>;
+
+#[cfg(feature = "python")]
+fn feed_text_as<M: Matcher + pyo3::PyClass>(
+    matcher: &Bound<'_, PyAny>,
+    scanner: &mut StreamScanner,
+    chunk: Option<&str>,
+) -> PyResult<Vec<Match>> {
+    let matcher = matcher.cast::<M>()?.borrow();
+    Ok(match chunk {
+        Some(chunk) => scanner.feed(&*matcher, chunk),
+        None => scanner.finish(&*matcher),
+    })
+}
+
+/// async iterator of verdicts: for each chunk of the source the new matches (empty when
+/// clean), and a last verdict with the matches held back at the end of the stream
+#[cfg(feature = "python")]
+#[gen_stub_pyclass]
+#[pyclass(module = "deny_filter.matchers", frozen)]
+pub struct StreamScan {
+    matcher: Py<PyAny>,
+    feed: Feed,
+    /// async iterator of the chunks
+    source: Py<PyAny>,
+    /// None once the source is exhausted
+    scanner: Mutex<Option<StreamScanner>>,
+}
+
+#[cfg(feature = "python")]
+impl StreamScan {
+    fn verdict(&self, py: Python<'_>, chunk: Option<&Bound<'_, PyAny>>) -> PyResult<Py<PyAny>> {
+        let mut state = self.scanner.lock().unwrap_or_else(PoisonError::into_inner);
+        let Some(scanner) = state.as_mut() else {
+            return Err(PyStopAsyncIteration::new_err(()));
+        };
+        let found = (self.feed)(self.matcher.bind(py), scanner, chunk)?;
+        if chunk.is_none() {
+            *state = None;
+        }
+        Ok(PyList::new(py, found)?.into_any().unbind())
+    }
+}
+
+#[cfg(feature = "python")]
+#[gen_stub_pymethods]
+#[pymethods]
+impl StreamScan {
+    fn __aiter__(slf: Py<Self>) -> Py<Self> {
+        slf
+    }
+
+    /// awaitable of the next verdict
+    /// # Errors
+    /// * the stream has ended
+    fn __anext__(slf: &Bound<'_, Self>) -> PyResult<NextVerdict> {
+        let scan = slf.get();
+        if scan
+            .scanner
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .is_none()
+        {
+            return Err(PyStopAsyncIteration::new_err(()));
+        }
+        let next = scan.source.bind(slf.py()).call_method0("__anext__")?;
+        Ok(NextVerdict {
+            scan: slf.clone().unbind(),
+            waiting: next.call_method0("__await__")?.unbind(),
+        })
+    }
+}
+
+/// awaitable of one verdict: drives the source's `__anext__` and scans the chunk it returns
+#[cfg(feature = "python")]
+#[gen_stub_pyclass]
+#[pyclass(module = "deny_filter.matchers", frozen)]
+pub struct NextVerdict {
+    scan: Py<StreamScan>,
+    /// iterator of the source's `__anext__` awaitable
+    waiting: Py<PyAny>,
+}
+
+#[cfg(feature = "python")]
+impl NextVerdict {
+    /// passes event loop values through, scans the chunk once the source returns it
+    fn resume(&self, py: Python<'_>, step: PyResult<Bound<'_, PyAny>>) -> PyResult<Py<PyAny>> {
+        let err = match step {
+            Ok(value) => return Ok(value.unbind()),
+            Err(err) => err,
+        };
+        let scan = self.scan.get();
+        let verdict = if err.is_instance_of::<PyStopIteration>(py) {
+            let chunk = err.value(py).getattr("value")?;
+            scan.verdict(py, Some(&chunk))?
+        } else if err.is_instance_of::<PyStopAsyncIteration>(py) {
+            scan.verdict(py, None)?
+        } else {
+            return Err(err);
+        };
+        Err(PyStopIteration::new_err((verdict,)))
+    }
+}
+
+#[cfg(feature = "python")]
+#[gen_stub_pymethods]
+#[pymethods]
+impl NextVerdict {
+    fn __await__(slf: Py<Self>) -> Py<Self> {
+        slf
+    }
+
+    /// # Errors
+    /// * `StopIteration` with the verdict, errors of the source
+    fn __next__(&self, py: Python<'_>) -> PyResult<Py<PyAny>> {
+        let step = self.waiting.bind(py).call_method0("__next__");
+        self.resume(py, step)
+    }
+
+    /// # Errors
+    /// * `StopIteration` with the verdict, errors of the source
+    fn send(&self, py: Python<'_>, value: &Bound<'_, PyAny>) -> PyResult<Py<PyAny>> {
+        let step = self.waiting.bind(py).call_method1("send", (value,));
+        self.resume(py, step)
+    }
+
+    /// # Errors
+    /// * `StopIteration` with the verdict, errors of the source
+    fn throw(&self, py: Python<'_>, error: &Bound<'_, PyAny>) -> PyResult<Py<PyAny>> {
+        let step = self.waiting.bind(py).call_method1("throw", (error,));
+        self.resume(py, step)
+    }
+}
+
+/// iterator of the `Match`es of a str, found a chunk at a time as the iteration goes
+#[cfg(feature = "python")]
+#[gen_stub_pyclass]
+#[pyclass(module = "deny_filter.matchers", frozen)]
+pub struct MatchIter {
+    matcher: Py<PyAny>,
+    feed: FeedText,
+    text: Py<PyString>,
+    state: Mutex<Chunked>,
+}
+
+#[cfg(feature = "python")]
+#[gen_stub_pymethods]
+#[pymethods]
+impl MatchIter {
+    fn __iter__(slf: PyRef<'_, Self>) -> PyRef<'_, Self> {
+        slf
+    }
+
+    /// # Errors
+    /// * the text cannot be encoded as utf-8
+    fn __next__(&self, py: Python<'_>) -> PyResult<Option<Match>> {
+        let text = self.text.bind(py);
+        let matcher = self.matcher.bind(py);
+        let mut state = self.state.lock().unwrap_or_else(PoisonError::into_inner);
+        let mut failed = None;
+        let found = state.next(text.to_str()?, |scanner, chunk| {
+            (self.feed)(matcher, scanner, chunk).unwrap_or_else(|e| {
+                failed = Some(e);
+                Vec::new()
+            })
+        });
+        failed.map_or(Ok(found), Err)
+    }
+}
+
+/// `iter_matches` of the python matchers
+#[cfg(feature = "python")]
+#[must_use]
+pub fn py_iter_matches<M: Matcher + pyo3::PyClass>(
+    matcher: PyRef<'_, M>,
+    text: &Bound<'_, PyString>,
+) -> MatchIter {
+    let scanner = StreamScanner::for_matcher(&*matcher);
+    let Ok(matcher) = matcher.into_pyobject(text.py());
+    MatchIter {
+        matcher: matcher.into_any().unbind(),
+        feed: feed_text_as::<M>,
+        text: text.clone().unbind(),
+        state: Mutex::new(Chunked::new(scanner)),
+    }
+}
+
+/// `scan_stream` of the python matchers
+/// # Errors
+/// * `chunks` is not an async iterable
+#[cfg(feature = "python")]
+pub fn py_scan_stream<M: Matcher + pyo3::PyClass>(
+    matcher: PyRef<'_, M>,
+    chunks: &Bound<'_, PyAny>,
+) -> PyResult<StreamScan> {
+    let scanner = StreamScanner::for_matcher(&*matcher);
+    let py = matcher.py();
+    Ok(StreamScan {
+        matcher: matcher.into_pyobject(py)?.into_any().unbind(),
+        feed: feed_as::<M>,
+        source: chunks.call_method0("__aiter__")?.unbind(),
+        scanner: Mutex::new(Some(scanner)),
+    })
+}
@@ -0,0 +1,259 @@
+#[cfg(all(feature = "tracing", feature = "python"))]
+use pyo3::prelude::*;
+#[cfg(all(feature = "tracing", feature = "python"))]
+use pyo3_stub_gen::derive::gen_stub_pyfunction;
+#[cfg(feature = "tracing")]
+use std::sync::atomic::{AtomicBool, Ordering::Relaxed};
+use std::time::Duration;
+
+use crate::metrics::BackendMetrics;
+
+/// matched deny words are traced only when set, they may be as sensitive as the payload
+#[cfg(feature = "tracing")]
+static UNSAFE_DEBUG: AtomicBool = AtomicBool::new(false);
+
+/// traces the deny word of each match, off by default; never enable it where the
+/// traces leave the trust boundary of the scanned payloads
+#[cfg(feature = "tracing")]
+pub fn set_unsafe_debug(on: bool) {
+    UNSAFE_DEBUG.store(on, Relaxed);
+}
+
+/// whether matched deny words are traced
+#[cfg(feature = "tracing")]
+#[must_use]
+pub fn unsafe_debug() -> bool {
+    UNSAFE_DEBUG.load(Relaxed)
+}
+
+/// one scan call: a `deny_filter.scan` span with the `backend`, `kind`, scanned `bytes`,
+/// `matched` and `elapsed_us` fields with the `tracing` feature; closing it records the
+/// scan and its bytes in the backend metrics
+pub struct ScanSpan<'m> {
+    metrics: &'m BackendMetrics,
+    #[cfg(feature = "tracing")]
+    span: tracing::span::EnteredSpan,
+}
+
+impl<'m> ScanSpan<'m> {
+    /// enters the span of a scan of `kind`, e.g. `scan_json`
+    #[must_use]
+    pub fn enter(metrics: &'m BackendMetrics, kind: &'static str) -> Self {
+        #[cfg(not(feature = "tracing"))]
+        let _ = kind;
+        Self {
+            metrics,
+            #[cfg(feature = "tracing")]
+            span: tracing::debug_span!(
+                "deny_filter.scan",
+                backend = metrics.name(),
+                kind,
+                bytes = tracing::field::Empty,
+                matched = tracing::field::Empty,
+                elapsed_us = tracing::field::Empty,
+            )
+            .entered(),
+        }
+    }
+
+    /// records the scan and its scanned bytes in the backend metrics and closes the span
+    pub fn finish(self, elapsed: Duration, scanned: usize, matched: bool) {
+        self.metrics.record_scan(elapsed, matched);
+        self.metrics.record_bytes(scanned);
+        #[cfg(feature = "tracing")]
+        {
+            let elapsed_us = u64::try_from(elapsed.as_micros()).unwrap_or(u64::MAX);
+            self.span.record("bytes", scanned);
+            self.span.record("matched", matched);
+            self.span.record("elapsed_us", elapsed_us);
+        }
+        #[cfg(not(feature = "tracing"))]
+        let _ = scanned;
+    }
+}
+
+/// traces a match in the current scan, with its deny word only in unsafe debug mode
+#[inline]
+pub fn matched(pattern: &str) {
+    #[cfg(feature = "tracing")]
+    if unsafe_debug() {
+        tracing::debug!(pattern, "deny word matched");
+    } else {
+        tracing::debug!("deny word matched");
+    }
+    #[cfg(not(feature = "tracing"))]
+    let _ = pattern;
+}
+
+#[cfg(all(feature = "tracing", feature = "python"))]
+mod py_log {
+    use pyo3::prelude::*;
+    use std::collections::HashMap;
+    use std::fmt::{self, Write as _};
+    use std::sync::Mutex;
+    use std::sync::PoisonError;
+    use std::sync::atomic::{AtomicU64, Ordering::Relaxed};
+    use tracing::field::{Field, Visit};
+    use tracing::span::{Attributes, Id, Record};
+    use tracing::{Event, Level, Metadata, Subscriber};
+
+    /// `name=value` pairs of span or event fields, the message first
+    #[derive(Default)]
+    struct Fields(String);
+
+    impl Visit for Fields {
+        fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+            if !self.0.is_empty() {
+                self.0.push(' ');
+            }
+            let _ = if field.name() == "message" {
+                write!(self.0, "{value:?}")
+            } else {
+                write!(self.0, "{}={value:?}", field.name())
+            };
+        }
+
+        fn record_str(&mut self, field: &Field, value: &str) {
+            self.record_debug(field, &format_args!("{value}"));
+        }
+    }
+
+    /// open span: name, level, fields and handles not closed yet
+    struct OpenSpan {
+        name: &'static str,
+        level: Level,
+        fields: Fields,
+        refs: usize,
+    }
+
+    /// forwards closed spans and events to the python logger `deny_filter`
+    pub(super) struct PyLogSubscriber {
+        level: Level,
+        next_id: AtomicU64,
+        spans: Mutex<HashMap<u64, OpenSpan>>,
+    }
+
+    impl PyLogSubscriber {
+        pub(super) fn new(level: Level) -> Self {
+            Self {
+                level,
+                next_id: AtomicU64::new(1),
+                spans: Mutex::new(HashMap::new()),
+            }
+        }
+
+        fn log(level: Level, message: &str) {
+            // python logging levels
+            let level = match level {
+                Level::ERROR => 40,
+                Level::WARN => 30,
+                Level::INFO => 20,
+                Level::DEBUG | Level::TRACE => 10,
+            };
+            Python::attach(|py| {
+                let logger = py
+                    .import("logging")
+                    .and_then(|logging| logging.call_method1("getLogger", ("deny_filter",)));
+                if let Ok(logger) = logger {
+                    let _ = logger.call_method1("log", (level, message));
+                }
+            });
+        }
+    }
+
+    impl Subscriber for PyLogSubscriber {
+        fn enabled(&self, metadata: &Metadata<'_>) -> bool {
+            metadata.level() <= &self.level
+        }
+
+        fn new_span(&self, span: &Attributes<'_>) -> Id {
+            let id = self.next_id.fetch_add(1, Relaxed);
+            let mut fields = Fields::default();
+            span.record(&mut fields);
+            let open = OpenSpan {
+                name: span.metadata().name(),
+                level: *span.metadata().level(),
+                fields,
+                refs: 1,
+            };
+            self.spans
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner)
+                .insert(id, open);
+            Id::from_u64(id)
+        }
+
+        fn record(&self, span: &Id, values: &Record<'_>) {
+            if let Some(open) = self
+                .spans
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner)
+                .get_mut(&span.into_u64())
+            {
+                values.record(&mut open.fields);
+            }
+        }
+
+        fn clone_span(&self, span: &Id) -> Id {
+            if let Some(open) = self
+                .spans
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner)
+                .get_mut(&span.into_u64())
+            {
+                open.refs += 1;
+            }
+            span.clone()
+        }
+
+        fn record_follows_from(&self, _span: &Id, _follows: &Id) {}
+
+        fn event(&self, event: &Event<'_>) {
+            let mut fields = Fields::default();
+            event.record(&mut fields);
+            Self::log(*event.metadata().level(), &fields.0);
+        }
+
+        fn enter(&self, _span: &Id) {}
+
+        fn exit(&self, _span: &Id) {}
+
+        fn try_close(&self, span: Id) -> bool {
+            let closed = {
+                let mut spans = self.spans.lock().unwrap_or_else(PoisonError::into_inner);
+                let Some(open) = spans.get_mut(&span.into_u64()) else {
+                    return false;
+                };
+                open.refs -= 1;
+                if open.refs > 0 {
+                    return false;
+                }
+                spans.remove(&span.into_u64())
+            };
+            // logged once the lock is released, python may trace a scan of its own
+            if let Some(open) = closed {
+                Self::log(open.level, &format!("{} {}", open.name, open.fields.0));
+            }
+            true
+        }
+    }
+}
+
+/// routes the scan spans and match events of this module to the python logger
+/// `deny_filter`, e.g. to export them with an OpenTelemetry logging handler; `level` is
+/// "error", "warn", "info", "debug" or "trace", scan spans are at debug level;
+/// `unsafe_debug` also logs the matched deny words
+/// # Errors
+/// * unknown level, another tracing subscriber is already installed
+#[cfg(all(feature = "tracing", feature = "python"))]
+#[gen_stub_pyfunction]
+#[pyfunction]
+#[pyo3(signature = (level = "debug", unsafe_debug = false))]
+pub fn enable_tracing(level: &str, unsafe_debug: bool) -> PyResult<()> {
+    let level: tracing::Level = level
+        .parse()
+        .map_err(|_| pyo3::exceptions::PyValueError::new_err(format!("unknown level {level:?}")))?;
+    set_unsafe_debug(unsafe_debug);
+    tracing::subscriber::set_global_default(py_log::PyLogSubscriber::new(level))
+        .map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(e.to_string()))
+}
@@ -0,0 +1,179 @@
+use pyo3::exceptions::PyBaseException;
+use pyo3::prelude::*;
+use pyo3::types::{PyBytes, PyFloat, PyFrame, PyInt, PyTraceback, PyTuple, PyType};
+
+use crate::limits::{Budget, LimitError};
+use crate::matcher::Matcher;
+
+/// whether `value` is a `traceback.FrameSummary`, the frames of `traceback.extract_tb`
+/// and of a `StackSummary`; the class is recognized by name in the mro, not cached, as
+/// each interpreter has its own `traceback` module
+fn is_frame_summary(value: &Bound<'_, PyAny>) -> bool {
+    value.get_type().mro().iter().any(|class| {
+        class.cast::<PyType>().is_ok_and(|class| {
+            class
+                .fully_qualified_name()
+                .is_ok_and(|name| name == "traceback.FrameSummary")
+        })
+    })
+}
+
+/// whether `scan_any` scans `value` as an error report: an exception, a traceback, a
+/// frame or a `traceback.FrameSummary`; bytes, numbers and None are none of them
+#[must_use]
+pub fn is_error(value: &Bound<'_, PyAny>) -> bool {
+    if value.is_none()
+        || value.is_instance_of::<PyBytes>()
+        || value.is_instance_of::<PyInt>()
+        || value.is_instance_of::<PyFloat>()
+    {
+        return false;
+    }
+    value.is_instance_of::<PyBaseException>()
+        || value.is_instance_of::<PyTraceback>()
+        || value.is_instance_of::<PyFrame>()
+        || is_frame_summary(value)
+}
+
+/// attribute of `value`, None when missing, None or failing
+fn attr<'py>(value: &Bound<'py, PyAny>, name: &str) -> Option<Bound<'py, PyAny>> {
+    value.getattr(name).ok().filter(|v| !v.is_none())
+}
+
+/// whether a str attribute of `value` has a deny word
+fn check_attr<M: Matcher + ?Sized>(
+    matcher: &M,
+    value: &Bound<'_, PyAny>,
+    name: &str,
+    budget: &mut Budget,
+) -> Result<bool, LimitError> {
+    match attr(value, name) {
+        Some(s) => match s.extract::<&str>() {
+            Ok(s) => matcher.check_within(s, budget),
+            Err(_) => Ok(false),
+        },
+        None => Ok(false),
+    }
+}
+
+/// whether a str value of the local variables of a frame has a deny word; other values
+/// are not followed, locals may reference the frame itself
+fn check_locals<M: Matcher + ?Sized>(
+    matcher: &M,
+    locals: Option<Bound<'_, PyAny>>,
+    budget: &mut Budget,
+) -> Result<bool, LimitError> {
+    let Some(items) = locals
+        .and_then(|locals| locals.call_method0("items").ok())
+        .and_then(|items| items.try_iter().ok())
+    else {
+        return Ok(false);
+    };
+    let items = items
+        .flatten()
+        .filter_map(|item| item.extract::<(Bound<'_, PyAny>, Bound<'_, PyAny>)>().ok());
+    for (key, value) in items {
+        if key
+            .extract::<&str>()
+            .is_ok_and(|key| matcher.ignored_keys().skips(key))
+        {
+            continue;
+        }
+        if let Ok(s) = value.extract::<&str>()
+            && matcher.check_within(s, budget)?
+        {
+            return Ok(true);
+        }
+    }
+    Ok(false)
+}
+
+/// whether an exception has a deny word in its message, `args` or `__notes__`, or in its
+/// traceback, cause, context or the exceptions of a group, each exception once per
+/// payload, also when its notes or args hold it; a traceback has them in the function
+/// name, file name and str locals of its frames
+/// # Errors
+/// * over a length limit with `Overflow::Raise`
+pub fn scan_error<M: Matcher + ?Sized>(
+    matcher: &M,
+    value: &Bound<'_, PyAny>,
+    budget: &mut Budget,
+) -> Result<bool, LimitError> {
+    if let Some(verdict) = budget.enter()? {
+        return Ok(verdict);
+    }
+    let found = scan_chain(matcher, value, budget);
+    budget.leave();
+    found
+}
+
+fn scan_chain<M: Matcher + ?Sized>(
+    matcher: &M,
+    value: &Bound<'_, PyAny>,
+    budget: &mut Budget,
+) -> Result<bool, LimitError> {
+    // causes, contexts, notes and args may form cycles
+    let mut pending = vec![value.clone()];
+    while let Some(value) = pending.pop() {
+        if !budget.visit(value.as_ptr() as usize) {
+            continue;
+        }
+        if value.is_instance_of::<PyBaseException>() {
+            let args = attr(&value, "args");
+            let args = args.as_ref().and_then(|args| args.cast::<PyTuple>().ok());
+            for arg in args.into_iter().flatten() {
+                if arg.is_instance_of::<PyBaseException>() {
+                    pending.push(arg);
+                } else if matcher.scan_value(&arg, budget)? {
+                    return Ok(true);
+                }
+            }
+            // the message is the only argument unless `__str__` is overridden
+            let single = args
+                .filter(|args| args.len() == 1)
+                .and_then(|args| args.get_item(0).ok());
+            if let Ok(message) = value.str()
+                && !single.is_some_and(|arg| arg.eq(&message).unwrap_or(false))
+                && matcher.check_within(&message.to_string_lossy(), budget)?
+            {
+                return Ok(true);
+            }
+            if let Some(notes) = attr(&value, "__notes__")
+                && matcher.scan_value(&notes, budget)?
+            {
+                return Ok(true);
+            }
+            if let Some(group) = attr(&value, "exceptions")
+                && let Ok(group) = group.cast::<PyTuple>()
+            {
+                pending.extend(group.iter());
+            }
+            for name in ["__traceback__", "__cause__", "__context__"] {
+                pending.extend(attr(&value, name));
+            }
+        } else if value.is_instance_of::<PyTraceback>() {
+            pending.extend(attr(&value, "tb_next"));
+            pending.extend(attr(&value, "tb_frame"));
+        } else if value.is_instance_of::<PyFrame>() {
+            if let Some(code) = attr(&value, "f_code")
+                && (check_attr(matcher, &code, "co_name", budget)?
+                    || check_attr(matcher, &code, "co_filename", budget)?)
+            {
+                return Ok(true);
+            }
+            if check_locals(matcher, attr(&value, "f_locals"), budget)? {
+                return Ok(true);
+            }
+        } else if is_frame_summary(&value) {
+            for name in ["name", "filename", "line"] {
+                if check_attr(matcher, &value, name, budget)? {
+                    return Ok(true);
+                }
+            }
+            if check_locals(matcher, attr(&value, "locals"), budget)? {
+                return Ok(true);
+            }
+        }
+    }
+    Ok(false)
+}
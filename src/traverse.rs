@@ -0,0 +1,480 @@
+use rmp::Marker;
+use serde_json::Value;
+use std::fmt;
+use std::ops::Range;
+
+use crate::limits::{LimitError, Limits};
+use crate::options::Overflow;
+
+/// one step of the path from the document root to a scanned string
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Segment<'a> {
+    Key(&'a str),
+    Index(usize),
+}
+
+/// formats a path as `$.key[0].other`
+#[must_use]
+pub fn format_path(path: &[Segment<'_>]) -> String {
+    let mut out = String::from("$");
+    for segment in path {
+        match segment {
+            Segment::Key(key) => {
+                out.push('.');
+                out.push_str(key);
+            }
+            Segment::Index(i) => {
+                out.push('[');
+                out.push_str(&i.to_string());
+                out.push(']');
+            }
+        }
+    }
+    out
+}
+
+/// malformed msgpack input, compressed input that cannot be decompressed, or input over
+/// a length limit
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Error {
+    /// buffer ended in the middle of a value
+    UnexpectedEof,
+    /// reserved marker byte 0xc1
+    ReservedMarker,
+    /// string value is not valid utf-8
+    InvalidUtf8,
+    /// bytes left after the top level value
+    TrailingBytes(usize),
+    /// corrupt gzip or zlib data
+    Decompress(String),
+    /// decompressed data over the size limit
+    TooLarge(usize),
+    /// a string or the payload over a length limit with `Overflow::Raise`
+    Limit(LimitError),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::UnexpectedEof => write!(f, "unexpected end of msgpack data"),
+            Error::ReservedMarker => write!(f, "reserved msgpack marker 0xc1"),
+            Error::InvalidUtf8 => write!(f, "msgpack string is not valid utf-8"),
+            Error::TrailingBytes(n) => write!(f, "{n} trailing bytes after msgpack value"),
+            Error::Decompress(e) => write!(f, "invalid compressed data: {e}"),
+            Error::TooLarge(n) => write!(f, "decompressed data over {n} bytes"),
+            Error::Limit(e) => e.fmt(f),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<LimitError> for Error {
+    fn from(e: LimitError) -> Self {
+        Error::Limit(e)
+    }
+}
+
+#[cfg(feature = "python")]
+impl From<Error> for pyo3::PyErr {
+    fn from(e: Error) -> Self {
+        pyo3::exceptions::PyValueError::new_err(e.to_string())
+    }
+}
+
+/// callback receiving each string value with its path, returns true to stop
+pub type Visit<'v, 'a> = dyn FnMut(&[Segment<'a>], &'a str) -> bool + 'v;
+
+/// `Visit` also receiving the byte range of the encoded string, marker and length included
+pub type VisitSpan<'v, 'a> = dyn FnMut(&[Segment<'a>], &'a str, Range<usize>) -> bool + 'v;
+
+/// walks a json value and visits every string value (keys are not visited)
+/// returns true when the visitor stopped the walk
+pub fn json<'a>(value: &'a Value, visit: &mut Visit<'_, 'a>) -> bool {
+    let mut path = Vec::new();
+    walk_json(value, &mut path, visit)
+}
+
+fn walk_json<'a>(value: &'a Value, path: &mut Vec<Segment<'a>>, visit: &mut Visit<'_, 'a>) -> bool {
+    match value {
+        Value::String(s) => visit(path, s),
+        Value::Array(items) => {
+            for (i, item) in items.iter().enumerate() {
+                path.push(Segment::Index(i));
+                let stop = walk_json(item, path, visit);
+                path.pop();
+                if stop {
+                    return true;
+                }
+            }
+            false
+        }
+        Value::Object(map) => {
+            for (key, item) in map {
+                path.push(Segment::Key(key));
+                let stop = walk_json(item, path, visit);
+                path.pop();
+                if stop {
+                    return true;
+                }
+            }
+            false
+        }
+        Value::Null | Value::Bool(_) | Value::Number(_) => false,
+    }
+}
+
+/// key or index of a python dict/list item on the path of a string
+#[cfg(feature = "python")]
+enum PyStep {
+    Key(String),
+    Index(usize),
+}
+
+/// walks a python dict/list/str like `scan_any` and visits every str value (keys are
+/// not visited); returns true when the visitor stopped the walk
+/// # Errors
+/// * errors of the visitor, a dict key whose `str()` fails
+#[cfg(feature = "python")]
+pub fn python(
+    value: &pyo3::Bound<'_, pyo3::PyAny>,
+    visit: &mut dyn FnMut(&[Segment<'_>], &str) -> pyo3::PyResult<bool>,
+) -> pyo3::PyResult<bool> {
+    walk_python(value, &mut Vec::new(), visit)
+}
+
+#[cfg(feature = "python")]
+fn walk_python(
+    value: &pyo3::Bound<'_, pyo3::PyAny>,
+    path: &mut Vec<PyStep>,
+    visit: &mut dyn FnMut(&[Segment<'_>], &str) -> pyo3::PyResult<bool>,
+) -> pyo3::PyResult<bool> {
+    use pyo3::prelude::*;
+    use pyo3::types::{PyDict, PyList};
+
+    if let Ok(s) = value.extract::<&str>() {
+        let segments: Vec<Segment<'_>> = path
+            .iter()
+            .map(|step| match step {
+                PyStep::Key(key) => Segment::Key(key),
+                PyStep::Index(i) => Segment::Index(*i),
+            })
+            .collect();
+        return visit(&segments, s);
+    }
+    let items: Vec<(PyStep, Bound<'_, PyAny>)> = if let Ok(dict) = value.cast::<PyDict>() {
+        dict.iter()
+            .map(|(key, item)| Ok((PyStep::Key(key.str()?.to_string()), item)))
+            .collect::<PyResult<_>>()?
+    } else if let Ok(list) = value.cast::<PyList>() {
+        list.iter()
+            .enumerate()
+            .map(|(i, item)| (PyStep::Index(i), item))
+            .collect()
+    } else {
+        return Ok(false);
+    };
+    for (step, item) in items {
+        path.push(step);
+        let stop = walk_python(&item, path, visit);
+        path.pop();
+        if stop? {
+            return Ok(true);
+        }
+    }
+    Ok(false)
+}
+
+/// walks a msgpack document and visits every string value (keys are not visited)
+/// returns true when the visitor stopped the walk
+/// # Errors
+/// * truncated or malformed msgpack
+/// * nested deeper than `DEFAULT_MSGPACK_DEPTH` containers
+pub fn msgpack<'a>(buf: &'a [u8], visit: &mut Visit<'_, 'a>) -> Result<bool, Error> {
+    msgpack_within(buf, &Limits::NONE, visit)
+}
+
+/// `msgpack` failing with `Error::Limit` at the first value nested deeper than
+/// `max_msgpack_depth` or `max_depth` containers (`DEFAULT_MSGPACK_DEPTH` without them) or past `max_msgpack_elements` values
+/// # Errors
+/// * truncated or malformed msgpack
+/// * a depth or element count limit exceeded
+pub fn msgpack_within<'a>(
+    buf: &'a [u8],
+    limits: &Limits,
+    visit: &mut Visit<'_, 'a>,
+) -> Result<bool, Error> {
+    msgpack_spans(buf, limits, &mut |path, s, _| visit(path, s))
+}
+
+/// `msgpack_within` visiting every string value with the range of its encoding in `buf`,
+/// e.g. to rewrite some strings of the document
+/// # Errors
+/// * truncated or malformed msgpack
+/// * a depth or element count limit exceeded
+pub fn msgpack_spans<'a>(
+    buf: &'a [u8],
+    limits: &Limits,
+    visit: &mut VisitSpan<'_, 'a>,
+) -> Result<bool, Error> {
+    let mut rd = buf;
+    let mut walker = Walker {
+        visit,
+        len: buf.len(),
+        marker_at: 0,
+        path: Vec::new(),
+        depth: 0,
+        elements: 0,
+        skipping: 0,
+        max_depth: limits.msgpack_depth(),
+        max_elements: limits.max_msgpack_elements,
+    };
+    let stop = walker.value(&mut rd)?;
+    if !stop && !rd.is_empty() {
+        return Err(Error::TrailingBytes(rd.len()));
+    }
+    Ok(stop)
+}
+
+/// `msgpack_within` applying `on_overflow` past the buffer size, depth and element count
+/// limits: `scan_prefix` keeps the verdict of the strings before the limit (of the first
+/// `max_msgpack_len` bytes for the size), `allow` passes, `block` stops the walk as if
+/// the visitor had, `raise` fails with `Error::Limit`
+/// # Errors
+/// * truncated or malformed msgpack
+/// * a limit exceeded with `Overflow::Raise`
+pub fn msgpack_limited<'a>(
+    buf: &'a [u8],
+    limits: &Limits,
+    visit: &mut Visit<'_, 'a>,
+) -> Result<bool, Error> {
+    let walked = match limits.max_msgpack_len {
+        Some(limit) if buf.len() > limit => {
+            let error = LimitError::Buffer {
+                len: buf.len(),
+                limit,
+            };
+            if limits.on_overflow != Overflow::ScanPrefix {
+                return Ok(limits.overflow(error)?);
+            }
+            // the cut ends in the middle of a value
+            match msgpack_within(&buf[..limit], limits, visit) {
+                Err(Error::UnexpectedEof) => Ok(false),
+                walked => walked,
+            }
+        }
+        _ => msgpack_within(buf, limits, visit),
+    };
+    match walked {
+        Err(Error::Limit(error)) => Ok(limits.overflow(error)?),
+        walked => walked,
+    }
+}
+
+/// every string value of a msgpack document with its `format_path` path, in document
+/// order; a pure bytes-in function for fuzzing and for callers without a visitor
+/// # Errors
+/// * truncated or malformed msgpack
+/// * a depth or element count limit exceeded
+pub fn msgpack_strings<'a>(
+    buf: &'a [u8],
+    limits: &Limits,
+) -> Result<Vec<(String, &'a str)>, Error> {
+    let mut strings = Vec::new();
+    msgpack_within(buf, limits, &mut |path, s| {
+        strings.push((format_path(path), s));
+        false
+    })?;
+    Ok(strings)
+}
+
+fn take<'a>(rd: &mut &'a [u8], n: usize) -> Result<&'a [u8], Error> {
+    if rd.len() < n {
+        return Err(Error::UnexpectedEof);
+    }
+    let (head, tail) = rd.split_at(n);
+    *rd = tail;
+    Ok(head)
+}
+
+fn read_len(rd: &mut &[u8], width: usize) -> Result<usize, Error> {
+    let bytes = take(rd, width)?;
+    Ok(bytes.iter().fold(0usize, |n, b| (n << 8) | usize::from(*b)))
+}
+
+fn read_marker(rd: &mut &[u8]) -> Result<Marker, Error> {
+    Ok(Marker::from_u8(take(rd, 1)?[0]))
+}
+
+fn read_str<'a>(rd: &mut &'a [u8], len: usize) -> Result<&'a str, Error> {
+    std::str::from_utf8(take(rd, len)?).map_err(|_| Error::InvalidUtf8)
+}
+
+/// state of a msgpack walk
+struct Walker<'v, 'w, 'a> {
+    visit: &'v mut VisitSpan<'w, 'a>,
+    /// bytes of the document, offsets are `len` minus the bytes left to read
+    len: usize,
+    /// offset of the marker of the current value
+    marker_at: usize,
+    path: Vec<Segment<'a>>,
+    /// containers around the current value
+    depth: usize,
+    /// values read so far
+    elements: usize,
+    /// non string map keys being skipped, their strings are not visited
+    skipping: usize,
+    max_depth: usize,
+    max_elements: Option<usize>,
+}
+
+impl<'a> Walker<'_, '_, 'a> {
+    fn value(&mut self, rd: &mut &'a [u8]) -> Result<bool, Error> {
+        self.marker_at = self.len - rd.len();
+        let marker = read_marker(rd)?;
+        self.marker(rd, marker)
+    }
+
+    /// reads a map key, string keys become path segments
+    fn key(&mut self, rd: &mut &'a [u8], index: usize) -> Result<Segment<'a>, Error> {
+        let len = match read_marker(rd)? {
+            Marker::FixStr(n) => usize::from(n),
+            Marker::Str8 => read_len(rd, 1)?,
+            Marker::Str16 => read_len(rd, 2)?,
+            Marker::Str32 => read_len(rd, 4)?,
+            other => {
+                self.skipping += 1;
+                self.marker(rd, other)?;
+                self.skipping -= 1;
+                return Ok(Segment::Index(index));
+            }
+        };
+        Ok(Segment::Key(read_str(rd, len)?))
+    }
+
+    fn string(&mut self, rd: &mut &'a [u8], len: usize) -> Result<bool, Error> {
+        let s = read_str(rd, len)?;
+        let span = self.marker_at..self.len - rd.len();
+        Ok(self.skipping == 0 && (self.visit)(&self.path, s, span))
+    }
+
+    fn marker(&mut self, rd: &mut &'a [u8], marker: Marker) -> Result<bool, Error> {
+        self.elements += 1;
+        if let Some(limit) = self.max_elements
+            && self.elements > limit
+        {
+            return Err(LimitError::Elements { limit }.into());
+        }
+        let skip = |rd: &mut &'a [u8], n: usize| take(rd, n).map(|_| false);
+        match marker {
+            Marker::FixPos(_) | Marker::FixNeg(_) | Marker::Null | Marker::True | Marker::False => {
+                Ok(false)
+            }
+            Marker::U8 | Marker::I8 => skip(rd, 1),
+            // fixext values carry a type byte in front of the data
+            Marker::U16 | Marker::I16 | Marker::FixExt1 => skip(rd, 2),
+            Marker::U32 | Marker::I32 | Marker::F32 => skip(rd, 4),
+            Marker::U64 | Marker::I64 | Marker::F64 => skip(rd, 8),
+            Marker::FixStr(n) => self.string(rd, usize::from(n)),
+            Marker::Str8 => {
+                let len = read_len(rd, 1)?;
+                self.string(rd, len)
+            }
+            Marker::Str16 => {
+                let len = read_len(rd, 2)?;
+                self.string(rd, len)
+            }
+            Marker::Str32 => {
+                let len = read_len(rd, 4)?;
+                self.string(rd, len)
+            }
+            Marker::Bin8 => {
+                let len = read_len(rd, 1)?;
+                skip(rd, len)
+            }
+            Marker::Bin16 => {
+                let len = read_len(rd, 2)?;
+                skip(rd, len)
+            }
+            Marker::Bin32 => {
+                let len = read_len(rd, 4)?;
+                skip(rd, len)
+            }
+            Marker::FixExt2 => skip(rd, 3),
+            Marker::FixExt4 => skip(rd, 5),
+            Marker::FixExt8 => skip(rd, 9),
+            Marker::FixExt16 => skip(rd, 17),
+            Marker::Ext8 => {
+                let len = read_len(rd, 1)?;
+                skip(rd, len.checked_add(1).ok_or(Error::UnexpectedEof)?)
+            }
+            Marker::Ext16 => {
+                let len = read_len(rd, 2)?;
+                skip(rd, len.checked_add(1).ok_or(Error::UnexpectedEof)?)
+            }
+            Marker::Ext32 => {
+                let len = read_len(rd, 4)?;
+                skip(rd, len.checked_add(1).ok_or(Error::UnexpectedEof)?)
+            }
+            Marker::FixArray(n) => self.array(rd, usize::from(n)),
+            Marker::Array16 => {
+                let len = read_len(rd, 2)?;
+                self.array(rd, len)
+            }
+            Marker::Array32 => {
+                let len = read_len(rd, 4)?;
+                self.array(rd, len)
+            }
+            Marker::FixMap(n) => self.map(rd, usize::from(n)),
+            Marker::Map16 => {
+                let len = read_len(rd, 2)?;
+                self.map(rd, len)
+            }
+            Marker::Map32 => {
+                let len = read_len(rd, 4)?;
+                self.map(rd, len)
+            }
+            Marker::Reserved => Err(Error::ReservedMarker),
+        }
+    }
+
+    fn enter(&mut self) -> Result<(), Error> {
+        self.depth += 1;
+        if self.depth > self.max_depth {
+            return Err(LimitError::Depth {
+                limit: self.max_depth,
+            }
+            .into());
+        }
+        Ok(())
+    }
+
+    fn array(&mut self, rd: &mut &'a [u8], len: usize) -> Result<bool, Error> {
+        self.enter()?;
+        for i in 0..len {
+            self.path.push(Segment::Index(i));
+            let stop = self.value(rd);
+            self.path.pop();
+            if stop? {
+                return Ok(true);
+            }
+        }
+        self.depth -= 1;
+        Ok(false)
+    }
+
+    fn map(&mut self, rd: &mut &'a [u8], len: usize) -> Result<bool, Error> {
+        self.enter()?;
+        for i in 0..len {
+            let key = self.key(rd, i)?;
+            self.path.push(key);
+            let stop = self.value(rd);
+            self.path.pop();
+            if stop? {
+                return Ok(true);
+            }
+        }
+        self.depth -= 1;
+        Ok(false)
+    }
+}
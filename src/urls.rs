@@ -0,0 +1,123 @@
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
+#[cfg(feature = "python")]
+use pyo3_stub_gen::derive::{gen_stub_pyclass, gen_stub_pymethods};
+
+use crate::matcher::Matcher;
+use crate::metrics::Timer;
+
+/// the part of a url with a deny word
+#[cfg_attr(feature = "python", gen_stub_pyclass)]
+#[cfg_attr(
+    feature = "python",
+    pyclass(module = "deny_filter.plugin", frozen, get_all, skip_from_py_object)
+)]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct UrlHit {
+    /// `scheme`, `userinfo`, `host`, `path`, `query` or `fragment`
+    pub part: String,
+    /// the decoded path segment, query key or value, or part with the deny word
+    pub value: String,
+    /// deny word that matched first
+    pub word: String,
+}
+
+#[cfg(feature = "python")]
+#[gen_stub_pymethods]
+#[pymethods]
+impl UrlHit {
+    fn __repr__(&self) -> String {
+        format!(
+            "UrlHit(part={:?}, value={:?}, word={:?})",
+            self.part, self.value, self.word
+        )
+    }
+}
+
+/// percent decoded text, `+` as space in query components; invalid escapes are kept
+/// and invalid utf-8 is replaced
+#[must_use]
+pub fn percent_decode(s: &str, plus_as_space: bool) -> String {
+    let bytes = s.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let escaped = (bytes[i] == b'%')
+            .then(|| s.get(i + 1..i + 3))
+            .flatten()
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+        match (bytes[i], escaped) {
+            (_, Some(byte)) => {
+                out.push(byte);
+                i += 3;
+                continue;
+            }
+            (b'+', None) if plus_as_space => out.push(b' '),
+            (byte, None) => out.push(byte),
+        }
+        i += 1;
+    }
+    String::from_utf8_lossy(&out).into_owned()
+}
+
+/// decoded parts of a url in order: scheme, userinfo, host, path segments, query keys
+/// and values, fragment; the port is left out
+fn parts(url: &str) -> Vec<(&'static str, String)> {
+    let mut parts = Vec::new();
+    let (rest, fragment) = url.split_once('#').unwrap_or((url, ""));
+    let (rest, query) = rest.split_once('?').unwrap_or((rest, ""));
+    let mut rest = rest.trim();
+    if let Some((scheme, tail)) = rest.split_once(':')
+        && scheme.starts_with(|c: char| c.is_ascii_alphabetic())
+        && scheme
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || "+-.".contains(c))
+    {
+        parts.push(("scheme", scheme.to_string()));
+        rest = tail;
+    }
+    if let Some(tail) = rest.strip_prefix("//") {
+        let (authority, path) = tail.find('/').map_or((tail, ""), |i| tail.split_at(i));
+        let (userinfo, host) = authority.rsplit_once('@').unwrap_or(("", authority));
+        if !userinfo.is_empty() {
+            parts.push(("userinfo", percent_decode(userinfo, false)));
+        }
+        let host = match host.rsplit_once(':') {
+            Some((host, port)) if port.chars().all(|c| c.is_ascii_digit()) => host,
+            _ => host,
+        };
+        parts.push(("host", percent_decode(host, false)));
+        rest = path;
+    }
+    for segment in rest.split('/').filter(|s| !s.is_empty()) {
+        parts.push(("path", percent_decode(segment, false)));
+    }
+    for pair in query.split('&').filter(|s| !s.is_empty()) {
+        let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+        parts.push(("query", percent_decode(key, true)));
+        if !value.is_empty() {
+            parts.push(("query", percent_decode(value, true)));
+        }
+    }
+    if !fragment.is_empty() {
+        parts.push(("fragment", percent_decode(fragment, false)));
+    }
+    parts
+}
+
+/// first decoded part of `url` with a deny word, so words hidden by percent encoding
+/// or in query strings are found
+pub fn scan_url<M: Matcher + ?Sized>(matcher: &M, url: &str) -> Option<UrlHit> {
+    let timer = Timer::start();
+    let found = parts(url).into_iter().find_map(|(part, value)| {
+        matcher.check(&value).then(|| UrlHit {
+            part: part.to_string(),
+            word: matcher.first_match(&value).unwrap_or_default().to_string(),
+            value,
+        })
+    });
+    matcher
+        .metrics()
+        .record_scan(timer.elapsed(), found.is_some());
+    found
+}
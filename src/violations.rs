@@ -0,0 +1,262 @@
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
+#[cfg(feature = "python")]
+use pyo3::types::PyDict;
+#[cfg(feature = "python")]
+use pyo3_stub_gen::derive::{gen_stub_pyclass, gen_stub_pymethods};
+use std::cell::RefCell;
+
+use crate::build_error::BuildError;
+use crate::cache::ResultCache;
+use crate::explain::Explanation;
+use crate::keys::KeyFilter;
+use crate::limits::{Budget, LimitError, Limits};
+use crate::matcher::Matcher;
+use crate::matches::Match;
+use crate::metrics::BackendMetrics;
+#[cfg(feature = "python")]
+use crate::metrics::Timer;
+use crate::options::ListMetadata;
+use crate::report::BuildReport;
+use crate::score::Weights;
+use crate::stats::Stats;
+#[cfg(feature = "python")]
+use crate::trace::ScanSpan;
+use crate::traverse::{self, Segment};
+
+/// a deny word of a payload with the top level key it is under
+#[cfg_attr(feature = "python", gen_stub_pyclass)]
+#[cfg_attr(
+    feature = "python",
+    pyclass(module = "deny_filter.plugin", frozen, get_all, skip_from_py_object)
+)]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Violation {
+    /// key of the dict item with the deny word, None for a payload that is no dict
+    pub field: Option<String>,
+    /// deny word, or label of the proximity rule, that matched; None when a limit
+    /// decided the verdict with `on_overflow="block"`
+    pub word: Option<String>,
+}
+
+#[cfg(feature = "python")]
+#[gen_stub_pymethods]
+#[pymethods]
+impl Violation {
+    fn __repr__(&self) -> String {
+        let show = |v: &Option<String>| v.as_ref().map_or("None".to_string(), |v| format!("{v:?}"));
+        format!(
+            "Violation(field={}, word={})",
+            show(&self.field),
+            show(&self.word)
+        )
+    }
+}
+
+/// matcher recording the strings `inner` matches and reporting them clean, so a walk of
+/// `scan_value` or `msgpack_limited` goes on through the whole payload
+pub(crate) struct Recorder<'m, M: ?Sized> {
+    inner: &'m M,
+    field: Option<String>,
+    found: RefCell<Vec<Violation>>,
+}
+
+impl<'m, M: Matcher + ?Sized> Recorder<'m, M> {
+    pub(crate) fn new(inner: &'m M) -> Self {
+        Self {
+            inner,
+            field: None,
+            found: RefCell::new(Vec::new()),
+        }
+    }
+
+    fn record(&self, word: Option<String>) {
+        self.found.borrow_mut().push(Violation {
+            field: self.field.clone(),
+            word,
+        });
+    }
+
+    pub(crate) fn into_found(self) -> Vec<Violation> {
+        self.found.into_inner()
+    }
+}
+
+impl<M: Matcher + ?Sized> Matcher for Recorder<'_, M> {
+    fn is_match(&self, s: &str) -> bool {
+        self.inner.is_match(s)
+    }
+
+    fn first_match(&self, s: &str) -> Option<&str> {
+        self.inner.first_match(s)
+    }
+
+    fn find_all(&self, s: &str) -> Vec<Match> {
+        self.inner.find_all(s)
+    }
+
+    fn find_overlapping(&self, s: &str) -> Result<Vec<Match>, BuildError> {
+        self.inner.find_overlapping(s)
+    }
+
+    fn explain(&self, s: &str) -> Option<Explanation> {
+        self.inner.explain(s)
+    }
+
+    fn metadata(&self) -> &ListMetadata {
+        self.inner.metadata()
+    }
+
+    fn build_report(&self) -> &BuildReport {
+        self.inner.build_report()
+    }
+
+    fn stats(&self) -> Stats {
+        self.inner.stats()
+    }
+
+    #[cfg(feature = "dot")]
+    fn to_dot(&self) -> String {
+        self.inner.to_dot()
+    }
+
+    fn metrics(&self) -> &BackendMetrics {
+        self.inner.metrics()
+    }
+
+    fn limits(&self) -> &Limits {
+        self.inner.limits()
+    }
+
+    fn ignored_keys(&self) -> &KeyFilter {
+        self.inner.ignored_keys()
+    }
+
+    fn result_cache(&self) -> Option<&ResultCache> {
+        self.inner.result_cache()
+    }
+
+    fn weights(&self) -> &Weights {
+        self.inner.weights()
+    }
+
+    fn proximity_matches(&self, s: &str) -> Vec<&str> {
+        self.inner.proximity_matches(s)
+    }
+
+    /// the verdict of `inner` recorded with the distinct deny words and proximity rules of
+    /// the text it scanned (a prefix over `max_string_len`), `first_match` when none can be
+    /// listed, no word when a limit decided it; always clean
+    fn check_within(&self, s: &str, budget: &mut Budget) -> Result<bool, LimitError> {
+        let scanned = budget.scanned();
+        if !self.inner.check_within(s, budget)? {
+            return Ok(false);
+        }
+        let s = &s[..budget.scanned() - scanned];
+        if s.is_empty() {
+            self.record(None);
+            return Ok(false);
+        }
+        let mut words: Vec<String> = Vec::new();
+        for word in self.inner.scored_words(s) {
+            if !words.contains(&word) {
+                words.push(word);
+            }
+        }
+        if words.is_empty() {
+            self.record(self.inner.first_match(s).map(str::to_string));
+        }
+        for word in words {
+            self.record(Some(word));
+        }
+        Ok(false)
+    }
+}
+
+/// every violation of a msgpack document, walked as `Matcher::scan_msgpack` walks it;
+/// the field is the top level map key of the string
+/// # Errors
+/// * truncated or malformed msgpack
+/// * over a limit with `Overflow::Raise`
+pub fn msgpack_violations<M: Matcher + ?Sized>(
+    matcher: &M,
+    buf: &[u8],
+) -> Result<Vec<Violation>, traverse::Error> {
+    let recorder = RefCell::new(Recorder::new(matcher));
+    let mut budget = matcher.limits().budget();
+    let mut failed = None;
+    let blocked = traverse::msgpack_limited(buf, matcher.limits(), &mut |path, s| {
+        let mut recorder = recorder.borrow_mut();
+        recorder.field = match path.first() {
+            Some(Segment::Key(key)) => Some((*key).to_string()),
+            _ => None,
+        };
+        recorder.check_at(path, s, &mut budget).unwrap_or_else(|e| {
+            failed = Some(e);
+            true
+        })
+    })?;
+    if let Some(e) = failed {
+        return Err(e.into());
+    }
+    let recorder = recorder.into_inner();
+    if blocked {
+        recorder.record(None);
+    }
+    Ok(recorder.into_found())
+}
+
+/// every violation of a python dict/list/str, walked as `Matcher::scan_any` walks it:
+/// nested values, ignored keys and limits included; empty exactly when `scan_any` is
+/// false
+/// # Errors
+/// * over a length limit with `on_overflow="raise"`
+#[cfg(feature = "python")]
+pub(crate) fn walk<M: Matcher + ?Sized>(
+    matcher: &M,
+    value: &Bound<'_, PyAny>,
+    budget: &mut Budget,
+) -> PyResult<Vec<Violation>> {
+    let mut recorder = Recorder::new(matcher);
+    if let Ok(dict) = value.cast::<PyDict>() {
+        // the top level of `scan_value`, with the key of each item
+        if let Some(blocked) = budget.enter()? {
+            if blocked {
+                recorder.record(None);
+            }
+        } else {
+            for (key, item) in dict {
+                if key
+                    .extract::<&str>()
+                    .is_ok_and(|key| matcher.ignored_keys().skips(key))
+                {
+                    continue;
+                }
+                recorder.field = Some(key.str()?.to_string());
+                if recorder.scan_value(&item, budget)? {
+                    recorder.record(None);
+                }
+            }
+            budget.leave();
+        }
+    } else if recorder.scan_value(value, budget)? {
+        recorder.record(None);
+    }
+    Ok(recorder.into_found())
+}
+
+/// `walk` of the python matchers
+/// # Errors
+/// * over a length limit with `on_overflow="raise"`
+#[cfg(feature = "python")]
+pub fn py_violations<M: Matcher + ?Sized>(
+    matcher: &M,
+    value: &Bound<'_, PyAny>,
+) -> PyResult<Vec<Violation>> {
+    let span = ScanSpan::enter(matcher.metrics(), "violations");
+    let timer = Timer::start();
+    let mut budget = matcher.limits().budget();
+    let found = walk(matcher, value, &mut budget)?;
+    span.finish(timer.elapsed(), budget.scanned(), !found.is_empty());
+    Ok(found)
+}
@@ -0,0 +1,67 @@
+use wasm_bindgen::prelude::*;
+
+use crate::backend::Backend;
+use crate::matcher::Matcher;
+
+/// deny list for edge workers and browsers, same matching as the python classes
+#[wasm_bindgen]
+pub struct WasmDenyList {
+    matcher: Box<dyn Matcher + Send + Sync>,
+}
+
+#[wasm_bindgen]
+impl WasmDenyList {
+    /// builds a deny list, backend is aho-corasick (default), regex, daachorse, packed or auto
+    /// # Errors
+    /// * unknown backend or invalid patterns
+    #[wasm_bindgen(constructor)]
+    pub fn new(words: Vec<String>, backend: Option<String>) -> Result<WasmDenyList, JsError> {
+        let backend: Backend = match backend {
+            Some(name) => name.parse().map_err(|e: String| JsError::new(&e))?,
+            None => Backend::default(),
+        };
+        let matcher = backend
+            .build(words)
+            .map_err(|e| JsError::new(&e.to_string()))?;
+        Ok(Self { matcher })
+    }
+
+    #[wasm_bindgen(js_name = isMatch)]
+    #[must_use]
+    pub fn is_match(&self, s: &str) -> bool {
+        self.matcher.is_match(s)
+    }
+
+    /// deny word that matched first
+    #[wasm_bindgen(js_name = firstMatch)]
+    #[must_use]
+    pub fn first_match(&self, s: &str) -> Option<String> {
+        self.matcher.first_match(s).map(ToString::to_string)
+    }
+
+    #[wasm_bindgen(js_name = scanStr)]
+    #[must_use]
+    pub fn scan_str(&self, s: &str) -> bool {
+        self.matcher.scan_str(s)
+    }
+
+    /// scans every string value of a json document
+    /// # Errors
+    /// * invalid json
+    #[wasm_bindgen(js_name = scanJson)]
+    pub fn scan_json(&self, json: &str) -> Result<bool, JsError> {
+        let value: serde_json::Value =
+            serde_json::from_str(json).map_err(|e| JsError::new(&e.to_string()))?;
+        Ok(self.matcher.scan_json(&value))
+    }
+
+    /// scans every string value of a msgpack document
+    /// # Errors
+    /// * malformed msgpack
+    #[wasm_bindgen(js_name = scanMsgpack)]
+    pub fn scan_msgpack(&self, buf: &[u8]) -> Result<bool, JsError> {
+        self.matcher
+            .scan_msgpack(buf)
+            .map_err(|e| JsError::new(&e.to_string()))
+    }
+}
@@ -0,0 +1,58 @@
+use serde_json::Value;
+use std::io;
+use std::path::Path;
+
+/// reads deny words from a file
+/// * `.json`: a list of words, `{"words": [...]}` or `{"deny_word_lists": [{"words": [...]}]}`
+/// * anything else: one word per line, blank lines and `#` comments are skipped
+/// # Errors
+/// * file cannot be read or json has none of the shapes above
+pub fn load(path: &Path) -> io::Result<Vec<String>> {
+    parse(path, &std::fs::read_to_string(path)?)
+}
+
+/// words of `data` read from `path`, the extension selects the format as in `load`
+/// # Errors
+/// * json has none of the shapes accepted by `load`
+pub fn parse(path: &Path, data: &str) -> io::Result<Vec<String>> {
+    if path.extension().is_some_and(|ext| ext == "json") {
+        let value: Value = serde_json::from_str(data)?;
+        from_json(&value)
+            .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "no deny words in json"))
+    } else {
+        Ok(from_lines(data))
+    }
+}
+
+/// one word per line, blank lines and `#` comments are skipped
+#[must_use]
+pub fn from_lines(data: &str) -> Vec<String> {
+    data.lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(ToString::to_string)
+        .collect()
+}
+
+/// words from the json shapes accepted by `load`
+#[must_use]
+pub fn from_json(value: &Value) -> Option<Vec<String>> {
+    match value {
+        Value::Array(items) => items
+            .iter()
+            .map(|item| item.as_str().map(ToString::to_string))
+            .collect(),
+        Value::Object(map) => {
+            if let Some(words) = map.get("words") {
+                return from_json(words);
+            }
+            let lists = map.get("deny_word_lists")?.as_array()?;
+            let mut words = Vec::new();
+            for list in lists {
+                words.extend(from_json(list)?);
+            }
+            Some(words)
+        }
+        _ => None,
+    }
+}
@@ -0,0 +1,100 @@
+# This file is automatically generated by pyo3_stub_gen
+# ruff: noqa: E501, F401, F403, F405
+
+import builtins
+import typing
+from deny_filter.matchers import *
+from deny_filter.plugin import *
+from . import matchers
+from . import plugin
+__all__ = [
+    "Analysis",
+    "BackendBench",
+    "BuildReport",
+    "CacheInfo",
+    "ChatHit",
+    "DenyList",
+    "DenyListAuto",
+    "DenyListDaac",
+    "DenyListFst",
+    "DenyListHashed",
+    "DenyListHybrid",
+    "DenyListPacked",
+    "DenyListRegistry",
+    "DenyListRs",
+    "DenyListTokens",
+    "EmailHit",
+    "Evaluation",
+    "Explanation",
+    "FileReport",
+    "HeaderHit",
+    "IncrementalDenyList",
+    "LineScan",
+    "ListMetadata",
+    "LogFilter",
+    "Match",
+    "MatchIter",
+    "Matcher",
+    "McpVerdict",
+    "NextVerdict",
+    "PendingScan",
+    "RegisteredList",
+    "ScanPool",
+    "ShadowDenyList",
+    "SignedLoader",
+    "Stats",
+    "StreamScan",
+    "UrlHit",
+    "Violation",
+    "analyze",
+    "benchmark",
+    "matchers",
+    "plugin",
+    "render_prometheus",
+    "reset_stats",
+    "serve_metrics",
+    "stats",
+]
+
+def analyze(words: typing.Sequence[builtins.str], **options: typing.Any) -> matchers.Analysis:
+    r"""
+    analysis of a deny word list, keyword options: see `Options`
+    # Errors
+    * unknown option or invalid value, match kind for a word not in the list
+    """
+
+def benchmark(words: typing.Sequence[builtins.str], samples: typing.Sequence[builtins.str], iterations: builtins.int = 100, **options: typing.Any) -> builtins.list[matchers.BackendBench]:
+    r"""
+    build time, memory and latency of each backend over `samples`, to choose a backend
+    from data, keyword options: see `Options`
+    # Errors
+    * pattern errors of a backend
+    * unknown option or invalid value
+    """
+
+def render_prometheus() -> builtins.str:
+    r"""
+    prometheus text exposition of the scan metrics
+    """
+
+def reset_stats() -> None:
+    r"""
+    sets the scan counters of every backend back to zero, for `stats` and the
+    prometheus metrics alike
+    """
+
+def serve_metrics(address: builtins.str = '127.0.0.1:9464') -> builtins.str:
+    r"""
+    serves the scan metrics over http, returns the bound address
+    # Errors
+    * address cannot be bound
+    """
+
+def stats() -> dict:
+    r"""
+    scan counters of this process: `scans`, `matches` and scanned `bytes` in total and
+    per backend under `backends`
+    # Errors
+    * the dict cannot be built
+    """
+
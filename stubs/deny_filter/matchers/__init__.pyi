@@ -0,0 +1,2655 @@
+# This file is automatically generated by pyo3_stub_gen
+# ruff: noqa: E501, F401, F403, F405
+
+import builtins
+from deny_filter import plugin
+import os
+import pathlib
+import typing
+__all__ = [
+    "Analysis",
+    "BackendBench",
+    "BuildReport",
+    "CacheInfo",
+    "DenyList",
+    "DenyListAuto",
+    "DenyListDaac",
+    "DenyListFst",
+    "DenyListHashed",
+    "DenyListHybrid",
+    "DenyListPacked",
+    "DenyListRs",
+    "DenyListTokens",
+    "Evaluation",
+    "Explanation",
+    "LineScan",
+    "ListMetadata",
+    "Match",
+    "MatchIter",
+    "Matcher",
+    "NextVerdict",
+    "PendingScan",
+    "ScanPool",
+    "Stats",
+    "StreamScan",
+]
+
+@typing.final
+class Analysis:
+    r"""
+    static analysis of a deny word list, without building an automaton
+    """
+    @property
+    def never_match(self) -> builtins.list[builtins.str]:
+        r"""
+        entries that can never match: blank, or without words in whole word mode
+        """
+    @property
+    def substrings(self) -> builtins.list[tuple[builtins.str, builtins.str]]:
+        r"""
+        entry and an entry contained in it; the longer entry adds nothing to `is_match`
+        """
+    @property
+    def unreachable(self) -> builtins.list[tuple[builtins.str, builtins.str]]:
+        r"""
+        entry and an earlier entry always reported instead: equal, or its prefix with `leftmost_first`
+        """
+    def __repr__(self) -> builtins.str: ...
+
+@typing.final
+class BackendBench:
+    r"""
+    build time, memory and `is_match` latency of one backend over a sample corpus
+    """
+    @property
+    def backend(self) -> builtins.str:
+        r"""
+        backend name, see `Backend`
+        """
+    @property
+    def build_seconds(self) -> builtins.float:
+        r"""
+        seconds to build the matcher
+        """
+    @property
+    def heap_bytes(self) -> builtins.int:
+        r"""
+        heap bytes of the automata as reported by the backend crate
+        """
+    @property
+    def scans(self) -> builtins.int:
+        r"""
+        timed `is_match` calls, samples times iterations
+        """
+    @property
+    def matches(self) -> builtins.int:
+        r"""
+        samples that match
+        """
+    @property
+    def throughput_mb_s(self) -> builtins.float:
+        r"""
+        scanned megabytes (10^6 bytes) per second
+        """
+    @property
+    def mean_us(self) -> builtins.float:
+        r"""
+        mean latency of one `is_match` call in microseconds
+        """
+    @property
+    def p50_us(self) -> builtins.float:
+        r"""
+        median latency in microseconds
+        """
+    @property
+    def p99_us(self) -> builtins.float:
+        r"""
+        99th percentile latency in microseconds
+        """
+    def __repr__(self) -> builtins.str: ...
+
+@typing.final
+class BuildReport:
+    r"""
+    what construction did to the deny word entries, for cleaning up word list sources
+    """
+    @property
+    def empty(self) -> builtins.list[builtins.int]:
+        r"""
+        indexes of empty or blank entries, dropped
+        """
+    @property
+    def duplicates(self) -> builtins.list[builtins.str]:
+        r"""
+        entries repeating an earlier entry after normalization
+        """
+    @property
+    def altered(self) -> builtins.list[tuple[builtins.str, builtins.str]]:
+        r"""
+        entry and its normalized form, when case folding or normalization changed it
+        """
+    @property
+    def subsumes(self) -> builtins.list[tuple[builtins.str, builtins.str]]:
+        r"""
+        entry and a shorter entry contained in it, e.g. `("free money", "free")`
+        """
+    def __repr__(self) -> builtins.str: ...
+
+@typing.final
+class CacheInfo:
+    r"""
+    hits, misses and size of a result cache
+    """
+    @property
+    def hits(self) -> builtins.int:
+        r"""
+        scans answered from the cache
+        """
+    @property
+    def misses(self) -> builtins.int:
+        r"""
+        scans that ran and were cached
+        """
+    @property
+    def size(self) -> builtins.int:
+        r"""
+        cached verdicts
+        """
+    @property
+    def capacity(self) -> builtins.int:
+        r"""
+        cached verdicts at most, see `cache_size`
+        """
+    def __repr__(self) -> builtins.str: ...
+
+@typing.final
+class DenyList:
+    @property
+    def metadata(self) -> ListMetadata:
+        r"""
+        version, source and timestamp options of the list
+        """
+    @property
+    def on_match(self) -> typing.Optional[typing.Any]:
+        r"""
+        callable run with `(pattern, path, snippet)` of the match deciding `scan_any`,
+        `scan_any_async` and `scan_msgpack_async`, None when unset
+        """
+    @on_match.setter
+    def on_match(self, value: typing.Optional[typing.Any]) -> None:
+        r"""
+        # Errors
+        * not callable or None
+        """
+    def __new__(cls, words: typing.Sequence[builtins.str], **options: typing.Any) -> DenyList:
+        r"""
+        constructor, keyword options: see `Options`
+        # Errors
+        * aho-corasic errors (too long patterns)
+        * unknown option or invalid value
+        """
+    def is_match(self, s: builtins.str) -> builtins.bool: ...
+    def find_all(self, text: builtins.str) -> builtins.list[Match]:
+        r"""
+        leftmost non-overlapping deny word occurrences, byte offsets
+        """
+    def explain(self, value: typing.Any) -> typing.Optional[Explanation]:
+        r"""
+        why a str, or the first matching string of a dict/list with its path, matched
+        """
+    def evaluate(self, samples: typing.Sequence[builtins.str], labels: typing.Sequence[builtins.bool]) -> Evaluation:
+        r"""
+        precision, recall and false positive words on a labeled corpus
+        # Errors
+        * samples and labels differ in length
+        """
+    def build_report(self) -> BuildReport:
+        r"""
+        dropped, duplicate, altered and subsuming deny words
+        """
+    def stats(self) -> Stats:
+        r"""
+        pattern count, trie states, pattern lengths and heap bytes
+        """
+    def to_dot(self) -> builtins.str:
+        r"""
+        graphviz digraph of the pattern tries, debug builds with the `dot` feature
+        """
+    def redact(self, text: builtins.str, mask: builtins.str = '*') -> builtins.str:
+        r"""
+        masks matches grapheme by grapheme
+        """
+    def highlight(self, text: builtins.str, open: builtins.str = '<mark>', close: builtins.str = '</mark>') -> builtins.str:
+        r"""
+        wraps matches, spans snapped to whole graphemes
+        """
+    def find_overlapping(self, text: builtins.str) -> builtins.list[Match]:
+        r"""
+        every deny word occurrence including overlapping ones, byte offsets
+        # Errors
+        * the overlapping automaton cannot be built
+        """
+    def cache_info(self) -> typing.Optional[CacheInfo]:
+        r"""
+        hits, misses and size of the `cache_size` verdict cache, None without it
+        """
+    def redact_any(self, value: typing.Any, mask: builtins.str = '*') -> typing.Any:
+        r"""
+        deep copy of a dict/list/str with the matches of every string masked, other
+        values kept as they are, e.g. for a `modified_payload`; limits apply as in
+        `scan_any`
+        # Errors
+        * a dict item cannot be copied
+        * over a limit with `on_overflow="raise"`
+        """
+    def redact_msgpack(self, data: bytes, mask: builtins.str = '*') -> bytes:
+        r"""
+        msgpack document re-encoded with the matches of every string value masked, e.g.
+        to forward a sanitized payload instead of dropping it
+        # Errors
+        * truncated or malformed msgpack
+        """
+    def redact_json(self, data: bytes, mask: builtins.str = '*', preserve_order: builtins.bool = False) -> bytes:
+        r"""
+        json document with the matches of every string value masked; with
+        `preserve_order` key order and formatting are kept, otherwise it is written back
+        compact with sorted keys
+        # Errors
+        * invalid json
+        """
+    def scan_arrow(self, batch: typing.Any, columns: typing.Optional[typing.Sequence[builtins.str]] = None) -> typing.Any:
+        r"""
+        boolean mask of the rows of an arrow record batch or table with a deny word in any
+        of `columns` (default: all string columns), or of the values of a string array
+        # Errors
+        * not arrow data, a missing or non string column
+        """
+    def scan_series(self, series: typing.Any) -> typing.Any:
+        r"""
+        polars boolean series, true where the string series has a deny word
+        # Errors
+        * not a polars string series
+        """
+    def scan_stream(self, chunks: typing.Any) -> StreamScan:
+        r"""
+        async iterator of verdicts over an async iterator of str/bytes chunks, each verdict
+        lists the matches completed by a chunk, words split across chunks are found
+        # Errors
+        * `chunks` is not an async iterable
+        """
+    def iter_matches(self, text: str) -> MatchIter:
+        r"""
+        iterator of the `Match`es of `text` found a chunk at a time as the iteration goes,
+        for inputs too large to collect all matches of at once
+        """
+    def scan_any_async(self, value: typing.Any) -> typing.Any:
+        r"""
+        awaitable `scan_any`: the strings are copied, then scanned on a background thread
+        without the GIL while the event loop keeps running
+        # Errors
+        * no running event loop
+        """
+    def scan_msgpack_async(self, data: bytes, decompress: builtins.bool = False, max_size: typing.Optional[builtins.int] = None) -> typing.Any:
+        r"""
+        awaitable verdict of the string values of a msgpack document, scanned on a
+        background thread without the GIL; malformed msgpack raises `ValueError` on await.
+        With `decompress` gzip and zlib documents are decompressed first, up to `max_size`
+        bytes (64 MiB by default)
+        # Errors
+        * no running event loop
+        """
+    def scan_msgpack_many(self, buffers: typing.Sequence[bytes], workers: typing.Optional[builtins.int] = None) -> builtins.list[builtins.bool]:
+        r"""
+        verdicts of a batch of msgpack documents, e.g. the messages of one consumer poll, in
+        order, scanned on `workers` threads (one per cpu) without the GIL
+        # Errors
+        * a truncated or malformed document, `ValueError` naming its index
+        """
+    def scan_chat_messages(self, messages: typing.Any) -> typing.Optional[plugin.ChatHit]:
+        r"""
+        role and index of the first message of a chat completion message list with a
+        deny word in its text content, None when all are clean
+        # Errors
+        * messages that are not json-like python values
+        """
+    def scan_anthropic_messages(self, messages: typing.Any) -> typing.Optional[plugin.ChatHit]:
+        r"""
+        `scan_chat_messages` of an anthropic messages list: text, `tool_use` input,
+        `tool_result` content and text document blocks are scanned
+        # Errors
+        * messages that are not json-like python values
+        """
+    def scan_mcp_request(self, message: typing.Any) -> plugin.McpVerdict:
+        r"""
+        verdict of an MCP JSON-RPC message (bytes, str or the decoded dict/list): tool and
+        prompt arguments, resource contents, tool results and sampling messages are scanned
+        # Errors
+        * invalid json
+        """
+    def scan_headers(self, headers: typing.Any) -> typing.Optional[plugin.HeaderHit]:
+        r"""
+        first http header with a deny word in a comma separated element of its value or in
+        the base64 decoded text of one, e.g. `Authorization: Basic ...`; any object with
+        `items()` of str pairs
+        # Errors
+        * no `items()` or items that are not str pairs
+        """
+    def scan_url(self, url: builtins.str) -> typing.Optional[plugin.UrlHit]:
+        r"""
+        first part of a url with a deny word: scheme, userinfo, host, path segments, query
+        keys and values and fragment, each percent decoded, `+` as space in the query
+        """
+    def scan_sql(self, text: builtins.str) -> builtins.bool:
+        r"""
+        whether sql text has a deny word as written or with its `/* */` and `--` comments
+        stripped and its whitespace collapsed, e.g. `SEL/**/ECT voila`
+        """
+    def scan_email(self, data: bytes) -> typing.Optional[plugin.EmailHit]:
+        r"""
+        header name or body content type of the first part of an RFC 822 / MIME email
+        with a deny word: subject and address headers, `text/plain` and `text/html` bodies
+        (html reduced to its text) after transfer decoding, None when clean
+        """
+    def scan_log_record(self, record: typing.Any) -> builtins.bool:
+        r"""
+        whether a `logging.LogRecord` or its `__dict__` has a deny word in its formatted
+        message, its exception and stack texts or an `extra` field
+        # Errors
+        * a record that is no dict and has no `__dict__`
+        * over a length limit with `on_overflow="raise"`
+        """
+    def log_filter(self, replacement: typing.Optional[builtins.str] = None) -> plugin.LogFilter:
+        r"""
+        `logging.Filter` of this deny list, `logger.addFilter(deny_list.log_filter())`:
+        records with a deny word are dropped, or logged with `replacement` as their message
+        """
+    def scan_selected(self, payload: typing.Any, selectors: typing.Sequence[builtins.str]) -> builtins.bool:
+        r"""
+        whether a string the `JSONPath` `selectors` select in `payload` has a deny word;
+        `payload` is a dict/list/str or the bytes of a msgpack document, e.g.
+        `scan_selected(body, ["$.messages[*].content", "$.tool.args.*"])`
+        # Errors
+        * invalid selector, malformed msgpack
+        * over a limit with `on_overflow="raise"`
+        """
+    def score(self, payload: typing.Any) -> builtins.float:
+        r"""
+        risk score of a dict/list/str or the bytes of a msgpack document: the weights of
+        the distinct deny words matched, summed or their maximum per `score_aggregation`
+        # Errors
+        * malformed msgpack
+        """
+    def scan_file(self, path: builtins.str | os.PathLike | pathlib.Path, format: typing.Optional[builtins.str] = None) -> plugin.FileReport:
+        r"""
+        hits of one file as text, json, ndjson or msgpack, None picks the format by
+        extension; read errors are in the report
+        # Errors
+        * unknown format
+        """
+    def scan_dir(self, path: builtins.str | os.PathLike | pathlib.Path, glob: typing.Optional[builtins.str] = None, format: typing.Optional[builtins.str] = None, workers: typing.Optional[builtins.int] = None) -> builtins.list[plugin.FileReport]:
+        r"""
+        reports of the files below `path` matching `glob` (e.g. `*.log` or
+        `logs/**/*.json`), in path order, scanned on `workers` threads (one per cpu)
+        without the GIL
+        # Errors
+        * unknown format, a directory cannot be read
+        """
+    def scan_mmap(self, path: builtins.str | os.PathLike | pathlib.Path, window: typing.Optional[builtins.int] = None) -> builtins.list[Match]:
+        r"""
+        every deny word occurrence of a text file of any size, mapped into memory and
+        scanned `window` bytes (1 MiB) at a time without the GIL; offsets count from the
+        file start
+        # Errors
+        * the file cannot be opened or mapped
+        """
+    def scan_lines(self, source: typing.Any) -> LineScan:
+        r"""
+        lazy iterator of `(line_number, word, (start, end))` of a file path or bytes, the
+        offsets in the line; lines are read as the iteration goes
+        # Errors
+        * the file cannot be opened
+        """
+    def scan_str(self, txt: builtins.str) -> builtins.bool:
+        r"""
+        # Errors
+        * over a length limit with `on_overflow="raise"`
+        """
+    def scan(self, args: dict) -> builtins.bool:
+        r"""
+        # Errors
+        * over a length limit with `on_overflow="raise"`
+        """
+    def scan_any(self, value: typing.Any) -> builtins.bool:
+        r"""
+        scans dict,str,list
+        # Errors
+        * over a length limit with `on_overflow="raise"`
+        """
+    def violations(self, value: typing.Any) -> builtins.list[plugin.Violation]:
+        r"""
+        every deny word of a dict/list/str with the top level key it is under, walked as
+        `scan_any` walks it (nested values, ignored keys, limits), so the list is empty
+        exactly when `scan_any` is false; a proximity rule is listed by its label and a
+        limit blocking with `on_overflow="block"` with no word
+        # Errors
+        * over a length limit with `on_overflow="raise"`
+        """
+
+@typing.final
+class DenyListAuto:
+    r"""
+    delegates to the backend that scanned a sample of inputs fastest at build time
+    """
+    @property
+    def backend(self) -> builtins.str:
+        r"""
+        name of the chosen backend
+        """
+    @property
+    def metadata(self) -> ListMetadata:
+        r"""
+        version, source and timestamp options of the list
+        """
+    @property
+    def on_match(self) -> typing.Optional[typing.Any]:
+        r"""
+        callable run with `(pattern, path, snippet)` of the match deciding `scan_any`,
+        `scan_any_async` and `scan_msgpack_async`, None when unset
+        """
+    @on_match.setter
+    def on_match(self, value: typing.Optional[typing.Any]) -> None:
+        r"""
+        # Errors
+        * not callable or None
+        """
+    @staticmethod
+    def build(words: typing.Sequence[builtins.str], sample_inputs: typing.Sequence[builtins.str], **options: typing.Any) -> DenyListAuto:
+        r"""
+        times every backend on `sample_inputs` and keeps the fastest,
+        keyword options: see `Options`
+        # Errors
+        * pattern errors of a backend
+        * unknown option or invalid value
+        """
+    def timings(self) -> builtins.list[tuple[builtins.str, builtins.float]]:
+        r"""
+        (backend, seconds) of the fastest pass of each candidate
+        """
+    def is_match(self, s: builtins.str) -> builtins.bool: ...
+    def find_all(self, text: builtins.str) -> builtins.list[Match]:
+        r"""
+        leftmost non-overlapping deny word occurrences, byte offsets
+        """
+    def explain(self, value: typing.Any) -> typing.Optional[Explanation]:
+        r"""
+        why a str, or the first matching string of a dict/list with its path, matched
+        """
+    def evaluate(self, samples: typing.Sequence[builtins.str], labels: typing.Sequence[builtins.bool]) -> Evaluation:
+        r"""
+        precision, recall and false positive words on a labeled corpus
+        # Errors
+        * samples and labels differ in length
+        """
+    def build_report(self) -> BuildReport:
+        r"""
+        dropped, duplicate, altered and subsuming deny words
+        """
+    def stats(self) -> Stats:
+        r"""
+        stats of the chosen backend, with its name in `backend`
+        """
+    def to_dot(self) -> builtins.str:
+        r"""
+        graphviz digraph of the chosen backend, debug builds with the `dot` feature
+        """
+    def redact(self, text: builtins.str, mask: builtins.str = '*') -> builtins.str:
+        r"""
+        masks matches grapheme by grapheme
+        """
+    def highlight(self, text: builtins.str, open: builtins.str = '<mark>', close: builtins.str = '</mark>') -> builtins.str:
+        r"""
+        wraps matches, spans snapped to whole graphemes
+        """
+    def find_overlapping(self, text: builtins.str) -> builtins.list[Match]:
+        r"""
+        every deny word occurrence including overlapping ones, byte offsets
+        # Errors
+        * the overlapping automaton cannot be built
+        """
+    def cache_info(self) -> typing.Optional[CacheInfo]:
+        r"""
+        hits, misses and size of the `cache_size` verdict cache, None without it
+        """
+    def redact_any(self, value: typing.Any, mask: builtins.str = '*') -> typing.Any:
+        r"""
+        deep copy of a dict/list/str with the matches of every string masked, other
+        values kept as they are, e.g. for a `modified_payload`; limits apply as in
+        `scan_any`
+        # Errors
+        * a dict item cannot be copied
+        * over a limit with `on_overflow="raise"`
+        """
+    def redact_msgpack(self, data: bytes, mask: builtins.str = '*') -> bytes:
+        r"""
+        msgpack document re-encoded with the matches of every string value masked, e.g.
+        to forward a sanitized payload instead of dropping it
+        # Errors
+        * truncated or malformed msgpack
+        """
+    def redact_json(self, data: bytes, mask: builtins.str = '*', preserve_order: builtins.bool = False) -> bytes:
+        r"""
+        json document with the matches of every string value masked; with
+        `preserve_order` key order and formatting are kept, otherwise it is written back
+        compact with sorted keys
+        # Errors
+        * invalid json
+        """
+    def scan_arrow(self, batch: typing.Any, columns: typing.Optional[typing.Sequence[builtins.str]] = None) -> typing.Any:
+        r"""
+        boolean mask of the rows of an arrow record batch or table with a deny word in any
+        of `columns` (default: all string columns), or of the values of a string array
+        # Errors
+        * not arrow data, a missing or non string column
+        """
+    def scan_series(self, series: typing.Any) -> typing.Any:
+        r"""
+        polars boolean series, true where the string series has a deny word
+        # Errors
+        * not a polars string series
+        """
+    def scan_stream(self, chunks: typing.Any) -> StreamScan:
+        r"""
+        async iterator of verdicts over an async iterator of str/bytes chunks, each verdict
+        lists the matches completed by a chunk, words split across chunks are found
+        # Errors
+        * `chunks` is not an async iterable
+        """
+    def iter_matches(self, text: str) -> MatchIter:
+        r"""
+        iterator of the `Match`es of `text` found a chunk at a time as the iteration goes,
+        for inputs too large to collect all matches of at once
+        """
+    def scan_any_async(self, value: typing.Any) -> typing.Any:
+        r"""
+        awaitable `scan_any`: the strings are copied, then scanned on a background thread
+        without the GIL while the event loop keeps running
+        # Errors
+        * no running event loop
+        """
+    def scan_msgpack_async(self, data: bytes, decompress: builtins.bool = False, max_size: typing.Optional[builtins.int] = None) -> typing.Any:
+        r"""
+        awaitable verdict of the string values of a msgpack document, scanned on a
+        background thread without the GIL; malformed msgpack raises `ValueError` on await.
+        With `decompress` gzip and zlib documents are decompressed first, up to `max_size`
+        bytes (64 MiB by default)
+        # Errors
+        * no running event loop
+        """
+    def scan_msgpack_many(self, buffers: typing.Sequence[bytes], workers: typing.Optional[builtins.int] = None) -> builtins.list[builtins.bool]:
+        r"""
+        verdicts of a batch of msgpack documents, e.g. the messages of one consumer poll, in
+        order, scanned on `workers` threads (one per cpu) without the GIL
+        # Errors
+        * a truncated or malformed document, `ValueError` naming its index
+        """
+    def scan_chat_messages(self, messages: typing.Any) -> typing.Optional[plugin.ChatHit]:
+        r"""
+        role and index of the first message of a chat completion message list with a
+        deny word in its text content, None when all are clean
+        # Errors
+        * messages that are not json-like python values
+        """
+    def scan_anthropic_messages(self, messages: typing.Any) -> typing.Optional[plugin.ChatHit]:
+        r"""
+        `scan_chat_messages` of an anthropic messages list: text, `tool_use` input,
+        `tool_result` content and text document blocks are scanned
+        # Errors
+        * messages that are not json-like python values
+        """
+    def scan_mcp_request(self, message: typing.Any) -> plugin.McpVerdict:
+        r"""
+        verdict of an MCP JSON-RPC message (bytes, str or the decoded dict/list): tool and
+        prompt arguments, resource contents, tool results and sampling messages are scanned
+        # Errors
+        * invalid json
+        """
+    def scan_headers(self, headers: typing.Any) -> typing.Optional[plugin.HeaderHit]:
+        r"""
+        first http header with a deny word in a comma separated element of its value or in
+        the base64 decoded text of one, e.g. `Authorization: Basic ...`; any object with
+        `items()` of str pairs
+        # Errors
+        * no `items()` or items that are not str pairs
+        """
+    def scan_url(self, url: builtins.str) -> typing.Optional[plugin.UrlHit]:
+        r"""
+        first part of a url with a deny word: scheme, userinfo, host, path segments, query
+        keys and values and fragment, each percent decoded, `+` as space in the query
+        """
+    def scan_sql(self, text: builtins.str) -> builtins.bool:
+        r"""
+        whether sql text has a deny word as written or with its `/* */` and `--` comments
+        stripped and its whitespace collapsed, e.g. `SEL/**/ECT voila`
+        """
+    def scan_email(self, data: bytes) -> typing.Optional[plugin.EmailHit]:
+        r"""
+        header name or body content type of the first part of an RFC 822 / MIME email
+        with a deny word: subject and address headers, `text/plain` and `text/html` bodies
+        (html reduced to its text) after transfer decoding, None when clean
+        """
+    def scan_log_record(self, record: typing.Any) -> builtins.bool:
+        r"""
+        whether a `logging.LogRecord` or its `__dict__` has a deny word in its formatted
+        message, its exception and stack texts or an `extra` field
+        # Errors
+        * a record that is no dict and has no `__dict__`
+        * over a length limit with `on_overflow="raise"`
+        """
+    def log_filter(self, replacement: typing.Optional[builtins.str] = None) -> plugin.LogFilter:
+        r"""
+        `logging.Filter` of this deny list, `logger.addFilter(deny_list.log_filter())`:
+        records with a deny word are dropped, or logged with `replacement` as their message
+        """
+    def scan_selected(self, payload: typing.Any, selectors: typing.Sequence[builtins.str]) -> builtins.bool:
+        r"""
+        whether a string the `JSONPath` `selectors` select in `payload` has a deny word;
+        `payload` is a dict/list/str or the bytes of a msgpack document, e.g.
+        `scan_selected(body, ["$.messages[*].content", "$.tool.args.*"])`
+        # Errors
+        * invalid selector, malformed msgpack
+        * over a limit with `on_overflow="raise"`
+        """
+    def score(self, payload: typing.Any) -> builtins.float:
+        r"""
+        risk score of a dict/list/str or the bytes of a msgpack document: the weights of
+        the distinct deny words matched, summed or their maximum per `score_aggregation`
+        # Errors
+        * malformed msgpack
+        """
+    def scan_file(self, path: builtins.str | os.PathLike | pathlib.Path, format: typing.Optional[builtins.str] = None) -> plugin.FileReport:
+        r"""
+        hits of one file as text, json, ndjson or msgpack, None picks the format by
+        extension; read errors are in the report
+        # Errors
+        * unknown format
+        """
+    def scan_dir(self, path: builtins.str | os.PathLike | pathlib.Path, glob: typing.Optional[builtins.str] = None, format: typing.Optional[builtins.str] = None, workers: typing.Optional[builtins.int] = None) -> builtins.list[plugin.FileReport]:
+        r"""
+        reports of the files below `path` matching `glob` (e.g. `*.log` or
+        `logs/**/*.json`), in path order, scanned on `workers` threads (one per cpu)
+        without the GIL
+        # Errors
+        * unknown format, a directory cannot be read
+        """
+    def scan_mmap(self, path: builtins.str | os.PathLike | pathlib.Path, window: typing.Optional[builtins.int] = None) -> builtins.list[Match]:
+        r"""
+        every deny word occurrence of a text file of any size, mapped into memory and
+        scanned `window` bytes (1 MiB) at a time without the GIL; offsets count from the
+        file start
+        # Errors
+        * the file cannot be opened or mapped
+        """
+    def scan_lines(self, source: typing.Any) -> LineScan:
+        r"""
+        lazy iterator of `(line_number, word, (start, end))` of a file path or bytes, the
+        offsets in the line; lines are read as the iteration goes
+        # Errors
+        * the file cannot be opened
+        """
+    def scan_str(self, txt: builtins.str) -> builtins.bool:
+        r"""
+        # Errors
+        * over a length limit with `on_overflow="raise"`
+        """
+    def scan(self, args: dict) -> builtins.bool:
+        r"""
+        # Errors
+        * over a length limit with `on_overflow="raise"`
+        """
+    def scan_any(self, value: typing.Any) -> builtins.bool:
+        r"""
+        scans dict,str,list
+        # Errors
+        * over a length limit with `on_overflow="raise"`
+        """
+    def violations(self, value: typing.Any) -> builtins.list[plugin.Violation]:
+        r"""
+        every deny word of a dict/list/str with the top level key it is under, walked as
+        `scan_any` walks it (nested values, ignored keys, limits), so the list is empty
+        exactly when `scan_any` is false; a proximity rule is listed by its label and a
+        limit blocking with `on_overflow="block"` with no word
+        # Errors
+        * over a length limit with `on_overflow="raise"`
+        """
+
+@typing.final
+class DenyListDaac:
+    r"""
+    daachorse backend, clonable and extracted from python by value, e.g. as an argument
+    of rust functions taking a `DenyListDaac`
+    """
+    @property
+    def metadata(self) -> ListMetadata:
+        r"""
+        version, source and timestamp options of the list
+        """
+    @property
+    def on_match(self) -> typing.Optional[typing.Any]:
+        r"""
+        callable run with `(pattern, path, snippet)` of the match deciding `scan_any`,
+        `scan_any_async` and `scan_msgpack_async`, None when unset
+        """
+    @on_match.setter
+    def on_match(self, value: typing.Optional[typing.Any]) -> None:
+        r"""
+        # Errors
+        * not callable or None
+        """
+    def __new__(cls, words: typing.Sequence[builtins.str], **options: typing.Any) -> DenyListDaac:
+        r"""
+        constructor, keyword options: see `Options`
+        # Errors
+        * daachorse errors (e.g. too long patterns)
+        * unknown option or invalid value
+        """
+    def is_match(self, s: builtins.str) -> builtins.bool: ...
+    def find_all(self, text: builtins.str) -> builtins.list[Match]:
+        r"""
+        leftmost non-overlapping deny word occurrences, byte offsets
+        """
+    def explain(self, value: typing.Any) -> typing.Optional[Explanation]:
+        r"""
+        why a str, or the first matching string of a dict/list with its path, matched
+        """
+    def evaluate(self, samples: typing.Sequence[builtins.str], labels: typing.Sequence[builtins.bool]) -> Evaluation:
+        r"""
+        precision, recall and false positive words on a labeled corpus
+        # Errors
+        * samples and labels differ in length
+        """
+    def build_report(self) -> BuildReport:
+        r"""
+        dropped, duplicate, altered and subsuming deny words
+        """
+    def stats(self) -> Stats:
+        r"""
+        pattern count, trie states, pattern lengths and heap bytes
+        """
+    def to_dot(self) -> builtins.str:
+        r"""
+        graphviz digraph of the pattern tries, debug builds with the `dot` feature
+        """
+    def redact(self, text: builtins.str, mask: builtins.str = '*') -> builtins.str:
+        r"""
+        masks matches grapheme by grapheme
+        """
+    def highlight(self, text: builtins.str, open: builtins.str = '<mark>', close: builtins.str = '</mark>') -> builtins.str:
+        r"""
+        wraps matches, spans snapped to whole graphemes
+        """
+    def find_overlapping(self, text: builtins.str) -> builtins.list[Match]:
+        r"""
+        every deny word occurrence including overlapping ones, byte offsets
+        # Errors
+        * the overlapping automaton cannot be built
+        """
+    def cache_info(self) -> typing.Optional[CacheInfo]:
+        r"""
+        hits, misses and size of the `cache_size` verdict cache, None without it
+        """
+    def redact_any(self, value: typing.Any, mask: builtins.str = '*') -> typing.Any:
+        r"""
+        deep copy of a dict/list/str with the matches of every string masked, other
+        values kept as they are, e.g. for a `modified_payload`; limits apply as in
+        `scan_any`
+        # Errors
+        * a dict item cannot be copied
+        * over a limit with `on_overflow="raise"`
+        """
+    def redact_msgpack(self, data: bytes, mask: builtins.str = '*') -> bytes:
+        r"""
+        msgpack document re-encoded with the matches of every string value masked, e.g.
+        to forward a sanitized payload instead of dropping it
+        # Errors
+        * truncated or malformed msgpack
+        """
+    def redact_json(self, data: bytes, mask: builtins.str = '*', preserve_order: builtins.bool = False) -> bytes:
+        r"""
+        json document with the matches of every string value masked; with
+        `preserve_order` key order and formatting are kept, otherwise it is written back
+        compact with sorted keys
+        # Errors
+        * invalid json
+        """
+    def scan_arrow(self, batch: typing.Any, columns: typing.Optional[typing.Sequence[builtins.str]] = None) -> typing.Any:
+        r"""
+        boolean mask of the rows of an arrow record batch or table with a deny word in any
+        of `columns` (default: all string columns), or of the values of a string array
+        # Errors
+        * not arrow data, a missing or non string column
+        """
+    def scan_series(self, series: typing.Any) -> typing.Any:
+        r"""
+        polars boolean series, true where the string series has a deny word
+        # Errors
+        * not a polars string series
+        """
+    def scan_stream(self, chunks: typing.Any) -> StreamScan:
+        r"""
+        async iterator of verdicts over an async iterator of str/bytes chunks, each verdict
+        lists the matches completed by a chunk, words split across chunks are found
+        # Errors
+        * `chunks` is not an async iterable
+        """
+    def iter_matches(self, text: str) -> MatchIter:
+        r"""
+        iterator of the `Match`es of `text` found a chunk at a time as the iteration goes,
+        for inputs too large to collect all matches of at once
+        """
+    def scan_any_async(self, value: typing.Any) -> typing.Any:
+        r"""
+        awaitable `scan_any`: the strings are copied, then scanned on a background thread
+        without the GIL while the event loop keeps running
+        # Errors
+        * no running event loop
+        """
+    def scan_msgpack_async(self, data: bytes, decompress: builtins.bool = False, max_size: typing.Optional[builtins.int] = None) -> typing.Any:
+        r"""
+        awaitable verdict of the string values of a msgpack document, scanned on a
+        background thread without the GIL; malformed msgpack raises `ValueError` on await.
+        With `decompress` gzip and zlib documents are decompressed first, up to `max_size`
+        bytes (64 MiB by default)
+        # Errors
+        * no running event loop
+        """
+    def scan_msgpack_many(self, buffers: typing.Sequence[bytes], workers: typing.Optional[builtins.int] = None) -> builtins.list[builtins.bool]:
+        r"""
+        verdicts of a batch of msgpack documents, e.g. the messages of one consumer poll, in
+        order, scanned on `workers` threads (one per cpu) without the GIL
+        # Errors
+        * a truncated or malformed document, `ValueError` naming its index
+        """
+    def scan_chat_messages(self, messages: typing.Any) -> typing.Optional[plugin.ChatHit]:
+        r"""
+        role and index of the first message of a chat completion message list with a
+        deny word in its text content, None when all are clean
+        # Errors
+        * messages that are not json-like python values
+        """
+    def scan_anthropic_messages(self, messages: typing.Any) -> typing.Optional[plugin.ChatHit]:
+        r"""
+        `scan_chat_messages` of an anthropic messages list: text, `tool_use` input,
+        `tool_result` content and text document blocks are scanned
+        # Errors
+        * messages that are not json-like python values
+        """
+    def scan_mcp_request(self, message: typing.Any) -> plugin.McpVerdict:
+        r"""
+        verdict of an MCP JSON-RPC message (bytes, str or the decoded dict/list): tool and
+        prompt arguments, resource contents, tool results and sampling messages are scanned
+        # Errors
+        * invalid json
+        """
+    def scan_headers(self, headers: typing.Any) -> typing.Optional[plugin.HeaderHit]:
+        r"""
+        first http header with a deny word in a comma separated element of its value or in
+        the base64 decoded text of one, e.g. `Authorization: Basic ...`; any object with
+        `items()` of str pairs
+        # Errors
+        * no `items()` or items that are not str pairs
+        """
+    def scan_url(self, url: builtins.str) -> typing.Optional[plugin.UrlHit]:
+        r"""
+        first part of a url with a deny word: scheme, userinfo, host, path segments, query
+        keys and values and fragment, each percent decoded, `+` as space in the query
+        """
+    def scan_sql(self, text: builtins.str) -> builtins.bool:
+        r"""
+        whether sql text has a deny word as written or with its `/* */` and `--` comments
+        stripped and its whitespace collapsed, e.g. `SEL/**/ECT voila`
+        """
+    def scan_email(self, data: bytes) -> typing.Optional[plugin.EmailHit]:
+        r"""
+        header name or body content type of the first part of an RFC 822 / MIME email
+        with a deny word: subject and address headers, `text/plain` and `text/html` bodies
+        (html reduced to its text) after transfer decoding, None when clean
+        """
+    def scan_log_record(self, record: typing.Any) -> builtins.bool:
+        r"""
+        whether a `logging.LogRecord` or its `__dict__` has a deny word in its formatted
+        message, its exception and stack texts or an `extra` field
+        # Errors
+        * a record that is no dict and has no `__dict__`
+        * over a length limit with `on_overflow="raise"`
+        """
+    def log_filter(self, replacement: typing.Optional[builtins.str] = None) -> plugin.LogFilter:
+        r"""
+        `logging.Filter` of this deny list, `logger.addFilter(deny_list.log_filter())`:
+        records with a deny word are dropped, or logged with `replacement` as their message
+        """
+    def scan_selected(self, payload: typing.Any, selectors: typing.Sequence[builtins.str]) -> builtins.bool:
+        r"""
+        whether a string the `JSONPath` `selectors` select in `payload` has a deny word;
+        `payload` is a dict/list/str or the bytes of a msgpack document, e.g.
+        `scan_selected(body, ["$.messages[*].content", "$.tool.args.*"])`
+        # Errors
+        * invalid selector, malformed msgpack
+        * over a limit with `on_overflow="raise"`
+        """
+    def score(self, payload: typing.Any) -> builtins.float:
+        r"""
+        risk score of a dict/list/str or the bytes of a msgpack document: the weights of
+        the distinct deny words matched, summed or their maximum per `score_aggregation`
+        # Errors
+        * malformed msgpack
+        """
+    def scan_file(self, path: builtins.str | os.PathLike | pathlib.Path, format: typing.Optional[builtins.str] = None) -> plugin.FileReport:
+        r"""
+        hits of one file as text, json, ndjson or msgpack, None picks the format by
+        extension; read errors are in the report
+        # Errors
+        * unknown format
+        """
+    def scan_dir(self, path: builtins.str | os.PathLike | pathlib.Path, glob: typing.Optional[builtins.str] = None, format: typing.Optional[builtins.str] = None, workers: typing.Optional[builtins.int] = None) -> builtins.list[plugin.FileReport]:
+        r"""
+        reports of the files below `path` matching `glob` (e.g. `*.log` or
+        `logs/**/*.json`), in path order, scanned on `workers` threads (one per cpu)
+        without the GIL
+        # Errors
+        * unknown format, a directory cannot be read
+        """
+    def scan_mmap(self, path: builtins.str | os.PathLike | pathlib.Path, window: typing.Optional[builtins.int] = None) -> builtins.list[Match]:
+        r"""
+        every deny word occurrence of a text file of any size, mapped into memory and
+        scanned `window` bytes (1 MiB) at a time without the GIL; offsets count from the
+        file start
+        # Errors
+        * the file cannot be opened or mapped
+        """
+    def scan_lines(self, source: typing.Any) -> LineScan:
+        r"""
+        lazy iterator of `(line_number, word, (start, end))` of a file path or bytes, the
+        offsets in the line; lines are read as the iteration goes
+        # Errors
+        * the file cannot be opened
+        """
+    def scan_str(self, txt: builtins.str) -> builtins.bool:
+        r"""
+        # Errors
+        * over a length limit with `on_overflow="raise"`
+        """
+    def scan(self, args: dict) -> builtins.bool:
+        r"""
+        # Errors
+        * over a length limit with `on_overflow="raise"`
+        """
+    def scan_any(self, value: typing.Any) -> builtins.bool:
+        r"""
+        scans dict,str,list
+        # Errors
+        * over a length limit with `on_overflow="raise"`
+        """
+    def violations(self, value: typing.Any) -> builtins.list[plugin.Violation]:
+        r"""
+        every deny word of a dict/list/str with the top level key it is under, walked as
+        `scan_any` walks it (nested values, ignored keys, limits), so the list is empty
+        exactly when `scan_any` is false; a proximity rule is listed by its label and a
+        limit blocking with `on_overflow="block"` with no word
+        # Errors
+        * over a length limit with `on_overflow="raise"`
+        """
+
+@typing.final
+class DenyListFst:
+    r"""
+    exact token membership in a finite state transducer set, for very large lists
+    (e.g. leaked credentials) where substring matching is not needed; the set shares
+    prefixes and suffixes and takes a fraction of the memory of an automaton
+    """
+    @property
+    def metadata(self) -> ListMetadata:
+        r"""
+        version, source and timestamp options of the list
+        """
+    @property
+    def on_match(self) -> typing.Optional[typing.Any]:
+        r"""
+        callable run with `(pattern, path, snippet)` of the match deciding `scan_any`,
+        `scan_any_async` and `scan_msgpack_async`, None when unset
+        """
+    @on_match.setter
+    def on_match(self, value: typing.Optional[typing.Any]) -> None:
+        r"""
+        # Errors
+        * not callable or None
+        """
+    def __new__(cls, words: typing.Sequence[builtins.str], normalized: builtins.bool = False, **options: typing.Any) -> DenyListFst:
+        r"""
+        constructor from deny words, keyword options: see `Options`
+        # Errors
+        * fst construction errors
+        * unknown option or invalid value
+        """
+    @staticmethod
+    def from_bytes(data: bytes, normalized: builtins.bool = False, **options: typing.Any) -> DenyListFst:
+        r"""
+        loads a set serialized with `to_bytes`
+        # Errors
+        * not a valid fst, unknown option or invalid value
+        """
+    def to_bytes(self) -> bytes:
+        r"""
+        serialized set
+        """
+    def __len__(self) -> builtins.int: ...
+    def is_match(self, s: builtins.str) -> builtins.bool: ...
+    def find_all(self, text: builtins.str) -> builtins.list[Match]:
+        r"""
+        listed tokens with byte offsets
+        """
+    def redact(self, text: builtins.str, mask: builtins.str = '*') -> builtins.str:
+        r"""
+        masks listed tokens grapheme by grapheme
+        """
+    def stats(self) -> Stats:
+        r"""
+        word count and fst bytes
+        """
+    def cache_info(self) -> typing.Optional[CacheInfo]:
+        r"""
+        hits, misses and size of the `cache_size` verdict cache, None without it
+        """
+    def redact_any(self, value: typing.Any, mask: builtins.str = '*') -> typing.Any:
+        r"""
+        deep copy of a dict/list/str with the matches of every string masked, other
+        values kept as they are, e.g. for a `modified_payload`; limits apply as in
+        `scan_any`
+        # Errors
+        * a dict item cannot be copied
+        * over a limit with `on_overflow="raise"`
+        """
+    def redact_msgpack(self, data: bytes, mask: builtins.str = '*') -> bytes:
+        r"""
+        msgpack document re-encoded with the matches of every string value masked, e.g.
+        to forward a sanitized payload instead of dropping it
+        # Errors
+        * truncated or malformed msgpack
+        """
+    def redact_json(self, data: bytes, mask: builtins.str = '*', preserve_order: builtins.bool = False) -> bytes:
+        r"""
+        json document with the matches of every string value masked; with
+        `preserve_order` key order and formatting are kept, otherwise it is written back
+        compact with sorted keys
+        # Errors
+        * invalid json
+        """
+    def scan_arrow(self, batch: typing.Any, columns: typing.Optional[typing.Sequence[builtins.str]] = None) -> typing.Any:
+        r"""
+        boolean mask of the rows of an arrow record batch or table with a deny word in any
+        of `columns` (default: all string columns), or of the values of a string array
+        # Errors
+        * not arrow data, a missing or non string column
+        """
+    def scan_series(self, series: typing.Any) -> typing.Any:
+        r"""
+        polars boolean series, true where the string series has a deny word
+        # Errors
+        * not a polars string series
+        """
+    def scan_stream(self, chunks: typing.Any) -> StreamScan:
+        r"""
+        async iterator of verdicts over an async iterator of str/bytes chunks, each verdict
+        lists the matches completed by a chunk, words split across chunks are found
+        # Errors
+        * `chunks` is not an async iterable
+        """
+    def iter_matches(self, text: str) -> MatchIter:
+        r"""
+        iterator of the `Match`es of `text` found a chunk at a time as the iteration goes,
+        for inputs too large to collect all matches of at once
+        """
+    def scan_any_async(self, value: typing.Any) -> typing.Any:
+        r"""
+        awaitable `scan_any`: the strings are copied, then scanned on a background thread
+        without the GIL while the event loop keeps running
+        # Errors
+        * no running event loop
+        """
+    def scan_msgpack_async(self, data: bytes, decompress: builtins.bool = False, max_size: typing.Optional[builtins.int] = None) -> typing.Any:
+        r"""
+        awaitable verdict of the string values of a msgpack document, scanned on a
+        background thread without the GIL; malformed msgpack raises `ValueError` on await.
+        With `decompress` gzip and zlib documents are decompressed first, up to `max_size`
+        bytes (64 MiB by default)
+        # Errors
+        * no running event loop
+        """
+    def scan_msgpack_many(self, buffers: typing.Sequence[bytes], workers: typing.Optional[builtins.int] = None) -> builtins.list[builtins.bool]:
+        r"""
+        verdicts of a batch of msgpack documents, e.g. the messages of one consumer poll, in
+        order, scanned on `workers` threads (one per cpu) without the GIL
+        # Errors
+        * a truncated or malformed document, `ValueError` naming its index
+        """
+    def scan_chat_messages(self, messages: typing.Any) -> typing.Optional[plugin.ChatHit]:
+        r"""
+        role and index of the first message of a chat completion message list with a
+        deny word in its text content, None when all are clean
+        # Errors
+        * messages that are not json-like python values
+        """
+    def scan_anthropic_messages(self, messages: typing.Any) -> typing.Optional[plugin.ChatHit]:
+        r"""
+        `scan_chat_messages` of an anthropic messages list: text, `tool_use` input,
+        `tool_result` content and text document blocks are scanned
+        # Errors
+        * messages that are not json-like python values
+        """
+    def scan_mcp_request(self, message: typing.Any) -> plugin.McpVerdict:
+        r"""
+        verdict of an MCP JSON-RPC message (bytes, str or the decoded dict/list): tool and
+        prompt arguments, resource contents, tool results and sampling messages are scanned
+        # Errors
+        * invalid json
+        """
+    def scan_headers(self, headers: typing.Any) -> typing.Optional[plugin.HeaderHit]:
+        r"""
+        first http header with a deny word in a comma separated element of its value or in
+        the base64 decoded text of one, e.g. `Authorization: Basic ...`; any object with
+        `items()` of str pairs
+        # Errors
+        * no `items()` or items that are not str pairs
+        """
+    def scan_url(self, url: builtins.str) -> typing.Optional[plugin.UrlHit]:
+        r"""
+        first part of a url with a deny word: scheme, userinfo, host, path segments, query
+        keys and values and fragment, each percent decoded, `+` as space in the query
+        """
+    def scan_sql(self, text: builtins.str) -> builtins.bool:
+        r"""
+        whether sql text has a deny word as written or with its `/* */` and `--` comments
+        stripped and its whitespace collapsed, e.g. `SEL/**/ECT voila`
+        """
+    def scan_email(self, data: bytes) -> typing.Optional[plugin.EmailHit]:
+        r"""
+        header name or body content type of the first part of an RFC 822 / MIME email
+        with a deny word: subject and address headers, `text/plain` and `text/html` bodies
+        (html reduced to its text) after transfer decoding, None when clean
+        """
+    def scan_log_record(self, record: typing.Any) -> builtins.bool:
+        r"""
+        whether a `logging.LogRecord` or its `__dict__` has a deny word in its formatted
+        message, its exception and stack texts or an `extra` field
+        # Errors
+        * a record that is no dict and has no `__dict__`
+        * over a length limit with `on_overflow="raise"`
+        """
+    def log_filter(self, replacement: typing.Optional[builtins.str] = None) -> plugin.LogFilter:
+        r"""
+        `logging.Filter` of this deny list, `logger.addFilter(deny_list.log_filter())`:
+        records with a deny word are dropped, or logged with `replacement` as their message
+        """
+    def scan_selected(self, payload: typing.Any, selectors: typing.Sequence[builtins.str]) -> builtins.bool:
+        r"""
+        whether a string the `JSONPath` `selectors` select in `payload` has a deny word;
+        `payload` is a dict/list/str or the bytes of a msgpack document, e.g.
+        `scan_selected(body, ["$.messages[*].content", "$.tool.args.*"])`
+        # Errors
+        * invalid selector, malformed msgpack
+        * over a limit with `on_overflow="raise"`
+        """
+    def score(self, payload: typing.Any) -> builtins.float:
+        r"""
+        risk score of a dict/list/str or the bytes of a msgpack document: the weights of
+        the distinct deny words matched, summed or their maximum per `score_aggregation`
+        # Errors
+        * malformed msgpack
+        """
+    def scan_file(self, path: builtins.str | os.PathLike | pathlib.Path, format: typing.Optional[builtins.str] = None) -> plugin.FileReport:
+        r"""
+        hits of one file as text, json, ndjson or msgpack, None picks the format by
+        extension; read errors are in the report
+        # Errors
+        * unknown format
+        """
+    def scan_dir(self, path: builtins.str | os.PathLike | pathlib.Path, glob: typing.Optional[builtins.str] = None, format: typing.Optional[builtins.str] = None, workers: typing.Optional[builtins.int] = None) -> builtins.list[plugin.FileReport]:
+        r"""
+        reports of the files below `path` matching `glob` (e.g. `*.log` or
+        `logs/**/*.json`), in path order, scanned on `workers` threads (one per cpu)
+        without the GIL
+        # Errors
+        * unknown format, a directory cannot be read
+        """
+    def scan_mmap(self, path: builtins.str | os.PathLike | pathlib.Path, window: typing.Optional[builtins.int] = None) -> builtins.list[Match]:
+        r"""
+        every deny word occurrence of a text file of any size, mapped into memory and
+        scanned `window` bytes (1 MiB) at a time without the GIL; offsets count from the
+        file start
+        # Errors
+        * the file cannot be opened or mapped
+        """
+    def scan_lines(self, source: typing.Any) -> LineScan:
+        r"""
+        lazy iterator of `(line_number, word, (start, end))` of a file path or bytes, the
+        offsets in the line; lines are read as the iteration goes
+        # Errors
+        * the file cannot be opened
+        """
+    def scan_str(self, txt: builtins.str) -> builtins.bool:
+        r"""
+        # Errors
+        * over a length limit with `on_overflow="raise"`
+        """
+    def scan(self, args: dict) -> builtins.bool:
+        r"""
+        # Errors
+        * over a length limit with `on_overflow="raise"`
+        """
+    def scan_any(self, value: typing.Any) -> builtins.bool:
+        r"""
+        scans dict,str,list
+        # Errors
+        * over a length limit with `on_overflow="raise"`
+        """
+    def violations(self, value: typing.Any) -> builtins.list[plugin.Violation]:
+        r"""
+        every deny word of a dict/list/str with the top level key it is under, walked as
+        `scan_any` walks it (nested values, ignored keys, limits), so the list is empty
+        exactly when `scan_any` is false; a proximity rule is listed by its label and a
+        limit blocking with `on_overflow="block"` with no word
+        # Errors
+        * over a length limit with `on_overflow="raise"`
+        """
+
+@typing.final
+class DenyListHashed:
+    r"""
+    exact match deny list of sha-256 digests, for secrets that must not be stored in plaintext;
+    tokens of the input are hashed and looked up, the list itself reveals nothing
+    """
+    @property
+    def metadata(self) -> ListMetadata:
+        r"""
+        version, source and timestamp options of the list
+        """
+    @property
+    def on_match(self) -> typing.Optional[typing.Any]:
+        r"""
+        callable run with `(pattern, path, snippet)` of the match deciding `scan_any`,
+        `scan_any_async` and `scan_msgpack_async`, None when unset
+        """
+    @on_match.setter
+    def on_match(self, value: typing.Optional[typing.Any]) -> None:
+        r"""
+        # Errors
+        * not callable or None
+        """
+    def __new__(cls, digests: typing.Sequence[builtins.str], normalized: builtins.bool = False, **options: typing.Any) -> DenyListHashed:
+        r"""
+        constructor from hex sha-256 digests, keyword options: see `Options`
+        # Errors
+        * a digest is not 64 hex digits
+        * unknown option or invalid value
+        """
+    @staticmethod
+    def digest(secret: builtins.str, normalized: builtins.bool = False) -> builtins.str:
+        r"""
+        hex digest to put on the list for a secret
+        """
+    def is_match(self, s: builtins.str) -> builtins.bool: ...
+    def find_all(self, text: builtins.str) -> builtins.list[Match]:
+        r"""
+        listed tokens with byte offsets, `word` is the `sha256:<hex>` label
+        """
+    def redact(self, text: builtins.str, mask: builtins.str = '*') -> builtins.str:
+        r"""
+        masks listed tokens grapheme by grapheme
+        """
+    def build_report(self) -> BuildReport:
+        r"""
+        empty and duplicate digests
+        """
+    def cache_info(self) -> typing.Optional[CacheInfo]:
+        r"""
+        hits, misses and size of the `cache_size` verdict cache, None without it
+        """
+    def redact_any(self, value: typing.Any, mask: builtins.str = '*') -> typing.Any:
+        r"""
+        deep copy of a dict/list/str with the matches of every string masked, other
+        values kept as they are, e.g. for a `modified_payload`; limits apply as in
+        `scan_any`
+        # Errors
+        * a dict item cannot be copied
+        * over a limit with `on_overflow="raise"`
+        """
+    def redact_msgpack(self, data: bytes, mask: builtins.str = '*') -> bytes:
+        r"""
+        msgpack document re-encoded with the matches of every string value masked, e.g.
+        to forward a sanitized payload instead of dropping it
+        # Errors
+        * truncated or malformed msgpack
+        """
+    def redact_json(self, data: bytes, mask: builtins.str = '*', preserve_order: builtins.bool = False) -> bytes:
+        r"""
+        json document with the matches of every string value masked; with
+        `preserve_order` key order and formatting are kept, otherwise it is written back
+        compact with sorted keys
+        # Errors
+        * invalid json
+        """
+    def scan_arrow(self, batch: typing.Any, columns: typing.Optional[typing.Sequence[builtins.str]] = None) -> typing.Any:
+        r"""
+        boolean mask of the rows of an arrow record batch or table with a deny word in any
+        of `columns` (default: all string columns), or of the values of a string array
+        # Errors
+        * not arrow data, a missing or non string column
+        """
+    def scan_series(self, series: typing.Any) -> typing.Any:
+        r"""
+        polars boolean series, true where the string series has a deny word
+        # Errors
+        * not a polars string series
+        """
+    def scan_stream(self, chunks: typing.Any) -> StreamScan:
+        r"""
+        async iterator of verdicts over an async iterator of str/bytes chunks, each verdict
+        lists the matches completed by a chunk, words split across chunks are found
+        # Errors
+        * `chunks` is not an async iterable
+        """
+    def iter_matches(self, text: str) -> MatchIter:
+        r"""
+        iterator of the `Match`es of `text` found a chunk at a time as the iteration goes,
+        for inputs too large to collect all matches of at once
+        """
+    def scan_any_async(self, value: typing.Any) -> typing.Any:
+        r"""
+        awaitable `scan_any`: the strings are copied, then scanned on a background thread
+        without the GIL while the event loop keeps running
+        # Errors
+        * no running event loop
+        """
+    def scan_msgpack_async(self, data: bytes, decompress: builtins.bool = False, max_size: typing.Optional[builtins.int] = None) -> typing.Any:
+        r"""
+        awaitable verdict of the string values of a msgpack document, scanned on a
+        background thread without the GIL; malformed msgpack raises `ValueError` on await.
+        With `decompress` gzip and zlib documents are decompressed first, up to `max_size`
+        bytes (64 MiB by default)
+        # Errors
+        * no running event loop
+        """
+    def scan_msgpack_many(self, buffers: typing.Sequence[bytes], workers: typing.Optional[builtins.int] = None) -> builtins.list[builtins.bool]:
+        r"""
+        verdicts of a batch of msgpack documents, e.g. the messages of one consumer poll, in
+        order, scanned on `workers` threads (one per cpu) without the GIL
+        # Errors
+        * a truncated or malformed document, `ValueError` naming its index
+        """
+    def scan_chat_messages(self, messages: typing.Any) -> typing.Optional[plugin.ChatHit]:
+        r"""
+        role and index of the first message of a chat completion message list with a
+        deny word in its text content, None when all are clean
+        # Errors
+        * messages that are not json-like python values
+        """
+    def scan_anthropic_messages(self, messages: typing.Any) -> typing.Optional[plugin.ChatHit]:
+        r"""
+        `scan_chat_messages` of an anthropic messages list: text, `tool_use` input,
+        `tool_result` content and text document blocks are scanned
+        # Errors
+        * messages that are not json-like python values
+        """
+    def scan_mcp_request(self, message: typing.Any) -> plugin.McpVerdict:
+        r"""
+        verdict of an MCP JSON-RPC message (bytes, str or the decoded dict/list): tool and
+        prompt arguments, resource contents, tool results and sampling messages are scanned
+        # Errors
+        * invalid json
+        """
+    def scan_headers(self, headers: typing.Any) -> typing.Optional[plugin.HeaderHit]:
+        r"""
+        first http header with a deny word in a comma separated element of its value or in
+        the base64 decoded text of one, e.g. `Authorization: Basic ...`; any object with
+        `items()` of str pairs
+        # Errors
+        * no `items()` or items that are not str pairs
+        """
+    def scan_url(self, url: builtins.str) -> typing.Optional[plugin.UrlHit]:
+        r"""
+        first part of a url with a deny word: scheme, userinfo, host, path segments, query
+        keys and values and fragment, each percent decoded, `+` as space in the query
+        """
+    def scan_sql(self, text: builtins.str) -> builtins.bool:
+        r"""
+        whether sql text has a deny word as written or with its `/* */` and `--` comments
+        stripped and its whitespace collapsed, e.g. `SEL/**/ECT voila`
+        """
+    def scan_email(self, data: bytes) -> typing.Optional[plugin.EmailHit]:
+        r"""
+        header name or body content type of the first part of an RFC 822 / MIME email
+        with a deny word: subject and address headers, `text/plain` and `text/html` bodies
+        (html reduced to its text) after transfer decoding, None when clean
+        """
+    def scan_log_record(self, record: typing.Any) -> builtins.bool:
+        r"""
+        whether a `logging.LogRecord` or its `__dict__` has a deny word in its formatted
+        message, its exception and stack texts or an `extra` field
+        # Errors
+        * a record that is no dict and has no `__dict__`
+        * over a length limit with `on_overflow="raise"`
+        """
+    def log_filter(self, replacement: typing.Optional[builtins.str] = None) -> plugin.LogFilter:
+        r"""
+        `logging.Filter` of this deny list, `logger.addFilter(deny_list.log_filter())`:
+        records with a deny word are dropped, or logged with `replacement` as their message
+        """
+    def scan_selected(self, payload: typing.Any, selectors: typing.Sequence[builtins.str]) -> builtins.bool:
+        r"""
+        whether a string the `JSONPath` `selectors` select in `payload` has a deny word;
+        `payload` is a dict/list/str or the bytes of a msgpack document, e.g.
+        `scan_selected(body, ["$.messages[*].content", "$.tool.args.*"])`
+        # Errors
+        * invalid selector, malformed msgpack
+        * over a limit with `on_overflow="raise"`
+        """
+    def score(self, payload: typing.Any) -> builtins.float:
+        r"""
+        risk score of a dict/list/str or the bytes of a msgpack document: the weights of
+        the distinct deny words matched, summed or their maximum per `score_aggregation`
+        # Errors
+        * malformed msgpack
+        """
+    def scan_file(self, path: builtins.str | os.PathLike | pathlib.Path, format: typing.Optional[builtins.str] = None) -> plugin.FileReport:
+        r"""
+        hits of one file as text, json, ndjson or msgpack, None picks the format by
+        extension; read errors are in the report
+        # Errors
+        * unknown format
+        """
+    def scan_dir(self, path: builtins.str | os.PathLike | pathlib.Path, glob: typing.Optional[builtins.str] = None, format: typing.Optional[builtins.str] = None, workers: typing.Optional[builtins.int] = None) -> builtins.list[plugin.FileReport]:
+        r"""
+        reports of the files below `path` matching `glob` (e.g. `*.log` or
+        `logs/**/*.json`), in path order, scanned on `workers` threads (one per cpu)
+        without the GIL
+        # Errors
+        * unknown format, a directory cannot be read
+        """
+    def scan_mmap(self, path: builtins.str | os.PathLike | pathlib.Path, window: typing.Optional[builtins.int] = None) -> builtins.list[Match]:
+        r"""
+        every deny word occurrence of a text file of any size, mapped into memory and
+        scanned `window` bytes (1 MiB) at a time without the GIL; offsets count from the
+        file start
+        # Errors
+        * the file cannot be opened or mapped
+        """
+    def scan_lines(self, source: typing.Any) -> LineScan:
+        r"""
+        lazy iterator of `(line_number, word, (start, end))` of a file path or bytes, the
+        offsets in the line; lines are read as the iteration goes
+        # Errors
+        * the file cannot be opened
+        """
+    def scan_str(self, txt: builtins.str) -> builtins.bool:
+        r"""
+        # Errors
+        * over a length limit with `on_overflow="raise"`
+        """
+    def scan(self, args: dict) -> builtins.bool:
+        r"""
+        # Errors
+        * over a length limit with `on_overflow="raise"`
+        """
+    def scan_any(self, value: typing.Any) -> builtins.bool:
+        r"""
+        scans dict,str,list
+        # Errors
+        * over a length limit with `on_overflow="raise"`
+        """
+    def violations(self, value: typing.Any) -> builtins.list[plugin.Violation]:
+        r"""
+        every deny word of a dict/list/str with the top level key it is under, walked as
+        `scan_any` walks it (nested values, ignored keys, limits), so the list is empty
+        exactly when `scan_any` is false; a proximity rule is listed by its label and a
+        limit blocking with `on_overflow="block"` with no word
+        # Errors
+        * over a length limit with `on_overflow="raise"`
+        """
+
+@typing.final
+class DenyListHybrid:
+    r"""
+    literal deny words in aho-corasick and a few regexes in a regex set,
+    scanned together with one result list
+    """
+    @property
+    def metadata(self) -> ListMetadata:
+        r"""
+        version, source and timestamp options of the list
+        """
+    @property
+    def on_match(self) -> typing.Optional[typing.Any]:
+        r"""
+        callable run with `(pattern, path, snippet)` of the match deciding `scan_any`,
+        `scan_any_async` and `scan_msgpack_async`, None when unset
+        """
+    @on_match.setter
+    def on_match(self, value: typing.Optional[typing.Any]) -> None:
+        r"""
+        # Errors
+        * not callable or None
+        """
+    def __new__(cls, words: typing.Sequence[builtins.str], patterns: typing.Sequence[builtins.str] = [], **options: typing.Any) -> DenyListHybrid:
+        r"""
+        constructor from literal words and regexes, keyword options: see `Options`
+        # Errors
+        * aho-corasic errors (too long patterns)
+        * invalid regex, unknown option or invalid value
+        """
+    @staticmethod
+    def from_entries(entries: typing.Sequence[builtins.str], **options: typing.Any) -> DenyListHybrid:
+        r"""
+        constructor from a mixed policy list, regex entries start with `re:`
+        # Errors
+        * invalid regex, unknown option or invalid value
+        """
+    def is_match(self, s: builtins.str) -> builtins.bool: ...
+    def find_all(self, text: builtins.str) -> builtins.list[Match]:
+        r"""
+        literal and regex occurrences, byte offsets
+        """
+    def explain(self, value: typing.Any) -> typing.Optional[Explanation]:
+        r"""
+        why a str, or the first matching string of a dict/list with its path, matched
+        """
+    def evaluate(self, samples: typing.Sequence[builtins.str], labels: typing.Sequence[builtins.bool]) -> Evaluation:
+        r"""
+        precision, recall and false positive words on a labeled corpus
+        # Errors
+        * samples and labels differ in length
+        """
+    def build_report(self) -> BuildReport:
+        r"""
+        dropped, duplicate, altered and subsuming literal words
+        """
+    def stats(self) -> Stats:
+        r"""
+        pattern count, trie states, pattern lengths and heap bytes of the literals
+        """
+    def redact(self, text: builtins.str, mask: builtins.str = '*') -> builtins.str:
+        r"""
+        masks matches grapheme by grapheme
+        """
+    def highlight(self, text: builtins.str, open: builtins.str = '<mark>', close: builtins.str = '</mark>') -> builtins.str:
+        r"""
+        wraps matches, spans snapped to whole graphemes
+        """
+    def find_overlapping(self, text: builtins.str) -> builtins.list[Match]:
+        r"""
+        every literal and regex occurrence including overlapping ones, byte offsets
+        # Errors
+        * the overlapping automaton cannot be built
+        """
+    def cache_info(self) -> typing.Optional[CacheInfo]:
+        r"""
+        hits, misses and size of the `cache_size` verdict cache, None without it
+        """
+    def redact_any(self, value: typing.Any, mask: builtins.str = '*') -> typing.Any:
+        r"""
+        deep copy of a dict/list/str with the matches of every string masked, other
+        values kept as they are, e.g. for a `modified_payload`; limits apply as in
+        `scan_any`
+        # Errors
+        * a dict item cannot be copied
+        * over a limit with `on_overflow="raise"`
+        """
+    def redact_msgpack(self, data: bytes, mask: builtins.str = '*') -> bytes:
+        r"""
+        msgpack document re-encoded with the matches of every string value masked, e.g.
+        to forward a sanitized payload instead of dropping it
+        # Errors
+        * truncated or malformed msgpack
+        """
+    def redact_json(self, data: bytes, mask: builtins.str = '*', preserve_order: builtins.bool = False) -> bytes:
+        r"""
+        json document with the matches of every string value masked; with
+        `preserve_order` key order and formatting are kept, otherwise it is written back
+        compact with sorted keys
+        # Errors
+        * invalid json
+        """
+    def scan_arrow(self, batch: typing.Any, columns: typing.Optional[typing.Sequence[builtins.str]] = None) -> typing.Any:
+        r"""
+        boolean mask of the rows of an arrow record batch or table with a deny word in any
+        of `columns` (default: all string columns), or of the values of a string array
+        # Errors
+        * not arrow data, a missing or non string column
+        """
+    def scan_series(self, series: typing.Any) -> typing.Any:
+        r"""
+        polars boolean series, true where the string series has a deny word
+        # Errors
+        * not a polars string series
+        """
+    def scan_stream(self, chunks: typing.Any) -> StreamScan:
+        r"""
+        async iterator of verdicts over an async iterator of str/bytes chunks, each verdict
+        lists the matches completed by a chunk, words split across chunks are found
+        # Errors
+        * `chunks` is not an async iterable
+        """
+    def iter_matches(self, text: str) -> MatchIter:
+        r"""
+        iterator of the `Match`es of `text` found a chunk at a time as the iteration goes,
+        for inputs too large to collect all matches of at once
+        """
+    def scan_any_async(self, value: typing.Any) -> typing.Any:
+        r"""
+        awaitable `scan_any`: the strings are copied, then scanned on a background thread
+        without the GIL while the event loop keeps running
+        # Errors
+        * no running event loop
+        """
+    def scan_msgpack_async(self, data: bytes, decompress: builtins.bool = False, max_size: typing.Optional[builtins.int] = None) -> typing.Any:
+        r"""
+        awaitable verdict of the string values of a msgpack document, scanned on a
+        background thread without the GIL; malformed msgpack raises `ValueError` on await.
+        With `decompress` gzip and zlib documents are decompressed first, up to `max_size`
+        bytes (64 MiB by default)
+        # Errors
+        * no running event loop
+        """
+    def scan_msgpack_many(self, buffers: typing.Sequence[bytes], workers: typing.Optional[builtins.int] = None) -> builtins.list[builtins.bool]:
+        r"""
+        verdicts of a batch of msgpack documents, e.g. the messages of one consumer poll, in
+        order, scanned on `workers` threads (one per cpu) without the GIL
+        # Errors
+        * a truncated or malformed document, `ValueError` naming its index
+        """
+    def scan_chat_messages(self, messages: typing.Any) -> typing.Optional[plugin.ChatHit]:
+        r"""
+        role and index of the first message of a chat completion message list with a
+        deny word in its text content, None when all are clean
+        # Errors
+        * messages that are not json-like python values
+        """
+    def scan_anthropic_messages(self, messages: typing.Any) -> typing.Optional[plugin.ChatHit]:
+        r"""
+        `scan_chat_messages` of an anthropic messages list: text, `tool_use` input,
+        `tool_result` content and text document blocks are scanned
+        # Errors
+        * messages that are not json-like python values
+        """
+    def scan_mcp_request(self, message: typing.Any) -> plugin.McpVerdict:
+        r"""
+        verdict of an MCP JSON-RPC message (bytes, str or the decoded dict/list): tool and
+        prompt arguments, resource contents, tool results and sampling messages are scanned
+        # Errors
+        * invalid json
+        """
+    def scan_headers(self, headers: typing.Any) -> typing.Optional[plugin.HeaderHit]:
+        r"""
+        first http header with a deny word in a comma separated element of its value or in
+        the base64 decoded text of one, e.g. `Authorization: Basic ...`; any object with
+        `items()` of str pairs
+        # Errors
+        * no `items()` or items that are not str pairs
+        """
+    def scan_url(self, url: builtins.str) -> typing.Optional[plugin.UrlHit]:
+        r"""
+        first part of a url with a deny word: scheme, userinfo, host, path segments, query
+        keys and values and fragment, each percent decoded, `+` as space in the query
+        """
+    def scan_sql(self, text: builtins.str) -> builtins.bool:
+        r"""
+        whether sql text has a deny word as written or with its `/* */` and `--` comments
+        stripped and its whitespace collapsed, e.g. `SEL/**/ECT voila`
+        """
+    def scan_email(self, data: bytes) -> typing.Optional[plugin.EmailHit]:
+        r"""
+        header name or body content type of the first part of an RFC 822 / MIME email
+        with a deny word: subject and address headers, `text/plain` and `text/html` bodies
+        (html reduced to its text) after transfer decoding, None when clean
+        """
+    def scan_log_record(self, record: typing.Any) -> builtins.bool:
+        r"""
+        whether a `logging.LogRecord` or its `__dict__` has a deny word in its formatted
+        message, its exception and stack texts or an `extra` field
+        # Errors
+        * a record that is no dict and has no `__dict__`
+        * over a length limit with `on_overflow="raise"`
+        """
+    def log_filter(self, replacement: typing.Optional[builtins.str] = None) -> plugin.LogFilter:
+        r"""
+        `logging.Filter` of this deny list, `logger.addFilter(deny_list.log_filter())`:
+        records with a deny word are dropped, or logged with `replacement` as their message
+        """
+    def scan_selected(self, payload: typing.Any, selectors: typing.Sequence[builtins.str]) -> builtins.bool:
+        r"""
+        whether a string the `JSONPath` `selectors` select in `payload` has a deny word;
+        `payload` is a dict/list/str or the bytes of a msgpack document, e.g.
+        `scan_selected(body, ["$.messages[*].content", "$.tool.args.*"])`
+        # Errors
+        * invalid selector, malformed msgpack
+        * over a limit with `on_overflow="raise"`
+        """
+    def score(self, payload: typing.Any) -> builtins.float:
+        r"""
+        risk score of a dict/list/str or the bytes of a msgpack document: the weights of
+        the distinct deny words matched, summed or their maximum per `score_aggregation`
+        # Errors
+        * malformed msgpack
+        """
+    def scan_file(self, path: builtins.str | os.PathLike | pathlib.Path, format: typing.Optional[builtins.str] = None) -> plugin.FileReport:
+        r"""
+        hits of one file as text, json, ndjson or msgpack, None picks the format by
+        extension; read errors are in the report
+        # Errors
+        * unknown format
+        """
+    def scan_dir(self, path: builtins.str | os.PathLike | pathlib.Path, glob: typing.Optional[builtins.str] = None, format: typing.Optional[builtins.str] = None, workers: typing.Optional[builtins.int] = None) -> builtins.list[plugin.FileReport]:
+        r"""
+        reports of the files below `path` matching `glob` (e.g. `*.log` or
+        `logs/**/*.json`), in path order, scanned on `workers` threads (one per cpu)
+        without the GIL
+        # Errors
+        * unknown format, a directory cannot be read
+        """
+    def scan_mmap(self, path: builtins.str | os.PathLike | pathlib.Path, window: typing.Optional[builtins.int] = None) -> builtins.list[Match]:
+        r"""
+        every deny word occurrence of a text file of any size, mapped into memory and
+        scanned `window` bytes (1 MiB) at a time without the GIL; offsets count from the
+        file start
+        # Errors
+        * the file cannot be opened or mapped
+        """
+    def scan_lines(self, source: typing.Any) -> LineScan:
+        r"""
+        lazy iterator of `(line_number, word, (start, end))` of a file path or bytes, the
+        offsets in the line; lines are read as the iteration goes
+        # Errors
+        * the file cannot be opened
+        """
+    def scan_str(self, txt: builtins.str) -> builtins.bool:
+        r"""
+        # Errors
+        * over a length limit with `on_overflow="raise"`
+        """
+    def scan(self, args: dict) -> builtins.bool:
+        r"""
+        # Errors
+        * over a length limit with `on_overflow="raise"`
+        """
+    def scan_any(self, value: typing.Any) -> builtins.bool:
+        r"""
+        scans dict,str,list
+        # Errors
+        * over a length limit with `on_overflow="raise"`
+        """
+    def violations(self, value: typing.Any) -> builtins.list[plugin.Violation]:
+        r"""
+        every deny word of a dict/list/str with the top level key it is under, walked as
+        `scan_any` walks it (nested values, ignored keys, limits), so the list is empty
+        exactly when `scan_any` is false; a proximity rule is listed by its label and a
+        limit blocking with `on_overflow="block"` with no word
+        # Errors
+        * over a length limit with `on_overflow="raise"`
+        """
+
+@typing.final
+class DenyListPacked:
+    @property
+    def metadata(self) -> ListMetadata:
+        r"""
+        version, source and timestamp options of the list
+        """
+    @property
+    def on_match(self) -> typing.Optional[typing.Any]:
+        r"""
+        callable run with `(pattern, path, snippet)` of the match deciding `scan_any`,
+        `scan_any_async` and `scan_msgpack_async`, None when unset
+        """
+    @on_match.setter
+    def on_match(self, value: typing.Optional[typing.Any]) -> None:
+        r"""
+        # Errors
+        * not callable or None
+        """
+    def __new__(cls, words: typing.Sequence[builtins.str], **options: typing.Any) -> DenyListPacked:
+        r"""
+        constructor, keyword options: see `Options`
+        # Errors
+        * aho-corasic errors (too long patterns for the fallback automaton)
+        * unknown option or invalid value
+        """
+    def is_match(self, s: builtins.str) -> builtins.bool: ...
+    def find_all(self, text: builtins.str) -> builtins.list[Match]:
+        r"""
+        leftmost non-overlapping deny word occurrences, byte offsets
+        """
+    def explain(self, value: typing.Any) -> typing.Optional[Explanation]:
+        r"""
+        why a str, or the first matching string of a dict/list with its path, matched
+        """
+    def evaluate(self, samples: typing.Sequence[builtins.str], labels: typing.Sequence[builtins.bool]) -> Evaluation:
+        r"""
+        precision, recall and false positive words on a labeled corpus
+        # Errors
+        * samples and labels differ in length
+        """
+    def build_report(self) -> BuildReport:
+        r"""
+        dropped, duplicate, altered and subsuming deny words
+        """
+    def stats(self) -> Stats:
+        r"""
+        pattern count, trie states, pattern lengths and heap bytes
+        """
+    def to_dot(self) -> builtins.str:
+        r"""
+        graphviz digraph of the pattern tries, debug builds with the `dot` feature
+        """
+    def redact(self, text: builtins.str, mask: builtins.str = '*') -> builtins.str:
+        r"""
+        masks matches grapheme by grapheme
+        """
+    def highlight(self, text: builtins.str, open: builtins.str = '<mark>', close: builtins.str = '</mark>') -> builtins.str:
+        r"""
+        wraps matches, spans snapped to whole graphemes
+        """
+    def find_overlapping(self, text: builtins.str) -> builtins.list[Match]:
+        r"""
+        every deny word occurrence including overlapping ones, byte offsets
+        # Errors
+        * the overlapping automaton cannot be built
+        """
+    def cache_info(self) -> typing.Optional[CacheInfo]:
+        r"""
+        hits, misses and size of the `cache_size` verdict cache, None without it
+        """
+    def redact_any(self, value: typing.Any, mask: builtins.str = '*') -> typing.Any:
+        r"""
+        deep copy of a dict/list/str with the matches of every string masked, other
+        values kept as they are, e.g. for a `modified_payload`; limits apply as in
+        `scan_any`
+        # Errors
+        * a dict item cannot be copied
+        * over a limit with `on_overflow="raise"`
+        """
+    def redact_msgpack(self, data: bytes, mask: builtins.str = '*') -> bytes:
+        r"""
+        msgpack document re-encoded with the matches of every string value masked, e.g.
+        to forward a sanitized payload instead of dropping it
+        # Errors
+        * truncated or malformed msgpack
+        """
+    def redact_json(self, data: bytes, mask: builtins.str = '*', preserve_order: builtins.bool = False) -> bytes:
+        r"""
+        json document with the matches of every string value masked; with
+        `preserve_order` key order and formatting are kept, otherwise it is written back
+        compact with sorted keys
+        # Errors
+        * invalid json
+        """
+    def scan_arrow(self, batch: typing.Any, columns: typing.Optional[typing.Sequence[builtins.str]] = None) -> typing.Any:
+        r"""
+        boolean mask of the rows of an arrow record batch or table with a deny word in any
+        of `columns` (default: all string columns), or of the values of a string array
+        # Errors
+        * not arrow data, a missing or non string column
+        """
+    def scan_series(self, series: typing.Any) -> typing.Any:
+        r"""
+        polars boolean series, true where the string series has a deny word
+        # Errors
+        * not a polars string series
+        """
+    def scan_stream(self, chunks: typing.Any) -> StreamScan:
+        r"""
+        async iterator of verdicts over an async iterator of str/bytes chunks, each verdict
+        lists the matches completed by a chunk, words split across chunks are found
+        # Errors
+        * `chunks` is not an async iterable
+        """
+    def iter_matches(self, text: str) -> MatchIter:
+        r"""
+        iterator of the `Match`es of `text` found a chunk at a time as the iteration goes,
+        for inputs too large to collect all matches of at once
+        """
+    def scan_any_async(self, value: typing.Any) -> typing.Any:
+        r"""
+        awaitable `scan_any`: the strings are copied, then scanned on a background thread
+        without the GIL while the event loop keeps running
+        # Errors
+        * no running event loop
+        """
+    def scan_msgpack_async(self, data: bytes, decompress: builtins.bool = False, max_size: typing.Optional[builtins.int] = None) -> typing.Any:
+        r"""
+        awaitable verdict of the string values of a msgpack document, scanned on a
+        background thread without the GIL; malformed msgpack raises `ValueError` on await.
+        With `decompress` gzip and zlib documents are decompressed first, up to `max_size`
+        bytes (64 MiB by default)
+        # Errors
+        * no running event loop
+        """
+    def scan_msgpack_many(self, buffers: typing.Sequence[bytes], workers: typing.Optional[builtins.int] = None) -> builtins.list[builtins.bool]:
+        r"""
+        verdicts of a batch of msgpack documents, e.g. the messages of one consumer poll, in
+        order, scanned on `workers` threads (one per cpu) without the GIL
+        # Errors
+        * a truncated or malformed document, `ValueError` naming its index
+        """
+    def scan_chat_messages(self, messages: typing.Any) -> typing.Optional[plugin.ChatHit]:
+        r"""
+        role and index of the first message of a chat completion message list with a
+        deny word in its text content, None when all are clean
+        # Errors
+        * messages that are not json-like python values
+        """
+    def scan_anthropic_messages(self, messages: typing.Any) -> typing.Optional[plugin.ChatHit]:
+        r"""
+        `scan_chat_messages` of an anthropic messages list: text, `tool_use` input,
+        `tool_result` content and text document blocks are scanned
+        # Errors
+        * messages that are not json-like python values
+        """
+    def scan_mcp_request(self, message: typing.Any) -> plugin.McpVerdict:
+        r"""
+        verdict of an MCP JSON-RPC message (bytes, str or the decoded dict/list): tool and
+        prompt arguments, resource contents, tool results and sampling messages are scanned
+        # Errors
+        * invalid json
+        """
+    def scan_headers(self, headers: typing.Any) -> typing.Optional[plugin.HeaderHit]:
+        r"""
+        first http header with a deny word in a comma separated element of its value or in
+        the base64 decoded text of one, e.g. `Authorization: Basic ...`; any object with
+        `items()` of str pairs
+        # Errors
+        * no `items()` or items that are not str pairs
+        """
+    def scan_url(self, url: builtins.str) -> typing.Optional[plugin.UrlHit]:
+        r"""
+        first part of a url with a deny word: scheme, userinfo, host, path segments, query
+        keys and values and fragment, each percent decoded, `+` as space in the query
+        """
+    def scan_sql(self, text: builtins.str) -> builtins.bool:
+        r"""
+        whether sql text has a deny word as written or with its `/* */` and `--` comments
+        stripped and its whitespace collapsed, e.g. `SEL/**/ECT voila`
+        """
+    def scan_email(self, data: bytes) -> typing.Optional[plugin.EmailHit]:
+        r"""
+        header name or body content type of the first part of an RFC 822 / MIME email
+        with a deny word: subject and address headers, `text/plain` and `text/html` bodies
+        (html reduced to its text) after transfer decoding, None when clean
+        """
+    def scan_log_record(self, record: typing.Any) -> builtins.bool:
+        r"""
+        whether a `logging.LogRecord` or its `__dict__` has a deny word in its formatted
+        message, its exception and stack texts or an `extra` field
+        # Errors
+        * a record that is no dict and has no `__dict__`
+        * over a length limit with `on_overflow="raise"`
+        """
+    def log_filter(self, replacement: typing.Optional[builtins.str] = None) -> plugin.LogFilter:
+        r"""
+        `logging.Filter` of this deny list, `logger.addFilter(deny_list.log_filter())`:
+        records with a deny word are dropped, or logged with `replacement` as their message
+        """
+    def scan_selected(self, payload: typing.Any, selectors: typing.Sequence[builtins.str]) -> builtins.bool:
+        r"""
+        whether a string the `JSONPath` `selectors` select in `payload` has a deny word;
+        `payload` is a dict/list/str or the bytes of a msgpack document, e.g.
+        `scan_selected(body, ["$.messages[*].content", "$.tool.args.*"])`
+        # Errors
+        * invalid selector, malformed msgpack
+        * over a limit with `on_overflow="raise"`
+        """
+    def score(self, payload: typing.Any) -> builtins.float:
+        r"""
+        risk score of a dict/list/str or the bytes of a msgpack document: the weights of
+        the distinct deny words matched, summed or their maximum per `score_aggregation`
+        # Errors
+        * malformed msgpack
+        """
+    def scan_file(self, path: builtins.str | os.PathLike | pathlib.Path, format: typing.Optional[builtins.str] = None) -> plugin.FileReport:
+        r"""
+        hits of one file as text, json, ndjson or msgpack, None picks the format by
+        extension; read errors are in the report
+        # Errors
+        * unknown format
+        """
+    def scan_dir(self, path: builtins.str | os.PathLike | pathlib.Path, glob: typing.Optional[builtins.str] = None, format: typing.Optional[builtins.str] = None, workers: typing.Optional[builtins.int] = None) -> builtins.list[plugin.FileReport]:
+        r"""
+        reports of the files below `path` matching `glob` (e.g. `*.log` or
+        `logs/**/*.json`), in path order, scanned on `workers` threads (one per cpu)
+        without the GIL
+        # Errors
+        * unknown format, a directory cannot be read
+        """
+    def scan_mmap(self, path: builtins.str | os.PathLike | pathlib.Path, window: typing.Optional[builtins.int] = None) -> builtins.list[Match]:
+        r"""
+        every deny word occurrence of a text file of any size, mapped into memory and
+        scanned `window` bytes (1 MiB) at a time without the GIL; offsets count from the
+        file start
+        # Errors
+        * the file cannot be opened or mapped
+        """
+    def scan_lines(self, source: typing.Any) -> LineScan:
+        r"""
+        lazy iterator of `(line_number, word, (start, end))` of a file path or bytes, the
+        offsets in the line; lines are read as the iteration goes
+        # Errors
+        * the file cannot be opened
+        """
+    def scan_str(self, txt: builtins.str) -> builtins.bool:
+        r"""
+        # Errors
+        * over a length limit with `on_overflow="raise"`
+        """
+    def scan(self, args: dict) -> builtins.bool:
+        r"""
+        # Errors
+        * over a length limit with `on_overflow="raise"`
+        """
+    def scan_any(self, value: typing.Any) -> builtins.bool:
+        r"""
+        scans dict,str,list
+        # Errors
+        * over a length limit with `on_overflow="raise"`
+        """
+    def violations(self, value: typing.Any) -> builtins.list[plugin.Violation]:
+        r"""
+        every deny word of a dict/list/str with the top level key it is under, walked as
+        `scan_any` walks it (nested values, ignored keys, limits), so the list is empty
+        exactly when `scan_any` is false; a proximity rule is listed by its label and a
+        limit blocking with `on_overflow="block"` with no word
+        # Errors
+        * over a length limit with `on_overflow="raise"`
+        """
+
+@typing.final
+class DenyListRs:
+    @property
+    def metadata(self) -> ListMetadata:
+        r"""
+        version, source and timestamp options of the list
+        """
+    @property
+    def on_match(self) -> typing.Optional[typing.Any]:
+        r"""
+        callable run with `(pattern, path, snippet)` of the match deciding `scan_any`,
+        `scan_any_async` and `scan_msgpack_async`, None when unset
+        """
+    @on_match.setter
+    def on_match(self, value: typing.Optional[typing.Any]) -> None:
+        r"""
+        # Errors
+        * not callable or None
+        """
+    def __new__(cls, words: typing.Sequence[builtins.str], **options: typing.Any) -> DenyListRs:
+        r"""
+        constructor, keyword options: see `Options`
+        # Errors
+        * regex problems (should not happen with simple match)
+        * unknown option or invalid value
+        """
+    def is_match(self, s: builtins.str) -> builtins.bool: ...
+    def find_all(self, text: builtins.str) -> builtins.list[Match]:
+        r"""
+        leftmost non-overlapping deny word occurrences, byte offsets
+        """
+    def explain(self, value: typing.Any) -> typing.Optional[Explanation]:
+        r"""
+        why a str, or the first matching string of a dict/list with its path, matched
+        """
+    def evaluate(self, samples: typing.Sequence[builtins.str], labels: typing.Sequence[builtins.bool]) -> Evaluation:
+        r"""
+        precision, recall and false positive words on a labeled corpus
+        # Errors
+        * samples and labels differ in length
+        """
+    def build_report(self) -> BuildReport:
+        r"""
+        dropped, duplicate, altered and subsuming deny words
+        """
+    def stats(self) -> Stats:
+        r"""
+        pattern count, trie states, pattern lengths and heap bytes
+        """
+    def to_dot(self) -> builtins.str:
+        r"""
+        graphviz digraph of the pattern tries, debug builds with the `dot` feature
+        """
+    def redact(self, text: builtins.str, mask: builtins.str = '*') -> builtins.str:
+        r"""
+        masks matches grapheme by grapheme
+        """
+    def highlight(self, text: builtins.str, open: builtins.str = '<mark>', close: builtins.str = '</mark>') -> builtins.str:
+        r"""
+        wraps matches, spans snapped to whole graphemes
+        """
+    def find_overlapping(self, text: builtins.str) -> builtins.list[Match]:
+        r"""
+        every deny word occurrence including overlapping ones, byte offsets
+        # Errors
+        * the overlapping automaton cannot be built
+        """
+    def cache_info(self) -> typing.Optional[CacheInfo]:
+        r"""
+        hits, misses and size of the `cache_size` verdict cache, None without it
+        """
+    def redact_any(self, value: typing.Any, mask: builtins.str = '*') -> typing.Any:
+        r"""
+        deep copy of a dict/list/str with the matches of every string masked, other
+        values kept as they are, e.g. for a `modified_payload`; limits apply as in
+        `scan_any`
+        # Errors
+        * a dict item cannot be copied
+        * over a limit with `on_overflow="raise"`
+        """
+    def redact_msgpack(self, data: bytes, mask: builtins.str = '*') -> bytes:
+        r"""
+        msgpack document re-encoded with the matches of every string value masked, e.g.
+        to forward a sanitized payload instead of dropping it
+        # Errors
+        * truncated or malformed msgpack
+        """
+    def redact_json(self, data: bytes, mask: builtins.str = '*', preserve_order: builtins.bool = False) -> bytes:
+        r"""
+        json document with the matches of every string value masked; with
+        `preserve_order` key order and formatting are kept, otherwise it is written back
+        compact with sorted keys
+        # Errors
+        * invalid json
+        """
+    def scan_arrow(self, batch: typing.Any, columns: typing.Optional[typing.Sequence[builtins.str]] = None) -> typing.Any:
+        r"""
+        boolean mask of the rows of an arrow record batch or table with a deny word in any
+        of `columns` (default: all string columns), or of the values of a string array
+        # Errors
+        * not arrow data, a missing or non string column
+        """
+    def scan_series(self, series: typing.Any) -> typing.Any:
+        r"""
+        polars boolean series, true where the string series has a deny word
+        # Errors
+        * not a polars string series
+        """
+    def scan_stream(self, chunks: typing.Any) -> StreamScan:
+        r"""
+        async iterator of verdicts over an async iterator of str/bytes chunks, each verdict
+        lists the matches completed by a chunk, words split across chunks are found
+        # Errors
+        * `chunks` is not an async iterable
+        """
+    def iter_matches(self, text: str) -> MatchIter:
+        r"""
+        iterator of the `Match`es of `text` found a chunk at a time as the iteration goes,
+        for inputs too large to collect all matches of at once
+        """
+    def scan_any_async(self, value: typing.Any) -> typing.Any:
+        r"""
+        awaitable `scan_any`: the strings are copied, then scanned on a background thread
+        without the GIL while the event loop keeps running
+        # Errors
+        * no running event loop
+        """
+    def scan_msgpack_async(self, data: bytes, decompress: builtins.bool = False, max_size: typing.Optional[builtins.int] = None) -> typing.Any:
+        r"""
+        awaitable verdict of the string values of a msgpack document, scanned on a
+        background thread without the GIL; malformed msgpack raises `ValueError` on await.
+        With `decompress` gzip and zlib documents are decompressed first, up to `max_size`
+        bytes (64 MiB by default)
+        # Errors
+        * no running event loop
+        """
+    def scan_msgpack_many(self, buffers: typing.Sequence[bytes], workers: typing.Optional[builtins.int] = None) -> builtins.list[builtins.bool]:
+        r"""
+        verdicts of a batch of msgpack documents, e.g. the messages of one consumer poll, in
+        order, scanned on `workers` threads (one per cpu) without the GIL
+        # Errors
+        * a truncated or malformed document, `ValueError` naming its index
+        """
+    def scan_chat_messages(self, messages: typing.Any) -> typing.Optional[plugin.ChatHit]:
+        r"""
+        role and index of the first message of a chat completion message list with a
+        deny word in its text content, None when all are clean
+        # Errors
+        * messages that are not json-like python values
+        """
+    def scan_anthropic_messages(self, messages: typing.Any) -> typing.Optional[plugin.ChatHit]:
+        r"""
+        `scan_chat_messages` of an anthropic messages list: text, `tool_use` input,
+        `tool_result` content and text document blocks are scanned
+        # Errors
+        * messages that are not json-like python values
+        """
+    def scan_mcp_request(self, message: typing.Any) -> plugin.McpVerdict:
+        r"""
+        verdict of an MCP JSON-RPC message (bytes, str or the decoded dict/list): tool and
+        prompt arguments, resource contents, tool results and sampling messages are scanned
+        # Errors
+        * invalid json
+        """
+    def scan_headers(self, headers: typing.Any) -> typing.Optional[plugin.HeaderHit]:
+        r"""
+        first http header with a deny word in a comma separated element of its value or in
+        the base64 decoded text of one, e.g. `Authorization: Basic ...`; any object with
+        `items()` of str pairs
+        # Errors
+        * no `items()` or items that are not str pairs
+        """
+    def scan_url(self, url: builtins.str) -> typing.Optional[plugin.UrlHit]:
+        r"""
+        first part of a url with a deny word: scheme, userinfo, host, path segments, query
+        keys and values and fragment, each percent decoded, `+` as space in the query
+        """
+    def scan_sql(self, text: builtins.str) -> builtins.bool:
+        r"""
+        whether sql text has a deny word as written or with its `/* */` and `--` comments
+        stripped and its whitespace collapsed, e.g. `SEL/**/ECT voila`
+        """
+    def scan_email(self, data: bytes) -> typing.Optional[plugin.EmailHit]:
+        r"""
+        header name or body content type of the first part of an RFC 822 / MIME email
+        with a deny word: subject and address headers, `text/plain` and `text/html` bodies
+        (html reduced to its text) after transfer decoding, None when clean
+        """
+    def scan_log_record(self, record: typing.Any) -> builtins.bool:
+        r"""
+        whether a `logging.LogRecord` or its `__dict__` has a deny word in its formatted
+        message, its exception and stack texts or an `extra` field
+        # Errors
+        * a record that is no dict and has no `__dict__`
+        * over a length limit with `on_overflow="raise"`
+        """
+    def log_filter(self, replacement: typing.Optional[builtins.str] = None) -> plugin.LogFilter:
+        r"""
+        `logging.Filter` of this deny list, `logger.addFilter(deny_list.log_filter())`:
+        records with a deny word are dropped, or logged with `replacement` as their message
+        """
+    def scan_selected(self, payload: typing.Any, selectors: typing.Sequence[builtins.str]) -> builtins.bool:
+        r"""
+        whether a string the `JSONPath` `selectors` select in `payload` has a deny word;
+        `payload` is a dict/list/str or the bytes of a msgpack document, e.g.
+        `scan_selected(body, ["$.messages[*].content", "$.tool.args.*"])`
+        # Errors
+        * invalid selector, malformed msgpack
+        * over a limit with `on_overflow="raise"`
+        """
+    def score(self, payload: typing.Any) -> builtins.float:
+        r"""
+        risk score of a dict/list/str or the bytes of a msgpack document: the weights of
+        the distinct deny words matched, summed or their maximum per `score_aggregation`
+        # Errors
+        * malformed msgpack
+        """
+    def scan_file(self, path: builtins.str | os.PathLike | pathlib.Path, format: typing.Optional[builtins.str] = None) -> plugin.FileReport:
+        r"""
+        hits of one file as text, json, ndjson or msgpack, None picks the format by
+        extension; read errors are in the report
+        # Errors
+        * unknown format
+        """
+    def scan_dir(self, path: builtins.str | os.PathLike | pathlib.Path, glob: typing.Optional[builtins.str] = None, format: typing.Optional[builtins.str] = None, workers: typing.Optional[builtins.int] = None) -> builtins.list[plugin.FileReport]:
+        r"""
+        reports of the files below `path` matching `glob` (e.g. `*.log` or
+        `logs/**/*.json`), in path order, scanned on `workers` threads (one per cpu)
+        without the GIL
+        # Errors
+        * unknown format, a directory cannot be read
+        """
+    def scan_mmap(self, path: builtins.str | os.PathLike | pathlib.Path, window: typing.Optional[builtins.int] = None) -> builtins.list[Match]:
+        r"""
+        every deny word occurrence of a text file of any size, mapped into memory and
+        scanned `window` bytes (1 MiB) at a time without the GIL; offsets count from the
+        file start
+        # Errors
+        * the file cannot be opened or mapped
+        """
+    def scan_lines(self, source: typing.Any) -> LineScan:
+        r"""
+        lazy iterator of `(line_number, word, (start, end))` of a file path or bytes, the
+        offsets in the line; lines are read as the iteration goes
+        # Errors
+        * the file cannot be opened
+        """
+    def scan_str(self, txt: builtins.str) -> builtins.bool:
+        r"""
+        # Errors
+        * over a length limit with `on_overflow="raise"`
+        """
+    def scan(self, args: dict) -> builtins.bool:
+        r"""
+        # Errors
+        * over a length limit with `on_overflow="raise"`
+        """
+    def scan_any(self, value: typing.Any) -> builtins.bool:
+        r"""
+        scans dict,str,list
+        # Errors
+        * over a length limit with `on_overflow="raise"`
+        """
+    def violations(self, value: typing.Any) -> builtins.list[plugin.Violation]:
+        r"""
+        every deny word of a dict/list/str with the top level key it is under, walked as
+        `scan_any` walks it (nested values, ignored keys, limits), so the list is empty
+        exactly when `scan_any` is false; a proximity rule is listed by its label and a
+        limit blocking with `on_overflow="block"` with no word
+        # Errors
+        * over a length limit with `on_overflow="raise"`
+        """
+
+@typing.final
+class DenyListTokens:
+    r"""
+    deny list on model token ids, blocks before detokenization
+    """
+    def __new__(cls, patterns: typing.Sequence[typing.Sequence[builtins.int]]) -> DenyListTokens:
+        r"""
+        constructor from token id sequences
+        # Errors
+        * empty pattern or token id above 0x10F7FF
+        """
+    @staticmethod
+    def with_vocab(vocab: typing.Mapping[builtins.str, builtins.int], words: typing.Sequence[builtins.str], prefixes: typing.Sequence[builtins.str] = []) -> DenyListTokens:
+        r"""
+        tokenizes deny words with a vocabulary (greedy longest match),
+        prefixes add variants such as "Ġ"/"▁" for a leading space
+        # Errors
+        * a word cannot be tokenized with the vocabulary
+        """
+    def is_match(self, ids: typing.Sequence[builtins.int]) -> builtins.bool: ...
+    def find(self, ids: typing.Sequence[builtins.int]) -> typing.Optional[tuple[builtins.int, builtins.int, builtins.int]]:
+        r"""
+        (pattern index, start, end) in token positions of the first match
+        """
+    def patterns(self) -> builtins.list[builtins.list[builtins.int]]:
+        r"""
+        token id patterns, including those computed from a vocabulary
+        """
+
+@typing.final
+class Evaluation:
+    r"""
+    quality of a matcher on a labeled corpus
+    """
+    @property
+    def true_positives(self) -> builtins.int: ...
+    @property
+    def false_positives(self) -> builtins.int: ...
+    @property
+    def true_negatives(self) -> builtins.int: ...
+    @property
+    def false_negatives(self) -> builtins.int: ...
+    @property
+    def precision(self) -> builtins.float:
+        r"""
+        matched samples labeled as deny, 1.0 when nothing matched
+        """
+    @property
+    def recall(self) -> builtins.float:
+        r"""
+        deny labeled samples matched, 1.0 without deny labels
+        """
+    @property
+    def false_positive_words(self) -> builtins.list[tuple[builtins.str, builtins.int]]:
+        r"""
+        deny word (or proximity rule) and its false positive count, most frequent first
+        """
+    def __repr__(self) -> builtins.str: ...
+
+@typing.final
+class Explanation:
+    r"""
+    why a text matched, to justify a block in review and appeals
+    """
+    @property
+    def word(self) -> builtins.str:
+        r"""
+        deny word in lowercase, or the `first NEAR/n second` label of a proximity rule
+        """
+    @property
+    def hit(self) -> typing.Optional[Match]:
+        r"""
+        offsets of the deny word hit in the text, None for proximity rules
+        """
+    @property
+    def pattern(self) -> builtins.str:
+        r"""
+        the deny word after normalization
+        """
+    @property
+    def normalized(self) -> builtins.str:
+        r"""
+        the normalized text that matched the pattern
+        """
+    @property
+    def steps(self) -> builtins.list[builtins.str]:
+        r"""
+        normalizations applied to the deny word and the text, in order
+        """
+    @property
+    def path(self) -> typing.Optional[builtins.str]:
+        r"""
+        `$.key[0]` path of the matched string for scans, None for plain text
+        """
+    def __repr__(self) -> builtins.str: ...
+
+@typing.final
+class LineScan:
+    r"""
+    iterator of `(line_number, word, (start, end))` of a file or bytes, lines are read and
+    scanned as the iteration goes
+    """
+    def __iter__(self) -> LineScan: ...
+    def __next__(self) -> typing.Optional[tuple[builtins.int, builtins.str, tuple[builtins.int, builtins.int]]]:
+        r"""
+        # Errors
+        * the file cannot be read
+        """
+
+@typing.final
+class ListMetadata:
+    r"""
+    which policy version a matcher was built from, recorded with its block decisions
+    """
+    @property
+    def version(self) -> typing.Optional[builtins.str]: ...
+    @property
+    def source(self) -> typing.Optional[builtins.str]:
+        r"""
+        where the list came from, e.g. a file path or url
+        """
+    @property
+    def timestamp(self) -> typing.Optional[builtins.str]:
+        r"""
+        when the list was published, e.g. ISO 8601
+        """
+    def __repr__(self) -> builtins.str: ...
+
+@typing.final
+class Match:
+    r"""
+    occurrence of a deny word in the scanned text
+    """
+    @property
+    def word(self) -> builtins.str:
+        r"""
+        deny word in lowercase
+        """
+    @property
+    def start(self) -> builtins.int:
+        r"""
+        byte offset of the first byte in the original text
+        """
+    @property
+    def end(self) -> builtins.int:
+        r"""
+        byte offset after the last byte in the original text
+        """
+    @property
+    def char_start(self) -> builtins.int:
+        r"""
+        code point offset of `start`, for slicing python strings
+        """
+    @property
+    def char_end(self) -> builtins.int:
+        r"""
+        code point offset of `end`
+        """
+    def __repr__(self) -> builtins.str: ...
+
+@typing.final
+class MatchIter:
+    r"""
+    iterator of the `Match`es of a str, found a chunk at a time as the iteration goes
+    """
+    def __iter__(self) -> MatchIter: ...
+    def __next__(self) -> typing.Optional[Match]:
+        r"""
+        # Errors
+        * the text cannot be encoded as utf-8
+        """
+
+class Matcher:
+    r"""
+    base class of python matchers, subclasses implement `is_match` and may override
+    `find_all`; instances are accepted wherever a matcher is, e.g. `DenyListRegistry.register`
+    """
+    def __new__(cls) -> Matcher: ...
+    def is_match(self, text: builtins.str) -> builtins.bool:
+        r"""
+        whether `text` has a deny word
+        # Errors
+        * `NotImplementedError` unless overridden
+        """
+    def find_all(self, text: builtins.str) -> builtins.list[Match]:
+        r"""
+        one match covering a matching text, override to report words and offsets
+        # Errors
+        * errors of `is_match`
+        """
+    def scan_str(self, txt: builtins.str) -> builtins.bool:
+        r"""
+        `is_match` of a str
+        # Errors
+        * errors of `is_match`
+        """
+    def scan_any(self, value: typing.Any) -> builtins.bool:
+        r"""
+        `is_match` of every string of a dict/list/str
+        # Errors
+        * errors of `is_match` are reported as unraisable and count as a match
+        """
+
+@typing.final
+class NextVerdict:
+    r"""
+    awaitable of one verdict: drives the source's `__anext__` and scans the chunk it returns
+    """
+    def __await__(self, slf: NextVerdict) -> NextVerdict: ...
+    def __next__(self) -> typing.Any:
+        r"""
+        # Errors
+        * `StopIteration` with the verdict, errors of the source
+        """
+    def send(self, value: typing.Any) -> typing.Any:
+        r"""
+        # Errors
+        * `StopIteration` with the verdict, errors of the source
+        """
+    def throw(self, error: typing.Any) -> typing.Any:
+        r"""
+        # Errors
+        * `StopIteration` with the verdict, errors of the source
+        """
+
+@typing.final
+class PendingScan:
+    r"""
+    scan submitted to a `PyScanPool`
+    """
+    def result(self) -> builtins.bool:
+        r"""
+        waits for the scan without holding the GIL
+        """
+
+@typing.final
+class ScanPool:
+    r"""
+    python handle of a `ScanPool`, scans run off the GIL
+    """
+    @property
+    def workers(self) -> builtins.int: ...
+    def __new__(cls, words: typing.Sequence[builtins.str], workers: builtins.int = 4, queue_size: builtins.int = 64, backend: builtins.str = 'aho-corasick', **options: typing.Any) -> ScanPool:
+        r"""
+        pool scanning with a matcher of `backend`, keyword options: see `Options`
+        # Errors
+        * unknown backend, invalid patterns or options, no workers
+        """
+    def submit(self, text: builtins.str) -> PendingScan:
+        r"""
+        queues a scan, waits off the GIL while the queue is full
+        """
+    def scan_many(self, texts: typing.Sequence[builtins.str]) -> builtins.list[builtins.bool]:
+        r"""
+        scans texts concurrently, results in input order
+        """
+    def scan_any(self, value: typing.Any) -> builtins.bool:
+        r"""
+        scans every string of a dict/list/str concurrently
+        """
+
+@typing.final
+class Stats:
+    r"""
+    size of the compiled automata, summed over the automata of all match kinds
+    """
+    @property
+    def patterns(self) -> builtins.int:
+        r"""
+        compiled (unique, normalized) patterns
+        """
+    @property
+    def states(self) -> builtins.int:
+        r"""
+        states of the pattern tries, one per distinct pattern prefix plus the roots
+        """
+    @property
+    def min_pattern_len(self) -> builtins.int:
+        r"""
+        shortest normalized pattern in bytes, 0 without patterns
+        """
+    @property
+    def max_pattern_len(self) -> builtins.int:
+        r"""
+        longest normalized pattern in bytes
+        """
+    @property
+    def heap_bytes(self) -> builtins.int:
+        r"""
+        heap bytes of the automata as reported by the backend crates
+        """
+    @property
+    def backend(self) -> typing.Optional[builtins.str]:
+        r"""
+        backend picked by `DenyListAuto`, None for the other matchers
+        """
+    def __repr__(self) -> builtins.str: ...
+
+@typing.final
+class StreamScan:
+    r"""
+    async iterator of verdicts: for each chunk of the source the new matches (empty when
+    clean), and a last verdict with the matches held back at the end of the stream
+    """
+    def __aiter__(self, slf: StreamScan) -> StreamScan: ...
+    def __anext__(self, slf: StreamScan) -> NextVerdict:
+        r"""
+        awaitable of the next verdict
+        # Errors
+        * the stream has ended
+        """
+
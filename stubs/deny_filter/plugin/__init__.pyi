@@ -0,0 +1,395 @@
+# This file is automatically generated by pyo3_stub_gen
+# ruff: noqa: E501, F401, F403, F405
+
+import builtins
+from deny_filter import matchers
+import os
+import pathlib
+import typing
+__all__ = [
+    "ChatHit",
+    "DenyListRegistry",
+    "EmailHit",
+    "FileReport",
+    "HeaderHit",
+    "IncrementalDenyList",
+    "LogFilter",
+    "McpVerdict",
+    "RegisteredList",
+    "ShadowDenyList",
+    "SignedLoader",
+    "UrlHit",
+    "Violation",
+]
+
+@typing.final
+class ChatHit:
+    r"""
+    the chat message with a deny word
+    """
+    @property
+    def role(self) -> builtins.str:
+        r"""
+        `role` of the message, empty when missing
+        """
+    @property
+    def index(self) -> builtins.int:
+        r"""
+        position of the message in the list
+        """
+    @property
+    def part(self) -> typing.Optional[builtins.int]:
+        r"""
+        position of the text block in a list `content`, None for string content
+        """
+    @property
+    def word(self) -> builtins.str:
+        r"""
+        deny word that matched first
+        """
+    def __repr__(self) -> builtins.str: ...
+
+@typing.final
+class DenyListRegistry:
+    def __new__(cls, configs: typing.Optional[dict] = None) -> DenyListRegistry:
+        r"""
+        registry of `configs`: name to `{"words": [...], "backend": ..., **options}`
+        # Errors
+        * a config without words, unknown backend, invalid patterns or options
+        """
+    def get(self, name: builtins.str) -> RegisteredList:
+        r"""
+        matcher of `name`
+        # Errors
+        * `KeyError` for unknown names
+        """
+    def reload(self, name: builtins.str, config: dict) -> None:
+        r"""
+        builds `config` and swaps it in as `name`, lists fetched before keep the old
+        matcher; on errors the current matcher stays
+        # Errors
+        * a config without words, unknown backend, invalid patterns or options
+        """
+    def register(self, name: builtins.str, matcher: typing.Any) -> None:
+        r"""
+        registers a python matcher (a `Matcher` subclass or any object with `is_match`)
+        as `name`, scans call back into python
+        # Errors
+        * the object has no callable `is_match`
+        """
+    def remove(self, name: builtins.str) -> None:
+        r"""
+        unregisters `name`
+        # Errors
+        * `KeyError` for unknown names
+        """
+    def names(self) -> builtins.list[builtins.str]:
+        r"""
+        registered names in sorted order
+        """
+    def scan_all(self, payload: typing.Any) -> builtins.dict[builtins.str, builtins.bool]:
+        r"""
+        verdict of every list on a dict/list/str, or bytes of a msgpack document, walked
+        once; a dict of list name to whether it matched
+        # Errors
+        * malformed msgpack
+        * over a length limit of a list with `on_overflow="raise"`
+        """
+    def __contains__(self, name: builtins.str) -> builtins.bool: ...
+    def __len__(self) -> builtins.int: ...
+
+@typing.final
+class EmailHit:
+    r"""
+    the part of an email with a deny word
+    """
+    @property
+    def part(self) -> builtins.str:
+        r"""
+        header name in lowercase, or `text/plain` or `text/html` for a body part
+        """
+    @property
+    def word(self) -> builtins.str:
+        r"""
+        deny word that matched first
+        """
+    def __repr__(self) -> builtins.str: ...
+
+@typing.final
+class FileReport:
+    r"""
+    `FileReport` as returned to python, hits are `(location, pattern)` pairs
+    """
+    @property
+    def path(self) -> builtins.str: ...
+    @property
+    def hits(self) -> builtins.list[tuple[builtins.str, builtins.str]]: ...
+    @property
+    def error(self) -> typing.Optional[builtins.str]: ...
+    def __repr__(self) -> builtins.str: ...
+
+@typing.final
+class HeaderHit:
+    r"""
+    the http header with a deny word
+    """
+    @property
+    def name(self) -> builtins.str:
+        r"""
+        header name in lowercase
+        """
+    @property
+    def value(self) -> builtins.str:
+        r"""
+        comma separated element of the value with the deny word, decoded when `decoded`
+        """
+    @property
+    def decoded(self) -> builtins.bool:
+        r"""
+        whether the deny word was found in the base64 decoded text of a token
+        """
+    @property
+    def word(self) -> builtins.str:
+        r"""
+        deny word that matched first
+        """
+    def __repr__(self) -> builtins.str: ...
+
+@typing.final
+class IncrementalDenyList:
+    r"""
+    python handle of an `IncrementalMatcher`: scans run on the current matcher while
+    `add_words` builds the next one without the GIL, one update at a time
+    """
+    @property
+    def delta_len(self) -> builtins.int:
+        r"""
+        deny words added since the last merge
+        """
+    def __new__(cls, words: typing.Sequence[builtins.str], merge_at: builtins.int = 1024, backend: builtins.str = 'aho-corasick', **options: typing.Any) -> IncrementalDenyList:
+        r"""
+        keyword options: see `Options`, used by the base and the delta
+        # Errors
+        * unknown backend, invalid patterns or options
+        """
+    def add_words(self, words: typing.Sequence[builtins.str]) -> builtins.bool:
+        r"""
+        adds deny words to the delta, True when that reached `merge_at` and was merged
+        # Errors
+        * invalid patterns, the list is unchanged
+        """
+    def merge(self) -> None:
+        r"""
+        rebuilds the base with the delta words, e.g. from a timer in a quiet period
+        # Errors
+        * invalid patterns, the list is unchanged
+        """
+    def is_match(self, s: builtins.str) -> builtins.bool: ...
+    def find_all(self, text: builtins.str) -> builtins.list[matchers.Match]: ...
+    def scan_str(self, txt: builtins.str) -> builtins.bool:
+        r"""
+        # Errors
+        * over a length limit with `on_overflow="raise"`
+        """
+    def scan(self, args: dict) -> builtins.bool:
+        r"""
+        # Errors
+        * over a length limit with `on_overflow="raise"`
+        """
+    def scan_any(self, value: typing.Any) -> builtins.bool:
+        r"""
+        # Errors
+        * over a length limit with `on_overflow="raise"`
+        """
+    def stats(self) -> matchers.Stats:
+        r"""
+        sizes of the base and delta automata added up
+        """
+    def __len__(self) -> builtins.int: ...
+
+@typing.final
+class LogFilter:
+    r"""
+    `logging.Filter` of a deny list: a record with a deny word is dropped, or logged with
+    `replacement` as its message and its `extra` fields, without arguments and exception
+    """
+    def filter(self, record: typing.Any) -> builtins.bool:
+        r"""
+        whether `logging` emits the record, see `scan_log_record`
+        # Errors
+        * over a length limit with `on_overflow="raise"`
+        """
+    def __repr__(self) -> builtins.str: ...
+
+@typing.final
+class McpVerdict:
+    r"""
+    verdict of an MCP JSON-RPC message in the shape of a gateway plugin result
+    """
+    @property
+    def continue_processing(self) -> builtins.bool:
+        r"""
+        false when a deny word was found
+        """
+    @property
+    def method(self) -> typing.Optional[builtins.str]:
+        r"""
+        method of the envelope with the deny word, None for responses
+        """
+    @property
+    def path(self) -> typing.Optional[builtins.str]:
+        r"""
+        `$.params.arguments.key` path of the string with the deny word
+        """
+    @property
+    def word(self) -> typing.Optional[builtins.str]:
+        r"""
+        deny word that matched first
+        """
+    def __repr__(self) -> builtins.str: ...
+
+@typing.final
+class RegisteredList:
+    r"""
+    matcher of a registry entry as it was when fetched, later reloads do not change it
+    """
+    @property
+    def metadata(self) -> matchers.ListMetadata:
+        r"""
+        version, source and timestamp options of the list
+        """
+    def is_match(self, s: builtins.str) -> builtins.bool: ...
+    def find_all(self, text: builtins.str) -> builtins.list[matchers.Match]:
+        r"""
+        non-overlapping matches with byte and code point offsets
+        """
+    def redact(self, text: builtins.str, mask: builtins.str = '*') -> builtins.str:
+        r"""
+        masks every match grapheme by grapheme
+        """
+    def scan_str(self, txt: builtins.str) -> builtins.bool:
+        r"""
+        # Errors
+        * over a length limit with `on_overflow="raise"`
+        """
+    def scan(self, args: dict) -> builtins.bool:
+        r"""
+        # Errors
+        * over a length limit with `on_overflow="raise"`
+        """
+    def scan_any(self, value: typing.Any) -> builtins.bool:
+        r"""
+        # Errors
+        * over a length limit with `on_overflow="raise"`
+        """
+    def stats(self) -> matchers.Stats: ...
+
+@typing.final
+class ShadowDenyList:
+    r"""
+    python handle of a `ShadowMatcher`, both lists built from words with one backend
+    """
+    @property
+    def sample_rate(self) -> builtins.float: ...
+    @property
+    def sampled(self) -> builtins.int:
+        r"""
+        scans the candidate evaluated, counted per name
+        """
+    @property
+    def candidate_blocks(self) -> builtins.int:
+        r"""
+        sampled scans the candidate would block but the active list allows
+        """
+    @property
+    def candidate_allows(self) -> builtins.int:
+        r"""
+        sampled scans the candidate would allow but the active list blocks
+        """
+    def __new__(cls, words: typing.Sequence[builtins.str], candidate_words: typing.Sequence[builtins.str], sample_rate: builtins.float = 0.1, name: builtins.str = 'candidate', backend: builtins.str = 'aho-corasick', **options: typing.Any) -> ShadowDenyList:
+        r"""
+        `words` decide, `candidate_words` are evaluated on `sample_rate` of the scans;
+        keyword options: see `Options`, used by both lists
+        # Errors
+        * unknown backend, invalid patterns or options, sample rate outside 0..=1
+        """
+    def is_match(self, s: builtins.str) -> builtins.bool: ...
+    def find_all(self, text: builtins.str) -> builtins.list[matchers.Match]:
+        r"""
+        matches of the active list
+        """
+    def scan_str(self, txt: builtins.str) -> builtins.bool:
+        r"""
+        # Errors
+        * over a length limit with `on_overflow="raise"`
+        """
+    def scan(self, args: dict) -> builtins.bool:
+        r"""
+        # Errors
+        * over a length limit with `on_overflow="raise"`
+        """
+    def scan_any(self, value: typing.Any) -> builtins.bool:
+        r"""
+        # Errors
+        * over a length limit with `on_overflow="raise"`
+        """
+
+@typing.final
+class SignedLoader:
+    r"""
+    loads word lists only when their detached ed25519 signature verifies,
+    so tampered lists are rejected before an automaton is built
+    """
+    def __new__(cls, public_key: bytes) -> SignedLoader:
+        r"""
+        loader verifying with a 32 byte ed25519 public key
+        # Errors
+        * not a 32 byte ed25519 public key
+        """
+    def load(self, path: builtins.str | os.PathLike | pathlib.Path, signature: typing.Optional[bytes] = None) -> builtins.list[builtins.str]:
+        r"""
+        verified words of a word list file, the signature defaults to `<path>.sig`
+        # Errors
+        * unreadable files, tampered list or malformed signature
+        """
+
+@typing.final
+class UrlHit:
+    r"""
+    the part of a url with a deny word
+    """
+    @property
+    def part(self) -> builtins.str:
+        r"""
+        `scheme`, `userinfo`, `host`, `path`, `query` or `fragment`
+        """
+    @property
+    def value(self) -> builtins.str:
+        r"""
+        the decoded path segment, query key or value, or part with the deny word
+        """
+    @property
+    def word(self) -> builtins.str:
+        r"""
+        deny word that matched first
+        """
+    def __repr__(self) -> builtins.str: ...
+
+@typing.final
+class Violation:
+    r"""
+    a deny word of a payload with the top level key it is under
+    """
+    @property
+    def field(self) -> typing.Optional[builtins.str]:
+        r"""
+        key of the dict item with the deny word, None for a payload that is no dict
+        """
+    @property
+    def word(self) -> typing.Optional[builtins.str]:
+        r"""
+        deny word, or label of the proximity rule, that matched; None when a limit
+        decided the verdict with `on_overflow="block"`
+        """
+    def __repr__(self) -> builtins.str: ...
+
@@ -0,0 +1,146 @@
+#![cfg(feature = "arrow")]
+
+use arrow_array::{Array, ArrayRef, Int32Array, RecordBatch, StringArray, StringViewArray};
+use deny_filter::arrow::{scan_array, scan_batch};
+use deny_filter::deny_list::DenyList;
+use std::sync::Arc;
+
+fn batch() -> RecordBatch {
+    let title: ArrayRef = Arc::new(StringArray::from(vec![Some("ok"), None, Some("HACK it")]));
+    let body: ArrayRef = Arc::new(StringViewArray::from(vec!["free hack", "fine", "x"]));
+    let id: ArrayRef = Arc::new(Int32Array::from(vec![1, 2, 3]));
+    RecordBatch::try_from_iter([("title", title), ("body", body), ("id", id)]).unwrap()
+}
+
+#[test]
+fn test_scan_batch() {
+    let deny_list = DenyList::new(vec!["hack".to_string()]).unwrap();
+    let mask = scan_batch(&deny_list, &batch(), None).unwrap();
+    assert_eq!(
+        mask.values().iter().collect::<Vec<_>>(),
+        [true, false, true]
+    );
+    assert_eq!(mask.null_count(), 0);
+
+    let title = ["title".to_string()];
+    let mask = scan_batch(&deny_list, &batch(), Some(&title)).unwrap();
+    assert_eq!(
+        mask.values().iter().collect::<Vec<_>>(),
+        [false, false, true]
+    );
+
+    assert!(scan_batch(&deny_list, &batch(), Some(&["id".to_string()])).is_err());
+    assert!(scan_batch(&deny_list, &batch(), Some(&["missing".to_string()])).is_err());
+    assert!(scan_array(&deny_list, &Int32Array::from(vec![1])).is_err());
+}
+
+#[cfg(feature = "python")]
+#[test]
+fn test_scan_arrow_capsules() {
+    use arrow_array::StructArray;
+    use pyo3::prelude::*;
+    use pyo3::types::{PyCapsule, PyDict};
+
+    Python::initialize();
+    Python::attach(|py| {
+        let module = PyModule::new(py, "deny_filter").unwrap();
+        deny_filter::pymodule::deny_filter(&module).unwrap();
+        let matcher = module
+            .getattr("DenyList")
+            .unwrap()
+            .call1((vec!["hack"],))
+            .unwrap();
+
+        let rows = StructArray::from(batch());
+        let (array, schema) = arrow_array::ffi::to_ffi(&rows.to_data()).unwrap();
+        let globals = PyDict::new(py);
+        globals
+            .set_item(
+                "schema",
+                PyCapsule::new(py, schema, Some(c"arrow_schema".into())).unwrap(),
+            )
+            .unwrap();
+        globals
+            .set_item(
+                "array",
+                PyCapsule::new(py, array, Some(c"arrow_array".into())).unwrap(),
+            )
+            .unwrap();
+        py.run(
+            c"class Batch:\n    def __arrow_c_array__(self, requested_schema=None):\n        return (schema, array)\nbatch = Batch()",
+            Some(&globals),
+            None,
+        )
+        .unwrap();
+        let batch = globals.get_item("batch").unwrap().unwrap();
+        let mask = matcher.call_method1("scan_arrow", (batch,)).unwrap();
+        let values: Vec<bool> = mask.call_method0("to_list").unwrap().extract().unwrap();
+        assert_eq!(values, vec![true, false, true]);
+        let capsules = mask.call_method0("__arrow_c_array__").unwrap();
+        assert_eq!(capsules.len().unwrap(), 2);
+        assert!(matcher.call_method1("scan_arrow", ("text",)).is_err());
+    });
+}
+
+#[cfg(feature = "python")]
+#[test]
+fn test_scan_series() {
+    use arrow_array::RecordBatchIterator;
+    use arrow_array::ffi_stream::FFI_ArrowArrayStream;
+    use pyo3::prelude::*;
+    use pyo3::types::{PyCapsule, PyDict};
+
+    Python::initialize();
+    Python::attach(|py| {
+        let module = PyModule::new(py, "deny_filter").unwrap();
+        deny_filter::pymodule::deny_filter(&module).unwrap();
+        let matcher = module
+            .getattr("DenyListDaac")
+            .unwrap()
+            .call1((vec!["hack"],))
+            .unwrap();
+
+        let frame = batch().project(&[0]).unwrap();
+        let schema = frame.schema();
+        let stream =
+            FFI_ArrowArrayStream::new(Box::new(RecordBatchIterator::new(vec![Ok(frame)], schema)));
+        let globals = PyDict::new(py);
+        globals
+            .set_item(
+                "stream",
+                PyCapsule::new(py, stream, Some(c"arrow_array_stream".into())).unwrap(),
+            )
+            .unwrap();
+        // stand-ins for polars: a string series exporting its frame, a series built from a mask
+        py.run(
+            cr#"
+import sys, types
+class Frame:
+    def __arrow_c_stream__(self, requested_schema=None):
+        return stream
+class Series:
+    def __init__(self, name, values=None):
+        self.name = name
+        self.values = values.to_list() if values is not None else None
+    def to_frame(self):
+        return Frame()
+    def alias(self, name):
+        self.name = name
+        return self
+sys.modules["polars"] = types.SimpleNamespace(Series=Series)
+series = Series("title")
+"#,
+            Some(&globals),
+            None,
+        )
+        .unwrap();
+        let series = globals.get_item("series").unwrap().unwrap();
+        let mask = matcher.call_method1("scan_series", (series,)).unwrap();
+        let values: Vec<bool> = mask.getattr("values").unwrap().extract().unwrap();
+        assert_eq!(values, vec![false, false, true]);
+        let name: String = mask.getattr("name").unwrap().extract().unwrap();
+        assert_eq!(name, "title");
+        py.run(c"del sys.modules['polars']", Some(&globals), None)
+            .unwrap();
+    });
+}
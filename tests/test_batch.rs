@@ -0,0 +1,36 @@
+use deny_filter::backend::Backend;
+use deny_filter::batch::scan_msgpack_many;
+use deny_filter::traverse::Error;
+use rmp::encode;
+
+/// {"content": text}
+fn message(text: &str) -> Vec<u8> {
+    let mut buf = Vec::new();
+    encode::write_map_len(&mut buf, 1).unwrap();
+    encode::write_str(&mut buf, "content").unwrap();
+    encode::write_str(&mut buf, text).unwrap();
+    buf
+}
+
+#[test]
+fn test_scan_msgpack_many() {
+    let matcher = Backend::AhoCorasick
+        .build(vec!["voila".to_string()])
+        .unwrap();
+    let messages: Vec<Vec<u8>> = (0..200)
+        .map(|i| message(if i % 7 == 3 { "et voila" } else { "fine" }))
+        .collect();
+    let buffers: Vec<&[u8]> = messages.iter().map(Vec::as_slice).collect();
+    let expected: Vec<bool> = (0..200).map(|i| i % 7 == 3).collect();
+    for workers in [1, 4, 500] {
+        let verdicts: Vec<bool> = scan_msgpack_many(&*matcher, &buffers, workers)
+            .into_iter()
+            .map(Result::unwrap)
+            .collect();
+        assert_eq!(verdicts, expected, "{workers} workers");
+    }
+    let truncated = &messages[3][..4];
+    let results = scan_msgpack_many(&*matcher, &[&messages[3], truncated, &messages[0]], 2);
+    assert_eq!(results, [Ok(true), Err(Error::UnexpectedEof), Ok(false)]);
+    assert!(scan_msgpack_many(&*matcher, &[], 4).is_empty());
+}
@@ -0,0 +1,33 @@
+use deny_filter::backend::Backend;
+use deny_filter::bench::benchmark;
+use deny_filter::deny_list_auto::CANDIDATES;
+use deny_filter::options::Options;
+
+#[test]
+fn test_benchmark_backends() {
+    let words: Vec<String> = ["hack", "voilà", "spam"].map(String::from).to_vec();
+    let samples: Vec<String> = (0..20)
+        .map(|i| {
+            format!(
+                "message {i} with some text{}",
+                if i % 4 == 0 { ", SPAM" } else { "" }
+            )
+        })
+        .collect();
+    let results = benchmark(&words, &samples, 3, &Options::default()).unwrap();
+    let names: Vec<&str> = results.iter().map(|r| r.backend.as_str()).collect();
+    assert_eq!(names, CANDIDATES.map(Backend::name));
+    for result in &results {
+        assert_eq!(result.scans, 60);
+        assert_eq!(result.matches, 5);
+        assert!(result.heap_bytes > 0);
+        assert!(result.p50_us <= result.p99_us);
+    }
+
+    let empty = benchmark(&words, &[], 3, &Options::default()).unwrap();
+    assert!(
+        empty
+            .iter()
+            .all(|r| r.scans == 0 && r.throughput_mb_s == 0.0)
+    );
+}
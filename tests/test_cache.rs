@@ -0,0 +1,63 @@
+use deny_filter::backend::Backend;
+use deny_filter::cache::{CacheInfo, ResultCache};
+use deny_filter::options::Options;
+use serde_json::json;
+
+#[test]
+fn test_result_cache() {
+    let cache = ResultCache::new(2);
+    let mut scans = 0;
+    let mut verdict = |s: &str| {
+        cache.verdict(s, || {
+            scans += 1;
+            (s == "bad", None)
+        })
+    };
+    assert!(verdict("bad").0);
+    assert!(!verdict("good").0);
+    assert!(verdict("bad").0);
+    // "good" is the least recently used and makes room
+    assert!(!verdict("fine").0);
+    assert!(verdict("bad").0);
+    assert!(!verdict("good").0);
+    assert_eq!(scans, 4);
+    assert_eq!(
+        cache.info(),
+        CacheInfo {
+            hits: 2,
+            misses: 4,
+            size: 2,
+            capacity: 2,
+        }
+    );
+}
+
+#[test]
+fn test_cache_size() {
+    let options = Options {
+        cache_size: Some(16),
+        ..Options::default()
+    };
+    for backend in Backend::ALL {
+        let matcher = backend
+            .build_with(vec!["voila".to_string()], &options)
+            .unwrap();
+        let prompt = "you are a helpful assistant, answer briefly and politely to everyone";
+        let payload = json!([prompt, prompt, "voila", "voila"]);
+        assert!(matcher.scan_json(&payload), "{backend:?}");
+        assert!(matcher.scan_str("voila"), "{backend:?}");
+        let info = matcher.result_cache().unwrap().info();
+        // the repeat within the payload is not scanned and the first match stops the
+        // walk, then "voila" is a hit across calls
+        assert_eq!(
+            (info.hits, info.misses, info.size),
+            (1, 2, 2),
+            "{backend:?}"
+        );
+    }
+    let matcher = Backend::AhoCorasick
+        .build_with(vec!["voila".to_string()], &Options::default())
+        .unwrap();
+    assert!(matcher.scan_str("voila"));
+    assert!(matcher.result_cache().is_none());
+}
@@ -0,0 +1,78 @@
+use deny_filter::backend::Backend;
+use deny_filter::chat::{ChatHit, scan_anthropic_messages, scan_chat_messages};
+use serde_json::json;
+
+#[test]
+fn test_scan_chat_messages() {
+    let deny_list = Backend::AhoCorasick
+        .build(vec!["hack".to_string(), "scam".to_string()])
+        .unwrap();
+    let messages = json!([
+        {"role": "system", "content": "You are helpful."},
+        {"role": "user", "content": [
+            {"type": "image_url", "image_url": {"url": "https://example.com/hack.png"}},
+            {"type": "text", "text": "what is this?"},
+        ]},
+        {"role": "assistant", "content": null, "tool_calls": [{"function": {"arguments": "hack"}}]},
+        {"role": "user", "content": [{"type": "text", "text": "fine"}, {"type": "text", "text": "a SCAM"}]},
+    ]);
+    assert_eq!(
+        scan_chat_messages(&*deny_list, &messages),
+        Some(ChatHit {
+            role: "user".to_string(),
+            index: 3,
+            part: Some(1),
+            word: "scam".to_string(),
+        })
+    );
+    let hit = scan_chat_messages(&*deny_list, &json!([{"content": "hack it"}])).unwrap();
+    assert_eq!((hit.role.as_str(), hit.index, hit.part), ("", 0, None));
+    let clean = json!(messages.as_array().unwrap()[..3]);
+    assert_eq!(scan_chat_messages(&*deny_list, &clean), None);
+    assert_eq!(
+        scan_chat_messages(&*deny_list, &json!({"content": "hack"})),
+        None
+    );
+}
+
+#[test]
+fn test_scan_anthropic_messages() {
+    let deny_list = Backend::AhoCorasick
+        .build(vec!["hack".to_string(), "scam".to_string()])
+        .unwrap();
+    let hit = |messages| scan_anthropic_messages(&*deny_list, &messages);
+    let tool_use = json!([
+        {"role": "user", "content": "look up the weather"},
+        {"role": "assistant", "content": [
+            {"type": "thinking", "thinking": "a hack?"},
+            {"type": "tool_use", "id": "t1", "name": "search", "input": {"query": ["weather", "HACK"]}},
+        ]},
+    ]);
+    assert_eq!(
+        hit(tool_use),
+        Some(ChatHit {
+            role: "assistant".to_string(),
+            index: 1,
+            part: Some(1),
+            word: "hack".to_string(),
+        })
+    );
+    let tool_result = json!([{"role": "user", "content": [
+        {"type": "image", "source": {"type": "base64", "data": "scam"}},
+        {"type": "tool_result", "tool_use_id": "t1", "content": [{"type": "text", "text": "a scam"}]},
+    ]}]);
+    let found = hit(tool_result).unwrap();
+    assert_eq!(
+        (found.index, found.part, found.word.as_str()),
+        (0, Some(1), "scam")
+    );
+    let document = json!([{"role": "user", "content": [
+        {"type": "document", "source": {"type": "text", "media_type": "text/plain", "data": "hack"}},
+    ]}]);
+    assert_eq!(hit(document).unwrap().part, Some(0));
+    let clean = json!([{"role": "user", "content": [
+        {"type": "tool_result", "tool_use_id": "t1", "content": "sunny"},
+        {"type": "redacted_thinking", "data": "hack"},
+    ]}]);
+    assert_eq!(hit(clean), None);
+}
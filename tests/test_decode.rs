@@ -0,0 +1,136 @@
+use deny_filter::backend::Backend;
+use deny_filter::options::Options;
+
+fn build(
+    words: &[&str],
+    options: &Options,
+) -> Box<dyn deny_filter::matcher::Matcher + Send + Sync> {
+    Backend::AhoCorasick
+        .build_with(words.iter().map(ToString::to_string).collect(), options)
+        .unwrap()
+}
+
+#[test]
+fn test_rot13() {
+    let options: Options = serde_json::from_str(r#"{"rot13": true}"#).unwrap();
+    for backend in Backend::ALL {
+        let matcher = backend
+            .build_with(vec!["voila".to_string()], &options)
+            .unwrap();
+        assert!(matcher.is_match("say IBVYN now"), "{backend}");
+        assert_eq!(matcher.first_match("ibvyn"), Some("voila"), "{backend}");
+        assert_eq!(matcher.redact("ok ibvyn!", "*"), "ok *****!", "{backend}");
+    }
+    let explanation = build(&["voila"], &options).explain("ibvyn").unwrap();
+    assert_eq!(explanation.steps[0], "rot13");
+    assert!(!build(&["voila"], &Options::default()).is_match("ibvyn"));
+}
+
+#[test]
+fn test_substitutions() {
+    let options: Options =
+        serde_json::from_str(r#"{"substitutions": {"0": "o", "1": "i", "4": "a", "€": "e"}}"#)
+            .unwrap();
+    let matcher = build(&["voila", "free"], &options);
+    assert!(matcher.is_match("v01l4"));
+    // multi-byte replaced chars keep their offsets
+    let text = "a fr€€ gift";
+    let hits = matcher.find_all(text);
+    assert_eq!(hits.len(), 1);
+    assert_eq!(&text[hits[0].start..hits[0].end], "fr€€");
+    // a word in the input and in its decoding is one occurrence
+    assert_eq!(matcher.find_overlapping("voila").unwrap().len(), 1);
+}
+
+#[test]
+fn test_overlapping_dedup() {
+    let options: Options =
+        serde_json::from_str(r#"{"collapse_repeats": true, "substitutions": {"x": "y"}}"#).unwrap();
+    let matcher = build(&["voila", "voilla"], &options);
+    // both words span "voillla" in the input and again in its decoding
+    let hits = matcher.find_overlapping("voillla x").unwrap();
+    let words: Vec<_> = hits
+        .iter()
+        .map(|m| (m.word.as_str(), m.start, m.end))
+        .collect();
+    assert_eq!(words, [("voila", 0, 7), ("voilla", 0, 7)]);
+}
+
+#[test]
+fn test_decode_depth() {
+    let options: Options =
+        serde_json::from_str(r#"{"rot13": true, "substitutions": {"0": "o"}}"#).unwrap();
+    // "i0vyn" needs both layers
+    assert!(build(&["voila"], &options).is_match("i0vyn"));
+    let shallow = Options {
+        decode_depth: Some(1),
+        ..options.clone()
+    };
+    assert!(!build(&["voila"], &shallow).is_match("i0vyn"));
+    let off = Options {
+        decode_depth: Some(0),
+        ..options
+    };
+    assert!(!build(&["voila"], &off).is_match("ibvyn"));
+}
+
+#[test]
+fn test_encoded_tokens() {
+    for (option, text) in [
+        ("hex", "ok 766f696c61!"),
+        ("base32", "ok ONQXSIDWN5UWYYI=!"),
+        ("base64", "ok dm9pbGEh!"),
+    ] {
+        let options: Options = serde_json::from_str(&format!(r#"{{"{option}": true}}"#)).unwrap();
+        let matcher = build(&["voila"], &options);
+        assert!(matcher.is_match(text), "{option}");
+        assert_eq!(matcher.explain(text).unwrap().steps[0], option);
+        // the whole encoded token is the occurrence
+        let hits = matcher.find_all(text);
+        let token = &text[3..text.len() - 1];
+        assert_eq!(&text[hits[0].start..hits[0].end], token, "{option}");
+        assert!(
+            !matcher.is_match("ok deadbeef 12345678 abcdefgh"),
+            "{option}"
+        );
+    }
+}
+
+#[test]
+fn test_nested_encodings() {
+    // base64 of the hex of "voila"
+    let text = "NzY2ZjY5NmM2MQ==";
+    let options: Options = serde_json::from_str(r#"{"hex": true, "base64": true}"#).unwrap();
+    let matcher = build(&["voila"], &options);
+    assert!(matcher.is_match(text));
+    assert_eq!(matcher.explain(text).unwrap().steps[..2], ["base64", "hex"]);
+    let shallow = Options {
+        decode_depth: Some(1),
+        ..options
+    };
+    assert!(!build(&["voila"], &shallow).is_match(text));
+}
+
+#[test]
+fn test_jwt_claims() {
+    // {"alg":"HS256","typ":"JWT"}.{"sub":"42","note":"say voilà"}.sig
+    let token = "eyJhbGciOiJIUzI1NiIsInR5cCI6IkpXVCJ9.\
+                 eyJzdWIiOiI0MiIsIm5vdGUiOiJzYXkgdm9pbFx1MDBlMCJ9.c2ln";
+    assert_eq!(
+        deny_filter::decode::jwt_text(token).unwrap(),
+        "alg HS256 typ JWT note say voilà sub 42"
+    );
+    // claims that are no json object
+    assert_eq!(
+        deny_filter::decode::jwt_text("eyJhbGciOiJub25lIn0.WyJ2b2lsYSJd."),
+        None
+    );
+    let options: Options = serde_json::from_str(r#"{"jwt": true}"#).unwrap();
+    let matcher = build(&["voilà"], &options);
+    let text = format!("Authorization: Bearer {token}");
+    assert!(matcher.is_match(&text));
+    assert_eq!(matcher.explain(&text).unwrap().steps[0], "jwt");
+    let hits = matcher.find_all(&text);
+    assert_eq!(&text[hits[0].start..hits[0].end], token);
+    assert!(!build(&["voilà"], &Options::default()).is_match(&text));
+}
@@ -73,6 +73,23 @@ def test_automaton_case_insensitive(deny_ac_plugin):
     assert not deny_ac_plugin._contains_deny_word("safe content")
 
 
+def test_plugin_reload(deny_ac_plugin):
+    """Test that reload swaps in a new list and keeps the active one on errors."""
+    old = deny_ac_plugin._active
+    diff = deny_ac_plugin.reload({"words": ["spam", "secret"]})
+    assert diff.added == ["spam"] and diff.removed == ["malware", "danger"]
+    assert deny_ac_plugin._contains_deny_word("SPAM")
+    assert not deny_ac_plugin._contains_deny_word("danger")
+    # scans holding the old list finish on it
+    assert deny_ac_plugin._contains_deny_word("danger", old)
+    with pytest.raises(ValueError):
+        deny_ac_plugin.reload({"words": ["spam"], "flag_threshold": 2.0, "block_threshold": 1.0})
+    assert deny_ac_plugin._contains_deny_word("spam")
+    health = deny_ac_plugin.health()
+    assert health.ready and health.patterns == 2
+    assert "flag_threshold" in health.last_error
+
+
 def test_mixed_case_patterns(deny_ac_plugin):
     """Test various mixed case patterns."""
     mixed_cases = [
@@ -93,6 +110,19 @@ def test_mixed_case_patterns(deny_ac_plugin):
         assert deny_ac_plugin._contains_deny_word(text), f"Failed to detect deny word in: {text}"
 
 
+def test_plugin_routing(deny_ac_plugin):
+    """Test that priority, hooks and tags of the deny list config override the plugin ones."""
+    assert deny_ac_plugin.priority == 100
+    deny_ac_plugin.reload(
+        {"words": ["spam"], "priority": 5, "hooks": ["prompt_pre_fetch"], "tags": ["pii"]}
+    )
+    assert deny_ac_plugin.priority == 5
+    assert deny_ac_plugin.hooks == ["prompt_pre_fetch"]
+    assert deny_ac_plugin.tags == ["pii"]
+    with pytest.raises(ValueError):
+        deny_ac_plugin.reload({"words": ["spam"], "hooks": ["tool_pre_invoke"]})
+
+
 @pytest.mark.asyncio
 async def test_plugin_prompt_pre_fetch_clean(deny_ac_plugin, plugin_context):
     """Test DenyListAcPlugin prompt_pre_fetch hook with clean input."""
@@ -109,6 +139,38 @@ async def test_plugin_prompt_pre_fetch_violation(deny_ac_plugin, plugin_context)
     assert result.violation is not None
 
 
+@pytest.mark.asyncio
+async def test_plugin_prompt_pre_fetch_context(deny_ac_plugin):
+    """Test that context identifiers are echoed into the details and the context is optional."""
+    gctx = GlobalContext(request_id="req-1", tenant_id="acme", user="alice")
+    payload = PromptPrehookPayload(prompt_id="test", args={"text": "DANGER zone"})
+    result = await deny_ac_plugin.prompt_pre_fetch(payload, PluginContext(global_context=gctx))
+    assert result.violation.details == {"request_id": "req-1", "tenant_id": "acme", "user": "alice"}
+    result = await deny_ac_plugin.prompt_pre_fetch(payload)
+    assert result.violation.details == {}
+
+
+@pytest.mark.asyncio
+async def test_plugin_prompt_pre_fetch_tenants():
+    """Test that the tenant of the context or the argument selects its own list."""
+    plugin_cfg = PluginConfig(
+        name="deny_ac_tenants",
+        kind=f"{DenyListAcPlugin.__module__}.{DenyListAcPlugin.__name__}",
+        hooks=[PromptHookType.PROMPT_PRE_FETCH],
+        priority=100,
+        config={"words": ["malware"], "tenants": {"acme": {"words": ["spam"]}}},
+    )
+    plugin = DenyListAcPlugin(config=plugin_cfg)
+    acme = PluginContext(global_context=GlobalContext(request_id="req-1", tenant_id="acme"))
+    spam = PromptPrehookPayload(prompt_id="test", args={"text": "spam"})
+    malware = PromptPrehookPayload(prompt_id="test", args={"text": "malware"})
+    assert (await plugin.prompt_pre_fetch(spam, acme)).violation is not None
+    assert (await plugin.prompt_pre_fetch(malware, acme)).violation is None
+    assert (await plugin.prompt_pre_fetch(spam, tenant="acme")).violation is not None
+    assert (await plugin.prompt_pre_fetch(spam, acme, tenant="other")).violation is None
+    assert (await plugin.prompt_pre_fetch(malware)).violation is not None
+
+
 @pytest.mark.asyncio
 async def test_plugin_prompt_pre_fetch_mixed_case(deny_ac_plugin, plugin_context):
     """Test DenyListAcPlugin prompt_pre_fetch hook with mixed case deny words."""
@@ -136,6 +198,53 @@ async def test_plugin_prompt_pre_fetch_multiple_args(deny_ac_plugin, plugin_cont
     assert result.violation is not None
 
 
+@pytest.mark.asyncio
+async def test_plugin_prompt_pre_fetch_audit(plugin_context):
+    """Test that audit mode reports the violation in metadata and lets the prompt through."""
+    plugin_cfg = PluginConfig(
+        name="deny_ac_audit",
+        kind=f"{DenyListAcPlugin.__module__}.{DenyListAcPlugin.__name__}",
+        hooks=[PromptHookType.PROMPT_PRE_FETCH],
+        priority=100,
+        config={"words": ["malware"], "mode": "audit", "version": "2025.2"},
+    )
+    plugin = DenyListAcPlugin(config=plugin_cfg)
+    payload = PromptPrehookPayload(prompt_id="test", args={"text": "Malware found"})
+    result = await plugin.prompt_pre_fetch(payload, plugin_context)
+    assert result.continue_processing
+    assert result.violation is None
+    assert result.metadata["deny_violation"]["code"] == "deny"
+    assert result.metadata["deny_violation"]["details"] == {
+        "version": "2025.2",
+        "request_id": "deny-ac-test-batch",
+    }
+
+
+@pytest.mark.asyncio
+async def test_plugin_prompt_pre_fetch_collect_all(plugin_context):
+    """Test that collect_all reports every deny word of every argument."""
+    plugin_cfg = PluginConfig(
+        name="deny_ac_collect_all",
+        kind=f"{DenyListAcPlugin.__module__}.{DenyListAcPlugin.__name__}",
+        hooks=[PromptHookType.PROMPT_PRE_FETCH],
+        priority=100,
+        config={"words": ["malware", "danger", "secret"], "collect_all": True},
+    )
+    plugin = DenyListAcPlugin(config=plugin_cfg)
+    payload = PromptPrehookPayload(
+        prompt_id="test",
+        args={"title": "SECRET malware", "content": "safe", "description": "Danger, secret"},
+    )
+    result = await plugin.prompt_pre_fetch(payload, plugin_context)
+    assert not result.continue_processing
+    assert result.violation.details["violations"] == [
+        {"field": "title", "word": "secret"},
+        {"field": "title", "word": "malware"},
+        {"field": "description", "word": "danger"},
+        {"field": "description", "word": "secret"},
+    ]
+
+
 @pytest.mark.asyncio
 async def test_plugin_shutdown(deny_ac_plugin):
     """Test DenyListAcPlugin shutdown method."""
@@ -0,0 +1,154 @@
+#!/usr/bin/env python3
+"""
+Pytest module for the deny list configuration model.
+"""
+
+import pytest
+
+from deny_filter import DenyList
+from plugins.deny_filter.deny import DenyListConfig
+
+
+def test_config_diff():
+    """Test added, removed and changed words and options between configurations."""
+    old = DenyListConfig(words=["free", "ass", {"word": "admin", "match": "anchored"}])
+    new = DenyListConfig(
+        words=["free", {"word": "ass", "match": "whole_word"}, "scam"],
+        match_kind="leftmost_longest",
+    )
+    diff = old.diff(new)
+    assert diff.added == ["scam"]
+    assert diff.removed == ["admin"]
+    assert diff.changed == {"ass": (None, "whole_word")}
+    assert diff.options == {"match_kind": ("leftmost_first", "leftmost_longest")}
+    assert not diff.is_empty()
+    assert old.diff(old).is_empty()
+
+
+def test_config_metadata():
+    """Test list metadata for violation details and on the built matcher."""
+    config = DenyListConfig(words=["free"], version="2025.1", source="lists/spam.txt")
+    assert config.metadata() == {"version": "2025.1", "source": "lists/spam.txt"}
+    deny_list = DenyList(config.deny_words(), **config.options())
+    assert deny_list.metadata.version == "2025.1"
+    assert deny_list.metadata.timestamp is None
+
+
+def test_config_fold():
+    """Test that the pure Python folding agrees with the Rust deny lists."""
+    words = ["voila", "straße", "İstanbul"]
+    texts = ["VOILA", "STRASSE", "İSTANBUL", "istanbul", "Straße"]
+    for case_fold in ["none", "ascii", "simple", "full", "turkish"]:
+        config = DenyListConfig(words=words, case_fold=case_fold)
+        deny_list = DenyList(config.deny_words(), **config.options())
+        folded = config.folded_words()
+        for text in texts:
+            expected = deny_list.is_match(text)
+            assert any(w in config.fold(text) for w in folded) == expected, (case_fold, text)
+
+
+def test_config_weights():
+    """Test that weights reach the Rust deny lists and the threshold stays in the plugin."""
+    config = DenyListConfig(
+        words=["Kill", {"word": "bomb", "weight": 2.5}, {"word": "gun", "match": "whole_word"}],
+        weights={"Kill": 4.0},
+        block_threshold=5.0,
+    )
+    assert config.word_weights() == {"Kill": 4.0, "bomb": 2.5, "gun": 1.0}
+    assert config.options() == {
+        "word_match": {"gun": "whole_word"},
+        "weights": {"Kill": 4.0, "bomb": 2.5},
+    }
+    assert config.weight("kill") == 4.0
+    deny_list = DenyList(config.deny_words(), **config.options())
+    assert deny_list.score({"text": "kill with a gun"}) == 5.0
+    rules = DenyListConfig(
+        words=["kill"], proximity=[{"first": "Wire", "second": "money", "within": 2, "weight": 3.0}]
+    )
+    assert rules.weight("wire NEAR/2 money") == 3.0
+    assert DenyList(rules.deny_words(), **rules.options()).score("wire the money") == 3.0
+    diff = config.diff(DenyListConfig(words=["Kill", "bomb", "gun"], block_threshold=5.0))
+    assert diff.reweighted == {"Kill": (4.0, 1.0), "bomb": (2.5, 1.0)}
+    assert diff.changed == {"gun": ("whole_word", None)}
+    for invalid in [
+        {"words": ["kill"], "weights": {"gun": 2.0}},
+        {"words": ["kill"], "weights": {"kill": -1.0}},
+        {"words": [{"word": "kill", "weight": float("inf")}]},
+        {"words": [{"word": "kill", "weight": 2.0}], "weights": {"kill": 3.0}},
+        {"words": ["kill"], "proximity": [{"first": "a", "second": "b", "within": 1, "weight": -1}]},
+    ]:
+        with pytest.raises(ValueError):
+            DenyListConfig(**invalid)
+
+
+def test_config_thresholds():
+    """Test the pure Python score and the threshold checks."""
+    config = DenyListConfig(
+        words=[{"word": "kill", "weight": 4.0}, "gun"], flag_threshold=1.0, block_threshold=5.0
+    )
+    assert config.scored()
+    assert config.score(["kill", "gun", "kill"]) == 5.0
+    assert config.score([]) == 0.0
+    assert config.model_copy(update={"score_aggregation": "max"}).score(["kill", "gun"]) == 4.0
+    assert not DenyListConfig(words=["kill"]).scored()
+    with pytest.raises(ValueError):
+        DenyListConfig(words=["kill"], flag_threshold=6.0, block_threshold=5.0)
+
+
+def test_config_messages():
+    """Test the violation texts picked for a locale."""
+    german = {"reason": "Nicht erlaubt", "description": "Gesperrtes Wort"}
+    config = DenyListConfig(words=["spam"], messages={"de": german, "pt_BR": german})
+    assert config.message("de-AT") == german
+    assert config.message("DE") == german
+    assert config.message("pt-br") == german
+    assert config.message("fr") == {}
+    assert config.message(None) == {}
+    assert "messages" not in config.options()
+
+
+def test_config_tenants():
+    """Test that tenant configurations are validated and not nested."""
+    config = DenyListConfig(
+        words=["spam"], tenants={"acme": {"words": ["scam"], "whole_word": True}}
+    )
+    assert config.tenants["acme"].whole_word
+    assert "tenants" not in config.options()
+    with pytest.raises(ValueError):
+        nested = {"words": ["x"], "tenants": {"b": {"words": []}}}
+        DenyListConfig(words=["spam"], tenants={"acme": nested})
+
+
+def test_config_regexes():
+    """Test that regex entries are validated per entry and kept apart from the words."""
+    card = {"pattern": r"\b\d{4}-\d{4}\b", "type": "regex"}
+    config = DenyListConfig(words=["spam", card])
+    assert config.deny_words() == ["spam"]
+    assert config.patterns() == [card["pattern"]]
+    assert config.entries() == {"spam": None, card["pattern"]: "regex"}
+    with pytest.raises(ValueError, match=r"words\[1\]: invalid regex"):
+        DenyListConfig(words=["spam", {"pattern": "(unclosed", "type": "regex"}])
+
+
+def test_config_allow():
+    """Test that allow literals and regexes reach the Rust deny lists as exceptions."""
+    town = {"pattern": r"scunthorpe\w*", "type": "regex"}
+    config = DenyListConfig(words=["ass", "cunt"], allow=["class action", town])
+    options = config.options()
+    assert options["allow"] == ["class action"]
+    assert options["allow_patterns"] == [town["pattern"]]
+    deny_list = DenyList(config.deny_words(), **options)
+    assert not deny_list.scan_str("a Class Action in Scunthorpe")
+    assert deny_list.scan_str("what an ass")
+    with pytest.raises(ValueError, match=r"allow\[0\]: invalid regex"):
+        DenyListConfig(words=["ass"], allow=[{"pattern": "(open", "type": "regex"}])
+
+
+def test_config_scan_bounds():
+    """Test that the depth and string count bounds reach the Rust deny lists."""
+    config = DenyListConfig(words=["hack"], max_depth=2, max_strings=3)
+    assert config.options() == {"max_depth": 2, "max_strings": 3}
+    deny_list = DenyList(config.deny_words(), **config.options())
+    assert not deny_list.scan_any({"a": [["hack"]]})
+    assert not deny_list.scan_any(["a", "b", "c", "hack"])
+    assert deny_list.scan_any({"a": ["hack"]})
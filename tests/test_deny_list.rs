@@ -1,3 +1,5 @@
+#![cfg(feature = "python")]
+
 mod test_py_deny_list;
 
 use deny_filter::deny_list::DenyList;
@@ -5,6 +7,7 @@ use deny_filter::deny_list_rs::DenyListRs;
 
 use deny_filter::build_error::build_error;
 use deny_filter::deny_list_daac::DenyListDaac;
+use deny_filter::deny_list_packed::DenyListPacked;
 use deny_filter::pymodule::deny_filter as deny_filter_mod;
 use pyo3::PyResult;
 use pyo3::prelude::*;
@@ -28,15 +31,15 @@ fn common_test_logic<T: deny_filter::matcher::Matcher>(deny_list: &T, py: Python
         .map(std::string::ToString::to_string)
         .collect();
     let list = PyList::new(py, list_data).unwrap();
-    assert!(deny_list.scan_any(&list));
+    assert!(deny_list.scan_any(&list).unwrap());
 
     let dict = PyDict::new(py);
     dict.set_item("user", BLOCK_PROMPT).unwrap();
     // should not scan non-string, improves test coverage
     dict.set_item("id", 1).unwrap();
 
-    assert!(deny_list.scan(&dict));
-    assert!(deny_list.scan_any(&dict));
+    assert!(deny_list.scan(&dict).unwrap());
+    assert!(deny_list.scan_any(&dict).unwrap());
 
     // test non blocked prompts
     assert!(!deny_list.scan_str(OK_PROMPT));
@@ -45,8 +48,8 @@ fn common_test_logic<T: deny_filter::matcher::Matcher>(deny_list: &T, py: Python
     dict.set_item("user", OK_PROMPT).unwrap();
     // should not scan non-string, improves test coverage
     dict.set_item("id", 1).unwrap();
-    assert!(!deny_list.scan(&dict));
-    assert!(!deny_list.scan_any(&dict));
+    assert!(!deny_list.scan(&dict).unwrap());
+    assert!(!deny_list.scan_any(&dict).unwrap());
 
     let list_data: Vec<String> = OK_PROMPT
         .split(' ')
@@ -54,7 +57,7 @@ fn common_test_logic<T: deny_filter::matcher::Matcher>(deny_list: &T, py: Python
         .collect();
     let list = PyList::new(py, &list_data).unwrap();
 
-    assert!(!deny_list.scan_any(&list));
+    assert!(!deny_list.scan_any(&list).unwrap());
 }
 
 #[test]
@@ -65,13 +68,15 @@ fn test_deny_lists() -> PyResult<()> {
         .collect();
     let deny_list = DenyList::new(words.clone())?;
     let deny_list_rs = DenyListRs::new(words.clone())?;
-    let deny_list_daac = DenyListDaac::new(words)?;
+    let deny_list_daac = DenyListDaac::new(words.clone())?;
+    let deny_list_packed = DenyListPacked::new(words)?;
 
     Python::initialize();
     Python::attach(|py| {
         common_test_logic(&deny_list, py);
         common_test_logic(&deny_list_rs, py);
         common_test_logic(&deny_list_daac, py);
+        common_test_logic(&deny_list_packed, py);
         let module = PyModule::new(py, "modules").unwrap();
         deny_filter_mod(&module).unwrap();
     });
@@ -82,3 +87,25 @@ fn test_deny_lists() -> PyResult<()> {
 
     Ok(())
 }
+
+#[test]
+fn test_daac_parity() -> PyResult<()> {
+    let empty = DenyListDaac::new(Vec::new())?;
+    assert!(!empty.is_match("anything"));
+    let deny_list = DenyListDaac::with_options(
+        vec!["hack".to_string()],
+        &deny_filter::options::Options {
+            whole_word: true,
+            ..Default::default()
+        },
+    )?;
+    Python::initialize();
+    Python::attach(|py| {
+        let object = Py::new(py, deny_list.clone())?;
+        // extracted by value, like an argument of a rust function
+        let extracted: DenyListDaac = object.bind(py).extract()?;
+        assert!(extracted.is_match("a hack"));
+        assert!(!extracted.is_match("hacking"));
+        Ok(())
+    })
+}
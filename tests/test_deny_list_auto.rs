@@ -0,0 +1,24 @@
+use deny_filter::backend::Backend;
+use deny_filter::deny_list_auto::{CANDIDATES, DenyListAuto};
+use deny_filter::matcher::Matcher;
+use deny_filter::options::Options;
+
+#[test]
+fn test_auto_picks_a_candidate() {
+    let words: Vec<String> = ["hack", "voilà", "spam"].map(String::from).to_vec();
+    let samples: Vec<String> = (0..50)
+        .map(|i| format!("message {i} with some text, maybe SPAM"))
+        .collect();
+    let auto = DenyListAuto::build(&words, &samples, &Options::default()).unwrap();
+    assert!(CANDIDATES.contains(&auto.backend()));
+    let timed: Vec<Backend> = auto.timings().iter().map(|(b, _)| *b).collect();
+    assert_eq!(timed, CANDIDATES);
+    assert_eq!(auto.stats().backend.as_deref(), Some(auto.backend().name()));
+    assert_eq!(auto.stats().patterns, 3);
+    assert!(auto.is_match("VOILÀ"));
+    assert_eq!(auto.find_all("no hack")[0].word, "hack");
+
+    let untimed = DenyListAuto::build(&words, &[], &Options::default()).unwrap();
+    assert_eq!(untimed.backend(), Backend::AhoCorasick);
+    assert!(untimed.timings().is_empty());
+}
@@ -0,0 +1,42 @@
+use deny_filter::deny_list_fst::{DenyListFst, FST_LABEL};
+use deny_filter::matcher::Matcher;
+use deny_filter::options::Options;
+
+#[test]
+fn test_fst_tokens() {
+    let words = vec![
+        "hunter2".to_string(),
+        "p@ssw0rd".to_string(),
+        "hunter2".to_string(),
+        " ".to_string(),
+    ];
+    let deny_list = DenyListFst::new(words).unwrap();
+    assert_eq!(deny_list.len(), 2);
+    assert_eq!(deny_list.build_report().empty, vec![3]);
+    assert!(deny_list.is_match("password: hunter2"));
+    assert!(deny_list.is_match("(p@ssw0rd)"));
+    assert!(!deny_list.is_match("hunter22 p@ss"));
+    assert!(!deny_list.is_match("HUNTER2"));
+    assert_eq!(deny_list.first_match("hunter2"), Some(FST_LABEL));
+
+    let found = deny_list.find_all("pw=x hunter2, ok");
+    assert_eq!(found.len(), 1);
+    assert_eq!(
+        (found[0].word.as_str(), found[0].start, found[0].end),
+        ("hunter2", 5, 12)
+    );
+
+    let loaded =
+        DenyListFst::from_bytes(deny_list.as_bytes().to_vec(), false, &Options::default()).unwrap();
+    assert!(loaded.is_match("hunter2"));
+    assert_eq!(loaded.stats().patterns, 2);
+    assert!(DenyListFst::from_bytes(vec![1, 2, 3], false, &Options::default()).is_err());
+}
+
+#[test]
+fn test_fst_normalized() {
+    let deny_list =
+        DenyListFst::with_options(vec!["Hunter2".to_string()], true, &Options::default()).unwrap();
+    assert!(deny_list.is_match("HUNTER2"));
+    assert_eq!(deny_list.explain("x hunter2").unwrap().pattern, "hunter2");
+}
@@ -0,0 +1,37 @@
+use deny_filter::deny_list_hashed::DenyListHashed;
+use deny_filter::matcher::Matcher;
+
+#[test]
+fn test_hashed_tokens() {
+    let digest = DenyListHashed::digest("sk-live-1234", false);
+    assert_eq!(digest.len(), 64);
+    let deny_list = DenyListHashed::new(vec![digest.to_uppercase(), String::new()], false).unwrap();
+    assert!(deny_list.is_match("sk-live-1234"));
+    assert!(deny_list.is_match("key: \"sk-live-1234\", ok"));
+    assert!(!deny_list.is_match("sk-live-12345"));
+    assert!(!deny_list.is_match("SK-LIVE-1234"));
+
+    let found = deny_list.find_all("é key=x sk-live-1234.");
+    assert_eq!(found.len(), 1);
+    assert_eq!(found[0].word, format!("sha256:{digest}"));
+    assert_eq!((found[0].start, found[0].end), (9, 21));
+    assert_eq!(found[0].char_start, 8);
+    assert_eq!(
+        deny_list.redact("token sk-live-1234", "*"),
+        "token ************"
+    );
+    assert_eq!(deny_list.build_report().empty, vec![1]);
+
+    assert!(DenyListHashed::new(vec!["abc".to_string()], false).is_err());
+}
+
+#[test]
+fn test_hashed_normalized() {
+    let deny_list =
+        DenyListHashed::new(vec![DenyListHashed::digest("Straße", true)], true).unwrap();
+    assert!(deny_list.is_match("to STRASSE now"));
+    assert!(deny_list.is_match("straße"));
+    let explanation = deny_list.explain("STRASSE").unwrap();
+    assert!(explanation.normalized.is_empty());
+    assert_eq!(explanation.steps.last().unwrap(), "sha256");
+}
@@ -0,0 +1,43 @@
+use deny_filter::deny_list_hybrid::{DenyListHybrid, split_entries};
+use deny_filter::matcher::Matcher;
+use deny_filter::options::Options;
+
+#[test]
+fn test_hybrid_matches() {
+    let deny_list = DenyListHybrid::new(
+        vec!["free money".to_string(), "hack".to_string()],
+        &[r"\b\d{3}-\d{2}-\d{4}\b".to_string()],
+    )
+    .unwrap();
+    assert!(deny_list.is_match("FREE MONEY"));
+    assert!(deny_list.is_match("ssn 123-45-6789"));
+    assert!(!deny_list.is_match("call 12-345"));
+    assert_eq!(
+        deny_list.first_match("id 123-45-6789"),
+        Some(r"\b\d{3}-\d{2}-\d{4}\b")
+    );
+
+    let found = deny_list.find_all("hack 123-45-6789 ünd hack");
+    let spans: Vec<(usize, usize)> = found.iter().map(|m| (m.start, m.end)).collect();
+    assert_eq!(spans, vec![(0, 4), (5, 16), (22, 26)]);
+    assert_eq!(found[2].char_start, 21);
+    assert_eq!(deny_list.redact("ssn 123-45-6789", "#"), "ssn ###########");
+
+    let explanation = deny_list.explain("x 123-45-6789").unwrap();
+    assert_eq!(explanation.normalized, "123-45-6789");
+    assert_eq!(deny_list.stats().patterns, 3);
+
+    assert!(DenyListHybrid::new(vec![], &["(".to_string()]).is_err());
+}
+
+#[test]
+fn test_hybrid_entries() {
+    let entries = vec!["spam".to_string(), "re:v[i1]agra".to_string()];
+    assert_eq!(
+        split_entries(entries.clone()),
+        (vec!["spam".to_string()], vec!["v[i1]agra".to_string()])
+    );
+    let deny_list = DenyListHybrid::from_entries(entries, &Options::default()).unwrap();
+    assert!(deny_list.is_match("cheap V1AGRA"));
+    assert!(deny_list.is_match("Spam"));
+}
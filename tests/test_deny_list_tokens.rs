@@ -0,0 +1,31 @@
+use deny_filter::deny_list_tokens::DenyListTokens;
+use std::collections::HashMap;
+
+#[test]
+fn test_token_patterns() {
+    let deny_list = DenyListTokens::new(vec![vec![5, 6], vec![70_000, 0xD800]]).unwrap();
+    assert_eq!(deny_list.find(&[1, 5, 6, 2]), Some((0, 1, 3)));
+    assert_eq!(deny_list.find(&[5, 7, 6]), None);
+    assert_eq!(deny_list.find(&[9, 70_000, 0xD800]), Some((1, 1, 3)));
+    // ids beyond the char range never match and split the sequence
+    assert_eq!(deny_list.find(&[5, u32::MAX, 6]), None);
+    assert_eq!(deny_list.find(&[u32::MAX, 3, 5, 6]), Some((0, 2, 4)));
+
+    assert!(DenyListTokens::new(vec![vec![]]).is_err());
+    assert!(DenyListTokens::new(vec![vec![u32::MAX]]).is_err());
+}
+
+#[test]
+fn test_from_vocab() {
+    let vocab: HashMap<String, u32> = [("vo", 1), ("voi", 2), ("la", 3), ("Ġvoila", 4), ("a", 5)]
+        .into_iter()
+        .map(|(k, v)| (k.to_string(), v))
+        .collect();
+    let deny_list =
+        DenyListTokens::from_vocab(&vocab, &["voila".to_string()], &["Ġ".to_string()]).unwrap();
+    assert_eq!(deny_list.patterns(), &[vec![2, 3], vec![4]]);
+    assert!(deny_list.find(&[5, 4]).is_some());
+    assert!(deny_list.find(&[1, 3]).is_none());
+
+    assert!(DenyListTokens::from_vocab(&vocab, &["xyz".to_string()], &[]).is_err());
+}
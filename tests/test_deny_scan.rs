@@ -0,0 +1,88 @@
+use std::fs;
+use std::io::Write;
+use std::path::PathBuf;
+use std::process::{Command, Stdio};
+
+const BIN: &str = env!("CARGO_BIN_EXE_deny-scan");
+
+/// fresh directory with a word list, a text, a json, an ndjson and a clean file
+fn fixture(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("deny_scan_{name}_{}", std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(dir.join("inputs/nested")).unwrap();
+    fs::write(dir.join("words.txt"), "# deny words\nvoila\n\nASDF\n").unwrap();
+    fs::write(dir.join("inputs/a.txt"), "first line\nthen VOILA\n").unwrap();
+    fs::write(
+        dir.join("inputs/nested/b.json"),
+        r#"{"messages": [{"content": "ok"}, {"content": "asdf"}]}"#,
+    )
+    .unwrap();
+    fs::write(
+        dir.join("inputs/nested/c.jsonl"),
+        "{\"role\": \"user\"}\n{\"content\": \"voila\"}\n",
+    )
+    .unwrap();
+    fs::write(dir.join("clean.txt"), "nothing to see").unwrap();
+    dir
+}
+
+#[test]
+fn test_scan_directory() {
+    let dir = fixture("dir");
+    let out = Command::new(BIN)
+        .arg("--words")
+        .arg(dir.join("words.txt"))
+        .arg(dir.join("inputs"))
+        .output()
+        .unwrap();
+    assert_eq!(out.status.code(), Some(1));
+    let stdout = String::from_utf8(out.stdout).unwrap();
+    assert!(stdout.contains("a.txt:2: voila"), "{stdout}");
+    assert!(
+        stdout.contains("b.json:$.messages[1].content: asdf"),
+        "{stdout}"
+    );
+    assert!(stdout.contains("c.jsonl:2:$.content: voila"), "{stdout}");
+}
+
+#[test]
+fn test_scan_clean_and_errors() {
+    let dir = fixture("clean");
+    let clean = Command::new(BIN)
+        .arg("-w")
+        .arg(dir.join("words.txt"))
+        .arg(dir.join("clean.txt"))
+        .status()
+        .unwrap();
+    assert_eq!(clean.code(), Some(0));
+
+    let missing = Command::new(BIN)
+        .arg("-w")
+        .arg(dir.join("words.txt"))
+        .arg(dir.join("missing.txt"))
+        .stderr(Stdio::null())
+        .status()
+        .unwrap();
+    assert_eq!(missing.code(), Some(2));
+}
+
+#[test]
+fn test_scan_stdin_msgpack() {
+    let dir = fixture("stdin");
+    let mut payload = Vec::new();
+    rmp::encode::write_array_len(&mut payload, 2).unwrap();
+    rmp::encode::write_str(&mut payload, "fine").unwrap();
+    rmp::encode::write_str(&mut payload, "voila").unwrap();
+
+    let mut child = Command::new(BIN)
+        .args(["-q", "--backend", "daachorse", "--format", "msgpack", "-w"])
+        .arg(dir.join("words.txt"))
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child.stdin.take().unwrap().write_all(&payload).unwrap();
+    let out = child.wait_with_output().unwrap();
+    assert_eq!(out.status.code(), Some(1));
+    assert!(out.stdout.is_empty());
+}
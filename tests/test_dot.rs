@@ -0,0 +1,22 @@
+#![cfg(feature = "dot")]
+use deny_filter::backend::Backend;
+use deny_filter::options::Options;
+
+#[test]
+fn test_to_dot() {
+    let options: Options = serde_json::from_str(r#"{"word_match": {"ab": "whole_word"}}"#).unwrap();
+    let matcher = Backend::AhoCorasick
+        .build_with(
+            vec!["ac".to_string(), "ab".to_string(), "a".to_string()],
+            &options,
+        )
+        .unwrap();
+    let dot = matcher.to_dot();
+    assert!(dot.starts_with("digraph deny_list {"));
+    assert!(dot.contains("0 [shape=box, label=\"Substring\"];"));
+    // "a" and "ac" share the substring trie node of "a"
+    assert!(dot.contains("0 -> 1 [label=\"a\"];\n  1 -> 2 [label=\"c\"];"));
+    assert!(dot.contains("1 [shape=doublecircle, xlabel=\"a\"];"));
+    assert!(dot.contains("[label=\"␣\"]"));
+    assert_eq!(dot.matches("doublecircle").count(), 3);
+}
@@ -0,0 +1,91 @@
+use deny_filter::backend::Backend;
+use deny_filter::email::{EmailHit, decode_header, email_texts, html_text, scan_email};
+
+const MIXED: &str = "From: Alice <alice@example.com>\r\n\
+Subject: =?utf-8?B?UXVhcnRlcmx5?= =?utf-8?Q?_report?=\r\n\
+Content-Type: multipart/mixed; boundary=\"outer\"\r\n\
+\r\n\
+preamble\r\n\
+--outer\r\n\
+Content-Type: multipart/alternative; boundary=inner\r\n\
+\r\n\
+--inner\r\n\
+Content-Type: text/plain; charset=utf-8\r\n\
+Content-Transfer-Encoding: quoted-printable\r\n\
+\r\n\
+caf=C3=A9 =\r\n\
+menu\r\n\
+--inner\r\n\
+Content-Type: text/html\r\n\
+Content-Transfer-Encoding: base64\r\n\
+\r\n\
+PHA+dm88Yj5pbDwvYj5hICZhbXA7PC9wPjxzY3JpcHQ+c2VjcmV0PC9zY3JpcHQ+\r\n\
+--inner--\r\n\
+--outer\r\n\
+Content-Type: application/octet-stream\r\n\
+\r\n\
+binary voila\r\n\
+--outer--\r\n";
+
+#[test]
+fn test_email_texts() {
+    let texts = email_texts(MIXED.as_bytes());
+    let texts: Vec<_> = texts
+        .iter()
+        .map(|(p, t)| (p.as_str(), t.as_str()))
+        .collect();
+    assert_eq!(
+        texts,
+        [
+            ("from", "Alice <alice@example.com>"),
+            ("subject", "Quarterly report"),
+            ("text/plain", "café menu"),
+            ("text/html", " voila & "),
+        ]
+    );
+}
+
+#[test]
+fn test_decode_header_and_html_text() {
+    assert_eq!(decode_header("=?iso-8859-1?Q?caf=E9?= ok"), "café ok");
+    assert_eq!(decode_header("plain =?bad"), "plain =?bad");
+    assert_eq!(
+        html_text("<div>a</div><!-- b > c -->d&lt;&#x41;&unknown;<style>e</STYLE>"),
+        " a d<A&unknown;"
+    );
+}
+
+#[test]
+fn test_scan_email() {
+    let deny_list = Backend::AhoCorasick
+        .build(vec!["voila".to_string(), "report".to_string()])
+        .unwrap();
+    assert_eq!(
+        scan_email(&*deny_list, MIXED.as_bytes()),
+        Some(EmailHit {
+            part: "subject".to_string(),
+            word: "report".to_string()
+        })
+    );
+    let deny_list = Backend::AhoCorasick
+        .build(vec!["voila".to_string(), "secret".to_string()])
+        .unwrap();
+    assert_eq!(
+        scan_email(&*deny_list, MIXED.as_bytes()).map(|hit| hit.part),
+        Some("text/html".to_string())
+    );
+    let forwarded = "Subject: fwd\n\
+Content-Type: message/rfc822\n\
+\n\
+Subject: voila\n\
+\n\
+body\n";
+    assert_eq!(
+        scan_email(&*deny_list, forwarded.as_bytes()).map(|hit| hit.part),
+        Some("subject".to_string())
+    );
+    assert_eq!(
+        scan_email(&*deny_list, b"Subject: hello\n\nclean body"),
+        None
+    );
+}
@@ -0,0 +1,55 @@
+#![cfg(feature = "cdylib")]
+
+use deny_filter::backend::Backend;
+use deny_filter::ffi::{deny_free, deny_is_match, deny_new, deny_scan_msgpack};
+use std::ffi::{CString, c_char};
+use std::iter;
+
+fn new_filter(words: &[&str], backend: Option<&str>) -> *mut deny_filter::ffi::DenyFilter {
+    let words: Vec<CString> = words.iter().map(|w| CString::new(*w).unwrap()).collect();
+    let ptrs: Vec<*const c_char> = words.iter().map(|w| w.as_ptr()).collect();
+    let backend = backend.map(|b| CString::new(b).unwrap());
+    let backend_ptr = backend.as_ref().map_or(std::ptr::null(), |b| b.as_ptr());
+    unsafe { deny_new(ptrs.as_ptr(), ptrs.len(), backend_ptr) }
+}
+
+#[test]
+fn test_ffi_round_trip() {
+    // the backend names documented for deny_new, each a backend of `Backend::from_str`
+    let header = include_str!("../include/deny_filter.h");
+    let doc = header
+        .lines()
+        .find(|line| line.contains("`backend` is NULL"))
+        .unwrap();
+    let names: Vec<&str> = doc.split('"').skip(1).step_by(2).collect();
+    assert_eq!(names, Backend::ALL.map(Backend::name));
+    for backend in iter::once(None).chain(names.into_iter().map(Some)) {
+        let filter = new_filter(&["Voila"], backend);
+        assert!(!filter.is_null(), "{backend:?}");
+        unsafe {
+            let text = "et VOILA";
+            assert_eq!(deny_is_match(filter, text.as_ptr(), text.len()), 1);
+            assert_eq!(deny_is_match(filter, text.as_ptr(), 3), 0);
+            assert_eq!(deny_is_match(filter, [0xff].as_ptr(), 1), -1);
+            assert_eq!(deny_is_match(filter, std::ptr::null(), 1), -1);
+
+            let msgpack = [0x91, 0xa5, b'v', b'o', b'i', b'l', b'a'];
+            assert_eq!(
+                deny_scan_msgpack(filter, msgpack.as_ptr(), msgpack.len()),
+                1
+            );
+            assert_eq!(deny_scan_msgpack(filter, msgpack.as_ptr(), 3), -1);
+            deny_free(filter);
+        }
+    }
+}
+
+#[test]
+fn test_ffi_invalid_arguments() {
+    assert!(new_filter(&["x"], Some("nope")).is_null());
+    unsafe {
+        assert!(deny_new(std::ptr::null(), 1, std::ptr::null()).is_null());
+        assert_eq!(deny_is_match(std::ptr::null(), std::ptr::null(), 0), -1);
+        deny_free(std::ptr::null_mut());
+    }
+}
@@ -0,0 +1,153 @@
+use deny_filter::deny_list::DenyList;
+use deny_filter::files::{self, glob_match};
+use deny_filter::hits::{Format, Hit};
+use std::fs;
+use std::path::PathBuf;
+
+/// fresh directory with text, json and ndjson files below `logs`
+fn fixture(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("deny_files_{name}_{}", std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(dir.join("logs/nested")).unwrap();
+    fs::write(dir.join("logs/a.log"), "fine\nwe hack\n").unwrap();
+    fs::write(dir.join("logs/b.txt"), "hack").unwrap();
+    fs::write(dir.join("logs/nested/c.log"), "all fine").unwrap();
+    fs::write(dir.join("logs/nested/d.json"), r#"{"msg": "hack"}"#).unwrap();
+    fs::write(dir.join("logs/nested/e.json"), "{").unwrap();
+    dir
+}
+
+#[test]
+fn test_glob_match() {
+    assert!(glob_match("*.log", "nested/c.log"));
+    assert!(!glob_match("*.log", "nested/c.json"));
+    assert!(glob_match("nested/?.log", "nested/c.log"));
+    assert!(!glob_match("nested/*.log", "nested/deeper/c.log"));
+    assert!(glob_match("**/c.log", "c.log"));
+    assert!(glob_match("nested/**/*.log", "nested/a/b/c.log"));
+}
+
+#[test]
+fn test_scan_dir() {
+    let dir = fixture("dir");
+    let matcher = DenyList::new(vec!["hack".to_string()]).unwrap();
+    let reports = files::scan_dir(&matcher, &dir, None, None, 3).unwrap();
+    let names: Vec<_> = reports
+        .iter()
+        .map(|r| {
+            r.path
+                .strip_prefix(&dir)
+                .unwrap()
+                .to_string_lossy()
+                .into_owned()
+        })
+        .collect();
+    assert_eq!(
+        names,
+        [
+            "logs/a.log",
+            "logs/b.txt",
+            "logs/nested/c.log",
+            "logs/nested/d.json",
+            "logs/nested/e.json"
+        ]
+    );
+    let hit = |location: &str| Hit {
+        location: location.to_string(),
+        pattern: "hack".to_string(),
+    };
+    assert_eq!(reports[0].hits, [hit("2")]);
+    assert!(reports[2].hits.is_empty());
+    assert_eq!(reports[3].hits, [hit("$.msg")]);
+    assert!(reports[4].error.is_some());
+
+    let logs = files::scan_dir(&matcher, &dir, Some("*.log"), None, 1).unwrap();
+    assert_eq!(logs.len(), 2);
+    let report = files::scan_file(
+        &matcher,
+        &dir.join("logs/nested/d.json"),
+        Some(Format::Text),
+    );
+    assert_eq!(report.hits, [hit("1")]);
+    assert!(
+        files::scan_file(&matcher, &dir.join("missing"), None)
+            .error
+            .is_some()
+    );
+    assert!(files::scan_dir(&matcher, &dir.join("missing/dir"), None, None, 1).is_err());
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[cfg(feature = "python")]
+#[test]
+fn test_py_scan_dir() {
+    use pyo3::prelude::*;
+
+    let dir = fixture("py");
+    Python::initialize();
+    Python::attach(|py| {
+        let module = PyModule::new(py, "deny_filter").unwrap();
+        deny_filter::pymodule::deny_filter(&module).unwrap();
+        let matcher = module
+            .getattr("DenyListRs")
+            .unwrap()
+            .call1((vec!["hack"],))
+            .unwrap();
+        let kwargs = pyo3::types::PyDict::new(py);
+        kwargs.set_item("glob", "logs/nested/*.json").unwrap();
+        let reports = matcher
+            .call_method("scan_dir", (dir.as_path(),), Some(&kwargs))
+            .unwrap();
+        assert_eq!(reports.len().unwrap(), 2);
+        let hits: Vec<(String, String)> = reports
+            .get_item(0)
+            .unwrap()
+            .getattr("hits")
+            .unwrap()
+            .extract()
+            .unwrap();
+        assert_eq!(hits, [("$.msg".to_string(), "hack".to_string())]);
+        let report = matcher
+            .call_method1("scan_file", (dir.join("logs/b.txt"),))
+            .unwrap();
+        assert_eq!(report.getattr("hits").unwrap().len().unwrap(), 1);
+        assert!(
+            matcher
+                .call_method1("scan_file", (dir.join("logs/b.txt"), "yaml"))
+                .is_err()
+        );
+        let found = matcher
+            .call_method1("scan_mmap", (dir.join("logs/a.log"), 4))
+            .unwrap();
+        let start: usize = found
+            .get_item(0)
+            .unwrap()
+            .getattr("start")
+            .unwrap()
+            .extract()
+            .unwrap();
+        assert_eq!((found.len().unwrap(), start), (1, 8));
+    });
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_scan_mmap() {
+    let dir = std::env::temp_dir().join(format!("deny_files_mmap_{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    let path = dir.join("big.log");
+    let text = "some filler with a hack in it, café and voila\n".repeat(500);
+    fs::write(&path, &text).unwrap();
+    let matcher = DenyList::new(vec!["hack".to_string(), "voila".to_string()]).unwrap();
+    let expected = deny_filter::matcher::Matcher::find_all(&matcher, &text);
+    assert_eq!(expected.len(), 1000);
+    // small windows cut words, multi-byte chars and lines
+    for window in [5, 64, 1000, files::DEFAULT_MMAP_WINDOW] {
+        let found = files::scan_mmap(&matcher, &path, window).unwrap();
+        assert_eq!(found, expected, "{window}");
+    }
+    fs::write(&path, "").unwrap();
+    assert!(files::scan_mmap(&matcher, &path, 8).unwrap().is_empty());
+    assert!(files::scan_mmap(&matcher, &dir.join("missing"), 8).is_err());
+    fs::remove_dir_all(&dir).unwrap();
+}
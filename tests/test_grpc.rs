@@ -0,0 +1,98 @@
+#![cfg(feature = "grpc")]
+
+use deny_filter::backend::Backend;
+use deny_filter::grpc::DenyFilterService;
+use deny_filter::grpc::proto::deny_filter_client::DenyFilterClient;
+use deny_filter::grpc::proto::scan_request::Payload;
+use deny_filter::grpc::proto::{ReloadConfigRequest, ScanRequest};
+use tokio_stream::StreamExt;
+use tokio_stream::wrappers::TcpListenerStream;
+use tonic::transport::Channel;
+
+async fn start() -> DenyFilterClient<Channel> {
+    let matcher = Backend::AhoCorasick
+        .build(vec!["voila".to_string()])
+        .unwrap();
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(
+        tonic::transport::Server::builder()
+            .add_service(DenyFilterService::new(matcher).into_server())
+            .serve_with_incoming(TcpListenerStream::new(listener)),
+    );
+    DenyFilterClient::connect(format!("http://{addr}"))
+        .await
+        .unwrap()
+}
+
+fn text(s: &str) -> ScanRequest {
+    ScanRequest {
+        payload: Some(Payload::Text(s.to_string())),
+    }
+}
+
+#[tokio::test]
+async fn test_scan_and_reload() {
+    let mut client = start().await;
+
+    let response = client.scan(text("ok\nVoila")).await.unwrap().into_inner();
+    assert!(response.matched);
+    assert_eq!(response.hits[0].location, "2");
+    assert_eq!(response.hits[0].pattern, "voila");
+
+    let json = ScanRequest {
+        payload: Some(Payload::Json(r#"{"a": ["x", "asdf"]}"#.to_string())),
+    };
+    assert!(
+        !client
+            .scan(json.clone())
+            .await
+            .unwrap()
+            .into_inner()
+            .matched
+    );
+
+    let reloaded = client
+        .reload_config(ReloadConfigRequest {
+            words: vec!["asdf".to_string(), "jkl".to_string()],
+            backend: "daachorse".to_string(),
+        })
+        .await
+        .unwrap()
+        .into_inner();
+    assert_eq!(reloaded.words, 2);
+
+    let response = client.scan(json).await.unwrap().into_inner();
+    assert_eq!(response.hits[0].location, "$.a[1]");
+
+    let bad = client
+        .reload_config(ReloadConfigRequest {
+            words: vec![],
+            backend: "nope".to_string(),
+        })
+        .await;
+    assert_eq!(bad.unwrap_err().code(), tonic::Code::InvalidArgument);
+
+    let invalid = ScanRequest {
+        payload: Some(Payload::Msgpack(vec![0xc1])),
+    };
+    assert_eq!(
+        client.scan(invalid).await.unwrap_err().code(),
+        tonic::Code::InvalidArgument
+    );
+}
+
+#[tokio::test]
+async fn test_scan_stream() {
+    let mut client = start().await;
+    let requests = tokio_stream::iter(vec![text("fine"), text("voila")]);
+    let responses: Vec<bool> = client
+        .scan_stream(requests)
+        .await
+        .unwrap()
+        .into_inner()
+        .map(|r| r.unwrap().matched)
+        .collect()
+        .await;
+    assert_eq!(responses, vec![false, true]);
+}
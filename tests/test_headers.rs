@@ -0,0 +1,43 @@
+use deny_filter::backend::Backend;
+use deny_filter::decode::{base64_text, decode_base64};
+use deny_filter::headers::{HeaderHit, scan_headers};
+
+#[test]
+fn test_decode_base64() {
+    assert_eq!(decode_base64("dXNlcjpoYWNr").unwrap(), b"user:hack");
+    assert_eq!(decode_base64("aGFjaw==").unwrap(), b"hack");
+    assert_eq!(decode_base64("aGFjaw").unwrap(), b"hack");
+    assert_eq!(decode_base64("-_8").unwrap(), [0xfb, 0xff]);
+    assert_eq!(decode_base64("aGFja"), None);
+    assert_eq!(decode_base64("aG=Fj"), None);
+    assert_eq!(base64_text("aGFjaw=="), Some("hack".to_string()));
+    assert_eq!(base64_text("aGFjaw"), None);
+    assert_eq!(base64_text("keep-alive"), None);
+}
+
+#[test]
+fn test_scan_headers() {
+    let deny_list = Backend::AhoCorasick
+        .build(vec!["hack".to_string()])
+        .unwrap();
+    let headers = [
+        ("Content-Type", "application/json"),
+        ("Connection", "keep-alive"),
+        ("AUTHORIZATION", "Basic dXNlcjpoYWNr"),
+    ];
+    assert_eq!(
+        scan_headers(&*deny_list, headers),
+        Some(HeaderHit {
+            name: "authorization".to_string(),
+            value: "user:hack".to_string(),
+            decoded: true,
+            word: "hack".to_string(),
+        })
+    );
+    let hit = scan_headers(&*deny_list, [("X-Tags", "fine, we HACK, ok")]).unwrap();
+    assert_eq!((hit.value.as_str(), hit.decoded), ("we HACK", false));
+    assert_eq!(
+        scan_headers(&*deny_list, [("Accept", "text/html, */*")]),
+        None
+    );
+}
@@ -0,0 +1,95 @@
+use deny_filter::backend::Backend;
+use deny_filter::incremental::IncrementalMatcher;
+use deny_filter::matcher::Matcher;
+use deny_filter::options::Options;
+
+fn words(words: &[&str]) -> Vec<String> {
+    words.iter().map(ToString::to_string).collect()
+}
+
+#[test]
+fn test_add_words_and_merge() {
+    let mut matcher = IncrementalMatcher::new(
+        Backend::AhoCorasick,
+        words(&["hack", "scam"]),
+        &Options::default(),
+        3,
+    )
+    .unwrap();
+    assert!(!matcher.is_match("a phish"));
+    assert!(!matcher.add_words(words(&["phish"])).unwrap());
+    assert_eq!((matcher.len(), matcher.delta_len()), (3, 1));
+    assert!(matcher.is_match("a PHISH"));
+    assert_eq!(matcher.first_match("a phish"), Some("phish"));
+    assert_eq!(matcher.stats().patterns, 3);
+    // a copy shares the base and takes the next words
+    let before = matcher.clone();
+    assert!(!matcher.add_words(words(&["phish", "spam"])).unwrap());
+    assert!(matcher.is_match("spam") && !before.is_match("spam"));
+    // the third delta word merges
+    assert!(matcher.add_words(words(&["fraud"])).unwrap());
+    assert_eq!((matcher.len(), matcher.delta_len()), (5, 0));
+    assert!(matcher.is_match("fraud") && matcher.is_match("phish"));
+}
+
+#[test]
+fn test_find_all_across_automata() {
+    let mut matcher = IncrementalMatcher::new(
+        Backend::AhoCorasick,
+        words(&["hack"]),
+        &Options::default(),
+        100,
+    )
+    .unwrap();
+    matcher.add_words(words(&["hacker", "scam"])).unwrap();
+    let found: Vec<_> = matcher
+        .find_all("a hacker scam, a hack")
+        .into_iter()
+        .map(|m| (m.word, m.start))
+        .collect();
+    assert_eq!(
+        found,
+        [
+            ("hacker".to_string(), 2),
+            ("scam".to_string(), 9),
+            ("hack".to_string(), 17)
+        ]
+    );
+    assert_eq!(matcher.redact("hacker", "*"), "******");
+    assert_eq!(matcher.find_overlapping("hacker").unwrap().len(), 2);
+    matcher.merge().unwrap();
+    assert_eq!(matcher.delta_len(), 0);
+    assert!(matcher.scan_str("scam"));
+}
+
+#[test]
+fn test_failed_add_keeps_the_matcher() {
+    let options = Options {
+        whole_word: true,
+        ..Options::default()
+    };
+    let mut matcher =
+        IncrementalMatcher::new(Backend::Regex, words(&["hack"]), &options, 10).unwrap();
+    // a word without letters in whole word mode
+    assert!(matcher.add_words(words(&["--"])).is_err());
+    assert_eq!((matcher.len(), matcher.delta_len()), (1, 0));
+    assert!(matcher.is_match("hack"));
+}
+
+#[test]
+fn test_add_words_with_word_keyed_options() {
+    let options: Options = serde_json::from_str(
+        r#"{"exceptions": {"shoot": ["photo shoot"]}, "word_match": {"Ass": "whole_word"}}"#,
+    )
+    .unwrap();
+    let mut matcher =
+        IncrementalMatcher::new(Backend::AhoCorasick, words(&["shoot", "ass"]), &options, 2)
+            .unwrap();
+    assert!(!matcher.add_words(words(&["phish"])).unwrap());
+    assert!(matcher.is_match("a phish") && matcher.is_match("shoot"));
+    assert!(!matcher.is_match("photo shoot") && !matcher.is_match("class"));
+    // the second delta word merges, the base keeps the options
+    assert!(matcher.add_words(words(&["spam"])).unwrap());
+    assert!(matcher.is_match("a phish") && matcher.is_match("spam"));
+    assert!(!matcher.is_match("photo shoot") && !matcher.is_match("class"));
+}
@@ -0,0 +1,250 @@
+use deny_filter::backend::Backend;
+use deny_filter::limits::{Guarded, LimitError, Limits, REPEAT_MIN_LEN};
+use deny_filter::options::{Options, Overflow};
+use deny_filter::traverse;
+
+fn limits(
+    max_string_len: Option<usize>,
+    max_payload_len: Option<usize>,
+    on_overflow: Overflow,
+) -> Limits {
+    Limits {
+        max_string_len,
+        max_payload_len,
+        on_overflow,
+        ..Limits::NONE
+    }
+}
+
+#[test]
+fn test_budget() {
+    let mut budget = limits(Some(4), Some(10), Overflow::ScanPrefix).budget();
+    assert_eq!(budget.take("abc"), Ok(Guarded::Scan("abc")));
+    // the prefix ends at a char boundary
+    assert_eq!(budget.take("abcdef"), Ok(Guarded::Scan("abcd")));
+    assert_eq!(budget.take("éé"), Ok(Guarded::Scan("é")));
+    // 1 payload byte left
+    assert_eq!(budget.take("é"), Ok(Guarded::Skip(false)));
+    assert_eq!(budget.take("x"), Ok(Guarded::Scan("x")));
+    assert_eq!(budget.take("y"), Ok(Guarded::Skip(false)));
+
+    let mut budget = limits(Some(4), None, Overflow::Allow).budget();
+    assert_eq!(budget.take("abcde"), Ok(Guarded::Skip(false)));
+    let mut budget = limits(Some(4), None, Overflow::Block).budget();
+    assert_eq!(budget.take("abcde"), Ok(Guarded::Skip(true)));
+    let mut budget = limits(None, Some(4), Overflow::Raise).budget();
+    assert_eq!(budget.take("abc"), Ok(Guarded::Scan("abc")));
+    assert_eq!(budget.take("de"), Err(LimitError::Payload { limit: 4 }));
+    assert_eq!(
+        limits(Some(2), None, Overflow::Raise).budget().take("abc"),
+        Err(LimitError::String { len: 3, limit: 2 })
+    );
+}
+
+#[test]
+fn test_matcher_limits() {
+    let build = |options: &str| {
+        let options: Options = serde_json::from_str(options).unwrap();
+        Backend::Daachorse
+            .build_with(vec!["hack".to_string()], &options)
+            .unwrap()
+    };
+    let long = format!("{}hack", "x".repeat(100));
+    let prefix = build(r#"{"max_string_len": 50}"#);
+    assert!(!prefix.scan_str(&long));
+    assert!(prefix.scan_str("hack it"));
+    // limits apply to scans, not to is_match
+    assert!(prefix.is_match(&long));
+    assert!(build(r#"{"max_string_len": 50, "on_overflow": "block"}"#).scan_str(&long));
+    assert!(!build(r#"{"max_string_len": 50, "on_overflow": "allow"}"#).scan_str(&long));
+    let raise = build(r#"{"max_string_len": 50, "on_overflow": "raise"}"#);
+    assert_eq!(
+        raise.try_scan_str(&long),
+        Err(LimitError::String {
+            len: 104,
+            limit: 50
+        })
+    );
+    // scans that cannot fail block
+    assert!(raise.scan_str(&long));
+
+    let payload = build(r#"{"max_payload_len": 8}"#);
+    let json = serde_json::json!({"a": "fine text", "b": "hack"});
+    assert!(!payload.scan_json(&json));
+    assert!(build("{}").scan_json(&json));
+    // {"a": "fine text", "b": "hack"}
+    let msgpack = b"\x82\xa1a\xa9fine text\xa1b\xa4hack";
+    assert_eq!(payload.scan_msgpack(msgpack), Ok(false));
+    let raise = build(r#"{"max_payload_len": 8, "on_overflow": "raise"}"#);
+    assert_eq!(
+        raise.scan_msgpack(msgpack),
+        Err(traverse::Error::Limit(LimitError::Payload { limit: 8 }))
+    );
+}
+
+#[test]
+fn test_msgpack_bounds() {
+    let bounds = |max_depth, max_elements| Limits {
+        max_msgpack_depth: max_depth,
+        max_msgpack_elements: max_elements,
+        ..Limits::NONE
+    };
+    // [[["hack"]]]
+    let nested = b"\x91\x91\x91\xa4hack";
+    let mut seen = Vec::new();
+    let walked = traverse::msgpack_within(nested, &bounds(Some(3), None), &mut |_, s| {
+        seen.push(s);
+        false
+    });
+    assert_eq!((walked, seen), (Ok(false), vec!["hack"]));
+    assert_eq!(
+        traverse::msgpack_within(nested, &bounds(Some(2), None), &mut |_, _| false),
+        Err(traverse::Error::Limit(LimitError::Depth { limit: 2 }))
+    );
+    // 4 values: the 3 arrays and the string
+    assert!(traverse::msgpack_within(nested, &bounds(None, Some(4)), &mut |_, _| false).is_ok());
+    assert_eq!(
+        traverse::msgpack_within(nested, &bounds(None, Some(3)), &mut |_, _| false),
+        Err(traverse::Error::Limit(LimitError::Elements { limit: 3 }))
+    );
+    // values of skipped non string keys count too: {[[1]]: "x"}
+    assert!(
+        traverse::msgpack_within(
+            b"\x81\x91\x91\x01\xa1x",
+            &bounds(Some(1), None),
+            &mut |_, _| false
+        )
+        .is_err()
+    );
+}
+
+#[test]
+fn test_msgpack_overflow() {
+    let build = |options: &str| {
+        let options: Options = serde_json::from_str(options).unwrap();
+        Backend::AhoCorasick
+            .build_with(vec!["hack".to_string()], &options)
+            .unwrap()
+    };
+    // ["hack", [["x"]]], then ["ok", [["hack"]]]
+    let early = b"\x92\xa4hack\x91\x91\xa1x";
+    let late = b"\x92\xa2ok\x91\x91\xa4hack";
+    let prefix = build(r#"{"max_msgpack_depth": 2}"#);
+    assert_eq!(prefix.scan_msgpack(early), Ok(true));
+    assert_eq!(prefix.scan_msgpack(late), Ok(false));
+    let block = build(r#"{"max_msgpack_depth": 2, "on_overflow": "block"}"#);
+    assert_eq!(block.scan_msgpack(late), Ok(true));
+    let allow = build(r#"{"max_msgpack_elements": 3, "on_overflow": "allow"}"#);
+    assert_eq!(allow.scan_msgpack(late), Ok(false));
+    let raise = build(r#"{"max_msgpack_len": 6, "on_overflow": "raise"}"#);
+    assert_eq!(
+        raise.scan_msgpack(late),
+        Err(traverse::Error::Limit(LimitError::Buffer {
+            len: 11,
+            limit: 6
+        }))
+    );
+    // the first 6 bytes hold "hack" of the early document
+    let cut = build(r#"{"max_msgpack_len": 6}"#);
+    assert_eq!(cut.scan_msgpack(early), Ok(true));
+    assert_eq!(cut.scan_msgpack(late), Ok(false));
+}
+
+#[test]
+fn test_depth_and_strings() {
+    let build = |options: &str| {
+        let options: Options = serde_json::from_str(options).unwrap();
+        Backend::AhoCorasick
+            .build_with(vec!["hack".to_string()], &options)
+            .unwrap()
+    };
+    let deep = serde_json::json!({"a": "ok", "b": [["hack"]]});
+    let many = serde_json::json!(["a", "b", "c", "hack"]);
+    let shallow = build(r#"{"max_depth": 2}"#);
+    assert!(!shallow.scan_json(&deep));
+    assert!(build(r#"{"max_depth": 3}"#).scan_json(&deep));
+    assert!(build(r#"{"max_depth": 2, "on_overflow": "block"}"#).scan_json(&deep));
+    // ["ok", [["hack"]]]
+    let late = b"\x92\xa2ok\x91\x91\xa4hack";
+    assert_eq!(shallow.scan_msgpack(late), Ok(false));
+    let raise = build(r#"{"max_depth": 2, "on_overflow": "raise"}"#);
+    assert_eq!(
+        raise.scan_msgpack(late),
+        Err(traverse::Error::Limit(LimitError::Depth { limit: 2 }))
+    );
+
+    let few = build(r#"{"max_strings": 3}"#);
+    assert!(!few.scan_json(&many));
+    assert!(few.scan_json(&serde_json::json!(["hack", "b", "c", "d"])));
+    assert!(build(r#"{"max_strings": 3, "on_overflow": "block"}"#).scan_json(&many));
+    let mut budget = Limits {
+        max_strings: Some(1),
+        on_overflow: Overflow::Raise,
+        ..Limits::NONE
+    }
+    .budget();
+    assert_eq!(budget.take("a"), Ok(Guarded::Scan("a")));
+    assert_eq!(budget.take("b"), Err(LimitError::Strings { limit: 1 }));
+}
+
+#[test]
+fn test_repeated() {
+    let [a, b, c] = ["a", "b", "c"].map(|s| s.repeat(REPEAT_MIN_LEN));
+    let mut budget = Limits::NONE.budget();
+    assert!(!budget.repeated(&a));
+    assert!(!budget.repeated(&b));
+    assert!(budget.repeated(&a));
+    assert!(budget.repeated(&b));
+    // a match is taken back, its repeats are scanned again
+    assert!(!budget.repeated(&c));
+    budget.matched();
+    assert!(!budget.repeated(&c));
+    assert!(budget.repeated(&c));
+    // short strings cost less to scan again than to hash
+    assert!(!budget.repeated("short"));
+    assert!(!budget.repeated("short"));
+    // nor is the string of a single string payload hashed
+    let mut budget = Limits::NONE.string_budget();
+    assert!(!budget.repeated(&a));
+    assert!(!budget.repeated(&a));
+
+    let build = |options: &str| {
+        let options: Options = serde_json::from_str(options).unwrap();
+        Backend::AhoCorasick
+            .build_with(vec!["hack".to_string()], &options)
+            .unwrap()
+    };
+    assert!(build("{}").scan_json(&serde_json::json!(["a", "a", "hack", "hack"])));
+    // repeats count against the limits like the first occurrence
+    let few = build(r#"{"max_strings": 3}"#);
+    assert!(!few.scan_json(&serde_json::json!(["a", "a", "a", "hack"])));
+    let short = build(r#"{"max_payload_len": 4, "on_overflow": "block"}"#);
+    assert!(short.scan_json(&serde_json::json!(["abc", "abc"])));
+}
+
+#[test]
+fn test_default_msgpack_depth() {
+    // a small document of nested single element arrays, far deeper than the stack allows
+    let mut nested = vec![0x91; 2_000_000];
+    nested.extend_from_slice(b"\xa5voila");
+    let limit = deny_filter::limits::DEFAULT_MSGPACK_DEPTH;
+    assert_eq!(
+        traverse::msgpack_strings(&nested, &Limits::NONE),
+        Err(traverse::Error::Limit(LimitError::Depth { limit }))
+    );
+    let build = |options: &str| {
+        let options: Options = serde_json::from_str(options).unwrap();
+        Backend::AhoCorasick
+            .build_with(vec!["voila".to_string()], &options)
+            .unwrap()
+    };
+    assert_eq!(build("{}").scan_msgpack(&nested), Ok(false));
+    assert_eq!(
+        build(r#"{"on_overflow": "block"}"#).scan_msgpack(&nested),
+        Ok(true)
+    );
+    // within the default depth the string is found
+    let mut shallow = vec![0x91; limit - 1];
+    shallow.extend_from_slice(b"\xa5voila");
+    assert_eq!(build("{}").scan_msgpack(&shallow), Ok(true));
+}
@@ -0,0 +1,70 @@
+use deny_filter::deny_list::DenyList;
+use deny_filter::lines::{LineHit, LineScanner};
+
+#[test]
+fn test_line_scanner() {
+    let matcher = DenyList::new(vec!["hack".to_string(), "voila".to_string()]).unwrap();
+    let input = b"fine\r\nhack and voila\n\xff hack\nno newline hack";
+    let hits = LineScanner::new(&input[..]).collect(&matcher).unwrap();
+    let hit = |line, word: &str, start, end| LineHit {
+        line,
+        word: word.to_string(),
+        start,
+        end,
+    };
+    assert_eq!(
+        hits,
+        [
+            hit(2, "hack", 0, 4),
+            hit(2, "voila", 9, 14),
+            // the invalid byte became a 3 byte U+FFFD
+            hit(3, "hack", 4, 8),
+            hit(4, "hack", 11, 15),
+        ]
+    );
+}
+
+#[cfg(feature = "python")]
+#[test]
+fn test_py_scan_lines() {
+    use pyo3::prelude::*;
+
+    let dir = std::env::temp_dir().join(format!("deny_lines_{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let path = dir.join("audit.log");
+    std::fs::write(&path, "ok\nwe hack\n\nhack hack\n").unwrap();
+    Python::initialize();
+    Python::attach(|py| {
+        let module = PyModule::new(py, "deny_filter").unwrap();
+        deny_filter::pymodule::deny_filter(&module).unwrap();
+        let matcher = module
+            .getattr("DenyList")
+            .unwrap()
+            .call1((vec!["hack"],))
+            .unwrap();
+        let hits: Vec<(usize, String, (usize, usize))> = matcher
+            .call_method1("scan_lines", (path.as_path(),))
+            .unwrap()
+            .try_iter()
+            .unwrap()
+            .map(|hit| hit.unwrap().extract().unwrap())
+            .collect();
+        let lines: Vec<_> = hits.iter().map(|(line, _, span)| (*line, *span)).collect();
+        assert_eq!(lines, [(2, (3, 7)), (4, (0, 4)), (4, (5, 9))]);
+        let first = matcher
+            .call_method1("scan_lines", (pyo3::types::PyBytes::new(py, b"a\nhack"),))
+            .unwrap()
+            .call_method0("__next__")
+            .unwrap();
+        assert_eq!(
+            first.extract::<(usize, String, (usize, usize))>().unwrap(),
+            (2, "hack".to_string(), (0, 4))
+        );
+        assert!(
+            matcher
+                .call_method1("scan_lines", (dir.join("missing"),))
+                .is_err()
+        );
+    });
+    std::fs::remove_dir_all(&dir).unwrap();
+}
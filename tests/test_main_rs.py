@@ -63,3 +63,52 @@ async def test_plugin_prompt_pre_fetch(deny_plugin, plugin_context):
     payload = PromptPrehookPayload(prompt_id="test", args={"text": "         ok"})
     result = await deny_plugin.prompt_pre_fetch(payload, plugin_context)
     assert result.violation is None
+
+
+@pytest.mark.asyncio
+async def test_plugin_prompt_pre_fetch_regexes(plugin_context):
+    """Test that regex entries of the config are matched next to the deny words."""
+    plugin_cfg = PluginConfig(
+        name="deny_test_regexes",
+        kind=f"{DenyListPluginRust.__module__}.{DenyListPluginRust.__name__}",
+        hooks=[PromptHookType.PROMPT_PRE_FETCH],
+        priority=100,
+        config={"words": ["malware", {"pattern": r"\b\d{4}-\d{4}\b", "type": "regex"}]},
+    )
+    plugin = DenyListPluginRust(config=plugin_cfg)
+    card = PromptPrehookPayload(prompt_id="test", args={"text": "card 1234-5678"})
+    malware = PromptPrehookPayload(prompt_id="test", args={"text": "malware"})
+    clean = PromptPrehookPayload(prompt_id="test", args={"text": "call 12-34"})
+    assert (await plugin.prompt_pre_fetch(card, plugin_context)).violation is not None
+    assert (await plugin.prompt_pre_fetch(malware, plugin_context)).violation is not None
+    assert (await plugin.prompt_pre_fetch(clean, plugin_context)).violation is None
+
+
+@pytest.mark.asyncio
+async def test_plugin_prompt_pre_fetch_collect_all(plugin_context):
+    """Test that collect_all blocks whatever scan_any blocks, nested and proximity hits too."""
+    plugin_cfg = PluginConfig(
+        name="deny_test_collect_all",
+        kind=f"{DenyListPluginRust.__module__}.{DenyListPluginRust.__name__}",
+        hooks=[PromptHookType.PROMPT_PRE_FETCH],
+        priority=100,
+        config={
+            "words": ["malware", "secret"],
+            "proximity": [{"first": "wire", "second": "money", "within": 2}],
+            "collect_all": True,
+        },
+    )
+    plugin = DenyListPluginRust(config=plugin_cfg)
+    payload = PromptPrehookPayload(
+        prompt_id="test",
+        args={"title": "SECRET malware", "notes": "wire the money", "content": "safe"},
+    )
+    result = await plugin.prompt_pre_fetch(payload, plugin_context)
+    assert not result.continue_processing
+    assert [(v["field"], v["word"]) for v in result.violation.details["violations"]] == [
+        ("title", "secret"),
+        ("title", "malware"),
+        ("notes", "wire NEAR/2 money"),
+    ]
+    clean = PromptPrehookPayload(prompt_id="test", args={"content": "safe"})
+    assert (await plugin.prompt_pre_fetch(clean, plugin_context)).violation is None
@@ -0,0 +1,48 @@
+use deny_filter::backend::Backend;
+use deny_filter::mcp::{McpVerdict, scan_mcp_request};
+use serde_json::json;
+
+#[test]
+fn test_scan_mcp_request() {
+    let deny_list = Backend::AhoCorasick
+        .build(vec!["hack".to_string(), "scam".to_string()])
+        .unwrap();
+    let scan = |message| scan_mcp_request(&*deny_list, &message);
+    let call = json!({
+        "jsonrpc": "2.0", "id": 1, "method": "tools/call",
+        "params": {"name": "hack", "arguments": {"query": ["weather", "a SCAM"]}, "_meta": {"note": "hack"}},
+    });
+    assert_eq!(
+        scan(call),
+        McpVerdict {
+            continue_processing: false,
+            method: Some("tools/call".to_string()),
+            path: Some("$.params.arguments.query[1]".to_string()),
+            word: Some("scam".to_string()),
+        }
+    );
+    let batch = json!([
+        {"jsonrpc": "2.0", "id": 1, "method": "tools/list", "params": {"cursor": "hack"}},
+        {"jsonrpc": "2.0", "id": 2, "result": {"content": [
+            {"type": "image", "data": "hack"},
+            {"type": "resource", "resource": {"uri": "file:///a", "text": "we hack"}},
+        ]}},
+    ]);
+    let verdict = scan(batch);
+    assert_eq!(verdict.method, None);
+    assert_eq!(
+        verdict.path.as_deref(),
+        Some("$[1].result.content[1].resource.text")
+    );
+    let read = json!({"jsonrpc": "2.0", "id": 3, "result": {"contents": [{"uri": "file:///b", "text": "hack"}]}});
+    assert_eq!(
+        scan(read).path.as_deref(),
+        Some("$.result.contents[0].text")
+    );
+    let clean = json!({"jsonrpc": "2.0", "id": 4, "method": "prompts/get", "params": {"name": "hack", "arguments": {"a": "fine"}}});
+    assert!(scan(clean).continue_processing);
+    assert!(
+        scan(json!({"jsonrpc": "2.0", "id": 5, "error": {"code": 1, "message": "hack"}}))
+            .continue_processing
+    );
+}
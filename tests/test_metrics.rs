@@ -0,0 +1,130 @@
+#![cfg(feature = "python")]
+
+use std::io::{Read, Write};
+use std::net::TcpStream;
+use std::thread;
+use std::time::Duration;
+
+use deny_filter::deny_list::DenyList;
+use deny_filter::deny_list_daac::DenyListDaac;
+use deny_filter::matcher::Matcher;
+use deny_filter::metrics::{MAX_CONNECTIONS, render, reset, scan_counts, serve};
+use pyo3::prelude::*;
+
+#[test]
+fn test_render_prometheus() -> PyResult<()> {
+    let deny_list = DenyList::new(vec!["Voila".to_string()])?;
+    assert!((deny_list.score("voila voila") - 1.0).abs() < 1e-9);
+
+    Python::initialize();
+    Python::attach(|py| {
+        assert!(deny_list.scan_str("et VOILA").unwrap());
+        assert!(!deny_list.scan_str("nothing here").unwrap());
+        let dict = pyo3::types::PyDict::new(py);
+        dict.set_item("text", "voila").unwrap();
+        assert!(deny_list.scan_any(dict.as_any()).unwrap());
+    });
+
+    let text = render();
+    assert!(text.contains("# TYPE deny_filter_scans_total counter"));
+    assert!(text.contains("deny_filter_scans_total{backend=\"aho_corasick\"}"));
+    assert!(
+        text.contains(
+            "deny_filter_pattern_matches_total{backend=\"aho_corasick\",pattern=\"voila\"}"
+        )
+    );
+    assert!(text.contains(
+        "deny_filter_scan_duration_seconds_bucket{backend=\"aho_corasick\",le=\"+Inf\"}"
+    ));
+    assert!(text.contains("# TYPE deny_filter_score summary"));
+    assert!(text.contains("deny_filter_score_count{backend=\"aho_corasick\"}"));
+    Ok(())
+}
+
+#[test]
+fn test_serve_metrics() {
+    let addr = serve("127.0.0.1:0").unwrap();
+    let mut stream = TcpStream::connect(addr).unwrap();
+    stream
+        .write_all(b"GET /metrics HTTP/1.1\r\nHost: localhost\r\n\r\n")
+        .unwrap();
+    let mut response = String::new();
+    stream.read_to_string(&mut response).unwrap();
+    assert!(response.starts_with("HTTP/1.1 200 OK"));
+    assert!(response.contains("# TYPE deny_filter_scan_duration_seconds histogram"));
+}
+
+#[test]
+fn test_serve_stalled_client() {
+    let addr = serve("127.0.0.1:0").unwrap();
+    // connects and never sends the request
+    let _stalled = TcpStream::connect(addr).unwrap();
+    let mut stream = TcpStream::connect(addr).unwrap();
+    stream
+        .set_read_timeout(Some(Duration::from_secs(2)))
+        .unwrap();
+    stream
+        .write_all(b"GET /metrics HTTP/1.1\r\nHost: localhost\r\n\r\n")
+        .unwrap();
+    let mut response = String::new();
+    stream.read_to_string(&mut response).unwrap();
+    assert!(response.starts_with("HTTP/1.1 200 OK"));
+}
+
+/// sends a request and reads the whole response, empty when the server closes unanswered
+fn get(addr: std::net::SocketAddr) -> String {
+    let mut stream = TcpStream::connect(addr).unwrap();
+    stream
+        .set_read_timeout(Some(Duration::from_secs(2)))
+        .unwrap();
+    let _ = stream.write_all(b"GET /metrics HTTP/1.1\r\nHost: localhost\r\n\r\n");
+    let mut response = String::new();
+    let _ = stream.read_to_string(&mut response);
+    response
+}
+
+#[test]
+fn test_serve_max_connections() {
+    let addr = serve("127.0.0.1:0").unwrap();
+    let stalled: Vec<_> = (0..MAX_CONNECTIONS)
+        .map(|_| TcpStream::connect(addr).unwrap())
+        .collect();
+    assert_eq!(get(addr), "");
+    // the stalled clients hang up and free their slots
+    drop(stalled);
+    let answered = (0..50).any(|_| {
+        thread::sleep(Duration::from_millis(20));
+        get(addr).starts_with("HTTP/1.1 200 OK")
+    });
+    assert!(answered);
+}
+
+#[test]
+fn test_scan_counts() {
+    let deny_list = DenyListDaac::new(vec!["voila".to_string()]).unwrap();
+    assert!(deny_list.try_scan_str("et voila").unwrap());
+    assert!(!deny_list.try_scan_str("rien").unwrap());
+    let counts = scan_counts()["daachorse"];
+    assert!(counts.scans >= 2 && counts.matches >= 1 && counts.bytes >= 12);
+    assert!(render().contains("deny_filter_scanned_bytes_total{backend=\"daachorse\"}"));
+
+    Python::initialize();
+    Python::attach(|py| {
+        let module = PyModule::new(py, "deny_filter").unwrap();
+        deny_filter::pymodule::deny_filter(&module).unwrap();
+        let globals = pyo3::types::PyDict::new(py);
+        globals.set_item("deny_filter", module).unwrap();
+        py.run(
+            cr#"
+stats = deny_filter.stats()
+assert stats["scans"] >= stats["backends"]["daachorse"]["scans"] >= 2
+deny_filter.reset_stats()
+"#,
+            Some(&globals),
+            None,
+        )
+        .unwrap();
+    });
+    reset();
+    assert_eq!(scan_counts()["daachorse"].bytes, 0);
+}
@@ -0,0 +1,150 @@
+#![cfg(feature = "object-store")]
+
+use deny_filter::backend::Backend;
+use deny_filter::hits::{Format, Hit};
+use deny_filter::object_scan::{scan_object, scan_prefix};
+use object_store::local::LocalFileSystem;
+use object_store::memory::InMemory;
+use object_store::path::Path;
+use object_store::{ObjectStoreExt, PutPayload};
+
+fn hit(location: &str, pattern: &str) -> Hit {
+    Hit {
+        location: location.to_string(),
+        pattern: pattern.to_string(),
+    }
+}
+
+async fn put(store: &InMemory, path: &str, data: &[u8]) {
+    store
+        .put(&Path::from(path), PutPayload::from(data.to_vec()))
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn test_scan_prefix() {
+    let store = InMemory::new();
+    put(&store, "logs/a.txt", b"fine\nhack here\n\nhack").await;
+    put(
+        &store,
+        "logs/b.ndjson",
+        b"{\"msg\": \"ok\"}\n\n{\"user\": {\"name\": \"voila\"}}\n",
+    )
+    .await;
+    put(&store, "logs/c.json", br#"{"items": ["fine", "hack"]}"#).await;
+    put(&store, "logs/d.jsonl", b"{\"a\": \"hack\"}\nnot json\n").await;
+    put(&store, "other/e.txt", b"hack").await;
+    let matcher = Backend::AhoCorasick
+        .build(vec!["hack".to_string(), "voila".to_string()])
+        .unwrap();
+
+    let reports = scan_prefix(&store, Some(&Path::from("logs")), matcher.as_ref(), None)
+        .await
+        .unwrap();
+    let paths: Vec<&str> = reports.iter().map(|r| r.path.as_str()).collect();
+    assert_eq!(
+        paths,
+        ["logs/a.txt", "logs/b.ndjson", "logs/c.json", "logs/d.jsonl"]
+    );
+    assert_eq!(reports[0].hits, [hit("2", "hack"), hit("4", "hack")]);
+    assert_eq!(reports[1].hits, [hit("3:$.user.name", "voila")]);
+    assert_eq!(reports[2].hits, [hit("$.items[1]", "hack")]);
+    // the malformed line stops the object, earlier hits are kept
+    assert_eq!(reports[3].hits, [hit("1:$.a", "hack")]);
+    assert!(reports[3].error.as_ref().unwrap().starts_with("line 2"));
+    assert!(reports[..3].iter().all(|r| r.error.is_none()));
+
+    let reports = scan_prefix(&store, None, matcher.as_ref(), Some(Format::Text))
+        .await
+        .unwrap();
+    assert_eq!(reports.len(), 5);
+    assert_eq!(reports[2].hits, [hit("1", "hack")]);
+
+    assert!(
+        scan_object(
+            &store,
+            &Path::from("missing.txt"),
+            matcher.as_ref(),
+            Format::Text
+        )
+        .await
+        .is_err()
+    );
+}
+
+#[tokio::test]
+async fn test_scan_object_chunks() {
+    let dir = std::env::temp_dir().join(format!("deny_object_{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    // larger than one read chunk of the local store, lines cross chunk borders
+    let text: String = (1..=20_000)
+        .map(|i| {
+            if i % 5_000 == 0 {
+                format!("line {i} hack\n")
+            } else {
+                format!("line {i}\n")
+            }
+        })
+        .collect();
+    std::fs::write(dir.join("big.txt"), text).unwrap();
+    let store = LocalFileSystem::new_with_prefix(&dir).unwrap();
+    let matcher = Backend::Daachorse.build(vec!["hack".to_string()]).unwrap();
+
+    let hits = scan_object(
+        &store,
+        &Path::from("big.txt"),
+        matcher.as_ref(),
+        Format::Text,
+    )
+    .await
+    .unwrap();
+    let lines: Vec<&str> = hits.iter().map(|h| h.location.as_str()).collect();
+    assert_eq!(lines, ["5000", "10000", "15000", "20000"]);
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[cfg(feature = "python")]
+#[test]
+fn test_py_object_scanner() {
+    use pyo3::prelude::*;
+
+    let dir = std::env::temp_dir().join(format!("deny_object_py_{}", std::process::id()));
+    std::fs::create_dir_all(dir.join("logs")).unwrap();
+    std::fs::write(dir.join("logs/a.ndjson"), "{\"msg\": \"we hack\"}\n").unwrap();
+    std::fs::write(dir.join("logs/b.txt"), "fine\n").unwrap();
+    let url = format!("file://{}", dir.display());
+    Python::initialize();
+    Python::attach(|py| {
+        let module = PyModule::new(py, "deny_filter").unwrap();
+        deny_filter::pymodule::deny_filter(&module).unwrap();
+        let scanner = module
+            .getattr("ObjectScanner")
+            .unwrap()
+            .call1((url.as_str(), vec!["hack"]))
+            .unwrap();
+        let reports = scanner.call_method1("scan", ("logs",)).unwrap();
+        assert_eq!(reports.len().unwrap(), 2);
+        let report = reports.get_item(0).unwrap();
+        let path: String = report.getattr("path").unwrap().extract().unwrap();
+        assert!(path.ends_with("logs/a.ndjson"));
+        let hits: Vec<(String, String)> = report.getattr("hits").unwrap().extract().unwrap();
+        assert_eq!(hits, [("1:$.msg".to_string(), "hack".to_string())]);
+        let hits: Vec<(String, String)> = reports
+            .get_item(1)
+            .unwrap()
+            .getattr("hits")
+            .unwrap()
+            .extract()
+            .unwrap();
+        assert!(hits.is_empty());
+        assert!(
+            module
+                .getattr("ObjectScanner")
+                .unwrap()
+                .call1((url.as_str(), vec!["hack"], "yaml"))
+                .is_err()
+        );
+    });
+    std::fs::remove_dir_all(&dir).unwrap();
+}
@@ -0,0 +1,507 @@
+use deny_filter::backend::Backend;
+use deny_filter::options::{CaseFold, Language, MatchKind, Options};
+
+fn build(
+    backend: Backend,
+    words: &[&str],
+    options: &Options,
+) -> Box<dyn deny_filter::matcher::Matcher + Send + Sync> {
+    backend
+        .build_with(words.iter().map(ToString::to_string).collect(), options)
+        .unwrap()
+}
+
+#[test]
+fn test_whole_word() {
+    let options = Options {
+        whole_word: true,
+        ..Options::default()
+    };
+    for backend in Backend::ALL {
+        let matcher = build(backend, &["hack", "Send Me"], &options);
+        assert!(matcher.is_match("we HACK it"), "{backend}");
+        assert!(!matcher.is_match("hacking"), "{backend}");
+        assert!(matcher.is_match("please, send   me!"), "{backend}");
+        assert_eq!(matcher.first_match("(hack)"), Some("hack"));
+        assert!(
+            backend
+                .build_with(vec!["--".to_string()], &options)
+                .is_err()
+        );
+    }
+}
+
+#[test]
+fn test_stem() {
+    let options: Options = serde_json::from_str(r#"{"stem": "english"}"#).unwrap();
+    assert_eq!(options.stem, Some(Language::English));
+    for backend in Backend::ALL {
+        let matcher = build(backend, &["hack"], &options);
+        assert!(matcher.is_match("they were Hacking"), "{backend}");
+        assert!(matcher.is_match("hacked."), "{backend}");
+        assert!(!matcher.is_match("hackathon"), "{backend}");
+        assert_eq!(matcher.first_match("hacks"), Some("hack"));
+    }
+    assert!(serde_json::from_str::<Options>(r#"{"stem": "klingon"}"#).is_err());
+    assert!(serde_json::from_str::<Options>(r#"{"whole_words": true}"#).is_err());
+}
+
+#[test]
+fn test_proximity() {
+    let options: Options = serde_json::from_str(
+        r#"{"proximity": [{"first": "password", "second": "send me", "within": 2}]}"#,
+    )
+    .unwrap();
+    for backend in Backend::ALL {
+        let matcher = build(backend, &["voila"], &options);
+        assert!(matcher.is_match("Send me the password"), "{backend}");
+        assert!(matcher.is_match("password: please send me"), "{backend}");
+        assert!(
+            !matcher.is_match("password was reset, later send me a report"),
+            "{backend}"
+        );
+        assert!(!matcher.is_match("password only"), "{backend}");
+        assert_eq!(
+            matcher.first_match("send me your password"),
+            Some("password NEAR/2 send me")
+        );
+        assert_eq!(matcher.first_match("voila password send me"), Some("voila"));
+    }
+    let stemmed: Options = serde_json::from_str(
+        r#"{"stem": "english", "proximity": [{"first": "kill", "second": "kill", "within": 0}]}"#,
+    )
+    .unwrap();
+    let matcher = build(Backend::AhoCorasick, &[], &stemmed);
+    assert!(matcher.is_match("killing kills"));
+    assert!(!matcher.is_match("killing"));
+}
+
+#[test]
+fn test_exceptions() {
+    let options: Options =
+        serde_json::from_str(r#"{"exceptions": {"Shoot": ["photo shoot", "shooting star"]}}"#)
+            .unwrap();
+    for backend in Backend::ALL {
+        let matcher = build(backend, &["shoot", "voila"], &options);
+        assert!(!matcher.is_match("a PHOTO shoot today"), "{backend}");
+        assert!(!matcher.is_match("look, a shooting star"), "{backend}");
+        assert!(matcher.is_match("don't shoot"), "{backend}");
+        assert!(matcher.is_match("photo shoot, then shoot"), "{backend}");
+        assert_eq!(matcher.first_match("photo shoot voila"), Some("voila"));
+        assert!(
+            backend
+                .build_with(vec!["voila".to_string()], &options)
+                .is_err()
+        );
+    }
+}
+
+#[test]
+fn test_allow() {
+    let options: Options = serde_json::from_str(
+        r#"{"allow": ["Class Action"], "allow_patterns": ["scunthorpe\\w*"]}"#,
+    )
+    .unwrap();
+    for backend in Backend::ALL {
+        let matcher = build(backend, &["ass", "cunt", "voila"], &options);
+        assert!(!matcher.is_match("a CLASS ACTION suit"), "{backend}");
+        assert!(!matcher.is_match("Scunthorpe United"), "{backend}");
+        assert!(matcher.is_match("class action, you ass"), "{backend}");
+        assert!(matcher.is_match("a classic"), "{backend}");
+        assert_eq!(matcher.first_match("scunthorpe voila"), Some("voila"));
+    }
+    for invalid in [r#"{"allow": [""]}"#, r#"{"allow_patterns": ["(open"]}"#] {
+        let options: Options = serde_json::from_str(invalid).unwrap();
+        assert!(
+            Backend::AhoCorasick
+                .build_with(vec!["ass".to_string()], &options)
+                .is_err()
+        );
+    }
+}
+
+#[test]
+fn test_word_match() {
+    let options: Options = serde_json::from_str(
+        r#"{"word_match": {"ass": "whole_word", "Admin": "anchored"}, "exceptions": {"ass": ["ass kicking"]}}"#,
+    )
+    .unwrap();
+    for backend in Backend::ALL {
+        let matcher = build(backend, &["ass", "free money", "admin"], &options);
+        assert!(!matcher.is_match("a classic assessment"), "{backend}");
+        assert!(matcher.is_match("what an ass!"), "{backend}");
+        assert!(!matcher.is_match("ass kicking"), "{backend}");
+        assert!(matcher.is_match("get FREE MONEYS"), "{backend}");
+        assert!(matcher.is_match("  Admin "), "{backend}");
+        assert!(!matcher.is_match("admin panel"), "{backend}");
+        assert_eq!(matcher.first_match("admin"), Some("admin"));
+        assert!(
+            backend
+                .build_with(vec!["voila".to_string()], &options)
+                .is_err()
+        );
+    }
+    assert!(serde_json::from_str::<Options>(r#"{"word_match": {"a": "prefix"}}"#).is_err());
+}
+
+#[test]
+fn test_find_overlapping() {
+    let text = "FREE money, İfree moneys";
+    for backend in Backend::ALL {
+        let matcher = build(backend, &["free", "free money", "ee"], &Options::default());
+        let found: Vec<(String, &str)> = matcher
+            .find_overlapping(text)
+            .unwrap()
+            .into_iter()
+            .map(|m| (m.word, &text[m.start..m.end]))
+            .collect();
+        assert_eq!(
+            found,
+            [
+                ("free".to_string(), "FREE"),
+                ("free money".to_string(), "FREE money"),
+                ("ee".to_string(), "EE"),
+                ("free".to_string(), "free"),
+                ("free money".to_string(), "free money"),
+                ("ee".to_string(), "ee"),
+            ],
+            "{backend}"
+        );
+    }
+    let options: Options =
+        serde_json::from_str(r#"{"stem": "english", "exceptions": {"hack": ["life hack"]}}"#)
+            .unwrap();
+    let matcher = build(Backend::Daachorse, &["hack"], &options);
+    let found = matcher
+        .find_overlapping("Hacking a life hack, HACKED!")
+        .unwrap();
+    let spans: Vec<(usize, usize)> = found.iter().map(|m| (m.start, m.end)).collect();
+    assert_eq!(spans, [(0, 7), (21, 27)]);
+}
+
+#[test]
+fn test_leftmost_longest() {
+    let text = "free money for free";
+    let longest = Options {
+        match_kind: MatchKind::LeftmostLongest,
+        ..Options::default()
+    };
+    for backend in Backend::ALL {
+        let first = build(backend, &["free", "free money"], &Options::default());
+        let words: Vec<String> = first.find_all(text).into_iter().map(|m| m.word).collect();
+        assert_eq!(words, ["free", "free"], "{backend}");
+
+        let matcher = build(backend, &["free", "free money"], &longest);
+        assert_eq!(matcher.first_match(text), Some("free money"), "{backend}");
+        let spans: Vec<(usize, usize)> = matcher
+            .find_all(text)
+            .iter()
+            .map(|m| (m.start, m.end))
+            .collect();
+        assert_eq!(spans, [(0, 10), (15, 19)], "{backend}");
+    }
+}
+
+#[test]
+fn test_char_offsets() {
+    let text = "Ünïcödé FREE 🙂 free money";
+    let matcher = build(
+        Backend::AhoCorasick,
+        &["free", "money"],
+        &Options::default(),
+    );
+    let found = matcher.find_all(text);
+    let chars: Vec<char> = text.chars().collect();
+    for m in &found {
+        let by_char: String = chars[m.char_start..m.char_end].iter().collect();
+        assert_eq!(by_char, &text[m.start..m.end]);
+    }
+    let offsets: Vec<(usize, usize)> = found.iter().map(|m| (m.char_start, m.char_end)).collect();
+    assert_eq!(offsets, [(8, 12), (15, 19), (20, 25)]);
+}
+
+#[test]
+fn test_redact_graphemes() {
+    // "ok" deny word ends before a combining enclosing circle, the family emoji is a zwj sequence
+    let text = "ok\u{20dd} 👨\u{200d}👩\u{200d}👧 free";
+    let matcher = build(
+        Backend::AhoCorasick,
+        &["ok", "👨", "free"],
+        &Options::default(),
+    );
+    assert_eq!(matcher.redact(text, "*"), "** * ****");
+    assert_eq!(
+        matcher.highlight(text, "[", "]"),
+        "[ok\u{20dd}] [👨\u{200d}👩\u{200d}👧] [free]"
+    );
+    assert_eq!(matcher.redact("clean", "#"), "clean");
+}
+
+#[test]
+fn test_case_folding() {
+    for backend in Backend::ALL {
+        let matcher = build(
+            backend,
+            &["straße", "ΣΟΦΟΣ", "İstanbul"],
+            &Options::default(),
+        );
+        assert!(matcher.is_match("STRASSE"), "{backend}");
+        assert!(matcher.is_match("STRAẞE"), "{backend}");
+        assert!(matcher.is_match("σοφος"), "{backend}");
+        assert!(matcher.is_match("σοφοσ"), "{backend}");
+        assert!(matcher.is_match("İSTANBUL"), "{backend}");
+        assert_eq!(matcher.first_match("die strasse"), Some("straße"));
+        let found = matcher.find_all("Die STRASSE");
+        assert_eq!((found[0].start, found[0].end), (4, 11), "{backend}");
+    }
+    let options = Options {
+        whole_word: true,
+        ..Options::default()
+    };
+    let matcher = build(Backend::AhoCorasick, &["İstanbul"], &options);
+    assert!(matcher.is_match("to İstanbul!"));
+}
+
+#[test]
+fn test_case_fold_modes() {
+    let options: Options = serde_json::from_str(r#"{"case_fold": "turkish"}"#).unwrap();
+    assert_eq!(options.case_fold, CaseFold::Turkish);
+    for backend in Backend::ALL {
+        let matcher = build(backend, &["istanbul", "ılık"], &options);
+        assert!(matcher.is_match("İSTANBUL"), "{backend}");
+        assert!(matcher.is_match("ILIK"), "{backend}");
+        assert!(!matcher.is_match("ISTANBUL"), "{backend}");
+    }
+    let options = Options {
+        case_fold: CaseFold::Ascii,
+        ..Options::default()
+    };
+    let matcher = build(Backend::AhoCorasick, &["free", "straße"], &options);
+    assert!(matcher.is_match("FREE"));
+    assert!(!matcher.is_match("STRASSE"));
+    let options = Options {
+        case_fold: CaseFold::Simple,
+        ..Options::default()
+    };
+    let matcher = build(Backend::AhoCorasick, &["straße", "σοφος"], &options);
+    assert!(matcher.is_match("STRAßE"));
+    assert!(!matcher.is_match("STRASSE"));
+    assert!(matcher.is_match("ΣΟΦΟΣ"));
+}
+
+#[test]
+fn test_case_sensitive() {
+    let options: Options = serde_json::from_str(r#"{"case_fold": "none"}"#).unwrap();
+    assert_eq!(options.case_fold, CaseFold::None);
+    for backend in Backend::ALL {
+        let matcher = build(backend, &["Voilà", "hack"], &options);
+        assert!(matcher.is_match("Voilà"), "{backend}");
+        assert!(!matcher.is_match("VOILÀ"), "{backend}");
+        assert!(!matcher.is_match("HACK"), "{backend}");
+        assert_eq!(matcher.first_match("a Voilà"), Some("Voilà"), "{backend}");
+    }
+}
+
+#[test]
+fn test_backends_agree_on_case() {
+    let words = ["Voilà", "hack", "STRASSE"];
+    let inputs = ["VOILÀ", "voilà", "Hack the straße", "HaCk", "nothing"];
+    for options in [
+        Options::default(),
+        Options {
+            case_fold: CaseFold::None,
+            ..Options::default()
+        },
+    ] {
+        let expected = build(Backend::AhoCorasick, &words, &options);
+        for backend in Backend::ALL {
+            let matcher = build(backend, &words, &options);
+            for input in inputs {
+                assert_eq!(
+                    matcher.find_all(input),
+                    expected.find_all(input),
+                    "{backend} {input:?} {:?}",
+                    options.case_fold
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn test_normalization() {
+    let (composed, decomposed) = ("caf\u{e9}", "cafe\u{301}");
+    for backend in Backend::ALL {
+        for (word, text) in [(composed, decomposed), (decomposed, composed)] {
+            let matcher = build(backend, &[word], &Options::default());
+            assert!(matcher.is_match(text), "{backend}");
+            let input = format!("le {text}!");
+            let found = matcher.find_all(&input);
+            assert_eq!(&input[found[0].start..found[0].end], text, "{backend}");
+        }
+    }
+    let options: Options = serde_json::from_str(r#"{"normalization": "nfd"}"#).unwrap();
+    assert!(build(Backend::AhoCorasick, &[composed], &options).is_match(decomposed));
+    let options: Options = serde_json::from_str(r#"{"normalization": "none"}"#).unwrap();
+    assert!(!build(Backend::AhoCorasick, &[composed], &options).is_match(decomposed));
+}
+
+#[test]
+fn test_explain() {
+    let options: Options = serde_json::from_str(
+        r#"{"stem": "english", "proximity": [{"first": "send", "second": "password", "within": 2}]}"#,
+    )
+    .unwrap();
+    for backend in Backend::ALL {
+        let matcher = build(backend, &["Hack"], &options);
+        let explanation = matcher.explain("Stop HACKING now").unwrap();
+        assert_eq!(explanation.word, "hack");
+        let hit = explanation.hit.unwrap();
+        assert_eq!((hit.start, hit.end), (5, 12), "{backend}");
+        assert_eq!(explanation.pattern, "hack");
+        assert_eq!(explanation.normalized, "hack");
+        assert_eq!(
+            explanation.steps,
+            vec!["nfc", "case_fold:full", "whole_word", "stem:english"]
+        );
+        let value = serde_json::json!({"args": {"items": ["ok", "send my password"]}});
+        let explanation = matcher.explain_json(&value).unwrap();
+        assert_eq!(explanation.word, "send NEAR/2 password");
+        assert_eq!(explanation.hit, None);
+        assert_eq!(explanation.path.as_deref(), Some("$.args.items[1]"));
+        assert!(matcher.explain("fine").is_none());
+    }
+}
+
+#[test]
+fn test_metadata() {
+    let options: Options =
+        serde_json::from_str(r#"{"version": "2025.1", "source": "spam.txt"}"#).unwrap();
+    let matcher = build(Backend::Daachorse, &["free"], &options);
+    assert_eq!(matcher.metadata().version.as_deref(), Some("2025.1"));
+    assert_eq!(matcher.metadata().source.as_deref(), Some("spam.txt"));
+    assert_eq!(matcher.metadata().timestamp, None);
+}
+
+#[test]
+fn test_auto_backend() {
+    let few: Vec<String> = ["a", "b"].map(String::from).to_vec();
+    let many: Vec<String> = (0..20).map(|i| format!("word{i}")).collect();
+    assert_eq!(Backend::select(&few), Backend::Packed);
+    assert_eq!(Backend::select(&many), Backend::AhoCorasick);
+    assert_eq!("auto".parse::<Backend>(), Ok(Backend::Auto));
+    let matcher = Backend::Auto.build(many).unwrap();
+    assert!(matcher.is_match("see WORD19"));
+}
+
+#[test]
+fn test_collapse_repeats() {
+    let options: Options = serde_json::from_str(r#"{"collapse_repeats": true}"#).unwrap();
+    for backend in Backend::ALL {
+        let matcher = build(backend, &["bad", "voila", "free"], &options);
+        assert!(matcher.is_match("so BAAAAAD"), "{backend}");
+        assert!(matcher.is_match("voiiiiilaaa"), "{backend}");
+        // runs of two are kept, and long runs match words with a double char too
+        assert!(!matcher.is_match("baad"), "{backend}");
+        assert!(matcher.is_match("freeeee"), "{backend}");
+        assert!(!matcher.is_match("fre"), "{backend}");
+        let hits = matcher.find_all("freeeee money");
+        assert_eq!(
+            (hits.len(), hits[0].start, hits[0].end),
+            (1, 0, 7),
+            "{backend}"
+        );
+        let text = "so baaaaad!";
+        let hits = matcher.find_all(text);
+        assert_eq!(
+            (hits[0].start, hits[0].end, hits[0].char_end),
+            (3, 10, 10),
+            "{backend}"
+        );
+        assert_eq!(matcher.redact(text, "*"), "so *******!", "{backend}");
+    }
+    let whole_word = Options {
+        whole_word: true,
+        ..options
+    };
+    let matcher = build(Backend::AhoCorasick, &["bad", "free"], &whole_word);
+    assert!(matcher.is_match("this is baaaad!"));
+    assert!(matcher.is_match("freeeee money"));
+    assert!(!build(Backend::AhoCorasick, &["bad"], &Options::default()).is_match("baaaad"));
+}
+
+#[test]
+fn test_punctuation_gaps() {
+    let options: Options = serde_json::from_str(r#"{"punctuation_gaps": true}"#).unwrap();
+    for backend in Backend::ALL {
+        let matcher = build(backend, &["voila", "hack"], &options);
+        assert!(matcher.is_match("v.o.i.l.a"), "{backend}");
+        assert!(matcher.is_match("V-O-I-L-A!"), "{backend}");
+        assert!(matcher.is_match("h*a**c_k"), "{backend}");
+        // whitespace is no gap, punctuation at word edges stays
+        assert!(!matcher.is_match("v o i l a"), "{backend}");
+        let text = "it's h.a.c.k, ok";
+        let hits = matcher.find_all(text);
+        assert_eq!((hits[0].start, hits[0].end), (5, 12), "{backend}");
+        assert_eq!(matcher.redact(text, "#"), "it's #######, ok", "{backend}");
+    }
+    let options = Options {
+        collapse_repeats: true,
+        whole_word: true,
+        ..options
+    };
+    let matcher = build(Backend::AhoCorasick, &["bad"], &options);
+    assert!(matcher.is_match("so b.a.a.a.d!"));
+    assert!(!matcher.is_match("b.a.d.g.e"));
+}
+
+#[test]
+fn test_squeeze() {
+    let options: Options = serde_json::from_str(r#"{"squeeze": true}"#).unwrap();
+    for backend in Backend::ALL {
+        let matcher = build(backend, &["voila", "a big deal"], &options);
+        assert!(matcher.is_match("say v o i l a"), "{backend}");
+        assert!(matcher.is_match("V  O  I  L  A"), "{backend}");
+        // wider gaps and sentences with single letter words stay apart
+        assert!(!matcher.is_match("v   o   i   l   a"), "{backend}");
+        assert!(matcher.is_match("it is a big deal"), "{backend}");
+        let text = "ok v o i l a!";
+        let hits = matcher.find_all(text);
+        assert_eq!((hits[0].start, hits[0].end), (3, 12), "{backend}");
+        assert_eq!(matcher.redact(text, "*"), "ok *********!", "{backend}");
+    }
+    let options = Options {
+        collapse_repeats: true,
+        ..options
+    };
+    let matcher = build(Backend::AhoCorasick, &["voila"], &options);
+    assert!(matcher.is_match("v o i i i l a"));
+}
+
+#[test]
+fn test_reversed() {
+    let options: Options = serde_json::from_str(r#"{"reversed": true}"#).unwrap();
+    for backend in Backend::ALL {
+        let matcher = build(backend, &["voila", "level", "stop", "pots"], &options);
+        assert!(matcher.is_match("say ALIOV now"), "{backend}");
+        assert_eq!(matcher.first_match("aliov"), Some("voila"), "{backend}");
+        // palindromes and words reversing each other are compiled once
+        assert_eq!(matcher.first_match("level"), Some("level"), "{backend}");
+        assert_eq!(matcher.first_match("spot pots"), Some("pots"), "{backend}");
+        let text = "ok aliov!";
+        let hits = matcher.find_all(text);
+        assert_eq!((hits[0].start, hits[0].end), (3, 8), "{backend}");
+        assert_eq!(matcher.redact(text, "*"), "ok *****!", "{backend}");
+    }
+    let matcher = build(Backend::AhoCorasick, &["voila"], &Options::default());
+    assert!(!matcher.is_match("aliov"));
+    let matcher = build(Backend::AhoCorasick, &["voila"], &options);
+    assert!(matcher.build_report().altered.is_empty());
+    let whole_word = Options {
+        whole_word: true,
+        ..options
+    };
+    let matcher = build(Backend::AhoCorasick, &["voila"], &whole_word);
+    assert!(matcher.is_match("an aliov here"));
+    assert!(!matcher.is_match("analiovs"));
+}
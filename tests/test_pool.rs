@@ -0,0 +1,27 @@
+use deny_filter::backend::Backend;
+use deny_filter::pool::ScanPool;
+
+#[test]
+fn test_scan_pool() {
+    let matcher = Backend::AhoCorasick
+        .build(vec!["hack".to_string()])
+        .unwrap();
+    let pool = ScanPool::new(matcher, 3, 2).unwrap();
+    assert_eq!(pool.workers(), 3);
+    let texts: Vec<String> = (0..50)
+        .map(|i| {
+            if i % 7 == 0 {
+                format!("hack {i}")
+            } else {
+                format!("ok {i}")
+            }
+        })
+        .collect();
+    let found = pool.scan_many(texts);
+    assert_eq!(found.len(), 50);
+    assert!(found.iter().enumerate().all(|(i, f)| *f == (i % 7 == 0)));
+    assert!(pool.submit("HACK".to_string()).recv().unwrap());
+
+    let matcher = Backend::AhoCorasick.build(vec![]).unwrap();
+    assert!(ScanPool::new(matcher, 0, 1).is_err());
+}
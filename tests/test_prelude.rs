@@ -0,0 +1,36 @@
+use deny_filter::prelude::*;
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+#[derive(Serialize)]
+struct Request {
+    id: u32,
+    messages: Vec<String>,
+    meta: BTreeMap<String, String>,
+}
+
+#[test]
+fn test_scan_serialize() {
+    let matcher = Backend::AhoCorasick
+        .build_with(
+            vec!["voila".to_string()],
+            &Options {
+                ignore_keys: vec!["meta".to_string()],
+                ..Options::default()
+            },
+        )
+        .unwrap();
+    let mut request = Request {
+        id: 7,
+        messages: vec!["hello".to_string()],
+        meta: [("note".to_string(), "voila".to_string())].into(),
+    };
+    assert!(!scan_serialize(&*matcher, &request).unwrap());
+    request.messages.push("et VOILA".to_string());
+    assert!(scan_serialize(&*matcher, &request).unwrap());
+
+    let deny_list = DenyList::new(vec!["voila".to_string()]).unwrap();
+    assert!(scan_serialize(&deny_list, "voila").unwrap());
+    let keys: BTreeMap<(u8, u8), &str> = [((1, 2), "voila")].into();
+    assert!(scan_serialize(&deny_list, &keys).is_err());
+}
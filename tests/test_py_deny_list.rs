@@ -1,5 +1,8 @@
+#![cfg(feature = "python")]
+
 use deny_filter::deny_list::DenyList;
 use deny_filter::deny_list_daac::DenyListDaac;
+use deny_filter::deny_list_packed::DenyListPacked;
 use deny_filter::deny_list_rs::DenyListRs;
 use pyo3::prelude::*;
 
@@ -13,14 +16,14 @@ macro_rules! test_matcher_variant {
                 let matcher = $struct_name::new(words).unwrap();
 
                 assert!(matcher.is_match("badword"));
-                assert!(matcher.scan_str("badword"));
+                assert!(matcher.scan_str("badword").unwrap());
 
                 let dict = pyo3::types::PyDict::new(py);
                 dict.set_item("key", "badword").unwrap();
-                assert!(matcher.scan(&dict));
+                assert!(matcher.scan(&dict).unwrap());
 
                 let list = pyo3::types::PyList::new(py, vec!["badword"]).unwrap();
-                assert!(matcher.scan_any(list.as_any()));
+                assert!(matcher.scan_any(list.as_any()).unwrap());
             });
         }
     };
@@ -29,3 +32,792 @@ macro_rules! test_matcher_variant {
 test_matcher_variant!(DenyList, test_denylist_coverage);
 test_matcher_variant!(DenyListRs, test_denylist_rs_coverage);
 test_matcher_variant!(DenyListDaac, test_denylist_daac_coverage);
+test_matcher_variant!(DenyListPacked, test_denylist_packed_coverage);
+
+#[test]
+fn test_keyword_options() {
+    Python::initialize();
+    Python::attach(|py| {
+        let module = PyModule::new(py, "deny_filter").unwrap();
+        deny_filter::pymodule::deny_filter(&module).unwrap();
+        let kwargs = pyo3::types::PyDict::new(py);
+        kwargs.set_item("stem", "english").unwrap();
+        let matcher = module
+            .getattr("DenyList")
+            .unwrap()
+            .call((vec!["hack"],), Some(&kwargs))
+            .unwrap();
+        let found: bool = matcher
+            .call_method1("is_match", ("hacking",))
+            .unwrap()
+            .extract()
+            .unwrap();
+        assert!(found);
+
+        kwargs.set_item("unknown", true).unwrap();
+        let err = module
+            .getattr("DenyList")
+            .unwrap()
+            .call((vec!["hack"],), Some(&kwargs))
+            .unwrap_err();
+        assert!(err.is_instance_of::<pyo3::exceptions::PyValueError>(py));
+    });
+}
+
+#[test]
+fn test_find_overlapping() {
+    Python::initialize();
+    Python::attach(|py| {
+        let module = PyModule::new(py, "deny_filter").unwrap();
+        deny_filter::pymodule::deny_filter(&module).unwrap();
+        let matcher = module
+            .getattr("DenyListRs")
+            .unwrap()
+            .call1((vec!["free", "free money"],))
+            .unwrap();
+        let found = matcher
+            .call_method1("find_overlapping", ("Free money",))
+            .unwrap();
+        assert_eq!(
+            found.repr().unwrap().to_string(),
+            concat!(
+                r#"[Match(word="free", start=0, end=4, char_start=0, char_end=4), "#,
+                r#"Match(word="free money", start=0, end=10, char_start=0, char_end=10)]"#
+            )
+        );
+        let end: usize = found
+            .get_item(1)
+            .unwrap()
+            .getattr("end")
+            .unwrap()
+            .extract()
+            .unwrap();
+        assert_eq!(end, 10);
+    });
+}
+
+#[test]
+fn test_explain_path() {
+    Python::initialize();
+    Python::attach(|py| {
+        let module = PyModule::new(py, "deny_filter").unwrap();
+        deny_filter::pymodule::deny_filter(&module).unwrap();
+        let matcher = module
+            .getattr("DenyList")
+            .unwrap()
+            .call1((vec!["free"],))
+            .unwrap();
+        let args = py
+            .eval(c"{'a': 'ok', 'b': [1, 'FREE stuff']}", None, None)
+            .unwrap();
+        let explanation = matcher.call_method1("explain", (args,)).unwrap();
+        let path: String = explanation.getattr("path").unwrap().extract().unwrap();
+        assert_eq!(path, "$.b[1]");
+        assert!(
+            matcher
+                .call_method1("explain", ("clean",))
+                .unwrap()
+                .is_none()
+        );
+    });
+}
+
+#[test]
+fn test_scan_pool() {
+    Python::initialize();
+    Python::attach(|py| {
+        let module = PyModule::new(py, "deny_filter").unwrap();
+        deny_filter::pymodule::deny_filter(&module).unwrap();
+        let pool = module
+            .getattr("ScanPool")
+            .unwrap()
+            .call1((vec!["hack"], 2, 1))
+            .unwrap();
+        let args = py
+            .eval(c"{'a': 'ok', 'b': ['x', {'c': 'we HACK'}]}", None, None)
+            .unwrap();
+        let found: bool = pool
+            .call_method1("scan_any", (args,))
+            .unwrap()
+            .extract()
+            .unwrap();
+        assert!(found);
+        let found: Vec<bool> = pool
+            .call_method1("scan_many", (vec!["hack", "ok", "hacker"],))
+            .unwrap()
+            .extract()
+            .unwrap();
+        assert_eq!(found, vec![true, false, true]);
+        let pending = pool.call_method1("submit", ("ok",)).unwrap();
+        let found: bool = pending.call_method0("result").unwrap().extract().unwrap();
+        assert!(!found);
+    });
+}
+
+#[test]
+fn test_scan_async() {
+    Python::initialize();
+    Python::attach(|py| {
+        let module = PyModule::new(py, "deny_filter").unwrap();
+        deny_filter::pymodule::deny_filter(&module).unwrap();
+        let globals = pyo3::types::PyDict::new(py);
+        globals.set_item("deny_filter", module).unwrap();
+        py.run(
+            cr#"
+import asyncio
+import zlib
+
+async def verdicts(matcher):
+    found = await asyncio.gather(
+        matcher.scan_any_async({"a": ["fine", {"b": "we HACK"}]}),
+        matcher.scan_any_async(["fine", 1]),
+        matcher.scan_msgpack_async(b"\x81\xa1a\xa4hack"),
+        matcher.scan_msgpack_async(zlib.compress(b"\x81\xa1a\xa4hack"), decompress=True),
+    )
+    try:
+        await matcher.scan_msgpack_async(b"\xc1")
+    except ValueError as err:
+        found.append(str(err))
+    return found
+
+found = asyncio.run(verdicts(deny_filter.DenyListRs(["hack"])))
+"#,
+            Some(&globals),
+            None,
+        )
+        .unwrap();
+        let found = globals.get_item("found").unwrap().unwrap();
+        assert_eq!(
+            found.repr().unwrap().to_string(),
+            "[True, False, True, True, 'reserved msgpack marker 0xc1']"
+        );
+    });
+}
+
+#[test]
+fn test_overflow_and_ignored_keys() {
+    Python::initialize();
+    Python::attach(|py| {
+        let module = PyModule::new(py, "deny_filter").unwrap();
+        deny_filter::pymodule::deny_filter(&module).unwrap();
+        let globals = pyo3::types::PyDict::new(py);
+        globals.set_item("deny_filter", module).unwrap();
+        py.run(
+            cr#"
+deny_list = deny_filter.DenyList(["hack"], max_payload_len=10, on_overflow="raise")
+assert deny_list.scan_any(["fine", "hack"])
+ignoring = deny_filter.DenyListRs(["hack"], ignore_key_patterns=[".*_embedding$"])
+assert not ignoring.scan_any({"text_embedding": "hack", "text": "fine"})
+assert not ignoring.scan({"text_embedding": "hack"})
+assert ignoring.scan_any({"embedding": ["hack"]})
+shallow = deny_filter.DenyList(["hack"], max_depth=2, max_strings=3)
+assert not shallow.scan_any({"a": [["hack"]]})
+assert shallow.scan_any({"a": ["hack"]})
+assert not shallow.scan_any(["a", "b", "c", "hack"])
+assert not shallow.scan_selected({"a": {"b": ["hack"]}}, ["$..*"])
+try:
+    deny_list.scan_any(["fine text", "hack"])
+except ValueError as err:
+    error = str(err)
+"#,
+            Some(&globals),
+            None,
+        )
+        .unwrap();
+        let error: String = globals
+            .get_item("error")
+            .unwrap()
+            .unwrap()
+            .extract()
+            .unwrap();
+        assert_eq!(error, "payload over the 10 byte limit");
+    });
+}
+
+#[test]
+fn test_scan_selected() {
+    Python::initialize();
+    Python::attach(|py| {
+        let module = PyModule::new(py, "deny_filter").unwrap();
+        deny_filter::pymodule::deny_filter(&module).unwrap();
+        let globals = pyo3::types::PyDict::new(py);
+        globals.set_item("deny_filter", module).unwrap();
+        py.run(
+            cr#"
+deny_list = deny_filter.DenyListDaac(["hack"])
+selectors = ["$.messages[*].content", "$.tool.args.*"]
+body = {"model": "hack", "messages": [{"role": "hack", "content": "fine"}], "tool": {"args": {"q": "ok"}}}
+assert not deny_list.scan_selected(body, selectors)
+body["tool"]["args"]["q"] = ["ok", "hack it"]
+assert deny_list.scan_selected(body, selectors)
+assert deny_list.scan_selected(b"\x81\xa4tool\x81\xa4args\x91\xa4hack", selectors)
+try:
+    deny_list.scan_selected(body, ["messages"])
+except ValueError as err:
+    error = str(err)
+"#,
+            Some(&globals),
+            None,
+        )
+        .unwrap();
+        let error: String = globals
+            .get_item("error")
+            .unwrap()
+            .unwrap()
+            .extract()
+            .unwrap();
+        assert_eq!(error, "selector \"messages\" does not start with $");
+    });
+}
+
+#[test]
+fn test_score() {
+    Python::initialize();
+    Python::attach(|py| {
+        let module = PyModule::new(py, "deny_filter").unwrap();
+        deny_filter::pymodule::deny_filter(&module).unwrap();
+        let globals = pyo3::types::PyDict::new(py);
+        globals.set_item("deny_filter", module).unwrap();
+        py.run(
+            cr#"
+deny_list = deny_filter.DenyListDaac(["kill", "gun"], weights={"kill": 4.5})
+assert deny_list.score({"a": "kill", "b": ["kill a gun", 3]}) == 5.5
+assert deny_list.score(b"\x91\xa4kill") == 4.5 and deny_list.score("fine") == 0.0
+assert deny_filter.DenyList(["kill", "gun"], score_aggregation="max").score("kill a gun") == 1.0
+shallow = deny_filter.DenyList(["kill"], max_depth=1, on_overflow="allow")
+assert shallow.score({"a": ["kill"]}) == 0.0 and not shallow.scan_any({"a": ["kill"]})
+assert shallow.score({"a": "kill"}) == 1.0
+short = deny_filter.DenyList(["kill"], max_string_len=6, on_overflow="scan_prefix")
+assert short.score(["a kill", "gun, kill"]) == 1.0 and short.score(["a gun, kill"]) == 0.0
+assert short.score(b"\x91\xabgun, a kill") == 0.0
+blocking = deny_filter.DenyList(["kill"], max_depth=1, on_overflow="block")
+assert blocking.score({"a": ["fine"]}) == float("inf")
+assert blocking.score(b"\x81\xa1a\x91\xa4fine") == float("inf")
+raising = deny_filter.DenyList(["kill"], max_strings=1, on_overflow="raise")
+try:
+    raising.score(["fine", "kill"])
+    raise AssertionError("no error")
+except ValueError:
+    pass
+"#,
+            Some(&globals),
+            None,
+        )
+        .unwrap();
+    });
+}
+
+#[test]
+fn test_redact_any() {
+    Python::initialize();
+    Python::attach(|py| {
+        let module = PyModule::new(py, "deny_filter").unwrap();
+        deny_filter::pymodule::deny_filter(&module).unwrap();
+        let globals = pyo3::types::PyDict::new(py);
+        globals.set_item("deny_filter", module).unwrap();
+        py.run(
+            cr#"
+deny_list = deny_filter.DenyListPacked(["hack"], ignore_keys=["raw"])
+payload = {"args": ["we hack", {"n": 1, "hack": "HACK it"}], "raw": "hack", "ok": None}
+redacted = deny_list.redact_any(payload, mask="x")
+assert redacted == {"args": ["we xxxx", {"n": 1, "hack": "xxxx it"}], "raw": "hack", "ok": None}
+assert payload["args"][0] == "we hack" and redacted["args"] is not payload["args"]
+assert deny_list.redact_any("a hack") == "a ****" and deny_list.redact_any(7) == 7
+loop = ["a hack"]
+loop.append(loop)
+copy = deny_list.redact_any(loop)
+assert copy[0] == "a ****" and copy[1] is copy
+shared = ["hack"]
+assert deny_list.redact_any([shared, {"k": shared}]) == [["****"], {"k": ["****"]}]
+shallow = deny_filter.DenyList(["hack"], max_depth=1, on_overflow="allow")
+assert shallow.redact_any({"a": "hack", "b": ["hack"]}) == {"a": "****", "b": ["hack"]}
+blocking = deny_filter.DenyList(["hack"], max_depth=1, max_strings=2, on_overflow="block")
+payload = {"a": "fine", "b": ["fine"], "c": "ok", "d": "more"}
+assert blocking.redact_any(payload) == {"a": "fine", "b": [], "c": "ok", "d": "****"}
+short = deny_filter.DenyList(["hack"], max_string_len=6)
+assert short.redact_any(["a hack hack"]) == ["a **** hack"]
+raising = deny_filter.DenyList(["hack"], max_strings=1, on_overflow="raise")
+try:
+    raising.redact_any(["fine", "hack"])
+    raise AssertionError("no error")
+except ValueError:
+    pass
+assert deny_list.redact_msgpack(b"\x82\xa4hack\xa6a hack\xa3raw\xa4hack") == b"\x82\xa4hack\xa6a ****\xa3raw\xa4hack"
+assert deny_list.redact_json(b'{"b": "a hack", "a": 1}', preserve_order=True) == b'{"b": "a ****", "a": 1}'
+assert deny_list.redact_json(b'{"b": "a hack", "a": 1}') == b'{"a":1,"b":"a ****"}'
+"#,
+            Some(&globals),
+            None,
+        )
+        .unwrap();
+    });
+}
+
+#[test]
+fn test_on_match() {
+    Python::initialize();
+    Python::attach(|py| {
+        let module = PyModule::new(py, "deny_filter").unwrap();
+        deny_filter::pymodule::deny_filter(&module).unwrap();
+        let globals = pyo3::types::PyDict::new(py);
+        globals.set_item("deny_filter", module).unwrap();
+        py.run(
+            cr#"
+import asyncio
+
+events = []
+deny_list = deny_filter.DenyList(["hack"])
+assert deny_list.on_match is None
+deny_list.on_match = lambda *event: events.append(event)
+assert deny_list.scan_any({"a": ["fine", {"b": "we HACK it"}]})
+assert not deny_list.scan_any(["fine"])
+
+async def scans():
+    await deny_list.scan_any_async(["fine", "hack"])
+    await deny_list.scan_msgpack_async(b"\x81\xa1a\x91\xa6a hack")
+
+asyncio.run(scans())
+deny_list.on_match = None
+deny_list.scan_any("hack")
+"#,
+            Some(&globals),
+            None,
+        )
+        .unwrap();
+        let events: Vec<(String, String, String)> = globals
+            .get_item("events")
+            .unwrap()
+            .unwrap()
+            .extract()
+            .unwrap();
+        let expected = [
+            ("hack", "$.a[1].b", "HACK"),
+            ("hack", "$[1]", "hack"),
+            ("hack", "$.a[0]", "hack"),
+        ]
+        .map(|(p, path, s)| (p.to_string(), path.to_string(), s.to_string()));
+        assert_eq!(events, expected);
+    });
+}
+
+#[test]
+fn test_scan_stream() {
+    Python::initialize();
+    Python::attach(|py| {
+        let module = PyModule::new(py, "deny_filter").unwrap();
+        deny_filter::pymodule::deny_filter(&module).unwrap();
+        let globals = pyo3::types::PyDict::new(py);
+        globals.set_item("deny_filter", module).unwrap();
+        py.run(
+            cr#"
+import asyncio
+
+async def chunks():
+    for chunk in ["fine, we ha", b"ck it ", b"voil\xc3", b"\xa0!"]:
+        await asyncio.sleep(0)
+        yield chunk
+
+async def verdicts(matcher):
+    return [[(m.word, m.char_start) for m in v] async for v in matcher.scan_stream(chunks())]
+
+found = asyncio.run(verdicts(deny_filter.DenyListDaac(["hack", "voil\u00e0"])))
+"#,
+            Some(&globals),
+            None,
+        )
+        .unwrap();
+        let found: Vec<Vec<(String, usize)>> = globals
+            .get_item("found")
+            .unwrap()
+            .unwrap()
+            .extract()
+            .unwrap();
+        assert_eq!(
+            found,
+            vec![
+                vec![],
+                vec![("hack".to_string(), 9)],
+                vec![],
+                vec![("voilà".to_string(), 17)],
+                vec![],
+            ]
+        );
+    });
+}
+
+#[test]
+fn test_iter_matches() {
+    Python::initialize();
+    Python::attach(|py| {
+        let module = PyModule::new(py, "deny_filter").unwrap();
+        deny_filter::pymodule::deny_filter(&module).unwrap();
+        let globals = pyo3::types::PyDict::new(py);
+        globals.set_item("deny_filter", module).unwrap();
+        py.run(
+            cr#"
+matches = deny_filter.DenyList(["hack", "voil\u00e0"]).iter_matches("we hack, voil\u00e0")
+first = next(matches)
+found = [(first.word, first.char_start)] + [(m.word, m.char_start) for m in matches]
+done = next(matches, None) is None
+"#,
+            Some(&globals),
+            None,
+        )
+        .unwrap();
+        let found: Vec<(String, usize)> = globals
+            .get_item("found")
+            .unwrap()
+            .unwrap()
+            .extract()
+            .unwrap();
+        assert_eq!(
+            found,
+            vec![("hack".to_string(), 3), ("voilà".to_string(), 9)]
+        );
+        assert!(
+            globals
+                .get_item("done")
+                .unwrap()
+                .unwrap()
+                .is_truthy()
+                .unwrap()
+        );
+    });
+}
+
+#[test]
+fn test_registry() {
+    Python::initialize();
+    Python::attach(|py| {
+        let module = PyModule::new(py, "deny_filter").unwrap();
+        deny_filter::pymodule::deny_filter(&module).unwrap();
+        let globals = pyo3::types::PyDict::new(py);
+        globals.set_item("deny_filter", module).unwrap();
+        py.run(
+            cr#"
+registry = deny_filter.DenyListRegistry({
+    "tenant-a": {"words": ["hack"]},
+    "tenant-b": {"words": ["spam"], "backend": "regex", "whole_word": True},
+})
+assert registry.names() == ["tenant-a", "tenant-b"] and len(registry) == 2
+old = registry.get("tenant-a")
+registry.reload("tenant-a", {"words": ["scam"], "backend": "daachorse"})
+assert old.scan_any({"text": "we hack"}) and not registry.get("tenant-a").is_match("hack")
+assert not registry.get("tenant-b").is_match("spammer")
+for config in [{}, {"words": ["x"], "backend": "nope"}, {"words": ["x"], "bogus": 1}]:
+    try:
+        registry.reload("tenant-a", config)
+        raise AssertionError(config)
+    except ValueError:
+        pass
+assert registry.get("tenant-a").find_all("SCAM")[0].word == "scam"
+payload = {"a": ["we scam"], "skip": "spam", "b": "spam it"}
+assert registry.scan_all(payload) == {"tenant-a": True, "tenant-b": True}
+assert registry.scan_all(["spam"]) == {"tenant-a": False, "tenant-b": True}
+registry.remove("tenant-b")
+assert "tenant-b" not in registry
+try:
+    registry.get("tenant-b")
+    raise AssertionError
+except KeyError:
+    pass
+"#,
+            Some(&globals),
+            None,
+        )
+        .unwrap();
+    });
+}
+
+#[test]
+fn test_deny_list_auto() {
+    Python::initialize();
+    Python::attach(|py| {
+        let module = PyModule::new(py, "deny_filter").unwrap();
+        deny_filter::pymodule::deny_filter(&module).unwrap();
+        let globals = pyo3::types::PyDict::new(py);
+        globals.set_item("deny_filter", module).unwrap();
+        py.run(
+            cr#"
+auto = deny_filter.DenyListAuto.build(["hack"], ["a clean line", "we hack"], whole_word=True)
+assert auto.backend in ("aho-corasick", "regex", "daachorse", "packed")
+assert auto.stats().backend == auto.backend
+assert [name for name, _ in auto.timings()] == ["aho-corasick", "regex", "daachorse", "packed"]
+assert auto.scan_any({"text": "HACK it"}) and not auto.is_match("hacker")
+"#,
+            Some(&globals),
+            None,
+        )
+        .unwrap();
+    });
+}
+
+#[test]
+fn test_python_matcher() {
+    Python::initialize();
+    Python::attach(|py| {
+        let module = PyModule::new(py, "deny_filter").unwrap();
+        deny_filter::pymodule::deny_filter(&module).unwrap();
+        let globals = pyo3::types::PyDict::new(py);
+        globals.set_item("deny_filter", module).unwrap();
+        py.run(
+            cr#"
+class Shouting(deny_filter.Matcher):
+    def is_match(self, text):
+        return text.isupper()
+
+class Emoji:
+    def is_match(self, text):
+        return "💣" in text
+    def find_all(self, text):
+        return [("💣", i, i + 1) for i, c in enumerate(text) if c == "💣"]
+
+class Broken(deny_filter.Matcher):
+    def is_match(self, text):
+        raise RuntimeError("down")
+
+shouting = Shouting()
+assert shouting.scan_any({"a": ["quiet", "LOUD"]}) and not shouting.scan_str("quiet")
+assert shouting.find_all("LOUD")[0].word == "Shouting"
+try:
+    deny_filter.Matcher().is_match("x")
+    raise AssertionError
+except NotImplementedError:
+    pass
+
+registry = deny_filter.DenyListRegistry()
+registry.register("shouting", shouting)
+registry.register("emoji", Emoji())
+registry.register("broken", Broken())
+assert registry.get("shouting").scan_any(["fine", "STOP"])
+hit = registry.get("emoji").find_all("é 💣!")[0]
+assert (hit.word, hit.start, hit.end, hit.char_start) == ("💣", 3, 7, 2)
+assert registry.get("emoji").redact("é 💣!") == "é *!"
+# errors fail closed
+assert registry.get("broken").is_match("anything")
+try:
+    registry.register("nothing", object())
+    raise AssertionError
+except TypeError:
+    pass
+"#,
+            Some(&globals),
+            None,
+        )
+        .unwrap();
+    });
+}
+
+#[test]
+fn test_scan_exceptions() {
+    Python::initialize();
+    Python::attach(|py| {
+        let module = PyModule::new(py, "deny_filter").unwrap();
+        deny_filter::pymodule::deny_filter(&module).unwrap();
+        let globals = pyo3::types::PyDict::new(py);
+        globals.set_item("deny_filter", module).unwrap();
+        py.run(
+            cr#"
+import traceback
+deny_list = deny_filter.DenyList(["hack"])
+
+def fail(secret):
+    raise RuntimeError("fine")
+
+try:
+    fail("a hack")
+except RuntimeError as err:
+    raised = err
+assert deny_list.scan_any(raised), "frame locals"
+assert deny_list.scan_any(raised.__traceback__)
+walk = traceback.walk_tb(raised.__traceback__)
+assert deny_list.scan_any(traceback.StackSummary.extract(walk, capture_locals=True))
+assert not deny_list.scan_any(traceback.extract_tb(raised.__traceback__))
+assert not deny_list.scan_any(ValueError("fine", 42))
+assert deny_list.scan_any(ValueError("fine", {"cmd": "hack"}))
+noted = ValueError("fine")
+noted.add_note("then a hack")
+assert deny_list.scan_any(noted)
+caused = KeyError("fine")
+caused.__cause__ = ValueError("hack")
+caused.__cause__.__context__ = caused
+assert deny_list.scan_any({"error": caused})
+assert deny_list.scan_any(ExceptionGroup("fine", [OSError("hack")]))
+looped = ValueError("fine")
+looped.__notes__ = ["fine", looped]
+looped.args = ("fine", [looped])
+looped.__context__ = KeyError("fine")
+looped.__context__.__cause__ = looped
+assert not deny_list.scan_any({"a": looped, "b": [looped]})
+class Summary(traceback.FrameSummary):
+    pass
+assert deny_list.scan_any([1, 2.5, b"x", None, Summary("hack.py", 1, "f", lookup_line=False)])
+class Custom(Exception):
+    def __str__(self):
+        return "hack"
+assert deny_list.scan_any(Custom("fine"))
+"#,
+            Some(&globals),
+            None,
+        )
+        .unwrap();
+    });
+}
+
+#[test]
+fn test_scan_log_record() {
+    Python::initialize();
+    Python::attach(|py| {
+        let module = PyModule::new(py, "deny_filter").unwrap();
+        deny_filter::pymodule::deny_filter(&module).unwrap();
+        let globals = pyo3::types::PyDict::new(py);
+        globals.set_item("deny_filter", module).unwrap();
+        py.run(
+            cr#"
+import logging
+deny_list = deny_filter.DenyListRs(["hack"], ignore_keys=["token"])
+
+def record(msg, args=(), **extra):
+    return logging.makeLogRecord({"msg": msg, "args": args, **extra})
+
+assert deny_list.scan_log_record(record("user %s logged in", ("hack",)))
+assert deny_list.scan_log_record(record("split h%sk", ("ac",)).__dict__)
+assert deny_list.scan_log_record(record("%(user)s", {"user": "hack"}))
+assert deny_list.scan_log_record(record("bad %d", ("hack",)))
+assert deny_list.scan_log_record(record("fine", user={"name": "hack"}))
+assert not deny_list.scan_log_record(record("fine", token="hack"))
+assert not deny_list.scan_log_record(record("fine", name="hack-service"))
+try:
+    raise ValueError("hack")
+except ValueError:
+    import sys
+    assert deny_list.scan_log_record(record("failed", exc_info=sys.exc_info()))
+
+records = []
+class Keep(logging.Handler):
+    def emit(self, record):
+        records.append(record)
+logger = logging.getLogger("deny_filter.test")
+logger.propagate = False
+logger.addHandler(Keep())
+logger.addFilter(deny_list.log_filter())
+logger.warning("fine %s", "text")
+logger.warning("a %s", "hack")
+assert [r.getMessage() for r in records] == ["fine text"]
+logger.filters.clear()
+logger.addFilter(deny_list.log_filter("[redacted]"))
+logger.warning("a %s", "hack", extra={"user": "hack"})
+assert (records[-1].getMessage(), records[-1].user) == ("[redacted]", "[redacted]")
+"#,
+            Some(&globals),
+            None,
+        )
+        .unwrap();
+    });
+}
+
+#[test]
+fn test_incremental_deny_list() {
+    Python::initialize();
+    Python::attach(|py| {
+        let module = PyModule::new(py, "deny_filter").unwrap();
+        deny_filter::pymodule::deny_filter(&module).unwrap();
+        let globals = pyo3::types::PyDict::new(py);
+        globals.set_item("deny_filter", module).unwrap();
+        py.run(
+            cr#"
+import threading
+deny_list = deny_filter.IncrementalDenyList(["hack"], merge_at=50)
+threads = [
+    threading.Thread(target=deny_list.add_words, args=([f"word{i}"],)) for i in range(20)
+]
+for thread in threads:
+    thread.start()
+for thread in threads:
+    thread.join()
+assert (len(deny_list), deny_list.delta_len) == (21, 20)
+assert deny_list.scan_any({"a": ["fine", "word7"]}) and deny_list.is_match("HACK")
+deny_list.merge()
+assert deny_list.delta_len == 0 and deny_list.stats().patterns == 21
+"#,
+            Some(&globals),
+            None,
+        )
+        .unwrap();
+    });
+}
+
+#[test]
+fn test_violations() {
+    Python::initialize();
+    Python::attach(|py| {
+        let module = PyModule::new(py, "deny_filter").unwrap();
+        deny_filter::pymodule::deny_filter(&module).unwrap();
+        let globals = pyo3::types::PyDict::new(py);
+        globals.set_item("deny_filter", module).unwrap();
+        py.run(
+            cr#"
+deny_list = deny_filter.DenyList(
+    ["malware", "secret"],
+    proximity=[{"first": "wire", "second": "money", "within": 2}],
+    ignore_keys=["image"],
+)
+payload = {
+    "title": "secret malware secret",
+    "messages": [{"content": "wire the money"}, "malware"],
+    "image": "malware",
+    "content": "safe",
+}
+assert deny_list.scan_any(payload)
+found = [(v.field, v.word) for v in deny_list.violations(payload)]
+assert found == [
+    ("title", "secret"),
+    ("title", "malware"),
+    ("messages", "wire NEAR/2 money"),
+    ("messages", "malware"),
+], found
+assert deny_list.violations({"content": "safe", "image": "malware"}) == []
+assert [v.word for v in deny_list.violations(["x", "secret"])] == ["secret"]
+# a limit blocking lists no word
+limited = deny_filter.DenyList(["malware"], max_depth=1, on_overflow="block")
+assert limited.scan_any({"a": [["fine"]]})
+assert [(v.field, v.word) for v in limited.violations({"a": [["fine"]]})] == [("a", None)]
+"#,
+            Some(&globals),
+            None,
+        )
+        .unwrap();
+    });
+}
+
+#[test]
+fn test_submodules() {
+    Python::initialize();
+    Python::attach(|py| {
+        let module = PyModule::new(py, "deny_filter").unwrap();
+        deny_filter::pymodule::deny_filter(&module).unwrap();
+        let globals = pyo3::types::PyDict::new(py);
+        globals.set_item("deny_filter", module).unwrap();
+        py.run(
+            cr#"
+import sys
+sys.modules["deny_filter"] = deny_filter
+from deny_filter.matchers import DenyList, Match
+from deny_filter.plugin import Violation, DenyListRegistry
+import deny_filter.plugin
+assert DenyList is deny_filter.DenyList and Violation is deny_filter.Violation
+assert deny_filter.plugin.McpVerdict is deny_filter.McpVerdict
+assert deny_filter.matchers.__name__ == "deny_filter.matchers"
+assert DenyList.__module__ == "deny_filter.matchers" and Violation.__module__ == "deny_filter.plugin"
+# every class is registered in one submodule
+matchers, plugin = set(deny_filter.matchers.__all__), set(deny_filter.plugin.__all__)
+assert not matchers & plugin and "DenyList" in matchers and "DenyListRegistry" in plugin
+assert {"matchers", "plugin", "render_prometheus"} <= set(deny_filter.__all__)
+assert matchers | plugin <= set(deny_filter.__all__)
+"#,
+            Some(&globals),
+            None,
+        )
+        .unwrap();
+    });
+}
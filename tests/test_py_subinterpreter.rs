@@ -0,0 +1,75 @@
+#![cfg(feature = "python")]
+
+use std::ffi::CStr;
+
+use pyo3::ffi;
+use pyo3::prelude::*;
+
+/// imports the package and scans with it, in whichever interpreter runs it
+const SCAN: &CStr = cr#"
+import traceback
+import deny_filter
+from deny_filter.matchers import DenyList
+deny_list = DenyList(["hack"], cache_size=4)
+assert deny_list.is_match("HACK")
+assert deny_list.scan_any({"a": ["fine", "a hack"]})
+assert [v.word for v in deny_list.violations({"a": "hack"})] == ["hack"]
+frames = traceback.StackSummary.from_list([("hack.py", 1, "f", None)])
+assert deny_list.scan_any(frames)
+"#;
+
+fn scan(py: Python<'_>) {
+    py.run(SCAN, None, None).unwrap();
+}
+
+#[test]
+fn test_import_in_subinterpreters() {
+    // SAFETY: registered before the interpreter starts
+    unsafe {
+        let appended = ffi::PyImport_AppendInittab(
+            c"deny_filter".as_ptr(),
+            Some(deny_filter::pymodule::PyInit_deny_filter),
+        );
+        assert_eq!(appended, 0);
+    }
+    Python::initialize();
+    Python::attach(scan);
+    // an interpreter sharing the main GIL, as `Py_NewInterpreter` creates it
+    unsafe {
+        let state = ffi::PyGILState_Ensure();
+        let main = ffi::PyThreadState_Get();
+        let sub = ffi::Py_NewInterpreter();
+        assert!(!sub.is_null());
+        scan(Python::assume_attached());
+        ffi::Py_EndInterpreter(sub);
+        ffi::PyThreadState_Swap(main);
+        ffi::PyGILState_Release(state);
+    }
+    Python::attach(scan);
+    // the "legacy" config of `_interpreters` (python 3.13+) shares the main GIL and imports
+    // the package, an "isolated" interpreter has its own GIL and must not
+    Python::attach(|py| {
+        if py.version_info() < (3, 13) {
+            return;
+        }
+        let globals = pyo3::types::PyDict::new(py);
+        globals.set_item("SCAN", SCAN.to_str().unwrap()).unwrap();
+        py.run(
+            cr#"
+import _interpreters
+legacy = _interpreters.create("legacy")
+assert _interpreters.exec(legacy, SCAN) is None
+_interpreters.destroy(legacy)
+isolated = _interpreters.create("isolated")
+failed = _interpreters.exec(isolated, "import deny_filter")
+_interpreters.destroy(isolated)
+assert failed is not None, "imported in an isolated interpreter"
+assert failed.type.__name__ == "ImportError", failed
+assert "does not support loading in subinterpreters" in failed.msg, failed.msg
+"#,
+            Some(&globals),
+            None,
+        )
+        .unwrap();
+    });
+}
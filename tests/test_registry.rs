@@ -0,0 +1,105 @@
+use deny_filter::backend::Backend;
+use deny_filter::options::{Options, WordMatch};
+use deny_filter::registry::DenyListRegistry;
+use rmp::encode;
+use serde_json::json;
+
+#[test]
+fn test_registry_reload() {
+    let registry = DenyListRegistry::new();
+    assert!(registry.is_empty());
+    registry
+        .reload(
+            "tenant-a",
+            Backend::AhoCorasick,
+            vec!["hack".to_string()],
+            &Options::default(),
+        )
+        .unwrap();
+    registry
+        .reload(
+            "tenant-b",
+            Backend::Regex,
+            vec!["spam".to_string()],
+            &Options::default(),
+        )
+        .unwrap();
+    assert_eq!(registry.names(), ["tenant-a", "tenant-b"]);
+
+    let before = registry.get("tenant-a").unwrap();
+    registry
+        .reload(
+            "tenant-a",
+            Backend::Daachorse,
+            vec!["scam".to_string()],
+            &Options::default(),
+        )
+        .unwrap();
+    // fetched matchers keep the list they were fetched with
+    assert!(before.is_match("hack"));
+    let after = registry.get("tenant-a").unwrap();
+    assert!(!after.is_match("hack"));
+    assert!(after.is_match("SCAM"));
+
+    // a failing build keeps the current matcher
+    let options = Options {
+        word_match: [("missing".to_string(), WordMatch::Substring)].into(),
+        ..Options::default()
+    };
+    assert!(
+        registry
+            .reload(
+                "tenant-a",
+                Backend::AhoCorasick,
+                vec!["x".to_string()],
+                &options
+            )
+            .is_err()
+    );
+    assert!(registry.get("tenant-a").unwrap().is_match("scam"));
+
+    assert!(registry.remove("tenant-b").is_some());
+    assert!(registry.get("tenant-b").is_none());
+    assert_eq!(registry.len(), 1);
+}
+
+#[test]
+fn test_registry_scan_all() {
+    let registry = DenyListRegistry::new();
+    let build = |name: &str, word: &str, options: &Options| {
+        registry
+            .reload(name, Backend::AhoCorasick, vec![word.to_string()], options)
+            .unwrap();
+    };
+    build("a", "hack", &Options::default());
+    build(
+        "b",
+        "spam",
+        &Options {
+            ignore_keys: vec!["meta".to_string()],
+            ..Options::default()
+        },
+    );
+    build("c", "scam", &Options::default());
+
+    let payload = json!({"text": "hack", "meta": {"note": "spam"}, "list": ["scam"]});
+    let verdicts = registry.scan_all_json(&payload).unwrap();
+    assert_eq!(
+        verdicts.into_iter().collect::<Vec<_>>(),
+        [
+            ("a".to_string(), true),
+            ("b".to_string(), false),
+            ("c".to_string(), true)
+        ]
+    );
+
+    let mut buf = Vec::new();
+    encode::write_map_len(&mut buf, 2).unwrap();
+    encode::write_str(&mut buf, "meta").unwrap();
+    encode::write_str(&mut buf, "spam").unwrap();
+    encode::write_str(&mut buf, "text").unwrap();
+    encode::write_str(&mut buf, "hack spam").unwrap();
+    let verdicts = registry.scan_all_msgpack(&buf).unwrap();
+    assert!(verdicts["a"] && verdicts["b"] && !verdicts["c"]);
+    assert!(registry.scan_all_msgpack(&buf[..buf.len() - 1]).is_err());
+}
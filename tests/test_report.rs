@@ -0,0 +1,116 @@
+use deny_filter::analyze::analyze;
+use deny_filter::backend::Backend;
+use deny_filter::options::{MatchKind, Options};
+
+#[test]
+fn test_build_report() {
+    let words = ["Free", "", "free money", "free", "  ", "straße", "money"];
+    for backend in Backend::ALL {
+        let matcher = backend
+            .build_with(
+                words.iter().map(ToString::to_string).collect(),
+                &Options::default(),
+            )
+            .unwrap();
+        assert!(!matcher.is_match("nothing to see"), "{backend}");
+        let report = matcher.build_report();
+        assert_eq!(report.empty, vec![1, 4]);
+        assert_eq!(report.duplicates, vec!["free"]);
+        assert_eq!(
+            report.altered,
+            vec![
+                ("Free".to_string(), "free".to_string()),
+                ("straße".to_string(), "strasse".to_string()),
+            ]
+        );
+        assert_eq!(
+            report.subsumes,
+            vec![
+                ("free money".to_string(), "Free".to_string()),
+                ("free money".to_string(), "money".to_string()),
+            ]
+        );
+    }
+}
+
+#[test]
+fn test_analyze() {
+    let words: Vec<String> = ["free", "free money", "Free", "money", " ", "--", "get free"]
+        .iter()
+        .map(ToString::to_string)
+        .collect();
+    let analysis = analyze(&words, &Options::default()).unwrap();
+    assert_eq!(analysis.never_match, vec![" "]);
+    let pair = |a: &str, b: &str| (a.to_string(), b.to_string());
+    assert_eq!(
+        analysis.substrings,
+        vec![
+            pair("free money", "free"),
+            pair("free money", "Free"),
+            pair("free money", "money"),
+            pair("get free", "free"),
+            pair("get free", "Free"),
+        ]
+    );
+    assert_eq!(
+        analysis.unreachable,
+        vec![pair("free money", "free"), pair("Free", "free")]
+    );
+    let options = Options {
+        whole_word: true,
+        match_kind: MatchKind::LeftmostLongest,
+        ..Options::default()
+    };
+    let analysis = analyze(&words, &options).unwrap();
+    assert_eq!(analysis.never_match, vec![" ", "--"]);
+    assert_eq!(analysis.unreachable, vec![pair("Free", "free")]);
+}
+
+#[test]
+fn test_stats() {
+    for backend in Backend::ALL {
+        let matcher = backend
+            .build_with(
+                vec![
+                    "free".to_string(),
+                    "Free Money".to_string(),
+                    "fun".to_string(),
+                ],
+                &Options::default(),
+            )
+            .unwrap();
+        let stats = matcher.stats();
+        assert_eq!(stats.patterns, 3);
+        // root, f, r, e, e, " money" and u, n
+        assert_eq!(stats.states, 1 + 4 + 6 + 2, "{backend}");
+        assert_eq!((stats.min_pattern_len, stats.max_pattern_len), (3, 10));
+        assert!(stats.heap_bytes > 0, "{backend}");
+    }
+}
+
+#[test]
+fn test_evaluate() {
+    let matcher = Backend::AhoCorasick
+        .build(vec!["ass".to_string(), "scam".to_string()])
+        .unwrap();
+    let eval = matcher.evaluate(&[
+        ("you ass", true),
+        ("classic", false),
+        ("assistant", false),
+        ("scam alert", true),
+        ("phishing", true),
+        ("hello", false),
+    ]);
+    assert_eq!(
+        (
+            eval.true_positives,
+            eval.false_positives,
+            eval.true_negatives,
+            eval.false_negatives
+        ),
+        (2, 2, 1, 1)
+    );
+    assert!((eval.precision - 0.5).abs() < 1e-9);
+    assert!((eval.recall - 2.0 / 3.0).abs() < 1e-9);
+    assert_eq!(eval.false_positive_words, vec![("ass".to_string(), 2)]);
+}
@@ -0,0 +1,65 @@
+#![allow(clippy::float_cmp)] // the weights are exact binary fractions
+
+use deny_filter::backend::Backend;
+use deny_filter::deny_list::DenyList;
+use deny_filter::matcher::Matcher;
+use deny_filter::options::Options;
+use serde_json::json;
+
+fn options(json: &str) -> Options {
+    serde_json::from_str(json).unwrap()
+}
+
+#[test]
+fn test_score() {
+    let words = vec!["kill".to_string(), "Bomb".to_string(), "gun".to_string()];
+    let weighted = options(r#"{"weights": {"kill": 5.0, "BOMB": 2.5}}"#);
+    for backend in Backend::ALL {
+        let matcher = backend.build_with(words.clone(), &weighted).unwrap();
+        assert!(
+            matcher.score("all fine").to_bits() == 0.0_f64.to_bits(),
+            "{backend}"
+        );
+        // distinct words count once, unweighted words weigh 1
+        assert_eq!(matcher.score("kill kill the bomb"), 7.5, "{backend}");
+        assert_eq!(matcher.score("a gun"), 1.0, "{backend}");
+        assert_eq!(
+            matcher.score_json(&json!({"a": ["kill"], "b": {"c": "kill a gun"}})),
+            6.0,
+            "{backend}"
+        );
+    }
+    let proximity = options(
+        r#"{"weights": {"kill": 5.0}, "proximity": [
+            {"first": "wire", "second": "money", "within": 2, "weight": 3.5},
+            {"first": "send", "second": "gun", "within": 1}
+        ]}"#,
+    );
+    let matcher = DenyList::with_options(words.clone(), &proximity).unwrap();
+    // rules weigh like words, 1 unless set
+    assert_eq!(matcher.score("wire the money"), 3.5);
+    assert_eq!(matcher.score("wire the money to kill"), 8.5);
+    assert_eq!(matcher.score("send a gun, wire money"), 5.5);
+    assert_eq!(
+        matcher.score_json(&json!(["wire", "money", "send gun"])),
+        2.0
+    );
+    let mut invalid = proximity.clone();
+    invalid.proximity[0].weight = Some(f64::NAN);
+    assert!(DenyList::with_options(words.clone(), &invalid).is_err());
+    let max = options(r#"{"weights": {"kill": 5.0}, "score_aggregation": "max"}"#);
+    let matcher = DenyList::with_options(words.clone(), &max).unwrap();
+    assert_eq!(matcher.score("kill with a gun"), 5.0);
+    assert_eq!(
+        DenyList::new(words.clone()).unwrap().score("kill a gun"),
+        2.0
+    );
+    for weight in [-1.0, f64::INFINITY, f64::NAN] {
+        let mut invalid = Options::default();
+        invalid.weights.insert("kill".to_string(), weight);
+        assert!(
+            DenyList::with_options(words.clone(), &invalid).is_err(),
+            "{weight}"
+        );
+    }
+}
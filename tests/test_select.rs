@@ -0,0 +1,55 @@
+use deny_filter::backend::Backend;
+use deny_filter::options::Options;
+use deny_filter::select::{self, Selector, parse_selectors};
+use deny_filter::traverse::Segment::{Index, Key};
+use serde_json::json;
+
+#[test]
+fn test_selector() {
+    let selector: Selector = "$.messages[*].content".parse().unwrap();
+    assert!(selector.selects(&[Key("messages"), Index(1), Key("content")]));
+    // strings below a selected value are selected
+    assert!(selector.selects(&[
+        Key("messages"),
+        Index(0),
+        Key("content"),
+        Index(2),
+        Key("text")
+    ]));
+    assert!(!selector.selects(&[Key("messages"), Index(0), Key("role")]));
+    assert!(!selector.selects(&[Key("messages")]));
+
+    let selector: Selector = "$..['tool name'][0]".parse().unwrap();
+    assert!(selector.selects(&[Key("a"), Key("b"), Key("tool name"), Index(0)]));
+    assert!(selector.selects(&[Key("tool name"), Index(0)]));
+    assert!(!selector.selects(&[Key("tool name"), Index(1)]));
+    assert!("$".parse::<Selector>().unwrap().selects(&[Key("any")]));
+
+    for invalid in ["messages", "$.", "$[x]", "$['open]", "$.a[1"] {
+        assert!(invalid.parse::<Selector>().is_err(), "{invalid}");
+    }
+}
+
+#[test]
+fn test_scan_selected() {
+    let matcher = Backend::AhoCorasick
+        .build_with(vec!["hack".to_string()], &Options::default())
+        .unwrap();
+    let selectors = parse_selectors(&["$.messages[*].content", "$.tool.args.*"]).unwrap();
+    let body = json!({
+        "model": "hack-3",
+        "messages": [{"role": "user", "content": "fine"}],
+        "tool": {"name": "hack", "args": {"q": "fine"}},
+    });
+    assert!(!select::scan_json(&*matcher, &body, &selectors));
+    let body = json!({"tool": {"args": {"q": ["fine", "we hack"]}}});
+    assert!(select::scan_json(&*matcher, &body, &selectors));
+    // {"model": "hack", "messages": [{"content": "hack"}]}
+    let msgpack = b"\x82\xa5model\xa4hack\xa8messages\x91\x81\xa7content\xa4hack";
+    assert_eq!(
+        select::scan_msgpack(&*matcher, msgpack, &selectors),
+        Ok(true)
+    );
+    let model = parse_selectors(&["$.messages[1]"]).unwrap();
+    assert_eq!(select::scan_msgpack(&*matcher, msgpack, &model), Ok(false));
+}
@@ -0,0 +1,50 @@
+use deny_filter::backend::Backend;
+use deny_filter::matcher::Matcher;
+use deny_filter::metrics::render;
+use deny_filter::shadow::ShadowMatcher;
+
+fn words(words: &[&str]) -> Vec<String> {
+    words.iter().map(ToString::to_string).collect()
+}
+
+#[test]
+fn test_shadow_sample() {
+    let shadow = ShadowMatcher::new(
+        Backend::AhoCorasick.build(words(&["hack"])).unwrap(),
+        Backend::Regex.build(words(&["scam"])).unwrap(),
+        0.5,
+        "test-sample",
+    )
+    .unwrap();
+    // the active list decides, every second scan is also seen by the candidate
+    let verdicts: Vec<bool> = ["a scam", "a scam", "we hack", "we hack", "fine", "fine"]
+        .into_iter()
+        .map(|text| shadow.scan_str(text))
+        .collect();
+    assert_eq!(verdicts, [false, false, true, true, false, false]);
+    let metrics = shadow.shadow_metrics();
+    assert_eq!(
+        (metrics.sampled(), metrics.blocks(), metrics.allows()),
+        (3, 1, 1)
+    );
+    let value = serde_json::json!({"a": ["fine", "scam"]});
+    assert!(!shadow.scan_json(&value));
+    assert!(!shadow.scan_json(&value));
+    assert_eq!((metrics.sampled(), metrics.blocks()), (4, 2));
+    let text = render();
+    assert!(text.contains("deny_filter_shadow_scans_total{shadow=\"test-sample\"} 4"));
+    assert!(
+        text.contains(
+            "deny_filter_shadow_diffs_total{shadow=\"test-sample\",candidate=\"block\"} 2"
+        )
+    );
+}
+
+#[test]
+fn test_shadow_sample_rate() {
+    let build = || Backend::AhoCorasick.build(words(&["hack"])).unwrap();
+    assert!(ShadowMatcher::new(build(), build(), 1.5, "test-rate").is_err());
+    let never = ShadowMatcher::new(build(), build(), 0.0, "test-rate").unwrap();
+    assert!(never.scan_str("hack"));
+    assert_eq!(never.shadow_metrics().sampled(), 0);
+}
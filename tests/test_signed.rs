@@ -0,0 +1,30 @@
+use deny_filter::signed::SignedLoader;
+use ed25519_dalek::{Signer, SigningKey};
+use std::fmt::Write as _;
+use std::fs;
+
+#[test]
+fn test_signed_loader() {
+    let dir = std::env::temp_dir().join(format!("deny_signed_{}", std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    let key = SigningKey::from_bytes(&[7; 32]);
+    let list = dir.join("words.txt");
+    let data = "# signed\nvoila\nasdf\n";
+    fs::write(&list, data).unwrap();
+    let signature = key.sign(data.as_bytes()).to_bytes();
+    let hex = signature.iter().fold(String::new(), |mut s, b| {
+        let _ = write!(s, "{b:02x}");
+        s
+    });
+    fs::write(dir.join("words.txt.sig"), format!("{hex}\n")).unwrap();
+
+    let loader = SignedLoader::new(key.verifying_key().as_bytes()).unwrap();
+    assert_eq!(loader.load(&list).unwrap(), vec!["voila", "asdf"]);
+    assert!(loader.load_with_signature(&list, &signature).is_ok());
+
+    fs::write(&list, "# signed\nvoila\n").unwrap();
+    let err = loader.load(&list).unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+    assert!(SignedLoader::new(&[1, 2, 3]).is_err());
+}
@@ -0,0 +1,22 @@
+use deny_filter::backend::Backend;
+use deny_filter::sql::{scan_sql, strip_sql};
+
+#[test]
+fn test_strip_sql() {
+    assert_eq!(strip_sql("SEL/**/ECT  *\n\tFROM t"), "SELECT * FROM t");
+    assert_eq!(strip_sql("a /* x\n y */ b -- tail\nc"), "a b c");
+    assert_eq!(strip_sql("x = '--kept /**/'"), "x = '--kept /**/'");
+    assert_eq!(strip_sql("'it''s' -- gone"), "'it''s'");
+    assert_eq!(strip_sql("a /* unterminated"), "a");
+}
+
+#[test]
+fn test_scan_sql() {
+    let deny_list = Backend::AhoCorasick
+        .build(vec!["select voila".to_string(), "drop".to_string()])
+        .unwrap();
+    assert!(scan_sql(&*deny_list, "SEL/**/ECT  voila FROM t"));
+    assert!(scan_sql(&*deny_list, "SELECT\n   voila"));
+    assert!(scan_sql(&*deny_list, "SELECT 1 -- then drop it"));
+    assert!(!scan_sql(&*deny_list, "SELECT 1 FROM t"));
+}
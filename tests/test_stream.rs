@@ -0,0 +1,57 @@
+use deny_filter::deny_list::DenyList;
+use deny_filter::matcher::Matcher;
+use deny_filter::matches::Match;
+use deny_filter::options::Options;
+use deny_filter::stream::{ITER_CHUNK, StreamScanner, iter_matches};
+
+fn words(found: &[Match]) -> Vec<(&str, usize, usize)> {
+    found
+        .iter()
+        .map(|m| (m.word.as_str(), m.start, m.char_start))
+        .collect()
+}
+
+#[test]
+fn test_stream_chunks() {
+    let deny_list = DenyList::new(vec!["hack".to_string(), "voilà".to_string()]).unwrap();
+    let mut scanner = StreamScanner::for_matcher(&deny_list);
+    assert!(scanner.feed(&deny_list, "we ha").is_empty());
+    assert_eq!(words(&scanner.feed(&deny_list, "ck it ")), [("hack", 3, 3)]);
+    // held back at the end of the input, it may go on
+    assert!(scanner.feed(&deny_list, "then hack").is_empty());
+    assert_eq!(words(&scanner.feed(&deny_list, "!")), [("hack", 16, 16)]);
+    assert!(scanner.feed(&deny_list, &"x".repeat(500)).is_empty());
+    // à split across byte chunks, offsets count bytes and code points of the whole stream
+    let voila = "voilà".as_bytes();
+    assert!(scanner.feed_bytes(&deny_list, &voila[..5]).is_empty());
+    assert!(scanner.feed_bytes(&deny_list, &voila[5..]).is_empty());
+    assert_eq!(words(&scanner.finish(&deny_list)), [("voilà", 521, 521)]);
+}
+
+#[test]
+fn test_stream_whole_word() {
+    let options = Options {
+        whole_word: true,
+        ..Options::default()
+    };
+    let deny_list = DenyList::with_options(vec!["hack".to_string()], &options).unwrap();
+    let mut scanner = StreamScanner::new(8);
+    assert!(scanner.feed(&deny_list, "hack").is_empty());
+    assert!(scanner.feed(&deny_list, "ing a hack").is_empty());
+    assert_eq!(words(&scanner.finish(&deny_list)), [("hack", 10, 10)]);
+}
+
+#[test]
+fn test_iter_matches() {
+    let deny_list = DenyList::new(vec!["hack".to_string(), "voilà".to_string()]).unwrap();
+    // words across chunk boundaries, multibyte chars on them
+    let text = format!(
+        "voilà {}hack é{}voilà",
+        "é".repeat(ITER_CHUNK / 2 - 3),
+        "x".repeat(ITER_CHUNK - 4)
+    );
+    let found: Vec<Match> = iter_matches(&deny_list, &text).collect();
+    assert_eq!(found.len(), 3);
+    assert_eq!(found, deny_list.find_all(&text));
+    assert_eq!(iter_matches(&deny_list, "clean").next(), None);
+}
@@ -0,0 +1,56 @@
+#![cfg(all(feature = "tracing", feature = "python"))]
+
+use pyo3::prelude::*;
+
+#[test]
+fn test_enable_tracing() {
+    Python::initialize();
+    Python::attach(|py| {
+        let module = PyModule::new(py, "deny_filter").unwrap();
+        deny_filter::pymodule::deny_filter(&module).unwrap();
+        let globals = pyo3::types::PyDict::new(py);
+        globals.set_item("deny_filter", module).unwrap();
+        py.run(
+            cr#"
+import logging
+
+class Records(logging.Handler):
+    def __init__(self):
+        super().__init__()
+        self.messages = []
+
+    def emit(self, record):
+        self.messages.append(record.getMessage())
+
+records = Records()
+logger = logging.getLogger("deny_filter")
+logger.addHandler(records)
+logger.setLevel(logging.DEBUG)
+deny_filter.enable_tracing("debug")
+assert deny_filter.DenyList(["voila"]).scan_str("et voila")
+assert "deny word matched" in records.messages
+assert any("kind=scan_str" in m and "bytes=8" in m and "matched=true" in m for m in records.messages)
+assert not any("voila" in m for m in records.messages), records.messages
+try:
+    deny_filter.enable_tracing("loud")
+    raise AssertionError
+except ValueError:
+    pass
+"#,
+            Some(&globals),
+            None,
+        )
+        .unwrap();
+        deny_filter::trace::set_unsafe_debug(true);
+        py.run(
+            cr#"
+records.messages.clear()
+assert deny_filter.DenyList(["voila"]).scan_any({"a": ["et voila"]})
+assert any("voila" in m for m in records.messages)
+"#,
+            Some(&globals),
+            None,
+        )
+        .unwrap();
+    });
+}
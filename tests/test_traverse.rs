@@ -0,0 +1,238 @@
+use deny_filter::backend::Backend;
+use deny_filter::deny_list::DenyList;
+use deny_filter::limits::{LimitError, Limits};
+use deny_filter::matcher::Matcher;
+use deny_filter::traverse::{self, Error, Segment, format_path};
+use flate2::Compression;
+use flate2::write::{GzEncoder, ZlibEncoder};
+use rmp::encode;
+use serde_json::json;
+use std::io::Write;
+
+/// {"id": 7, "messages": [{"content": "et voila"}], "bin": b"voila"}
+fn sample_msgpack() -> Vec<u8> {
+    let mut buf = Vec::new();
+    encode::write_map_len(&mut buf, 3).unwrap();
+    encode::write_str(&mut buf, "id").unwrap();
+    encode::write_uint(&mut buf, 7).unwrap();
+    encode::write_str(&mut buf, "messages").unwrap();
+    encode::write_array_len(&mut buf, 1).unwrap();
+    encode::write_map_len(&mut buf, 1).unwrap();
+    encode::write_str(&mut buf, "content").unwrap();
+    encode::write_str(&mut buf, "et voila").unwrap();
+    encode::write_str(&mut buf, "bin").unwrap();
+    encode::write_bin(&mut buf, b"voila").unwrap();
+    buf
+}
+
+#[test]
+fn test_msgpack_paths() {
+    let buf = sample_msgpack();
+    let mut seen = Vec::new();
+    let stopped = traverse::msgpack(&buf, &mut |path, s| {
+        seen.push((format_path(path), s.to_string()));
+        false
+    })
+    .unwrap();
+    assert!(!stopped);
+    assert_eq!(
+        seen,
+        vec![("$.messages[0].content".to_string(), "et voila".to_string())]
+    );
+}
+
+#[test]
+fn test_msgpack_errors() {
+    let buf = sample_msgpack();
+    let mut ignore = |_: &[Segment<'_>], _: &str| false;
+    assert_eq!(
+        traverse::msgpack(&buf[..buf.len() - 2], &mut ignore),
+        Err(Error::UnexpectedEof)
+    );
+    assert_eq!(
+        traverse::msgpack(&[0xc1], &mut ignore),
+        Err(Error::ReservedMarker)
+    );
+    assert_eq!(
+        traverse::msgpack(&[0xa2, 0xff, 0xfe], &mut ignore),
+        Err(Error::InvalidUtf8)
+    );
+    assert_eq!(
+        traverse::msgpack(&[0xc0, 0xc0], &mut ignore),
+        Err(Error::TrailingBytes(1))
+    );
+    // an ext32 of the largest length, past the end on every target
+    assert_eq!(
+        traverse::msgpack(&[0xc9, 0xff, 0xff, 0xff, 0xff], &mut ignore),
+        Err(Error::UnexpectedEof)
+    );
+}
+
+#[test]
+fn test_msgpack_strings() {
+    let buf = sample_msgpack();
+    assert_eq!(
+        traverse::msgpack_strings(&buf, &Limits::NONE),
+        Ok(vec![("$.messages[0].content".to_string(), "et voila")])
+    );
+    let shallow = Limits {
+        max_msgpack_depth: Some(1),
+        ..Limits::NONE
+    };
+    assert_eq!(
+        traverse::msgpack_strings(&buf, &shallow),
+        Err(Error::Limit(LimitError::Depth { limit: 1 }))
+    );
+    assert_eq!(
+        traverse::msgpack_strings(&[0xc1], &Limits::NONE),
+        Err(Error::ReservedMarker)
+    );
+}
+
+#[test]
+fn test_json_paths() {
+    let value = json!({"a": [1, {"b": "x"}], "c": null});
+    let mut seen = Vec::new();
+    traverse::json(&value, &mut |path, s| {
+        seen.push((format_path(path), s.to_string()));
+        false
+    });
+    assert_eq!(seen, vec![("$.a[1].b".to_string(), "x".to_string())]);
+}
+
+#[test]
+fn test_matcher_scan_msgpack_and_json() {
+    let deny_list = DenyList::new(vec!["VOILA".to_string()]).unwrap();
+    assert_eq!(deny_list.scan_msgpack(&sample_msgpack()), Ok(true));
+    assert!(deny_list.scan_msgpack(&[0xc1]).is_err());
+    assert!(deny_list.scan_json(&json!({"text": ["Voila"]})));
+    assert!(!deny_list.scan_json(&json!({"voila": "key only"})));
+}
+
+/// every backend shares the trait's traversal: values are scanned, keys and binaries are not
+#[test]
+fn test_backends_scan_documents_alike() {
+    for backend in Backend::ALL {
+        let matcher = backend.build(vec!["VOILA".to_string()]).unwrap();
+        assert_eq!(
+            matcher.scan_msgpack(&sample_msgpack()),
+            Ok(true),
+            "{backend}"
+        );
+        assert!(matcher.scan_msgpack(&[0xc1]).is_err(), "{backend}");
+        let mut keys_only = Vec::new();
+        encode::write_map_len(&mut keys_only, 1).unwrap();
+        encode::write_str(&mut keys_only, "voila").unwrap();
+        encode::write_bin(&mut keys_only, b"voila").unwrap();
+        assert_eq!(matcher.scan_msgpack(&keys_only), Ok(false), "{backend}");
+        assert!(matcher.scan_json(&json!({"text": ["Voila"]})), "{backend}");
+        assert!(
+            !matcher.scan_json(&json!({"voila": "key only"})),
+            "{backend}"
+        );
+    }
+}
+
+#[test]
+fn test_msgpack_compressed() {
+    let buf = sample_msgpack();
+    let mut gzip = GzEncoder::new(Vec::new(), Compression::default());
+    gzip.write_all(&buf).unwrap();
+    let gzip = gzip.finish().unwrap();
+    let mut zlib = ZlibEncoder::new(Vec::new(), Compression::default());
+    zlib.write_all(&buf).unwrap();
+    let zlib = zlib.finish().unwrap();
+    let matcher = DenyList::new(vec!["voila".to_string()]).unwrap();
+    for compressed in [&gzip, &zlib, &buf] {
+        assert_eq!(
+            matcher.scan_msgpack_compressed(compressed, 1 << 20),
+            Ok(true)
+        );
+    }
+    assert!(matcher.scan_msgpack(&gzip).is_err());
+    assert_eq!(
+        matcher.scan_msgpack_compressed(&zlib, buf.len() - 1),
+        Err(Error::TooLarge(buf.len() - 1))
+    );
+    assert!(matches!(
+        matcher.scan_msgpack_compressed(&gzip[..gzip.len() / 2], 1 << 20),
+        Err(Error::Decompress(_))
+    ));
+}
+
+#[test]
+fn test_ignore_keys() {
+    let options: deny_filter::options::Options = serde_json::from_str(
+        r#"{"ignore_keys": ["messages"], "ignore_key_patterns": [".*_b64$"]}"#,
+    )
+    .unwrap();
+    for backend in Backend::ALL {
+        let matcher = backend
+            .build_with(vec!["voila".to_string()], &options)
+            .unwrap();
+        assert_eq!(
+            matcher.scan_msgpack(&sample_msgpack()),
+            Ok(false),
+            "{backend}"
+        );
+        assert!(
+            !matcher.scan_json(&json!({"a": {"image_b64": ["voila"]}, "b": "fine"})),
+            "{backend}"
+        );
+        assert!(matcher.scan_json(&json!({"b64": "voila"})), "{backend}");
+    }
+    let invalid: deny_filter::options::Options =
+        serde_json::from_str(r#"{"ignore_key_patterns": ["("]}"#).unwrap();
+    assert!(Backend::AhoCorasick.build_with(vec![], &invalid).is_err());
+}
+
+#[test]
+fn test_redact_msgpack() {
+    let matcher = DenyList::new(vec!["voilà".to_string(), "id".to_string()]).unwrap();
+    let mut buf = Vec::new();
+    encode::write_map_len(&mut buf, 2).unwrap();
+    encode::write_str(&mut buf, "id").unwrap();
+    encode::write_array_len(&mut buf, 2).unwrap();
+    encode::write_str(&mut buf, "et voilà").unwrap();
+    encode::write_uint(&mut buf, 7).unwrap();
+    encode::write_str(&mut buf, "bin").unwrap();
+    encode::write_bin(&mut buf, b"voila").unwrap();
+    let mut expected = Vec::new();
+    encode::write_map_len(&mut expected, 2).unwrap();
+    encode::write_str(&mut expected, "id").unwrap();
+    encode::write_array_len(&mut expected, 2).unwrap();
+    encode::write_str(&mut expected, "et *****").unwrap();
+    encode::write_uint(&mut expected, 7).unwrap();
+    encode::write_str(&mut expected, "bin").unwrap();
+    encode::write_bin(&mut expected, b"voila").unwrap();
+    assert_eq!(matcher.redact_msgpack(&buf, "*"), Ok(expected));
+    assert_eq!(
+        matcher.redact_msgpack(&sample_msgpack(), "*"),
+        Ok(sample_msgpack())
+    );
+    assert_eq!(
+        matcher.redact_msgpack(&buf[..buf.len() - 1], "*"),
+        Err(Error::UnexpectedEof)
+    );
+}
+
+#[test]
+fn test_redact_json() {
+    let options: deny_filter::options::Options =
+        serde_json::from_str(r#"{"ignore_keys": ["raw"]}"#).unwrap();
+    let matcher = Backend::AhoCorasick
+        .build_with(vec!["voilà".to_string()], &options)
+        .unwrap();
+    let text =
+        r#"{"z": ["et voilà", 1.50], "voilà": {"raw": "voilà"}, "a": "voilà \"ok\""}"#.as_bytes();
+    assert_eq!(
+        String::from_utf8(matcher.redact_json(text, "*", true).unwrap()).unwrap(),
+        r#"{"z": ["et *****", 1.50], "voilà": {"raw": "voilà"}, "a": "***** \"ok\""}"#
+    );
+    assert_eq!(
+        String::from_utf8(matcher.redact_json(text, "*", false).unwrap()).unwrap(),
+        r#"{"a":"***** \"ok\"","voilà":{"raw":"voilà"},"z":["et *****",1.5]}"#
+    );
+    assert!(matcher.redact_json(b"{\"a\": ", "*", true).is_err());
+    assert!(matcher.redact_json(b"[] []", "*", true).is_err());
+}
@@ -0,0 +1,17 @@
+#![cfg(feature = "wasm")]
+
+use deny_filter::wasm::WasmDenyList;
+
+#[test]
+fn test_wasm_deny_list() {
+    for backend in [None, Some("regex"), Some("daachorse")] {
+        let deny_list =
+            WasmDenyList::new(vec!["Voila".to_string()], backend.map(ToString::to_string)).unwrap();
+        assert!(deny_list.is_match("et VOILA"));
+        assert!(deny_list.scan_str("voila"));
+        assert!(!deny_list.is_match("nothing"));
+        assert_eq!(deny_list.first_match("voila!"), Some("voila".to_string()));
+        assert!(deny_list.scan_json(r#"{"a": ["voila"]}"#).unwrap());
+        assert!(!deny_list.scan_msgpack(&[0xa2, b'o', b'k']).unwrap());
+    }
+}